@@ -0,0 +1,159 @@
+use std::fmt::Debug;
+
+use ::{ComplexField, DenseMatrix, LinearOperator, Matrix, LU};
+use super::block_matrix::{Block, BlockMatrix};
+
+/// A block LU factorization `A = L U` of a block matrix with square diagonal
+/// blocks, where `L` is block lower triangular and `U` is block unit upper
+/// triangular.
+///
+/// The diagonal blocks of `L` are the successive Schur complements, kept as
+/// dense LU factorizations with partial pivoting. Zero blocks stay zero
+/// unless elimination fills them in.
+#[derive(Clone, Debug)]
+pub struct BlockLU<T> where T: Copy {
+    offsets: Vec<usize>,
+    diag: Vec<LU<T>>,
+    /// The blocks of `L` below the diagonal and of `U` above it, row major,
+    /// with None for a zero block.
+    off: Vec<Option<DenseMatrix<T>>>,
+}
+
+/// Copy a block into a dense matrix, or None if it is zero.
+fn block_to_dense<T>(b: &dyn Block<T>) -> Option<DenseMatrix<T>>
+    where T: ComplexField
+{
+    if b.is_zero_block() { return None }
+    let nonzeros = b.nonzeros();
+    if nonzeros.is_empty() { return None }
+    let (m, n) = b.shape();
+    let a = DenseMatrix::zeros(m, n);
+    for (i, j, x) in nonzeros {
+        a.set(i, j, x);
+    }
+    Some(a)
+}
+
+impl<T: ComplexField + Debug + 'static> BlockMatrix<T> {
+    /// Compute the block LU factorization by block Gaussian elimination.
+    /// Fails with `Error::Singular` if a diagonal block or Schur complement
+    /// is singular, and unless the diagonal blocks are square.
+    pub fn block_lu(&self) -> ::Result<BlockLU<T>> {
+        let sizes = self.row_sizes();
+        if sizes != self.col_sizes() { return Err(::Error::InvalidDimensions) }
+        let p = sizes.len();
+        let mut work: Vec<Option<DenseMatrix<T>>> = Vec::with_capacity(p*p);
+        for bi in 0..p {
+            for bj in 0..p {
+                work.push(block_to_dense(self.block(bi, bj).unwrap()));
+            }
+        }
+
+        let mut diag = Vec::with_capacity(p);
+        for k in 0..p {
+            let a_kk = work[k*p + k].take().unwrap_or_else(|| DenseMatrix::zeros(sizes[k], sizes[k]));
+            let lu = a_kk.lu()?;
+            if lu.is_singular() { return Err(::Error::Singular) }
+            // U_kj = A_kk^-1 A_kj, then A_ij -= L_ik U_kj.
+            for j in (k + 1)..p {
+                if let Some(a_kj) = work[k*p + j].take() {
+                    work[k*p + j] = Some(lu.solve(&a_kj)?);
+                }
+            }
+            for i in (k + 1)..p {
+                for j in (k + 1)..p {
+                    let prod = match (&work[i*p + k], &work[k*p + j]) {
+                        (Some(l), Some(u)) => l * u,
+                        _ => continue,
+                    };
+                    work[i*p + j] = Some(match work[i*p + j].take() {
+                        Some(a) => &a - &prod,
+                        None => -prod,
+                    });
+                }
+            }
+            diag.push(lu);
+        }
+
+        let mut offsets = vec![0];
+        for s in sizes {
+            let last = offsets[offsets.len() - 1];
+            offsets.push(last + s);
+        }
+        Ok(BlockLU { offsets, diag, off: work })
+    }
+
+    /// Compute the Schur complement `S = D - C A^-1 B` of the leading block
+    /// of a 2 by 2 block matrix `[[A, B], [C, D]]`. Fails unless the block
+    /// grid is 2 by 2 with a square leading block, and with
+    /// `Error::Singular` if `A` is singular.
+    pub fn schur_complement(&self) -> ::Result<DenseMatrix<T>> {
+        if self.block_dims() != (2, 2) || self.row_sizes()[0] != self.col_sizes()[0] {
+            return Err(::Error::InvalidDimensions)
+        }
+        let n = self.row_sizes()[0];
+        let a = block_to_dense(self.block(0, 0).unwrap()).unwrap_or_else(|| DenseMatrix::zeros(n, n));
+        let lu = a.lu()?;
+        if lu.is_singular() { return Err(::Error::Singular) }
+        let d = self.block(1, 1).unwrap();
+        let (m, k) = d.shape();
+        let s = block_to_dense(d).unwrap_or_else(|| DenseMatrix::zeros(m, k));
+        match (block_to_dense(self.block(1, 0).unwrap()), block_to_dense(self.block(0, 1).unwrap())) {
+            (Some(c), Some(b)) => Ok(&s - &(&c * &lu.solve(&b)?)),
+            _ => Ok(s),
+        }
+    }
+
+    /// Solve `A x = b` by block LU.
+    pub fn solve_vector(&self, b: &[T]) -> ::Result<Vec<T>> {
+        self.block_lu()?.solve_vector(b)
+    }
+}
+
+impl<T: ComplexField> BlockLU<T> {
+    /// Get the factored block of `L` below the diagonal or of `U` above it,
+    /// or None if it is zero.
+    pub fn off_diagonal(&self, bi: usize, bj: usize) -> Option<&DenseMatrix<T>> {
+        let p = self.diag.len();
+        if bi >= p || bj >= p { return None }
+        self.off[bi*p + bj].as_ref()
+    }
+
+    /// Get the LU factorization of the `k`th Schur complement.
+    pub fn diagonal(&self, k: usize) -> Option<&LU<T>> {
+        self.diag.get(k)
+    }
+
+    /// Solve `A x = b` by block forward and back substitution.
+    pub fn solve_vector(&self, b: &[T]) -> ::Result<Vec<T>> {
+        let o = &self.offsets;
+        let p = self.diag.len();
+        if b.len() != o[p] { return Err(::Error::InvalidDimensions) }
+
+        let mut x = b.to_vec();
+        for k in 0..p {
+            let mut rhs = x[o[k]..o[k + 1]].to_vec();
+            for i in 0..k {
+                if let Some(ref l) = self.off[k*p + i] {
+                    for (r, v) in rhs.iter_mut().zip(l.apply(&x[o[i]..o[i + 1]])) {
+                        *r = *r - v;
+                    }
+                }
+            }
+            let n = rhs.len();
+            let y = self.diag[k].solve(&DenseMatrix::from_vec(rhs, n, 1, None).unwrap())?;
+            x[o[k]..o[k + 1]].copy_from_slice(&y.elements());
+        }
+        for k in (0..p).rev() {
+            for j in (k + 1)..p {
+                if let Some(ref u) = self.off[k*p + j] {
+                    let v = u.apply(&x[o[j]..o[j + 1]]);
+                    for (r, v) in x[o[k]..o[k + 1]].iter_mut().zip(v) {
+                        *r = *r - v;
+                    }
+                }
+            }
+        }
+        Ok(x)
+    }
+}
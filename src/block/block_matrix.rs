@@ -0,0 +1,380 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use ::{Num};
+use ::{BandedMatrix, CirculantMatrix, ComplexField, DenseMatrix, DiagonalMatrix, HankelMatrix};
+use ::{IdentityMatrix, LinearOperator, Matrix, PermutationMatrix, SparseMatrix, ToeplitzMatrix};
+use ::{TriangularMatrix, TridiagonalMatrix, VandermondeMatrix, ZeroMatrix};
+
+/// A Block is a matrix that can be placed in a BlockMatrix. Unlike `Matrix`
+/// it can be made into a trait object, so blocks of different types can sit
+/// side by side.
+pub trait Block<T>: LinearOperator<T> {
+    /// Get the element at (i, j), which must be in bounds.
+    fn entry(&self, i: usize, j: usize) -> T;
+
+    /// Set the element at (i, j), which must be in bounds. Panics if the
+    /// block cannot hold the value there.
+    fn set_entry(&self, i: usize, j: usize, val: T);
+
+    /// Get the nonzero elements as `(i, j, a_ij)` triplets.
+    fn nonzeros(&self) -> Vec<(usize, usize, T)>;
+
+    /// Get the transpose as a new block.
+    fn transpose_block(&self) -> Box<dyn Block<T>>;
+
+    /// Returns true if the block is known to be zero without looking at its
+    /// elements, so block operations can skip it.
+    #[inline]
+    fn is_zero_block(&self) -> bool { false }
+}
+
+/// Find the nonzero elements of a matrix by visiting every element.
+fn scan_nonzeros<T, A>(a: &A) -> Vec<(usize, usize, T)>
+    where T: Clone + Copy + Num, A: Matrix<T>
+{
+    let n = a.cols();
+    a.elements().into_iter().enumerate()
+        .filter(|&(_, x)| !x.is_zero())
+        .map(|(k, x)| (k / n, k % n, x))
+        .collect()
+}
+
+/// Implement Block for matrix types, given the extra bound on `T` and how to
+/// find the nonzero elements of each type.
+macro_rules! block_impl {
+    ([$bound:path] $($t:ty, |$a:ident| $nonzeros:expr);*) => ($(
+        impl<T: Clone + Copy + $bound + 'static> Block<T> for $t {
+            #[inline]
+            fn entry(&self, i: usize, j: usize) -> T {
+                self.get(i, j).unwrap()
+            }
+
+            #[inline]
+            fn set_entry(&self, i: usize, j: usize, val: T) {
+                self.set(i, j, val);
+            }
+
+            fn nonzeros(&self) -> Vec<(usize, usize, T)> {
+                let $a = self;
+                $nonzeros
+            }
+
+            fn transpose_block(&self) -> Box<dyn Block<T>> {
+                Box::new(self.clone().transpose())
+            }
+        }
+    )*)
+}
+
+block_impl! { [Num]
+    DenseMatrix<T>, |a| scan_nonzeros(a);
+    SparseMatrix<T>, |a| a.triplets().into_iter().filter(|t| !t.2.is_zero()).collect();
+    IdentityMatrix<T>, |a| (0..a.rows()).map(|i| (i, i, T::one())).collect();
+    DiagonalMatrix<T>, |a| a.triplets().into_iter().filter(|t| !t.2.is_zero()).collect();
+    PermutationMatrix<T>, |a| a.perm().iter().enumerate().map(|(i, &p)| (i, p, T::one())).collect();
+    TriangularMatrix<T>, |a| scan_nonzeros(a);
+    BandedMatrix<T>, |a| a.triplets().into_iter().filter(|t| !t.2.is_zero()).collect();
+    TridiagonalMatrix<T>, |a| a.to_banded().nonzeros();
+    VandermondeMatrix<T>, |a| scan_nonzeros(a)
+}
+
+block_impl! { [ComplexField]
+    ToeplitzMatrix<T>, |a| scan_nonzeros(a);
+    CirculantMatrix<T>, |a| scan_nonzeros(a);
+    HankelMatrix<T>, |a| scan_nonzeros(a)
+}
+
+impl<T: Clone + Copy + Num + 'static> Block<T> for ZeroMatrix<T> {
+    #[inline]
+    fn entry(&self, _i: usize, _j: usize) -> T { T::zero() }
+
+    fn set_entry(&self, i: usize, j: usize, val: T) {
+        self.set(i, j, val);
+    }
+
+    fn nonzeros(&self) -> Vec<(usize, usize, T)> { Vec::new() }
+
+    fn transpose_block(&self) -> Box<dyn Block<T>> {
+        Box::new(ZeroMatrix::new(self.cols(), self.rows()))
+    }
+
+    #[inline]
+    fn is_zero_block(&self) -> bool { true }
+}
+
+/// A BlockMatrix is a grid of blocks of any types, such as
+/// `[[A, I], [0, S]]`. Each block row shares a height and each block column
+/// a width. Products and element access go through the blocks, so the
+/// matrix is never flattened unless it is converted.
+pub struct BlockMatrix<T> {
+    /// The offset of the first row of each block row, then the total rows.
+    row_offsets: Vec<usize>,
+    /// The offset of the first column of each block column, then the total
+    /// columns.
+    col_offsets: Vec<usize>,
+    /// The blocks in row major order.
+    blocks: Vec<Box<dyn Block<T>>>,
+}
+
+impl<T: Clone + Copy + Num + 'static> BlockMatrix<T> {
+    /// Create a new block matrix from rows of blocks. Fails unless there is at
+    /// least one block, every block row has the same number of blocks, and
+    /// the blocks of each block row and block column agree in height and
+    /// width.
+    pub fn new(blocks: Vec<Vec<Box<dyn Block<T>>>>) -> ::Result<BlockMatrix<T>> {
+        let q = blocks.first().map_or(0, |row| row.len());
+        if q == 0 || blocks.iter().any(|row| row.len() != q) {
+            return Err(::Error::InvalidDimensions)
+        }
+        let mut row_offsets = vec![0];
+        for row in &blocks {
+            let m = row[0].shape().0;
+            if row.iter().any(|b| b.shape().0 != m) { return Err(::Error::InvalidDimensions) }
+            let last = row_offsets[row_offsets.len() - 1];
+            row_offsets.push(last + m);
+        }
+        let mut col_offsets = vec![0];
+        for j in 0..q {
+            let n = blocks[0][j].shape().1;
+            if blocks.iter().any(|row| row[j].shape().1 != n) { return Err(::Error::InvalidDimensions) }
+            let last = col_offsets[col_offsets.len() - 1];
+            col_offsets.push(last + n);
+        }
+        Ok(BlockMatrix { row_offsets, col_offsets, blocks: blocks.into_iter().flatten().collect() })
+    }
+
+    /// Get the number of block rows and block columns.
+    #[inline]
+    pub fn block_dims(&self) -> (usize, usize) {
+        (self.row_offsets.len() - 1, self.col_offsets.len() - 1)
+    }
+
+    /// Get the height of each block row.
+    pub fn row_sizes(&self) -> Vec<usize> {
+        self.row_offsets.windows(2).map(|w| w[1] - w[0]).collect()
+    }
+
+    /// Get the width of each block column.
+    pub fn col_sizes(&self) -> Vec<usize> {
+        self.col_offsets.windows(2).map(|w| w[1] - w[0]).collect()
+    }
+
+    /// Get the block at block row `bi` and block column `bj`.
+    pub fn block(&self, bi: usize, bj: usize) -> Option<&dyn Block<T>> {
+        let (p, q) = self.block_dims();
+        if bi >= p || bj >= q { return None }
+        Some(&*self.blocks[bi*q + bj])
+    }
+
+    /// Find the block holding an index, given the block offsets.
+    #[inline]
+    fn locate(offsets: &[usize], i: usize) -> usize {
+        // The last offset is the total, so the search never returns past it.
+        match offsets.binary_search(&i) {
+            Ok(b) => b,
+            Err(b) => b - 1,
+        }
+    }
+
+    /// Get the nonzero elements as `(i, j, a_ij)` triplets, block by block.
+    pub fn triplets(&self) -> Vec<(usize, usize, T)> {
+        let (p, q) = self.block_dims();
+        let mut v = Vec::new();
+        for bi in 0..p {
+            for bj in 0..q {
+                let b = &self.blocks[bi*q + bj];
+                if b.is_zero_block() { continue }
+                let (r, c) = (self.row_offsets[bi], self.col_offsets[bj]);
+                v.extend(b.nonzeros().into_iter().map(|(i, j, x)| (r + i, c + j, x)));
+            }
+        }
+        v
+    }
+
+    /// Check that every element at a position where `outside` holds is zero.
+    fn is_zero_where<F: Fn(usize, usize) -> bool>(&self, outside: F) -> bool {
+        self.triplets().into_iter().all(|(i, j, _)| !outside(i, j))
+    }
+
+    /// Get the transpose by transposing the grid and each block.
+    fn transposed(&self) -> BlockMatrix<T> {
+        let (p, q) = self.block_dims();
+        let mut blocks = Vec::with_capacity(p*q);
+        for bj in 0..q {
+            for bi in 0..p {
+                blocks.push(self.blocks[bi*q + bj].transpose_block());
+            }
+        }
+        BlockMatrix {
+            row_offsets: self.col_offsets.clone(),
+            col_offsets: self.row_offsets.clone(),
+            blocks,
+        }
+    }
+
+    /// Flatten into a dense matrix.
+    pub fn to_dense(&self) -> DenseMatrix<T> {
+        DenseMatrix::from_vec(self.elements(), self.rows(), self.cols(), None).unwrap()
+    }
+
+    /// Flatten into a sparse matrix holding the nonzero elements.
+    pub fn to_sparse(&self) -> SparseMatrix<T> {
+        SparseMatrix::from_tuple(self.triplets(), self.rows(), self.cols())
+    }
+}
+
+impl<T: Clone + Copy + Num + 'static> Matrix<T> for BlockMatrix<T> {
+    fn is_symmetric(&self) -> bool {
+        self.is_square() && self.triplets().into_iter().all(|(i, j, x)| self.get(j, i) == Some(x))
+    }
+
+    fn is_orthogonal(&self) -> bool {
+        // Build `A^T A` from the products of the nonzeros that share a row.
+        if !self.is_square() { return false }
+        let mut rows: Vec<Vec<(usize, T)>> = vec![Vec::new(); self.rows()];
+        for (i, j, x) in self.triplets() {
+            rows[i].push((j, x));
+        }
+        let mut gram: HashMap<(usize, usize), T> = HashMap::new();
+        for row in &rows {
+            for &(j, x) in row {
+                for &(k, y) in row {
+                    let g = gram.entry((j, k)).or_insert_with(T::zero);
+                    *g = *g + x*y;
+                }
+            }
+        }
+        (0..self.cols()).all(|j| gram.get(&(j, j)).is_some_and(|g| g.is_one()))
+            && gram.iter().all(|(&(j, k), g)| j == k || g.is_zero())
+    }
+
+    fn is_unitary(&self) -> bool where T: ComplexField { self.to_dense().is_unitary() }
+
+    fn is_diagonal(&self) -> bool { self.is_zero_where(|i, j| i != j) }
+
+    fn is_lower_triangular(&self) -> bool { self.is_zero_where(|i, j| j > i) }
+
+    fn is_unilower_triangular(&self) -> bool {
+        self.is_lower_triangular() && self.diags().iter().all(|d| d.is_one())
+    }
+
+    fn is_strictly_lower_triangular(&self) -> bool { self.is_zero_where(|i, j| j >= i) }
+
+    fn is_lower_hessenberg(&self) -> bool { self.is_zero_where(|i, j| j > i + 1) }
+
+    fn is_upper_triangular(&self) -> bool { self.is_zero_where(|i, j| i > j) }
+
+    fn is_uniupper_triangular(&self) -> bool {
+        self.is_upper_triangular() && self.diags().iter().all(|d| d.is_one())
+    }
+
+    fn is_strictly_upper_triangular(&self) -> bool { self.is_zero_where(|i, j| i >= j) }
+
+    fn is_upper_hessenberg(&self) -> bool { self.is_zero_where(|i, j| i > j + 1) }
+
+    fn trace(&self) -> T {
+        self.diags().into_iter().fold(T::zero(), |acc, d| acc + d)
+    }
+
+    fn transpose(self) -> Self { self.transposed() }
+
+    fn rows(&self) -> usize { self.row_offsets[self.row_offsets.len() - 1] }
+
+    fn cols(&self) -> usize { self.col_offsets[self.col_offsets.len() - 1] }
+
+    fn get(&self, i: usize, j: usize) -> Option<T> {
+        if i >= self.rows() || j >= self.cols() { return None }
+        let (bi, bj) = (Self::locate(&self.row_offsets, i), Self::locate(&self.col_offsets, j));
+        let b = &self.blocks[bi*self.block_dims().1 + bj];
+        Some(b.entry(i - self.row_offsets[bi], j - self.col_offsets[bj]))
+    }
+
+    fn set(&self, i: usize, j: usize, val: T) -> Option<T> {
+        if i >= self.rows() || j >= self.cols() { return None }
+        let (bi, bj) = (Self::locate(&self.row_offsets, i), Self::locate(&self.col_offsets, j));
+        let b = &self.blocks[bi*self.block_dims().1 + bj];
+        b.set_entry(i - self.row_offsets[bi], j - self.col_offsets[bj], val);
+        Some(val)
+    }
+
+    fn elements(&self) -> Vec<T> {
+        let n = self.cols();
+        let mut v = vec![T::zero(); self.rows()*n];
+        for (i, j, x) in self.triplets() {
+            v[i*n + j] = x;
+        }
+        v
+    }
+}
+
+impl<T: Clone + Copy + Num + 'static> LinearOperator<T> for BlockMatrix<T> {
+    fn shape(&self) -> (usize, usize) { (self.rows(), self.cols()) }
+
+    fn apply(&self, x: &[T]) -> Vec<T> {
+        let (p, q) = self.block_dims();
+        let mut y = vec![T::zero(); self.rows()];
+        for bi in 0..p {
+            let (r0, r1) = (self.row_offsets[bi], self.row_offsets[bi + 1]);
+            for bj in 0..q {
+                let b = &self.blocks[bi*q + bj];
+                if b.is_zero_block() { continue }
+                let yb = b.apply(&x[self.col_offsets[bj]..self.col_offsets[bj + 1]]);
+                for (y_i, v) in y[r0..r1].iter_mut().zip(yb) {
+                    *y_i = *y_i + v;
+                }
+            }
+        }
+        y
+    }
+
+    fn apply_transpose(&self, x: &[T]) -> Vec<T> {
+        let (p, q) = self.block_dims();
+        let mut y = vec![T::zero(); self.cols()];
+        for bj in 0..q {
+            let (c0, c1) = (self.col_offsets[bj], self.col_offsets[bj + 1]);
+            for bi in 0..p {
+                let b = &self.blocks[bi*q + bj];
+                if b.is_zero_block() { continue }
+                let yb = b.apply_transpose(&x[self.row_offsets[bi]..self.row_offsets[bi + 1]]);
+                for (y_j, v) in y[c0..c1].iter_mut().zip(yb) {
+                    *y_j = *y_j + v;
+                }
+            }
+        }
+        y
+    }
+}
+
+/// Block matrices nest, so a block can itself be a BlockMatrix.
+impl<T: Clone + Copy + Num + 'static> Block<T> for BlockMatrix<T> {
+    #[inline]
+    fn entry(&self, i: usize, j: usize) -> T {
+        self.get(i, j).unwrap()
+    }
+
+    #[inline]
+    fn set_entry(&self, i: usize, j: usize, val: T) {
+        self.set(i, j, val);
+    }
+
+    fn nonzeros(&self) -> Vec<(usize, usize, T)> {
+        self.triplets().into_iter().filter(|t| !t.2.is_zero()).collect()
+    }
+
+    fn transpose_block(&self) -> Box<dyn Block<T>> {
+        Box::new(self.transposed())
+    }
+}
+
+impl<T> fmt::Display for BlockMatrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BlockMatrix(row_offsets: {:?}, col_offsets: {:?})", self.row_offsets, self.col_offsets)
+    }
+}
+
+impl<T> fmt::Debug for BlockMatrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
@@ -0,0 +1,110 @@
+pub use self::block_lu::BlockLU;
+pub use self::block_matrix::{Block, BlockMatrix};
+
+mod block_lu;
+mod block_matrix;
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+    use ::{DenseMatrix, Error, IdentityMatrix, LinearOperator, Matrix, ZeroMatrix};
+    use super::{Block, BlockMatrix};
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert!((x - y).abs() < 1e-10, "{} != {}", x, y);
+        }
+    }
+
+    #[test]
+    fn test_block_matrix() {
+        let A = dense![1.0, 2.0; 3.0, 4.0].unwrap();
+        let S = sparse![vec![(0, 1, 5.0), (1, 0, -1.0)]; 2, 2];
+        let B = block![A.clone(), eye!(2); zeros!(1, 2), sparse![vec![(0, 1, 7.0)]; 1, 2];
+                       zeros!(2, 2), S.clone()].unwrap();
+        let D = dense![1.0, 2.0, 1.0, 0.0;
+                       3.0, 4.0, 0.0, 1.0;
+                       0.0, 0.0, 0.0, 7.0;
+                       0.0, 0.0, 0.0, 5.0;
+                       0.0, 0.0, -1.0, 0.0].unwrap();
+        assert_eq!(B.dims(), (5, 4));
+        assert_eq!(B.block_dims(), (3, 2));
+        assert_eq!(B.row_sizes(), vec![2, 1, 2]);
+        assert_eq!(B.get(4, 2), Some(-1.0));
+        assert_eq!(B.get(5, 0), None);
+        assert_eq!(B.to_dense(), D);
+        assert_eq!(B.to_sparse(), D);
+        assert_eq!(B.to_sparse().nnz(), 9);
+        assert_eq!(B.trace(), 10.0);
+
+        let x = [1.0, -1.0, 2.0, 0.5];
+        assert_eq!(B.apply(&x), D.apply(&x));
+        assert_eq!(B.apply_transpose(&[1.0, 2.0, 3.0, 4.0, 5.0]), D.apply_transpose(&[1.0, 2.0, 3.0, 4.0, 5.0]));
+        assert_eq!(B.transpose().to_dense(), D.transpose());
+
+        // Blocks are updated in place through the block matrix.
+        let C = block![A.clone(), eye!(2); zeros!(2, 2), S.clone()].unwrap();
+        C.set(3, 2, 9.0);
+        assert_eq!(S.get(1, 0), Some(9.0));
+        assert!(C.is_upper_hessenberg() && !C.is_upper_triangular());
+
+        assert!(block![A.clone(), eye!(3)].is_err());
+        assert!(block![A.clone(); eye!(3)].is_err());
+        assert!(BlockMatrix::<f64>::new(vec![vec![Box::new(A.clone()) as Box<dyn Block<f64>>], vec![]]).is_err());
+    }
+
+    #[test]
+    fn test_nested_block_matrix() {
+        let I: IdentityMatrix<f64> = eye!(2);
+        let inner = block![I.clone(), dense![2.0; 2, 1]].unwrap();
+        let B = block![inner; dense![1.0, 2.0, 3.0].unwrap()].unwrap();
+        assert_eq!(B.to_dense(), dense![1.0, 0.0, 2.0; 0.0, 1.0, 2.0; 1.0, 2.0, 3.0].unwrap());
+        assert!(!B.is_orthogonal());
+        assert_eq!(B.transpose().to_dense(), dense![1.0, 0.0, 1.0; 0.0, 1.0, 2.0; 2.0, 2.0, 3.0].unwrap());
+
+        let Z: ZeroMatrix<f64> = zeros!(2, 2);
+        assert!(block![I.clone(), Z.clone(); Z.clone(), I.clone()].unwrap().is_orthogonal());
+        assert!(block![Z.clone(), I.clone(); I.clone(), Z.clone()].unwrap().is_orthogonal());
+        assert!(!block![I.clone(), I.clone(); Z.clone(), I.clone()].unwrap().is_orthogonal());
+        assert!(!block![I.clone(), Z.clone(); Z.clone(), Z.clone()].unwrap().is_orthogonal());
+        assert!(!block![I.clone(), Z.clone()].unwrap().is_orthogonal());
+    }
+
+    #[test]
+    fn test_block_lu() {
+        // A KKT system [[H, A^T], [A, 0]].
+        let H = dense![4.0, 1.0; 1.0, 3.0].unwrap();
+        let A = dense![1.0, 1.0].unwrap();
+        let Z: ZeroMatrix<f64> = zeros!(1, 1);
+        let K = block![H.clone(), A.clone().transpose(); A.clone(), Z].unwrap();
+        let b = [1.0, 2.0, 3.0];
+        let x = K.solve_vector(&b).unwrap();
+        assert_close(&K.apply(&x), &b);
+
+        // S = -A H^-1 A^T = -(3 - 1 - 1 + 4)/11.
+        let S = K.schur_complement().unwrap();
+        assert_close(&S.elements(), &[-5.0 / 11.0]);
+
+        // A 3 by 3 block grid with a zero block that fills in.
+        let B = block![dense![2.0, 1.0; 0.0, 3.0].unwrap(), dense![1.0; 2, 1], zeros!(2, 2);
+                       dense![1.0, 0.0].unwrap(), dense![5.0; 1, 1], dense![1.0, 2.0].unwrap();
+                       dense![0.0, 1.0; 1.0, 1.0].unwrap(), zeros!(2, 1), eye!(2)].unwrap();
+        let D = B.to_dense();
+        let lu = B.block_lu().unwrap();
+        assert!(lu.off_diagonal(1, 0).is_some() && lu.off_diagonal(0, 2).is_none());
+        let b = [1.0, -2.0, 0.5, 3.0, 1.0];
+        let x = lu.solve_vector(&b).unwrap();
+        assert_close(&D.apply(&x), &b);
+        assert_close(&x, &D.lu().unwrap().solve(&DenseMatrix::from_vec(b.to_vec(), 5, 1, None).unwrap())
+                           .unwrap().elements());
+
+        // The leading block is singular even though the matrix is not.
+        let P = block![zeros!(1, 1), dense![1.0; 1, 1]; dense![1.0; 1, 1], zeros!(1, 1)].unwrap();
+        match P.solve_vector(&[1.0, 2.0]) {
+            Err(Error::Singular) => (),
+            _ => panic!("expected a singular leading block"),
+        }
+        assert!(block![H.clone(), A.clone().transpose()].unwrap().block_lu().is_err());
+    }
+}
@@ -1,8 +1,8 @@
 use std::fmt;
 use std::cell::Cell;
 
-use ::{FromPrimitive, Num, ToPrimitive, Zero};
-use ::{Matrix, ReadOrder};
+use ::Num;
+use ::{Conjugate, DenseColumn, LinearOperator, Matrix, ReadOrder};
 
 /// A DenseMatrix is a matrix that contains many data points in
 /// non-concentrated areas. Holds m times n numbers in memory.
@@ -69,24 +69,20 @@ impl<T: Clone + Copy + Num> DenseMatrix<T> {
 
     /// Create a new dense matrix of zeros given the matrix dimensions m and n.
     #[inline]
-    pub fn zeros(m: usize, n: usize) -> DenseMatrix<T>
-        where T: Zero + FromPrimitive,
-    {
+    pub fn zeros(m: usize, n: usize) -> DenseMatrix<T> {
         DenseMatrix{
             read_order: ReadOrder::default(),
             m: m, n: n,
-            mat: vec![Cell::new(T::from_usize(0 as usize).unwrap()); m*n],
+            mat: vec![Cell::new(T::zero()); m*n],
         }
     }
 
     /// Create a new identity matrix given its dimension.
     #[inline]
-    pub fn identity(n: usize) -> DenseMatrix<T>
-        where T: FromPrimitive,
-    {
-        let mut mat = vec![Cell::new(T::from_usize(0 as usize).unwrap()); n*n];
+    pub fn identity(n: usize) -> DenseMatrix<T> {
+        let mut mat = vec![Cell::new(T::zero()); n*n];
         for i in 0..n {
-            mat[i*n + i] = Cell::new(T::from_usize(1 as usize).unwrap());
+            mat[i*n + i] = Cell::new(T::one());
         }
 
         DenseMatrix{
@@ -105,11 +101,16 @@ impl<T: Clone + Copy + Num> DenseMatrix<T> {
         self
     }
 
+    /// Get the conjugate transpose `A^H`. For real matrices this is just the
+    /// transpose.
+    #[inline]
+    pub fn conj_transpose(&self) -> DenseMatrix<T> where T: Conjugate {
+        self.conj().transpose()
+    }
+
     /// Get the ith row as an 1 by m `DenseMatrix<T>`.
     #[inline]
-    pub fn row(&self, i: usize) -> Option<DenseMatrix<T>>
-        where T: FromPrimitive + ToPrimitive,
-    {
+    pub fn row(&self, i: usize) -> Option<DenseMatrix<T>> {
         if i >= self.rows() { return None }
         let mut v = Vec::with_capacity(self.cols());
         for j in 0..self.cols() {
@@ -120,9 +121,7 @@ impl<T: Clone + Copy + Num> DenseMatrix<T> {
 
     /// Get the jth col as an m by 1 `DenseMatrix<T>`.
     #[inline]
-    pub fn col(&self, j: usize) -> Option<DenseMatrix<T>>
-        where T: FromPrimitive + ToPrimitive,
-    {
+    pub fn col(&self, j: usize) -> Option<DenseMatrix<T>> {
         if j >= self.cols() { return None }
         let mut v = Vec::with_capacity(self.rows());
         for i in 0..self.rows() {
@@ -130,11 +129,18 @@ impl<T: Clone + Copy + Num> DenseMatrix<T> {
         }
         Some(Self::from_vec(v, self.rows(), 1, None).unwrap())
     }
+
+    /// Stack the elements into a column in the matrix's read order: the rows
+    /// one after another for `RowMajor`, or the columns for `ColMajor`. The
+    /// latter is the usual `vec` of linear algebra.
+    ///
+    /// This is the storage order, so no elements are reordered.
+    pub fn vec(&self) -> DenseColumn<T> {
+        DenseColumn::from_vec(self.mat.iter().map(Cell::get).collect())
+    }
 }
 
-impl<T: Clone + Copy + Num + Zero + ToPrimitive + FromPrimitive>
-    Matrix<T> for DenseMatrix<T>
-{
+impl<T: Clone + Copy + Num> Matrix<T> for DenseMatrix<T> {
     fn is_symmetric(&self) -> bool {
         if !self.is_square() { return false }
         for i in 1..self.rows() {
@@ -148,8 +154,21 @@ impl<T: Clone + Copy + Num + Zero + ToPrimitive + FromPrimitive>
     }
 
     fn is_orthogonal(&self) -> bool {
-        // TODO stub
-        return false
+        // Exact check of `A^T A = I`, one pair of columns at a time.
+        if !self.is_square() { return false }
+        let n = self.rows();
+        for i in 0..n {
+            for j in 0..n {
+                let mut acc = T::zero();
+                for k in 0..n {
+                    acc = acc + self.get(k, i).unwrap() * self.get(k, j).unwrap();
+                }
+                if acc != if i == j { T::one() } else { T::zero() } {
+                    return false
+                }
+            }
+        }
+        true
     }
 
     fn is_diagonal(&self) -> bool {
@@ -244,7 +263,7 @@ impl<T: Clone + Copy + Num + Zero + ToPrimitive + FromPrimitive>
                     .get())
             },
             ReadOrder::ColMajor => {
-                Some(self.mat.get(self.m*j + i)
+                Some(self.mat.get(self.n*j + i)
                     .expect("DenseMatrix::element")
                     .get())
             },
@@ -261,7 +280,7 @@ impl<T: Clone + Copy + Num + Zero + ToPrimitive + FromPrimitive>
                 }
             },
             ReadOrder::ColMajor => {
-                match self.mat.get(self.m*j + i) {
+                match self.mat.get(self.n*j + i) {
                     Some(e) => { e.set(val); Some(val) },
                     None => None,
                 }
@@ -272,12 +291,60 @@ impl<T: Clone + Copy + Num + Zero + ToPrimitive + FromPrimitive>
     /// Get the elements of the matrix as a Vec.
     /// Returns the elements in row major order.
     fn elements(&self) -> Vec<T> {
-        self.mat.iter().map(|c| c.get()).collect()
+        match self.read_order {
+            ReadOrder::RowMajor => self.mat.iter().map(|c| c.get()).collect(),
+            ReadOrder::ColMajor => {
+                let mut elements = Vec::with_capacity(self.m*self.n);
+                for i in 0..self.rows() {
+                    for j in 0..self.cols() {
+                        elements.push(self.get(i, j).unwrap());
+                    }
+                }
+                elements
+            },
+        }
+    }
+}
+
+impl<T: Copy + Conjugate> Conjugate for DenseMatrix<T> {
+    fn conj(&self) -> DenseMatrix<T> {
+        DenseMatrix {
+            read_order: self.read_order.clone(),
+            m: self.m, n: self.n,
+            mat: self.mat.iter().map(|c| Cell::new(c.get().conj())).collect(),
+        }
+    }
+}
+
+impl<T: Clone + Copy + Num> LinearOperator<T> for DenseMatrix<T> {
+    fn shape(&self) -> (usize, usize) {
+        self.dims()
+    }
+
+    fn apply(&self, x: &[T]) -> Vec<T> {
+        let mut y = Vec::with_capacity(self.rows());
+        for i in 0..self.rows() {
+            let mut acc = T::zero();
+            for j in 0..self.cols() {
+                acc = acc + self.get(i, j).unwrap() * x[j];
+            }
+            y.push(acc);
+        }
+        y
+    }
+
+    fn apply_transpose(&self, x: &[T]) -> Vec<T> {
+        let mut y = vec![T::zero(); self.cols()];
+        for i in 0..self.rows() {
+            for j in 0..self.cols() {
+                y[j] = y[j] + self.get(i, j).unwrap() * x[i];
+            }
+        }
+        y
     }
 }
 
-impl<T: Clone + Copy + Num + ToPrimitive + FromPrimitive>
-    IntoIterator for DenseMatrix<T> {
+impl<T: Clone + Copy + Num> IntoIterator for DenseMatrix<T> {
     type Item = T;
     type IntoIter = DenseMatrixIntoIterator<T>;
 
@@ -297,8 +364,7 @@ pub struct DenseMatrixIntoIterator<T> where T: Copy {
     index: usize,
 }
 
-impl<T: Clone + Copy + Num + ToPrimitive + FromPrimitive>
-    Iterator for DenseMatrixIntoIterator<T> {
+impl<T: Clone + Copy + Num> Iterator for DenseMatrixIntoIterator<T> {
     type Item = T;
 
     fn next(&mut self) -> Option<T> {
@@ -1,7 +1,7 @@
 use std::cell::Cell;
 use std::fmt;
 
-use ::{Num, Vector, Zero};
+use ::{DenseMatrix, Num, ReadOrder, Vector, Zero};
 
 #[derive(Clone, Debug)]
 pub struct DenseRow<T> where T: Copy {
@@ -67,6 +67,21 @@ impl<T: Clone + Copy + Num> DenseColumn<T> {
     pub fn from_vec(vec: Vec<T>) -> DenseColumn<T> {
         DenseColumn { elems: vec.iter().map(|&e| Cell::new(e)).collect() }
     }
+
+    /// Reshape into an m by n matrix filled in the given read order, which
+    /// inverts `DenseMatrix::vec` for a matrix of that read order. If no
+    /// ReadOrder is provided, then assumes `ReadOrder::RowMajor`.
+    pub fn unvec(&self, m: usize, n: usize, read_order: Option<ReadOrder>)
+        -> ::Result<DenseMatrix<T>>
+    {
+        let v = self.elems.iter().map(Cell::get).collect();
+        match read_order {
+            // Column major storage of an m by n matrix is the row major
+            // storage of its n by m transpose.
+            Some(ReadOrder::ColMajor) => DenseMatrix::from_vec(v, n, m, Some(ReadOrder::ColMajor)),
+            _ => DenseMatrix::from_vec(v, m, n, None),
+        }
+    }
 }
 
 impl<T: Clone + Copy + Num> Vector<T> for DenseColumn<T> {
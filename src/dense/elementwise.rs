@@ -0,0 +1,278 @@
+use std::cmp::Ordering;
+
+use ::{Float, Num, NumCast, Zero};
+use ::{ComplexField, DenseColumn, DenseMatrix, DenseRow, Matrix, Vector};
+use ::linalg::scaled_norm_2;
+
+static ELEMENTWISE_DIM_ERROR: &str = "Cannot combine matrices of given dimensions";
+
+/// Find the position of the first greatest or least element of a nonempty
+/// sequence, skipping elements that are not comparable such as NaN.
+fn arg_extreme<T, I>(iter: I, want: Ordering) -> Option<(usize, T)>
+    where T: Copy + PartialOrd, I: Iterator<Item = T>
+{
+    let mut best: Option<(usize, T)> = None;
+    for (k, x) in iter.enumerate() {
+        best = match best {
+            None if x.partial_cmp(&x).is_some() => Some((k, x)),
+            Some((_, b)) if x.partial_cmp(&b) == Some(want) => Some((k, x)),
+            _ => best,
+        };
+    }
+    best
+}
+
+impl<T: Clone + Copy + Num> DenseMatrix<T> {
+    /// Apply `f` to every element.
+    pub fn map<U, F>(&self, f: F) -> DenseMatrix<U>
+        where U: Clone + Copy + Num, F: Fn(T) -> U
+    {
+        let mat = self.elements().into_iter().map(f).collect();
+        DenseMatrix::from_vec(mat, self.rows(), self.cols(), None).unwrap()
+    }
+
+    /// Apply `f` to every pair of elements at the same position.
+    ///
+    /// Panics if the dimensions do not agree.
+    pub fn zip_map<U, F>(&self, other: &DenseMatrix<T>, f: F) -> DenseMatrix<U>
+        where U: Clone + Copy + Num, F: Fn(T, T) -> U
+    {
+        if self.dims() != other.dims() {
+            panic!("{}: lhs={:?} rhs={:?}", ELEMENTWISE_DIM_ERROR, self.dims(), other.dims())
+        }
+        let mat = self.elements().into_iter()
+            .zip(other.elements())
+            .map(|(a, b)| f(a, b))
+            .collect();
+        DenseMatrix::from_vec(mat, self.rows(), self.cols(), None).unwrap()
+    }
+
+    /// The element-wise (Hadamard) product.
+    ///
+    /// Panics if the dimensions do not agree.
+    pub fn hadamard(&self, other: &DenseMatrix<T>) -> DenseMatrix<T> {
+        self.zip_map(other, |a, b| a * b)
+    }
+
+    /// The element-wise quotient.
+    ///
+    /// Panics if the dimensions do not agree.
+    pub fn element_div(&self, other: &DenseMatrix<T>) -> DenseMatrix<T> {
+        self.zip_map(other, |a, b| a / b)
+    }
+
+    /// Get the sum of all elements.
+    pub fn sum(&self) -> T {
+        self.mat.iter().fold(T::zero(), |acc, a| acc + a.get())
+    }
+
+    /// Get the product of all elements.
+    pub fn product(&self) -> T {
+        self.mat.iter().fold(T::one(), |acc, a| acc * a.get())
+    }
+
+    /// Get the least element, ignoring NaN. Returns None if there is none.
+    pub fn min(&self) -> Option<T> where T: PartialOrd {
+        self.argmin().map(|(i, j)| self.get(i, j).unwrap())
+    }
+
+    /// Get the greatest element, ignoring NaN. Returns None if there is none.
+    pub fn max(&self) -> Option<T> where T: PartialOrd {
+        self.argmax().map(|(i, j)| self.get(i, j).unwrap())
+    }
+
+    /// Get the position of the first least element in row major order.
+    pub fn argmin(&self) -> Option<(usize, usize)> where T: PartialOrd {
+        let n = self.cols();
+        arg_extreme(self.elements().into_iter(), Ordering::Less).map(|(k, _)| (k / n, k % n))
+    }
+
+    /// Get the position of the first greatest element in row major order.
+    pub fn argmax(&self) -> Option<(usize, usize)> where T: PartialOrd {
+        let n = self.cols();
+        arg_extreme(self.elements().into_iter(), Ordering::Greater).map(|(k, _)| (k / n, k % n))
+    }
+
+    /// Fold the rows together element-wise, giving one value per column.
+    /// The fold starts from `init`, or else from the first row, and gives
+    /// `None` without either.
+    fn fold_rows<F: Fn(T, T) -> T>(&self, init: Option<T>, f: F) -> Option<DenseRow<T>> {
+        if init.is_none() && self.rows() == 0 { return None }
+        let skip = if init.is_none() { 1 } else { 0 };
+        let row = (0..self.cols())
+            .map(|j| (skip..self.rows()).fold(init.unwrap_or_else(|| self.get(0, j).unwrap()), |acc, i| {
+                f(acc, self.get(i, j).unwrap())
+            }))
+            .collect();
+        Some(DenseRow::from_vec(row))
+    }
+
+    /// Fold the columns together element-wise, giving one value per row.
+    /// The fold starts from `init`, or else from the first column, and gives
+    /// `None` without either.
+    fn fold_cols<F: Fn(T, T) -> T>(&self, init: Option<T>, f: F) -> Option<DenseColumn<T>> {
+        if init.is_none() && self.cols() == 0 { return None }
+        let skip = if init.is_none() { 1 } else { 0 };
+        let col = (0..self.rows())
+            .map(|i| (skip..self.cols()).fold(init.unwrap_or_else(|| self.get(i, 0).unwrap()), |acc, j| {
+                f(acc, self.get(i, j).unwrap())
+            }))
+            .collect();
+        Some(DenseColumn::from_vec(col))
+    }
+
+    /// Sum the rows together, giving the sum of each column.
+    pub fn sum_rows(&self) -> DenseRow<T> {
+        self.fold_rows(Some(T::zero()), |a, b| a + b).unwrap()
+    }
+
+    /// Sum the columns together, giving the sum of each row.
+    pub fn sum_cols(&self) -> DenseColumn<T> {
+        self.fold_cols(Some(T::zero()), |a, b| a + b).unwrap()
+    }
+
+    /// Average the rows, giving the mean of each column. Without rows the
+    /// means are `0/0`, which is NaN for floating point types.
+    pub fn mean_rows(&self) -> DenseRow<T> where T: NumCast {
+        let m = T::from(self.rows()).unwrap();
+        self.sum_rows().map(|s| s / m)
+    }
+
+    /// Average the columns, giving the mean of each row. Without columns the
+    /// means are `0/0`, which is NaN for floating point types.
+    pub fn mean_cols(&self) -> DenseColumn<T> where T: NumCast {
+        let n = T::from(self.cols()).unwrap();
+        self.sum_cols().map(|s| s / n)
+    }
+
+    /// Get the least element of each column, or `None` if there are no rows.
+    pub fn min_rows(&self) -> Option<DenseRow<T>> where T: PartialOrd {
+        self.fold_rows(None, |a, b| if b < a { b } else { a })
+    }
+
+    /// Get the least element of each row, or `None` if there are no columns.
+    pub fn min_cols(&self) -> Option<DenseColumn<T>> where T: PartialOrd {
+        self.fold_cols(None, |a, b| if b < a { b } else { a })
+    }
+
+    /// Get the greatest element of each column, or `None` if there are no
+    /// rows.
+    pub fn max_rows(&self) -> Option<DenseRow<T>> where T: PartialOrd {
+        self.fold_rows(None, |a, b| if b > a { b } else { a })
+    }
+
+    /// Get the greatest element of each row, or `None` if there are no
+    /// columns.
+    pub fn max_cols(&self) -> Option<DenseColumn<T>> where T: PartialOrd {
+        self.fold_cols(None, |a, b| if b > a { b } else { a })
+    }
+}
+
+macro_rules! vector_elementwise_impl {
+    ($($t:ident)*) => ($(
+        impl<T: Clone + Copy + Num> $t<T> {
+            /// Apply `f` to every element.
+            pub fn map<U, F>(&self, f: F) -> $t<U>
+                where U: Clone + Copy + Num, F: Fn(T) -> U
+            {
+                $t::from_vec((0..self.len()).map(|i| f(self.get(i).unwrap())).collect())
+            }
+
+            /// Apply `f` to every pair of elements at the same position.
+            ///
+            /// Panics if the lengths do not agree.
+            pub fn zip_map<U, F>(&self, other: &$t<T>, f: F) -> $t<U>
+                where U: Clone + Copy + Num, F: Fn(T, T) -> U
+            {
+                if self.len() != other.len() {
+                    panic!("{}: lhs={} rhs={}", ELEMENTWISE_DIM_ERROR, self.len(), other.len())
+                }
+                $t::from_vec((0..self.len())
+                    .map(|i| f(self.get(i).unwrap(), other.get(i).unwrap()))
+                    .collect())
+            }
+
+            /// The element-wise (Hadamard) product.
+            pub fn hadamard(&self, other: &$t<T>) -> $t<T> {
+                self.zip_map(other, |a, b| a * b)
+            }
+
+            /// The element-wise quotient.
+            pub fn element_div(&self, other: &$t<T>) -> $t<T> {
+                self.zip_map(other, |a, b| a / b)
+            }
+
+            /// Get the sum of all elements.
+            pub fn sum(&self) -> T {
+                (0..self.len()).fold(T::zero(), |acc, i| acc + self.get(i).unwrap())
+            }
+
+            /// Get the product of all elements.
+            pub fn product(&self) -> T {
+                (0..self.len()).fold(T::one(), |acc, i| acc * self.get(i).unwrap())
+            }
+
+            /// Get the mean of all elements.
+            pub fn mean(&self) -> T where T: NumCast {
+                self.sum() / T::from(self.len()).unwrap()
+            }
+
+            /// Get the least element, ignoring NaN.
+            pub fn min(&self) -> Option<T> where T: PartialOrd {
+                self.argmin().map(|i| self.get(i).unwrap())
+            }
+
+            /// Get the greatest element, ignoring NaN.
+            pub fn max(&self) -> Option<T> where T: PartialOrd {
+                self.argmax().map(|i| self.get(i).unwrap())
+            }
+
+            /// Get the index of the first least element.
+            pub fn argmin(&self) -> Option<usize> where T: PartialOrd {
+                let iter = (0..self.len()).map(|i| self.get(i).unwrap());
+                arg_extreme(iter, Ordering::Less).map(|(i, _)| i)
+            }
+
+            /// Get the index of the first greatest element.
+            pub fn argmax(&self) -> Option<usize> where T: PartialOrd {
+                let iter = (0..self.len()).map(|i| self.get(i).unwrap());
+                arg_extreme(iter, Ordering::Greater).map(|(i, _)| i)
+            }
+
+            /// Compute the p-norm `(sum |x_i|^p)^(1/p)` for `p >= 1`. An
+            /// infinite `p` gives the largest modulus.
+            pub fn norm_p(&self, p: T::Real) -> T::Real where T: ComplexField {
+                if p.is_infinite() { return self.norm_inf() }
+                // Scale by the largest modulus so that the powers stay finite.
+                let scale = self.norm_inf();
+                if scale == T::Real::zero() || !scale.is_finite() { return scale }
+                let sum = (0..self.len()).fold(T::Real::zero(), |acc, i| {
+                    acc + (self.get(i).unwrap().modulus() / scale).powf(p)
+                });
+                scale * sum.powf(p.recip())
+            }
+
+            /// Compute the 1-norm, the sum of moduli.
+            pub fn norm_1(&self) -> T::Real where T: ComplexField {
+                (0..self.len()).fold(T::Real::zero(), |acc, i| acc + self.get(i).unwrap().modulus())
+            }
+
+            /// Compute the Euclidean norm.
+            pub fn norm_2(&self) -> T::Real where T: ComplexField {
+                scaled_norm_2((0..self.len()).map(|i| self.get(i).unwrap().modulus()))
+            }
+
+            /// Compute the infinity norm, the largest modulus. NaN if any
+            /// element is NaN.
+            pub fn norm_inf(&self) -> T::Real where T: ComplexField {
+                // `max` would drop a NaN, so keep it by hand.
+                (0..self.len()).fold(T::Real::zero(), |acc, i| {
+                    let r = self.get(i).unwrap().modulus();
+                    if r.is_nan() || r > acc { r } else { acc }
+                })
+            }
+        }
+    )*)
+}
+
+vector_elementwise_impl! { DenseRow DenseColumn }
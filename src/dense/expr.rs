@@ -0,0 +1,226 @@
+use std::ops::{Add, Mul, Sub};
+
+use ::{DenseMatrix, Matrix, Num};
+
+static EXPR_DIM_ERROR: &str = "Cannot combine matrix expressions of given dimensions";
+
+/// A DenseExpr is a lazily evaluated element-wise expression over dense
+/// matrices.
+///
+/// Expressions are built from `DenseMatrix::lazy` with `+`, `-`, scalar `*`
+/// and `hadamard`, and hold only references to their operands. Nothing is
+/// computed until `eval` or `DenseMatrix::assign`, which make a single pass
+/// over the output and evaluate the whole expression tree per element, so a
+/// chain such as `(A.lazy() + &B) * 2.0 - &C` allocates no temporaries.
+///
+/// Fusion is opt in. The operators on `DenseMatrix` itself stay eager and
+/// return a `DenseMatrix`, so `&A + &B` keeps its type and can be stored,
+/// compared and passed on as before. Start a chain with `lazy` to fuse it:
+/// `(A.lazy() + &B + &C).eval()` makes one pass where `&(&A + &B) + &C`
+/// allocates a temporary per operator.
+pub trait DenseExpr: Sized {
+    /// The element type.
+    type Elem: Copy + Num;
+
+    /// Get the dimensions of the result.
+    fn dims(&self) -> (usize, usize);
+
+    /// Evaluate the element at i, j.
+    fn at(&self, i: usize, j: usize) -> Self::Elem;
+
+    /// Evaluate the expression into a new matrix.
+    fn eval(&self) -> DenseMatrix<Self::Elem> {
+        let (m, n) = self.dims();
+        let mut mat = Vec::with_capacity(m*n);
+        for i in 0..m {
+            for j in 0..n {
+                mat.push(self.at(i, j));
+            }
+        }
+        DenseMatrix::from_vec(mat, m, n, None).unwrap()
+    }
+
+    /// The element-wise (Hadamard) product.
+    fn hadamard<R>(self, rhs: R) -> HadamardExpr<Self, R>
+        where R: DenseExpr<Elem = Self::Elem>
+    {
+        check_expr_dims(&self, &rhs);
+        HadamardExpr { lhs: self, rhs }
+    }
+}
+
+#[inline]
+fn check_expr_dims<L: DenseExpr, R: DenseExpr>(lhs: &L, rhs: &R) {
+    if lhs.dims() != rhs.dims() {
+        panic!("{}: lhs={:?} rhs={:?}", EXPR_DIM_ERROR, lhs.dims(), rhs.dims())
+    }
+}
+
+/// A borrowed matrix as the leaf of an expression.
+#[derive(Clone, Copy, Debug)]
+pub struct LazyMatrix<'a, T: 'a> where T: Copy {
+    mat: &'a DenseMatrix<T>,
+}
+
+/// The lazy sum of two expressions.
+#[derive(Clone, Copy, Debug)]
+pub struct AddExpr<L, R> {
+    lhs: L,
+    rhs: R,
+}
+
+/// The lazy difference of two expressions.
+#[derive(Clone, Copy, Debug)]
+pub struct SubExpr<L, R> {
+    lhs: L,
+    rhs: R,
+}
+
+/// The lazy element-wise product of two expressions.
+#[derive(Clone, Copy, Debug)]
+pub struct HadamardExpr<L, R> {
+    lhs: L,
+    rhs: R,
+}
+
+/// An expression lazily multiplied by a scalar.
+#[derive(Clone, Copy, Debug)]
+pub struct ScaleExpr<E, T> {
+    expr: E,
+    scalar: T,
+}
+
+impl<T: Clone + Copy + Num> DenseMatrix<T> {
+    /// Borrow the matrix as the start of a lazy expression.
+    #[inline]
+    pub fn lazy(&self) -> LazyMatrix<'_, T> {
+        LazyMatrix { mat: self }
+    }
+
+    /// Evaluate an expression into this matrix in place, without allocating.
+    /// The expression may refer to this matrix itself.
+    ///
+    /// Panics if the dimensions do not agree.
+    pub fn assign<E: DenseExpr<Elem = T>>(&self, expr: E) {
+        if self.dims() != expr.dims() {
+            panic!("{}: lhs={:?} rhs={:?}", EXPR_DIM_ERROR, self.dims(), expr.dims())
+        }
+        for i in 0..self.rows() {
+            for j in 0..self.cols() {
+                self.set(i, j, expr.at(i, j));
+            }
+        }
+    }
+}
+
+impl<'a, T: Clone + Copy + Num> DenseExpr for LazyMatrix<'a, T> {
+    type Elem = T;
+
+    #[inline]
+    fn dims(&self) -> (usize, usize) { self.mat.dims() }
+
+    #[inline]
+    fn at(&self, i: usize, j: usize) -> T { self.mat.get(i, j).unwrap() }
+}
+
+/// A plain matrix reference can appear as any operand after the first.
+impl<T: Clone + Copy + Num> DenseExpr for &DenseMatrix<T> {
+    type Elem = T;
+
+    #[inline]
+    fn dims(&self) -> (usize, usize) { Matrix::dims(*self) }
+
+    #[inline]
+    fn at(&self, i: usize, j: usize) -> T { self.get(i, j).unwrap() }
+}
+
+impl<L, R> DenseExpr for AddExpr<L, R>
+    where L: DenseExpr, R: DenseExpr<Elem = L::Elem>
+{
+    type Elem = L::Elem;
+
+    #[inline]
+    fn dims(&self) -> (usize, usize) { self.lhs.dims() }
+
+    #[inline]
+    fn at(&self, i: usize, j: usize) -> L::Elem { self.lhs.at(i, j) + self.rhs.at(i, j) }
+}
+
+impl<L, R> DenseExpr for SubExpr<L, R>
+    where L: DenseExpr, R: DenseExpr<Elem = L::Elem>
+{
+    type Elem = L::Elem;
+
+    #[inline]
+    fn dims(&self) -> (usize, usize) { self.lhs.dims() }
+
+    #[inline]
+    fn at(&self, i: usize, j: usize) -> L::Elem { self.lhs.at(i, j) - self.rhs.at(i, j) }
+}
+
+impl<L, R> DenseExpr for HadamardExpr<L, R>
+    where L: DenseExpr, R: DenseExpr<Elem = L::Elem>
+{
+    type Elem = L::Elem;
+
+    #[inline]
+    fn dims(&self) -> (usize, usize) { self.lhs.dims() }
+
+    #[inline]
+    fn at(&self, i: usize, j: usize) -> L::Elem { self.lhs.at(i, j) * self.rhs.at(i, j) }
+}
+
+impl<E: DenseExpr> DenseExpr for ScaleExpr<E, E::Elem> {
+    type Elem = E::Elem;
+
+    #[inline]
+    fn dims(&self) -> (usize, usize) { self.expr.dims() }
+
+    #[inline]
+    fn at(&self, i: usize, j: usize) -> E::Elem { self.expr.at(i, j) * self.scalar }
+}
+
+macro_rules! expr_ops_impl {
+    ($t:ty, $($g:tt)*) => (
+        impl<$($g)*, Rhs> Add<Rhs> for $t
+            where $t: DenseExpr, Rhs: DenseExpr<Elem = <$t as DenseExpr>::Elem>
+        {
+            type Output = AddExpr<$t, Rhs>;
+
+            #[inline]
+            fn add(self, rhs: Rhs) -> AddExpr<$t, Rhs> {
+                check_expr_dims(&self, &rhs);
+                AddExpr { lhs: self, rhs }
+            }
+        }
+
+        impl<$($g)*, Rhs> Sub<Rhs> for $t
+            where $t: DenseExpr, Rhs: DenseExpr<Elem = <$t as DenseExpr>::Elem>
+        {
+            type Output = SubExpr<$t, Rhs>;
+
+            #[inline]
+            fn sub(self, rhs: Rhs) -> SubExpr<$t, Rhs> {
+                check_expr_dims(&self, &rhs);
+                SubExpr { lhs: self, rhs }
+            }
+        }
+
+        impl<$($g)*> Mul<<$t as DenseExpr>::Elem> for $t
+            where $t: DenseExpr
+        {
+            type Output = ScaleExpr<$t, <$t as DenseExpr>::Elem>;
+
+            #[inline]
+            fn mul(self, scalar: <$t as DenseExpr>::Elem) -> Self::Output {
+                ScaleExpr { expr: self, scalar }
+            }
+        }
+    )
+}
+
+expr_ops_impl! { LazyMatrix<'a, T>, 'a, T: Clone + Copy + Num }
+expr_ops_impl! { AddExpr<L, R>, L, R }
+expr_ops_impl! { SubExpr<L, R>, L, R }
+expr_ops_impl! { HadamardExpr<L, R>, L, R }
+expr_ops_impl! { ScaleExpr<E, S>, E, S }
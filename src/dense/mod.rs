@@ -1,8 +1,12 @@
 pub use self::dense_matrix::DenseMatrix;
 pub use self::dense_vector::{DenseColumn, DenseRow};
+pub use self::expr::{AddExpr, DenseExpr, HadamardExpr, LazyMatrix, ScaleExpr, SubExpr};
 
 mod dense_matrix;
 mod dense_vector;
+mod elementwise;
+mod expr;
+mod reshape;
 
 #[cfg(test)]
 mod tests {
@@ -48,6 +52,35 @@ mod tests {
         assert_eq!(B.trace(), 6);
     }
 
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_col_major() {
+        use matrix::{Matrix, ReadOrder};
+
+        // Stored as 2 by 3 row major and read as its 3 by 2 transpose.
+        let A = DenseMatrix::from_vec(vec![1, 2, 3, 4, 5, 6], 2, 3,
+                                      Some(ReadOrder::ColMajor)).unwrap();
+        assert_eq!(A.dims(), (3, 2));
+        assert_eq!(A.get(0, 1), Some(4));
+        assert_eq!(A.get(2, 0), Some(3));
+        assert_eq!(A.get(2, 1), Some(6));
+
+        assert_eq!(A.set(2, 1, 7), Some(7));
+        assert_eq!(A.get(2, 1), Some(7));
+        assert_eq!(A.elements(), vec![1, 4, 2, 5, 3, 7]);
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_is_orthogonal() {
+        use matrix::Matrix;
+
+        assert!(DenseMatrix::<i32>::identity(3).is_orthogonal());
+        assert!(dense![0, 1, 0; 0, 0, -1; 1, 0, 0].unwrap().is_orthogonal());
+        assert!(!dense![1, 1; 1, -1].unwrap().is_orthogonal());
+        assert!(!dense![1, 0, 0; 0, 1, 0].unwrap().is_orthogonal());
+    }
+
     #[allow(non_snake_case)]
     #[test]
     fn test_add() {
@@ -60,4 +93,186 @@ mod tests {
         let A_plus_B = DenseMatrix::new(&vf).unwrap();
         assert_eq!(A+B, A_plus_B);
     }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_lazy_expr() {
+        use dense::DenseExpr;
+        use matrix::Matrix;
+
+        let A = DenseMatrix::new(&[vec![1, 2], vec![3, 4]]).unwrap();
+        let B = DenseMatrix::new(&[vec![5, 6], vec![7, 8]]).unwrap();
+        let C = DenseMatrix::new(&[vec![1, 1], vec![1, 1]]).unwrap();
+
+        let E = ((A.lazy() + &B) * 2 - &C).eval();
+        assert_eq!(E, DenseMatrix::new(&[vec![11, 15], vec![19, 23]]).unwrap());
+
+        // The fused chain agrees with the eager operators.
+        assert_eq!((A.lazy() + &B + &C).eval(), &(&A + &B) + &C);
+
+        let H = A.lazy().hadamard(&B).eval();
+        assert_eq!(H, DenseMatrix::new(&[vec![5, 12], vec![21, 32]]).unwrap());
+
+        // Assignment evaluates in place, even when the target is an operand.
+        A.assign(A.lazy() + &A.clone().transpose());
+        assert_eq!(A, DenseMatrix::new(&[vec![2, 5], vec![5, 8]]).unwrap());
+        assert_eq!(A.get(0, 1).unwrap(), 5);
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    #[should_panic]
+    fn test_lazy_expr_dims() {
+        let A = DenseMatrix::new(&[vec![1, 2], vec![3, 4]]).unwrap();
+        let B = DenseMatrix::new(&[vec![1, 2, 3]]).unwrap();
+        let _panic = A.lazy() + &B;
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_elementwise() {
+        use matrix::Matrix;
+
+        let A = dense![1.0, -2.0; 3.0, 4.0].unwrap();
+        let B = dense![2.0, 2.0; 0.5, 8.0].unwrap();
+        assert_eq!(A.map(|x| x * x), dense![1.0, 4.0; 9.0, 16.0].unwrap());
+        assert_eq!(A.map(|x| (x > 0.0) as u8).sum(), 3);
+        assert_eq!(A.hadamard(&B), dense![2.0, -4.0; 1.5, 32.0].unwrap());
+        assert_eq!(A.element_div(&B), dense![0.5, -1.0; 6.0, 0.5].unwrap());
+        assert_eq!(A.zip_map(&B, |a: f64, b| a.max(b)).sum(), 15.0);
+        assert_eq!(A.sum(), 6.0);
+        assert_eq!(A.product(), -24.0);
+        assert_eq!(A.min(), Some(-2.0));
+        assert_eq!(A.max(), Some(4.0));
+        assert_eq!(A.argmin(), Some((0, 1)));
+        assert_eq!(A.clone().transpose().argmax(), Some((1, 1)));
+        assert_eq!(A.transpose().argmin(), Some((1, 0)));
+
+        let C = dense![f64::NAN, 1.0; 5.0, 5.0].unwrap();
+        assert_eq!(C.argmax(), Some((1, 0)));
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_axis_reductions() {
+        use dense::{DenseColumn, DenseRow};
+        use vector::Vector;
+
+        fn to_vec<V: Vector<f64>>(v: &V) -> Vec<f64> {
+            (0..v.len()).map(|i| v.get(i).unwrap()).collect()
+        }
+
+        let A = dense![1.0, 2.0, 3.0;
+                       5.0, 0.0, 9.0].unwrap();
+        assert_eq!(to_vec(&A.sum_rows()), vec![6.0, 2.0, 12.0]);
+        assert_eq!(to_vec(&A.sum_cols()), vec![6.0, 14.0]);
+        assert_eq!(to_vec(&A.mean_rows()), vec![3.0, 1.0, 6.0]);
+        assert_eq!(to_vec(&A.mean_cols()), vec![2.0, 14.0 / 3.0]);
+        assert_eq!(to_vec(&A.min_rows().unwrap()), vec![1.0, 0.0, 3.0]);
+        assert_eq!(to_vec(&A.max_cols().unwrap()), vec![3.0, 9.0]);
+
+        // Reductions along an empty axis.
+        let E: DenseMatrix<f64> = DenseMatrix::zeros(0, 3);
+        assert_eq!(to_vec(&E.sum_rows()), vec![0.0, 0.0, 0.0]);
+        assert_eq!(E.sum_cols().len(), 0);
+        assert!(E.mean_rows().get(0).unwrap().is_nan());
+        assert!(E.min_rows().is_none());
+        assert_eq!(E.max_cols().unwrap().len(), 0);
+        let F: DenseMatrix<f64> = DenseMatrix::zeros(2, 0);
+        assert_eq!(to_vec(&F.sum_cols()), vec![0.0, 0.0]);
+        assert!(F.max_cols().is_none());
+        assert_eq!(F.min_rows().unwrap().len(), 0);
+
+        // Centering the columns with the column means.
+        let C = A.broadcast_sub_row(&A.mean_rows());
+        assert_eq!(to_vec(&C.sum_rows()), vec![0.0, 0.0, 0.0]);
+
+        let u = DenseRow::from_vec(vec![3, 1, 4, 1, 5]);
+        let v = DenseRow::from_vec(vec![2, 7, 1, 8, 2]);
+        assert_eq!(u.hadamard(&v).sum(), 6 + 7 + 4 + 8 + 10);
+        assert_eq!(u.product(), 60);
+        assert_eq!(u.argmin(), Some(1));
+        assert_eq!(v.argmax(), Some(3));
+        assert_eq!(u.max(), Some(5));
+        let w = DenseColumn::from_vec(vec![1.0, 2.0, 6.0]);
+        assert_eq!(w.map(|x| x * 2.0).mean(), 6.0);
+        assert_eq!(w.element_div(&w).sum(), 3.0);
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_vec_unvec() {
+        use dense::DenseColumn;
+        use matrix::{Matrix, ReadOrder};
+        use vector::Vector;
+
+        fn to_vec<V: Vector<i32>>(v: &V) -> Vec<i32> {
+            (0..v.len()).map(|i| v.get(i).unwrap()).collect()
+        }
+
+        let A = DenseMatrix::new(&[vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        let v = A.vec();
+        assert_eq!(to_vec(&v), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(v.unvec(2, 3, None).unwrap(), A);
+
+        // A column major matrix stacks its columns.
+        let B = DenseMatrix::from_vec(vec![1, 4, 2, 5, 3, 6], 3, 2, Some(ReadOrder::ColMajor)).unwrap();
+        assert_eq!(B, A);
+        let w = B.vec();
+        assert_eq!(to_vec(&w), vec![1, 4, 2, 5, 3, 6]);
+        let C = w.unvec(2, 3, Some(ReadOrder::ColMajor)).unwrap();
+        assert_eq!(C, A);
+        assert!(matches!(C.read_order, ReadOrder::ColMajor));
+
+        // vec(A^T) reads A by columns.
+        assert_eq!(to_vec(&A.clone().transpose().vec()), vec![1, 2, 3, 4, 5, 6]);
+        assert!(DenseColumn::from_vec(vec![1, 2, 3]).unvec(2, 2, None).is_err());
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_stack_reshape() {
+        use matrix::Matrix;
+
+        let A = DenseMatrix::new(&[vec![1, 2], vec![3, 4]]).unwrap();
+        let B = DenseMatrix::new(&[vec![5], vec![6]]).unwrap();
+        let H = DenseMatrix::hstack(&[&A, &B]).unwrap();
+        assert_eq!(H, DenseMatrix::new(&[vec![1, 2, 5], vec![3, 4, 6]]).unwrap());
+        let V = DenseMatrix::vstack(&[&A, &B.clone().transpose()]).unwrap();
+        assert_eq!(V, DenseMatrix::new(&[vec![1, 2], vec![3, 4], vec![5, 6]]).unwrap());
+        assert!(DenseMatrix::hstack(&[&A, &V]).is_err());
+        assert!(DenseMatrix::<i32>::vstack(&[]).is_err());
+
+        let D = DenseMatrix::block_diag(&[&A, &B]);
+        assert_eq!(D, DenseMatrix::new(&[vec![1, 2, 0],
+                                            vec![3, 4, 0],
+                                            vec![0, 0, 5],
+                                            vec![0, 0, 6]]).unwrap());
+
+        assert_eq!(H.reshape(3, 2).unwrap(), DenseMatrix::new(&[vec![1, 2], vec![5, 3], vec![4, 6]]).unwrap());
+        assert!(H.reshape(4, 2).is_err());
+        assert_eq!(B.tile(1, 2), DenseMatrix::new(&[vec![5, 5], vec![6, 6]]).unwrap());
+        assert_eq!(A.tile(2, 1), DenseMatrix::vstack(&[&A, &A]).unwrap());
+        assert_eq!(B.repeat(2, 1), DenseMatrix::new(&[vec![5], vec![5], vec![6], vec![6]]).unwrap());
+        assert_eq!(A.repeat(1, 2).row(1).unwrap(), DenseMatrix::new(&[vec![3, 3, 4, 4]]).unwrap());
+
+        assert_eq!(A.flip_ud(), DenseMatrix::new(&[vec![3, 4], vec![1, 2]]).unwrap());
+        assert_eq!(A.flip_lr(), DenseMatrix::new(&[vec![2, 1], vec![4, 3]]).unwrap());
+        assert_eq!(H.rot90(1), DenseMatrix::new(&[vec![5, 6], vec![2, 4], vec![1, 3]]).unwrap());
+        assert_eq!(H.rot90(2), H.flip_ud().flip_lr());
+        assert_eq!(H.rot90(3), H.rot90(1).rot90(2));
+        assert_eq!(H.rot90(4), H);
+        // Transposed operands are read logically.
+        assert_eq!(A.clone().transpose().flip_lr(), A.rot90(3));
+
+        let R = A.insert_row(1, &[7, 8]).unwrap();
+        assert_eq!(R, DenseMatrix::new(&[vec![1, 2], vec![7, 8], vec![3, 4]]).unwrap());
+        assert_eq!(R.remove_row(1).unwrap(), A);
+        let C = A.insert_col(2, &[9, 9]).unwrap();
+        assert_eq!(C, DenseMatrix::new(&[vec![1, 2, 9], vec![3, 4, 9]]).unwrap());
+        assert_eq!(C.remove_col(2).unwrap(), A);
+        assert!(A.insert_row(3, &[0, 0]).is_err());
+        assert!(A.insert_col(0, &[0]).is_err());
+        assert!(A.remove_col(2).is_err());
+    }
 }
@@ -0,0 +1,149 @@
+use ::{Num};
+use ::{DenseMatrix, Matrix};
+
+/// Build an m by n row major matrix with `a_ij = f(i, j)`.
+fn from_fn<T, F>(m: usize, n: usize, f: F) -> DenseMatrix<T>
+    where T: Clone + Copy + Num, F: Fn(usize, usize) -> T
+{
+    let mut mat = Vec::with_capacity(m*n);
+    for i in 0..m {
+        for j in 0..n {
+            mat.push(f(i, j));
+        }
+    }
+    DenseMatrix::from_vec(mat, m, n, None).unwrap()
+}
+
+/// The results are always new row major matrices, whatever the read order of
+/// the operands.
+impl<T: Clone + Copy + Num> DenseMatrix<T> {
+    /// Concatenate matrices with equal row counts side by side. Fails if
+    /// `mats` is empty or the row counts differ.
+    pub fn hstack(mats: &[&DenseMatrix<T>]) -> ::Result<DenseMatrix<T>> {
+        let m = match mats.first() {
+            Some(a) => a.rows(),
+            None => return Err(::Error::InvalidDimensions),
+        };
+        if mats.iter().any(|a| a.rows() != m) { return Err(::Error::InvalidDimensions) }
+        let n = mats.iter().map(|a| a.cols()).sum();
+        let mut mat = Vec::with_capacity(m*n);
+        for i in 0..m {
+            for a in mats {
+                mat.extend((0..a.cols()).map(|j| a.get(i, j).unwrap()));
+            }
+        }
+        DenseMatrix::from_vec(mat, m, n, None)
+    }
+
+    /// Concatenate matrices with equal column counts one above another. Fails
+    /// if `mats` is empty or the column counts differ.
+    pub fn vstack(mats: &[&DenseMatrix<T>]) -> ::Result<DenseMatrix<T>> {
+        let n = match mats.first() {
+            Some(a) => a.cols(),
+            None => return Err(::Error::InvalidDimensions),
+        };
+        if mats.iter().any(|a| a.cols() != n) { return Err(::Error::InvalidDimensions) }
+        let m = mats.iter().map(|a| a.rows()).sum();
+        let mut mat = Vec::with_capacity(m*n);
+        for a in mats {
+            mat.extend(a.elements());
+        }
+        DenseMatrix::from_vec(mat, m, n, None)
+    }
+
+    /// Place matrices along the diagonal of a block matrix, with zeros
+    /// elsewhere.
+    pub fn block_diag(mats: &[&DenseMatrix<T>]) -> DenseMatrix<T> {
+        let m = mats.iter().map(|a| a.rows()).sum();
+        let n = mats.iter().map(|a| a.cols()).sum();
+        let c = DenseMatrix::zeros(m, n);
+        let (mut r0, mut c0) = (0, 0);
+        for a in mats {
+            for i in 0..a.rows() {
+                for j in 0..a.cols() {
+                    c.set(r0 + i, c0 + j, a.get(i, j).unwrap());
+                }
+            }
+            r0 += a.rows();
+            c0 += a.cols();
+        }
+        c
+    }
+
+    /// Reshape into an m by n matrix, reading and filling in row major order.
+    /// Fails unless the number of elements is unchanged.
+    pub fn reshape(&self, m: usize, n: usize) -> ::Result<DenseMatrix<T>> {
+        DenseMatrix::from_vec(self.elements(), m, n, None)
+    }
+
+    /// Repeat the whole matrix `r` times vertically and `c` times
+    /// horizontally.
+    pub fn tile(&self, r: usize, c: usize) -> DenseMatrix<T> {
+        let (m, n) = self.dims();
+        from_fn(m*r, n*c, |i, j| self.get(i % m, j % n).unwrap())
+    }
+
+    /// Repeat each element into an `r` by `c` block, so that the result is
+    /// `A ⊗ ones(r, c)`.
+    pub fn repeat(&self, r: usize, c: usize) -> DenseMatrix<T> {
+        let (m, n) = self.dims();
+        from_fn(m*r, n*c, |i, j| self.get(i / r, j / c).unwrap())
+    }
+
+    /// Reverse the order of the rows.
+    pub fn flip_ud(&self) -> DenseMatrix<T> {
+        let (m, n) = self.dims();
+        from_fn(m, n, |i, j| self.get(m - 1 - i, j).unwrap())
+    }
+
+    /// Reverse the order of the columns.
+    pub fn flip_lr(&self) -> DenseMatrix<T> {
+        let (m, n) = self.dims();
+        from_fn(m, n, |i, j| self.get(i, n - 1 - j).unwrap())
+    }
+
+    /// Rotate by 90 degrees counterclockwise `k` times.
+    pub fn rot90(&self, k: usize) -> DenseMatrix<T> {
+        let (m, n) = self.dims();
+        match k % 4 {
+            0 => from_fn(m, n, |i, j| self.get(i, j).unwrap()),
+            1 => from_fn(n, m, |i, j| self.get(j, n - 1 - i).unwrap()),
+            2 => from_fn(m, n, |i, j| self.get(m - 1 - i, n - 1 - j).unwrap()),
+            _ => from_fn(n, m, |i, j| self.get(m - 1 - j, i).unwrap()),
+        }
+    }
+
+    /// Insert `row` so that it becomes row i. Fails unless `i <= rows()` and
+    /// `row` has length `cols()`.
+    pub fn insert_row(&self, i: usize, row: &[T]) -> ::Result<DenseMatrix<T>> {
+        let (m, n) = self.dims();
+        if i > m || row.len() != n { return Err(::Error::InvalidDimensions) }
+        Ok(from_fn(m + 1, n, |r, j| {
+            if r < i { self.get(r, j).unwrap() } else if r == i { row[j] } else { self.get(r - 1, j).unwrap() }
+        }))
+    }
+
+    /// Insert `col` so that it becomes column j. Fails unless `j <= cols()`
+    /// and `col` has length `rows()`.
+    pub fn insert_col(&self, j: usize, col: &[T]) -> ::Result<DenseMatrix<T>> {
+        let (m, n) = self.dims();
+        if j > n || col.len() != m { return Err(::Error::InvalidDimensions) }
+        Ok(from_fn(m, n + 1, |i, c| {
+            if c < j { self.get(i, c).unwrap() } else if c == j { col[i] } else { self.get(i, c - 1).unwrap() }
+        }))
+    }
+
+    /// Remove row i. Fails unless `i < rows()`.
+    pub fn remove_row(&self, i: usize) -> ::Result<DenseMatrix<T>> {
+        let (m, n) = self.dims();
+        if i >= m { return Err(::Error::InvalidDimensions) }
+        Ok(from_fn(m - 1, n, |r, j| self.get(if r < i { r } else { r + 1 }, j).unwrap()))
+    }
+
+    /// Remove column j. Fails unless `j < cols()`.
+    pub fn remove_col(&self, j: usize) -> ::Result<DenseMatrix<T>> {
+        let (m, n) = self.dims();
+        if j >= n { return Err(::Error::InvalidDimensions) }
+        Ok(from_fn(m, n - 1, |i, c| self.get(i, if c < j { c } else { c + 1 }).unwrap()))
+    }
+}
@@ -0,0 +1,108 @@
+use ::{Complex, DenseMatrix, Float, LinearOperator, Zero};
+use ::linalg::{complex_schur, triangular_eigenvectors};
+use super::{ComplexEigenPairs, EigsOptions};
+use super::krylov;
+
+/// Compute `k = opts.nev` eigenpairs of a general (nonsymmetric) operator
+/// with the implicitly restarted Arnoldi method.
+///
+/// Unwanted real Ritz values are applied as single shifts and complex
+/// conjugate pairs as a real double shift, so all restart arithmetic stays
+/// real. The eigenvalues and eigenvectors are returned as `Complex<T>`; when
+/// a wanted eigenvalue is complex its conjugate is usually wanted too.
+pub fn arnoldi<T, A>(a: &A, opts: &EigsOptions<T>) -> ::Result<ComplexEigenPairs<T>>
+    where T: Float, A: LinearOperator<T>,
+{
+    let (n, cols) = a.shape();
+    let k = opts.nev;
+    if n != cols || k == 0 || k > n { return Err(::Error::InvalidDimensions) }
+    let m = match opts.ncv {
+        Some(ncv) => ncv,
+        None => if 2*k + 1 > 20 { 2*k + 1 } else { 20 },
+    };
+    let m = if m > n { n } else { m };
+    if k + 1 >= m && m < n { return Err(::Error::InvalidDimensions) }
+
+    let mut v0 = match opts.v0 {
+        Some(ref v0) if v0.len() == n => v0.clone(),
+        Some(_) => return Err(::Error::InvalidDimensions),
+        None => krylov::start_vector(n, 0),
+    };
+    let v0_norm = krylov::norm(&v0);
+    for a in v0.iter_mut() { *a = *a / v0_norm }
+
+    let mut v = vec![v0];
+    let mut h = vec![T::zero(); m*m];
+    let mut start = 0;
+    for iter in 0..(opts.max_iter + 1) {
+        let beta = krylov::extend(a, &mut v, &mut h, m, start);
+
+        let hc: Vec<Complex<T>> = h.iter().map(|&x| Complex::new(x, T::zero())).collect();
+        let schur = complex_schur(&hc, m)?;
+        let (q, t) = (schur.q, schur.t);
+        let ritz: Vec<Complex<T>> = (0..m).map(|i| t[i*m + i]).collect();
+        let s = triangular_eigenvectors(&t, m);
+        let mut y = vec![Complex::zero(); m*m];
+        for i in 0..m {
+            for j in 0..m {
+                y[i*m + j] = (0..m).fold(Complex::zero(), |acc, l| acc + q[i*m + l] * s[l*m + j]);
+            }
+        }
+        let order = opts.which.order(&ritz);
+
+        let converged = order[..k].iter().all(|&i| {
+            y[(m - 1)*m + i].scale(beta).norm() <= opts.threshold(ritz[i].norm())
+        });
+        if converged || m == n {
+            let wanted = &order[..k];
+            let mut mat = vec![Complex::zero(); n*k];
+            for (col, &c) in wanted.iter().enumerate() {
+                for l in 0..m {
+                    let coef = y[l*m + c];
+                    for i in 0..n {
+                        mat[i*k + col] = mat[i*k + col] + coef.scale(v[l][i]);
+                    }
+                }
+                let norm = (0..n).fold(T::zero(), |acc, i| acc + mat[i*k + col].norm_sqr()).sqrt();
+                for i in 0..n {
+                    mat[i*k + col] = mat[i*k + col].unscale(norm);
+                }
+            }
+            return Ok(ComplexEigenPairs {
+                values: wanted.iter().map(|&i| ritz[i]).collect(),
+                vectors: DenseMatrix::from_vec(mat, n, k, None).unwrap(),
+            })
+        }
+        if iter == opts.max_iter { break }
+
+        // Never split a complex conjugate pair between the kept and the
+        // discarded parts of the factorization.
+        let tiny = T::epsilon().sqrt();
+        let mut keep = k;
+        let last = ritz[order[k - 1]];
+        if last.im.abs() > tiny * last.norm() &&
+            (ritz[order[k]] - last.conj()).norm() <= tiny * last.norm() &&
+            keep + 1 < m
+        {
+            keep += 1;
+        }
+
+        let mut acc = vec![T::zero(); m*m];
+        for i in 0..m {
+            acc[i*m + i] = T::one();
+        }
+        for &i in order[keep..].iter() {
+            let mu = ritz[i];
+            if mu.im.abs() <= tiny * mu.norm() {
+                let p = krylov::shifted(&h, m, mu.re);
+                krylov::apply_shift(&mut h, &mut acc, &p, m);
+            } else if mu.im > T::zero() {
+                let p = krylov::double_shifted(&h, m, mu.re, mu.im);
+                krylov::apply_shift(&mut h, &mut acc, &p, m);
+            }
+        }
+        krylov::truncate(&mut v, &mut h, &acc, m, keep, beta);
+        start = keep;
+    }
+    Err(::Error::NotConverged)
+}
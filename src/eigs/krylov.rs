@@ -0,0 +1,238 @@
+// Building blocks shared by the restarted Krylov solvers.
+//
+// A Krylov factorization of length `m` is `A V = V H + f e_m^T`, where the
+// `m` columns of `V` are orthonormal, `H` is an `m` by `m` upper Hessenberg
+// matrix and `f` is orthogonal to `V`. We keep `V` as `m + 1` vectors, the
+// last one being `f / |f|`, and `H` as a row major `m*m` Vec.
+
+use ::{Float, LinearOperator};
+
+#[inline]
+pub fn dot<T: Float>(x: &[T], y: &[T]) -> T {
+    x.iter().zip(y).fold(T::zero(), |acc, (&a, &b)| acc + a * b)
+}
+
+#[inline]
+pub fn norm<T: Float>(x: &[T]) -> T {
+    dot(x, x).sqrt()
+}
+
+/// Compute `y = y - alpha x`.
+#[inline]
+pub fn sub_scaled<T: Float>(y: &mut [T], alpha: T, x: &[T]) {
+    for (a, &b) in y.iter_mut().zip(x) {
+        *a = *a - alpha * b;
+    }
+}
+
+/// A deterministic pseudo-random vector in `[-1, 1)^n`, used when the caller
+/// does not supply a starting vector.
+pub fn start_vector<T: Float>(n: usize, seed: u64) -> Vec<T> {
+    let mut state = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+    (0..n).map(|_| {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let unit = (state >> 11) as f64 / (1u64 << 53) as f64;
+        T::from(2.0 * unit - 1.0).unwrap()
+    }).collect()
+}
+
+/// Orthogonalize `w` against `basis` with two passes of classical
+/// Gram-Schmidt, returning the accumulated coefficients.
+pub fn orthogonalize<T: Float>(basis: &[Vec<T>], w: &mut [T]) -> Vec<T> {
+    let mut coeffs = vec![T::zero(); basis.len()];
+    for _ in 0..2 {
+        for (i, v) in basis.iter().enumerate() {
+            let c = dot(v, w);
+            coeffs[i] = coeffs[i] + c;
+            sub_scaled(w, c, v);
+        }
+    }
+    coeffs
+}
+
+/// Find a unit vector orthogonal to `basis`, used to continue the iteration
+/// after the Krylov space has become invariant. Returns the zero vector when
+/// `basis` already spans the whole space.
+fn fresh_vector<T: Float>(basis: &[Vec<T>], n: usize) -> Vec<T> {
+    if basis.len() >= n { return vec![T::zero(); n] }
+    let mut seed = basis.len() as u64;
+    loop {
+        let mut w = start_vector(n, seed);
+        orthogonalize(basis, &mut w);
+        let beta = norm(&w);
+        if beta > T::from(1e-3).unwrap() {
+            return w.iter().map(|&a| a / beta).collect()
+        }
+        seed += 1;
+    }
+}
+
+/// Extend a Krylov factorization of length `start` to length `m`, assuming
+/// `v[start]` is already a unit vector orthogonal to `v[0..start]`.
+///
+/// Returns the norm of the final residual `f`. On return `v` holds `m + 1`
+/// vectors.
+pub fn extend<T, A>(a: &A, v: &mut Vec<Vec<T>>, h: &mut [T], m: usize, start: usize) -> T
+    where T: Float, A: LinearOperator<T>,
+{
+    let n = v[0].len();
+    v.truncate(start + 1);
+    let mut beta = T::zero();
+    for j in start..m {
+        let mut w = a.apply(&v[j]);
+        let coeffs = orthogonalize(&v[..(j + 1)], &mut w);
+        for (i, &c) in coeffs.iter().enumerate() {
+            h[i*m + j] = c;
+        }
+        for i in (j + 2)..m {
+            h[i*m + j] = T::zero();
+        }
+        let scale = coeffs.iter().fold(T::zero(), |acc, &c| acc.max(c.abs()));
+        beta = norm(&w);
+        if beta <= T::epsilon() * scale || beta == T::zero() {
+            beta = T::zero();
+            v.push(fresh_vector(&v[..], n));
+        } else {
+            v.push(w.iter().map(|&a| a / beta).collect());
+        }
+        if j + 1 < m { h[(j + 1)*m + j] = beta }
+    }
+    beta
+}
+
+/// Apply one implicit restart shift polynomial to the factorization. `p` is
+/// the shift polynomial evaluated at `H`; we compute its QR factorization
+/// `p(H) = Q R` and update `H = Q^T H Q` and `acc = acc Q`.
+pub fn apply_shift<T: Float>(h: &mut [T], acc: &mut [T], p: &[T], m: usize) {
+    let two = T::one() + T::one();
+    let mut r = p.to_vec();
+    let mut q = vec![T::zero(); m*m];
+    for i in 0..m {
+        q[i*m + i] = T::one();
+    }
+    for k in 0..m.saturating_sub(1) {
+        let mut x: Vec<T> = (k..m).map(|i| r[i*m + k]).collect();
+        let xnorm = norm(&x);
+        if xnorm == T::zero() { continue }
+        let alpha = if x[0] >= T::zero() { -xnorm } else { xnorm };
+        x[0] = x[0] - alpha;
+        let vnorm = norm(&x);
+        if vnorm == T::zero() { continue }
+        for a in x.iter_mut() { *a = *a / vnorm }
+        for j in 0..m {
+            let s = (k..m).fold(T::zero(), |acc, i| acc + x[i - k] * r[i*m + j]);
+            for i in k..m {
+                r[i*m + j] = r[i*m + j] - two * x[i - k] * s;
+            }
+        }
+        for i in 0..m {
+            let s = (k..m).fold(T::zero(), |acc, j| acc + q[i*m + j] * x[j - k]);
+            for j in k..m {
+                q[i*m + j] = q[i*m + j] - two * s * x[j - k];
+            }
+        }
+    }
+
+    let mut hq = vec![T::zero(); m*m];
+    for i in 0..m {
+        for j in 0..m {
+            hq[i*m + j] = (0..m).fold(T::zero(), |acc, l| acc + h[i*m + l] * q[l*m + j]);
+        }
+    }
+    for i in 0..m {
+        for j in 0..m {
+            h[i*m + j] = (0..m).fold(T::zero(), |acc, l| acc + q[l*m + i] * hq[l*m + j]);
+        }
+    }
+    let old = acc.to_vec();
+    for i in 0..m {
+        for j in 0..m {
+            acc[i*m + j] = (0..m).fold(T::zero(), |a, l| a + old[i*m + l] * q[l*m + j]);
+        }
+    }
+}
+
+/// Evaluate `H - mu I`.
+pub fn shifted<T: Float>(h: &[T], m: usize, mu: T) -> Vec<T> {
+    let mut p = h.to_vec();
+    for i in 0..m {
+        p[i*m + i] = p[i*m + i] - mu;
+    }
+    p
+}
+
+/// Evaluate `H^2 - 2 re H + (re^2 + im^2) I`, the real double shift for the
+/// complex conjugate pair `re +- i im`.
+pub fn double_shifted<T: Float>(h: &[T], m: usize, re: T, im: T) -> Vec<T> {
+    let two = T::one() + T::one();
+    let mut p = vec![T::zero(); m*m];
+    for i in 0..m {
+        for j in 0..m {
+            let hh = (0..m).fold(T::zero(), |acc, l| acc + h[i*m + l] * h[l*m + j]);
+            p[i*m + j] = hh - two * re * h[i*m + j];
+        }
+        p[i*m + i] = p[i*m + i] + re * re + im * im;
+    }
+    p
+}
+
+/// Truncate a shifted factorization of length `m` with residual norm `beta`
+/// down to length `k`, using the accumulated restart transformation `acc`.
+pub fn truncate<T: Float>(v: &mut Vec<Vec<T>>, h: &mut [T], acc: &[T], m: usize,
+                          k: usize, beta: T)
+{
+    let n = v[0].len();
+    let mut vk: Vec<Vec<T>> = Vec::with_capacity(k + 1);
+    for j in 0..(k + 1) {
+        let mut col = vec![T::zero(); n];
+        for i in 0..m {
+            let q = acc[i*m + j];
+            if q != T::zero() {
+                for (c, &a) in col.iter_mut().zip(&v[i]) {
+                    *c = *c + q * a;
+                }
+            }
+        }
+        vk.push(col);
+    }
+
+    let h_sub = h[k*m + k - 1];
+    let sigma = acc[(m - 1)*m + k - 1];
+    let mut f: Vec<T> = vk[k].iter().zip(&v[m])
+        .map(|(&a, &b)| a * h_sub + b * beta * sigma)
+        .collect();
+    vk.truncate(k);
+    orthogonalize(&vk, &mut f);
+    let beta_k = norm(&f);
+
+    let old = h.to_vec();
+    for a in h.iter_mut() { *a = T::zero() }
+    for i in 0..k {
+        for j in 0..k {
+            h[i*m + j] = old[i*m + j];
+        }
+    }
+    let scale = old.iter().fold(T::zero(), |acc, &a| acc.max(a.abs()));
+    if beta_k <= T::epsilon() * scale || beta_k == T::zero() {
+        let fresh = fresh_vector(&vk, n);
+        vk.push(fresh);
+    } else {
+        h[k*m + k - 1] = beta_k;
+        vk.push(f.iter().map(|&a| a / beta_k).collect());
+    }
+    *v = vk;
+}
+
+/// Form the Ritz vectors `V y` for the given columns of the row major `m*m`
+/// coefficient matrix `y`.
+pub fn ritz_vectors<T: Float>(v: &[Vec<T>], y: &[T], m: usize, cols: &[usize]) -> Vec<Vec<T>> {
+    let n = v[0].len();
+    cols.iter().map(|&c| {
+        let mut x = vec![T::zero(); n];
+        for i in 0..m {
+            sub_scaled(&mut x, -y[i*m + c], &v[i]);
+        }
+        let xnorm = norm(&x);
+        x.iter().map(|&a| a / xnorm).collect()
+    }).collect()
+}
@@ -0,0 +1,88 @@
+use ::{Complex, DenseMatrix, Float, LinearOperator};
+use ::linalg::jacobi_eigen;
+use super::{EigenPairs, EigsOptions};
+use super::krylov;
+
+/// Compute `k = opts.nev` eigenpairs of a symmetric operator with the
+/// implicitly restarted Lanczos method.
+///
+/// Each restart applies the unwanted Ritz values as exact shifts, which
+/// filters them out of the starting vector while keeping the wanted part of
+/// the Krylov space. Only matrix-vector products with `a` are needed, so `a`
+/// may be a `SparseMatrix` or any other `LinearOperator`.
+///
+/// Interior or smallest-magnitude eigenvalues converge slowly; prefer
+/// `Which::SmallestAlgebraic` for positive (semi-)definite operators.
+pub fn lanczos<T, A>(a: &A, opts: &EigsOptions<T>) -> ::Result<EigenPairs<T>>
+    where T: Float, A: LinearOperator<T>,
+{
+    let (n, cols) = a.shape();
+    let k = opts.nev;
+    if n != cols || k == 0 || k > n { return Err(::Error::InvalidDimensions) }
+    let m = match opts.ncv {
+        Some(ncv) => ncv,
+        None => if 2*k + 1 > 20 { 2*k + 1 } else { 20 },
+    };
+    let m = if m > n { n } else { m };
+    if k >= m && m < n { return Err(::Error::InvalidDimensions) }
+
+    let mut v0 = match opts.v0 {
+        Some(ref v0) if v0.len() == n => v0.clone(),
+        Some(_) => return Err(::Error::InvalidDimensions),
+        None => krylov::start_vector(n, 0),
+    };
+    let v0_norm = krylov::norm(&v0);
+    for a in v0.iter_mut() { *a = *a / v0_norm }
+
+    let mut v = vec![v0];
+    let mut h = vec![T::zero(); m*m];
+    let mut start = 0;
+    for iter in 0..(opts.max_iter + 1) {
+        let beta = krylov::extend(a, &mut v, &mut h, m, start);
+
+        let mut sym = h.clone();
+        for i in 0..m {
+            for j in 0..i {
+                let avg = (h[i*m + j] + h[j*m + i]) / (T::one() + T::one());
+                sym[i*m + j] = avg;
+                sym[j*m + i] = avg;
+            }
+        }
+        let (theta, y) = jacobi_eigen(&sym, m);
+        let ritz: Vec<Complex<T>> = theta.iter()
+            .map(|&t| Complex::new(t, T::zero()))
+            .collect();
+        let order = opts.which.order(&ritz);
+
+        let converged = order[..k].iter().all(|&i| {
+            (beta * y[(m - 1)*m + i]).abs() <= opts.threshold(theta[i])
+        });
+        if converged || m == n {
+            let wanted = &order[..k];
+            let x = krylov::ritz_vectors(&v, &y, m, wanted);
+            let mut mat = Vec::with_capacity(n*k);
+            for i in 0..n {
+                for col in x.iter() {
+                    mat.push(col[i]);
+                }
+            }
+            return Ok(EigenPairs {
+                values: wanted.iter().map(|&i| theta[i]).collect(),
+                vectors: DenseMatrix::from_vec(mat, n, k, None).unwrap(),
+            })
+        }
+        if iter == opts.max_iter { break }
+
+        let mut acc = vec![T::zero(); m*m];
+        for i in 0..m {
+            acc[i*m + i] = T::one();
+        }
+        for &i in order[k..].iter() {
+            let p = krylov::shifted(&h, m, theta[i]);
+            krylov::apply_shift(&mut h, &mut acc, &p, m);
+        }
+        krylov::truncate(&mut v, &mut h, &acc, m, k, beta);
+        start = k;
+    }
+    Err(::Error::NotConverged)
+}
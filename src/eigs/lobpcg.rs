@@ -0,0 +1,170 @@
+use ::{Complex, DenseMatrix, Float, LinearOperator};
+use ::linalg::jacobi_eigen;
+use super::{EigenPairs, EigsOptions, Which};
+use super::krylov::{dot, norm, orthogonalize, start_vector, sub_scaled};
+
+/// A Preconditioner approximates the action of `A^-1` on a residual vector.
+/// Any `Fn(&[T]) -> Vec<T>` closure can be used as one.
+pub trait Preconditioner<T> {
+    /// Apply the preconditioner to the residual `r`.
+    fn precondition(&self, r: &[T]) -> Vec<T>;
+}
+
+impl<T, F> Preconditioner<T> for F where F: Fn(&[T]) -> Vec<T> {
+    fn precondition(&self, r: &[T]) -> Vec<T> {
+        self(r)
+    }
+}
+
+/// The identity preconditioner, which leaves residuals unchanged.
+#[derive(Clone, Copy, Debug)]
+pub struct NoPreconditioner;
+
+impl<T: Copy> Preconditioner<T> for NoPreconditioner {
+    fn precondition(&self, r: &[T]) -> Vec<T> {
+        r.to_vec()
+    }
+}
+
+/// Add `cols` to the orthonormal `basis`, dropping any that are (nearly)
+/// linearly dependent on what is already there.
+fn extend_basis<T: Float>(basis: &mut Vec<Vec<T>>, cols: Vec<Vec<T>>) {
+    for mut w in cols {
+        let before = norm(&w);
+        if before == T::zero() { continue }
+        orthogonalize(&basis[..], &mut w);
+        let after = norm(&w);
+        if after > T::from(1e-10).unwrap() * before {
+            basis.push(w.iter().map(|&a| a / after).collect());
+        }
+    }
+}
+
+/// Combine the columns of `basis` with the `dim` by `dim` row major
+/// coefficients `c`, taking columns `cols` of `c` and rows `rows` of it.
+fn combine<T: Float>(basis: &[Vec<T>], c: &[T], dim: usize, rows: ::std::ops::Range<usize>,
+                     cols: &[usize]) -> Vec<Vec<T>>
+{
+    let n = basis[0].len();
+    cols.iter().map(|&j| {
+        let mut x = vec![T::zero(); n];
+        for i in rows.clone() {
+            sub_scaled(&mut x, -c[i*dim + j], &basis[i]);
+        }
+        x
+    }).collect()
+}
+
+/// Compute `k = opts.nev` extreme eigenpairs of a symmetric operator with
+/// the locally optimal block preconditioned conjugate gradient method.
+///
+/// Every iteration performs a Rayleigh-Ritz projection onto the span of the
+/// current iterates, their preconditioned residuals and the previous search
+/// directions. A good preconditioner (an approximate inverse of `a`) speeds
+/// up convergence dramatically; pass `NoPreconditioner` to go without.
+///
+/// LOBPCG finds the algebraically largest or smallest eigenvalues, so the
+/// magnitude variants of `Which` are treated as their algebraic ones.
+pub fn lobpcg<T, A, P>(a: &A, precond: &P, opts: &EigsOptions<T>) -> ::Result<EigenPairs<T>>
+    where T: Float, A: LinearOperator<T>, P: Preconditioner<T>,
+{
+    let (n, cols) = a.shape();
+    let k = opts.nev;
+    if n != cols || k == 0 || k >= n { return Err(::Error::InvalidDimensions) }
+    let largest = match opts.which {
+        Which::LargestAlgebraic | Which::LargestMagnitude => true,
+        Which::SmallestAlgebraic | Which::SmallestMagnitude => false,
+    };
+    let pick = |theta: &[T]| -> Vec<usize> {
+        let dim = theta.len();
+        if largest { ((dim - k)..dim).rev().collect() } else { (0..k).collect() }
+    };
+
+    let mut x: Vec<Vec<T>> = Vec::with_capacity(k);
+    match opts.v0 {
+        Some(ref v0) if v0.len() == n => extend_basis(&mut x, vec![v0.clone()]),
+        Some(_) => return Err(::Error::InvalidDimensions),
+        None => {},
+    }
+    let mut seed = 0;
+    while x.len() < k {
+        extend_basis(&mut x, vec![start_vector(n, seed)]);
+        seed += 1;
+    }
+
+    // Initial Rayleigh-Ritz on the block X.
+    let mut ax: Vec<Vec<T>> = x.iter().map(|xi| a.apply(xi)).collect();
+    let mut gram = vec![T::zero(); k*k];
+    for i in 0..k {
+        for j in 0..k {
+            gram[i*k + j] = (dot(&x[i], &ax[j]) + dot(&x[j], &ax[i])) / (T::one() + T::one());
+        }
+    }
+    let (theta, c) = jacobi_eigen(&gram, k);
+    let sel = pick(&theta);
+    let mut theta: Vec<T> = sel.iter().map(|&i| theta[i]).collect();
+    x = combine(&x, &c, k, 0..k, &sel);
+    ax = combine(&ax, &c, k, 0..k, &sel);
+    let mut p: Vec<Vec<T>> = Vec::new();
+
+    for _ in 0..opts.max_iter {
+        let mut active = Vec::with_capacity(k);
+        for i in 0..k {
+            let mut r = ax[i].clone();
+            sub_scaled(&mut r, theta[i], &x[i]);
+            if norm(&r) > opts.threshold(theta[i]) {
+                active.push(precond.precondition(&r));
+            }
+        }
+        if active.is_empty() {
+            let mut mat = Vec::with_capacity(n*k);
+            for i in 0..n {
+                for xj in x.iter() {
+                    mat.push(xj[i]);
+                }
+            }
+            let mut order: Vec<usize> = (0..k).collect();
+            let ritz: Vec<Complex<T>> = theta.iter()
+                .map(|&t| Complex::new(t, T::zero()))
+                .collect();
+            order.sort_by(|&i, &j| opts.which.compare(ritz[i], ritz[j]));
+            let mut sorted = Vec::with_capacity(n*k);
+            for i in 0..n {
+                for &j in order.iter() {
+                    sorted.push(mat[i*k + j]);
+                }
+            }
+            return Ok(EigenPairs {
+                values: order.iter().map(|&i| theta[i]).collect(),
+                vectors: DenseMatrix::from_vec(sorted, n, k, None).unwrap(),
+            })
+        }
+
+        // The basis is [X, W, P] with X kept exactly as it is.
+        let mut basis = x.clone();
+        extend_basis(&mut basis, active);
+        extend_basis(&mut basis, p);
+        let dim = basis.len();
+        let mut abasis = ax.clone();
+        for b in basis[k..].iter() {
+            abasis.push(a.apply(b));
+        }
+
+        let mut gram = vec![T::zero(); dim*dim];
+        for i in 0..dim {
+            for j in 0..(i + 1) {
+                let g = (dot(&basis[i], &abasis[j]) + dot(&basis[j], &abasis[i])) /
+                    (T::one() + T::one());
+                gram[i*dim + j] = g;
+                gram[j*dim + i] = g;
+            }
+        }
+        let (values, c) = jacobi_eigen(&gram, dim);
+        let sel = pick(&values);
+        theta = sel.iter().map(|&i| values[i]).collect();
+        x = combine(&basis, &c, dim, 0..dim, &sel);
+        ax = combine(&abasis, &c, dim, 0..dim, &sel);
+        p = combine(&basis, &c, dim, k..dim, &sel);
+    }
+    Err(::Error::NotConverged)
+}
@@ -0,0 +1,286 @@
+pub use self::arnoldi::arnoldi;
+pub use self::lanczos::lanczos;
+pub use self::lobpcg::{lobpcg, NoPreconditioner, Preconditioner};
+pub use self::randomized::{randomized_evd, randomized_svd, range_finder};
+
+mod arnoldi;
+mod krylov;
+mod lanczos;
+mod lobpcg;
+mod randomized;
+
+use std::cmp::Ordering;
+
+use ::{Complex, DenseMatrix, Float};
+
+/// Which part of the spectrum an iterative eigensolver should look for.
+/// For complex eigenvalues, "algebraic" compares the real parts.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Which {
+    #[default]
+    LargestMagnitude,
+    SmallestMagnitude,
+    LargestAlgebraic,
+    SmallestAlgebraic,
+}
+
+impl Which {
+    /// Order two eigenvalues so that the wanted one comes first.
+    fn compare<T: Float>(&self, a: Complex<T>, b: Complex<T>) -> Ordering {
+        let ord = match *self {
+            Which::LargestMagnitude => b.norm().partial_cmp(&a.norm()),
+            Which::SmallestMagnitude => a.norm().partial_cmp(&b.norm()),
+            Which::LargestAlgebraic => b.re.partial_cmp(&a.re),
+            Which::SmallestAlgebraic => a.re.partial_cmp(&b.re),
+        };
+        ord.unwrap_or(Ordering::Equal)
+    }
+
+    /// Get the indices of `values` sorted so that the wanted ones come first.
+    fn order<T: Float>(&self, values: &[Complex<T>]) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..values.len()).collect();
+        order.sort_by(|&i, &j| self.compare(values[i], values[j]));
+        order
+    }
+}
+
+/// Options shared by the iterative eigensolvers.
+#[derive(Clone, Debug)]
+pub struct EigsOptions<T> {
+    /// The number of eigenpairs `k` to compute.
+    pub nev: usize,
+    /// The Krylov subspace size. Defaults to `max(2k + 1, 20)`, capped at the
+    /// matrix dimension. Ignored by LOBPCG.
+    pub ncv: Option<usize>,
+    /// Which eigenvalues to look for.
+    pub which: Which,
+    /// Relative residual tolerance for an eigenpair to count as converged.
+    pub tol: T,
+    /// Maximum number of restarts (Lanczos, Arnoldi) or iterations (LOBPCG).
+    pub max_iter: usize,
+    /// Optional starting vector. A pseudo-random one is used otherwise.
+    pub v0: Option<Vec<T>>,
+}
+
+impl<T: Float> EigsOptions<T> {
+    /// Create options asking for `nev` eigenpairs at the `which` end of the
+    /// spectrum, with defaults for everything else.
+    pub fn new(nev: usize, which: Which) -> EigsOptions<T> {
+        EigsOptions { nev, which, ..EigsOptions::default() }
+    }
+
+    /// The relative residual threshold for an eigenvalue `theta`.
+    fn threshold(&self, theta: T) -> T {
+        let floor = T::epsilon().powf(T::from(2.0 / 3.0).unwrap());
+        self.tol * theta.abs().max(floor)
+    }
+}
+
+impl<T: Float> Default for EigsOptions<T> {
+    fn default() -> EigsOptions<T> {
+        EigsOptions {
+            nev: 1,
+            ncv: None,
+            which: Which::default(),
+            tol: T::from(1e-10).unwrap(),
+            max_iter: 1000,
+            v0: None,
+        }
+    }
+}
+
+/// Options for the randomized low-rank methods.
+#[derive(Clone, Debug)]
+pub struct RandomizedOptions {
+    /// The target rank `k`.
+    pub rank: usize,
+    /// Extra random vectors beyond `k`, which make the basis more likely to
+    /// capture the wanted subspace. Defaults to 10.
+    pub oversample: usize,
+    /// Number of power iterations with `A A^T`. Defaults to 2.
+    pub power_iters: usize,
+    /// Seed for the random test vectors, so that runs can be reproduced.
+    pub seed: u64,
+}
+
+impl RandomizedOptions {
+    /// Create options asking for a rank `k` approximation, with defaults for
+    /// everything else.
+    pub fn new(rank: usize) -> RandomizedOptions {
+        RandomizedOptions { rank, ..RandomizedOptions::default() }
+    }
+}
+
+impl Default for RandomizedOptions {
+    fn default() -> RandomizedOptions {
+        RandomizedOptions { rank: 1, oversample: 10, power_iters: 2, seed: 0 }
+    }
+}
+
+/// The largest singular values of an operator in decreasing order, with
+/// the left and right singular vectors as the columns of `u` and `v`.
+#[derive(Clone, Debug)]
+pub struct SingularTriplets<T> where T: Copy {
+    pub values: Vec<T>,
+    pub u: DenseMatrix<T>,
+    pub v: DenseMatrix<T>,
+}
+
+/// Real eigenpairs of a symmetric operator. The `i`th column of `vectors`
+/// is the unit eigenvector for `values[i]`.
+#[derive(Clone, Debug)]
+pub struct EigenPairs<T> where T: Copy {
+    pub values: Vec<T>,
+    pub vectors: DenseMatrix<T>,
+}
+
+/// Possibly complex eigenpairs of a general operator. The `i`th column of
+/// `vectors` is the unit eigenvector for `values[i]`.
+#[derive(Clone, Debug)]
+pub struct ComplexEigenPairs<T> where T: Copy {
+    pub values: Vec<Complex<T>>,
+    pub vectors: DenseMatrix<Complex<T>>,
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+    use ::{Complex, DenseMatrix, LinearOperator, Matrix, Rng, SparseMatrix};
+    use super::{arnoldi, lanczos, lobpcg, EigsOptions, NoPreconditioner, Which};
+    use super::{randomized_evd, randomized_svd, range_finder, RandomizedOptions};
+
+    fn laplacian(n: usize) -> SparseMatrix<f64> {
+        let mut tuples = Vec::new();
+        for i in 0..n {
+            tuples.push((i, i, 2.0));
+            if i > 0 { tuples.push((i, i - 1, -1.0)) }
+            if i + 1 < n { tuples.push((i, i + 1, -1.0)) }
+        }
+        sparse![tuples; n, n]
+    }
+
+    fn laplacian_eigenvalue(n: usize, k: usize) -> f64 {
+        2.0 - 2.0 * ((k as f64) * ::std::f64::consts::PI / (n as f64 + 1.0)).cos()
+    }
+
+    #[test]
+    fn test_lanczos_largest() {
+        let n = 100;
+        let A = laplacian(n);
+        let eigs = lanczos(&A, &EigsOptions::new(3, Which::LargestAlgebraic)).unwrap();
+        for i in 0..3 {
+            assert!((eigs.values[i] - laplacian_eigenvalue(n, n - i)).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_lanczos_smallest() {
+        let n = 40;
+        let A = laplacian(n);
+        let mut opts = EigsOptions::new(2, Which::SmallestAlgebraic);
+        opts.ncv = Some(20);
+        let eigs = lanczos(&A, &opts).unwrap();
+        for i in 0..2 {
+            assert!((eigs.values[i] - laplacian_eigenvalue(n, i + 1)).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_arnoldi_complex_pairs() {
+        // Block diagonal with 2x2 rotation blocks [a, -b; b, a], whose
+        // eigenvalues are a +- bi.
+        let n = 40;
+        let mut tuples = Vec::new();
+        for blk in 0..(n / 2) {
+            let (a, b) = (blk as f64 + 1.0, 0.5);
+            let i = 2 * blk;
+            tuples.push((i, i, a));
+            tuples.push((i, i + 1, -b));
+            tuples.push((i + 1, i, b));
+            tuples.push((i + 1, i + 1, a));
+        }
+        let A = sparse![tuples; n, n];
+        let eigs = arnoldi(&A, &EigsOptions::new(2, Which::LargestMagnitude)).unwrap();
+        let top = Complex::new(20.0, 0.5);
+        for z in eigs.values.iter() {
+            assert!((*z - top).norm() < 1e-8 || (*z - top.conj()).norm() < 1e-8);
+        }
+        assert!((eigs.values[0] - eigs.values[1].conj()).norm() < 1e-8);
+    }
+
+    #[test]
+    fn test_lobpcg_smallest() {
+        let n = 60;
+        let A = laplacian(n);
+        let mut opts = EigsOptions::new(3, Which::SmallestAlgebraic);
+        opts.tol = 1e-8;
+        let eigs = lobpcg(&A, &NoPreconditioner, &opts).unwrap();
+        for i in 0..3 {
+            assert!((eigs.values[i] - laplacian_eigenvalue(n, i + 1)).abs() < 1e-8);
+        }
+
+        // A diagonal preconditioner given as a closure.
+        let jacobi = |r: &[f64]| -> Vec<f64> { r.iter().map(|&a| a / 2.0).collect() };
+        let eigs = lobpcg(&A, &jacobi, &opts).unwrap();
+        assert!((eigs.values[0] - laplacian_eigenvalue(n, 1)).abs() < 1e-8);
+        assert!(eigs.values.iter().all(|v| v.is_finite()));
+    }
+
+    #[test]
+    fn test_randomized_svd() {
+        // Singular values 2^-i with the rows permuted.
+        let (m, n) = (50, 30);
+        let tuples = (0..n).map(|i| ((7*i + 3) % m, i, 0.5f64.powi(i as i32))).collect();
+        let A: SparseMatrix<f64> = sparse![tuples; m, n];
+        let mut opts = RandomizedOptions::new(5);
+        opts.seed = 42;
+        let svd = randomized_svd(&A, &opts).unwrap();
+        assert_eq!((svd.u.dims(), svd.v.dims()), ((m, 5), (n, 5)));
+        for i in 0..5 {
+            assert!((svd.values[i] - 0.5f64.powi(i as i32)).abs() < 1e-12);
+            let v: Vec<f64> = (0..n).map(|r| svd.v.get(r, i).unwrap()).collect();
+            for (r, x) in A.apply(&v).into_iter().enumerate() {
+                assert!((x - svd.values[i] * svd.u.get(r, i).unwrap()).abs() < 1e-12);
+            }
+        }
+        let again = randomized_svd(&A, &opts).unwrap();
+        assert_eq!(svd.values, again.values);
+
+        // A dense matrix of rank 3 is captured exactly.
+        let mut rng = Rng::new(7);
+        let X: Vec<f64> = (0..60).map(|_| rng.normal()).collect();
+        let Y: Vec<f64> = (0..45).map(|_| rng.normal()).collect();
+        let B = DenseMatrix::from_vec((0..300).map(|k| {
+            (0..3).fold(0.0, |acc, r| acc + X[(k / 15)*3 + r] * Y[(k % 15)*3 + r])
+        }).collect(), 20, 15, None).unwrap();
+        let Q = range_finder(&B, &RandomizedOptions::new(3)).unwrap();
+        for j in 0..15 {
+            let col: Vec<f64> = (0..20).map(|i| B.get(i, j).unwrap()).collect();
+            let proj = Q.apply(&Q.apply_transpose(&col));
+            assert!(col.iter().zip(&proj).all(|(a, b)| (a - b).abs() < 1e-10));
+        }
+        let svd = randomized_svd(&B, &RandomizedOptions::new(4)).unwrap();
+        assert!(svd.values[2] > 1e-3 && svd.values[3] < 1e-10);
+        assert!(randomized_svd(&B, &RandomizedOptions::new(16)).is_err());
+
+        // A symmetric matrix with eigenvalues of both signs.
+        let lambda = [-4.0, 3.0, 2.0, -1.0, 0.5, 0.25];
+        let V = DenseMatrix::from_vec((0..100).map(|_| rng.normal()).collect(), 10, 10, None).unwrap().qr().q();
+        let S = DenseMatrix::from_vec((0..100).map(|k| {
+            lambda.iter().enumerate().fold(0.0, |acc, (r, l)| {
+                acc + l * V.get(k / 10, r).unwrap() * V.get(k % 10, r).unwrap()
+            })
+        }).collect(), 10, 10, None).unwrap();
+        let eigs = randomized_evd(&S, &RandomizedOptions::new(3)).unwrap();
+        assert_eq!(eigs.values.len(), 3);
+        for (x, y) in eigs.values.iter().zip(&lambda) {
+            assert!((x - y).abs() < 1e-10);
+        }
+        for j in 0..3 {
+            let v: Vec<f64> = (0..10).map(|i| eigs.vectors.get(i, j).unwrap()).collect();
+            for (i, x) in S.apply(&v).into_iter().enumerate() {
+                assert!((x - eigs.values[j] * v[i]).abs() < 1e-10);
+            }
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use std::cmp::Ordering;
+
+use ::{DenseMatrix, Float, LinearOperator, Rng};
+use ::linalg::jacobi_eigen;
+use super::{EigenPairs, RandomizedOptions, SingularTriplets};
+use super::krylov;
+
+/// The most sweeps of the one-sided Jacobi SVD. It converges quadratically,
+/// so this is never reached in practice.
+static MAX_SWEEPS: usize = 60;
+
+/// Orthonormalize `cols` in order, dropping any that vanish. Vectors that
+/// are nearly dependent on the earlier ones are orthogonalized again after
+/// normalizing, so the result is orthonormal to working precision.
+fn orthonormalize<T: Float>(cols: Vec<Vec<T>>) -> Vec<Vec<T>> {
+    let half = T::from(0.5).unwrap();
+    let mut basis: Vec<Vec<T>> = Vec::with_capacity(cols.len());
+    for mut w in cols {
+        for _ in 0..3 {
+            let before = krylov::norm(&w);
+            krylov::orthogonalize(&basis, &mut w);
+            let after = krylov::norm(&w);
+            if after == T::zero() { break }
+            for x in w.iter_mut() { *x = *x / after }
+            if after > half * before { break }
+        }
+        if krylov::norm(&w) > half { basis.push(w) }
+    }
+    basis
+}
+
+/// Get an orthonormal basis for the approximate range of `a`, as columns.
+fn range<T, A>(a: &A, opts: &RandomizedOptions) -> ::Result<Vec<Vec<T>>>
+    where T: Float, A: LinearOperator<T>,
+{
+    let (m, n) = a.shape();
+    let k = opts.rank;
+    let min = if m < n { m } else { n };
+    if k == 0 || k > min { return Err(::Error::InvalidDimensions) }
+    let l = if k + opts.oversample < min { k + opts.oversample } else { min };
+
+    let mut rng = Rng::new(opts.seed);
+    let omega: Vec<Vec<T>> = (0..l)
+        .map(|_| (0..n).map(|_| T::from(rng.normal()).unwrap()).collect())
+        .collect();
+    let mut q = orthonormalize(omega.iter().map(|w| a.apply(w)).collect());
+    for _ in 0..opts.power_iters {
+        let z = orthonormalize(q.iter().map(|w| a.apply_transpose(w)).collect());
+        q = orthonormalize(z.iter().map(|w| a.apply(w)).collect());
+    }
+    Ok(q)
+}
+
+/// Collect `cols` vectors of length `m` into an `m` by `cols.len()` matrix.
+fn to_dense<T: Float>(cols: &[Vec<T>], m: usize) -> DenseMatrix<T> {
+    let k = cols.len();
+    let mut mat = vec![T::zero(); m*k];
+    for (j, col) in cols.iter().enumerate() {
+        for (i, &x) in col.iter().enumerate() {
+            mat[i*k + j] = x;
+        }
+    }
+    DenseMatrix::from_vec(mat, m, k, None).unwrap()
+}
+
+/// Get the combinations `sum_r basis[r] * y[r*k + j]` for each column `j`
+/// of the row major `basis.len()` by `k` matrix `y`.
+fn combine<T: Float>(basis: &[Vec<T>], y: &[T], k: usize, cols: &[usize]) -> Vec<Vec<T>> {
+    let len = if basis.is_empty() { 0 } else { basis[0].len() };
+    cols.iter().map(|&j| {
+        let mut v = vec![T::zero(); len];
+        for (r, b) in basis.iter().enumerate() {
+            krylov::sub_scaled(&mut v, -y[r*k + j], b);
+        }
+        v
+    }).collect()
+}
+
+/// Compute an orthonormal basis `Q` with `m` rows whose span approximates
+/// the range of the `m` by `n` operator `a`, so that `A ≈ Q Q^T A`.
+///
+/// `A` is applied to `opts.rank + opts.oversample` Gaussian vectors drawn
+/// from `opts.seed`, followed by `opts.power_iters` rounds of `A A^T`, each
+/// reorthonormalized. Power iterations sharpen the basis when the singular
+/// values decay slowly. Columns are dropped if the range is exhausted, so
+/// `Q` may be narrower than requested for a matrix of low rank.
+pub fn range_finder<T, A>(a: &A, opts: &RandomizedOptions) -> ::Result<DenseMatrix<T>>
+    where T: Float, A: LinearOperator<T>,
+{
+    let q = range(a, opts)?;
+    Ok(to_dense(&q, a.shape().0))
+}
+
+/// Approximate the `k = opts.rank` largest singular triplets of an `m` by
+/// `n` operator with the randomized SVD of Halko, Martinsson and Tropp.
+///
+/// With `Q` from `range_finder`, the small matrix `B = Q^T A` is
+/// decomposed exactly by one-sided Jacobi, and `U = Q U_B`. Only products
+/// with `A` and `A^T` are needed, so `a` may be a `SparseMatrix`. Fewer than
+/// `k` triplets are returned if `A` has lower rank.
+pub fn randomized_svd<T, A>(a: &A, opts: &RandomizedOptions) -> ::Result<SingularTriplets<T>>
+    where T: Float, A: LinearOperator<T>,
+{
+    let (m, n) = a.shape();
+    let q = range(a, opts)?;
+    let l = q.len();
+
+    // W = B^T, whose columns are rotated until orthogonal: W J = V Sigma,
+    // so that B = J Sigma V^T.
+    let mut w: Vec<Vec<T>> = q.iter().map(|q_i| a.apply_transpose(q_i)).collect();
+    let mut j = vec![T::zero(); l*l];
+    for i in 0..l {
+        j[i*l + i] = T::one();
+    }
+    let tol = T::epsilon() * T::from(l).unwrap();
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..l {
+            for r in (p + 1)..l {
+                let alpha = krylov::dot(&w[p], &w[p]);
+                let beta = krylov::dot(&w[r], &w[r]);
+                let gamma = krylov::dot(&w[p], &w[r]);
+                if gamma.abs() <= tol * (alpha * beta).sqrt() { continue }
+                rotated = true;
+                let zeta = (beta - alpha) / (gamma + gamma);
+                let t = zeta.signum() / (zeta.abs() + (T::one() + zeta * zeta).sqrt());
+                let c = T::one() / (T::one() + t * t).sqrt();
+                let s = c * t;
+                let (head, tail) = w.split_at_mut(r);
+                for (x, y) in head[p].iter_mut().zip(tail[0].iter_mut()) {
+                    let (u, v) = (*x, *y);
+                    *x = c * u - s * v;
+                    *y = s * u + c * v;
+                }
+                for x in 0..l {
+                    let (u, v) = (j[x*l + p], j[x*l + r]);
+                    j[x*l + p] = c * u - s * v;
+                    j[x*l + r] = s * u + c * v;
+                }
+            }
+        }
+        if !rotated { break }
+    }
+
+    let sigma: Vec<T> = w.iter().map(|w_i| krylov::norm(w_i)).collect();
+    let mut order: Vec<usize> = (0..l).filter(|&i| sigma[i] > T::zero()).collect();
+    order.sort_by(|&x, &y| sigma[y].partial_cmp(&sigma[x]).unwrap_or(Ordering::Equal));
+    order.truncate(opts.rank);
+    let values: Vec<T> = order.iter().map(|&i| sigma[i]).collect();
+    let v: Vec<Vec<T>> = order.iter()
+        .map(|&i| w[i].iter().map(|&x| x / sigma[i]).collect())
+        .collect();
+    let u = combine(&q, &j, l, &order);
+    Ok(SingularTriplets { values, u: to_dense(&u, m), v: to_dense(&v, n) })
+}
+
+/// Approximate the `k = opts.rank` eigenpairs of largest magnitude of a
+/// symmetric operator by projecting it onto the basis `Q` from
+/// `range_finder` and solving the small eigenproblem for `Q^T A Q`.
+pub fn randomized_evd<T, A>(a: &A, opts: &RandomizedOptions) -> ::Result<EigenPairs<T>>
+    where T: Float, A: LinearOperator<T>,
+{
+    let (m, n) = a.shape();
+    if m != n { return Err(::Error::InvalidDimensions) }
+    let q = range(a, opts)?;
+    let l = q.len();
+    let aq: Vec<Vec<T>> = q.iter().map(|q_j| a.apply(q_j)).collect();
+    let mut c = vec![T::zero(); l*l];
+    let two = T::one() + T::one();
+    for i in 0..l {
+        for j in 0..(i + 1) {
+            let x = (krylov::dot(&q[i], &aq[j]) + krylov::dot(&q[j], &aq[i])) / two;
+            c[i*l + j] = x;
+            c[j*l + i] = x;
+        }
+    }
+    let (theta, y) = jacobi_eigen(&c, l);
+    let mut order: Vec<usize> = (0..l).collect();
+    order.sort_by(|&x, &y| theta[y].abs().partial_cmp(&theta[x].abs()).unwrap_or(Ordering::Equal));
+    order.truncate(opts.rank);
+    let values = order.iter().map(|&i| theta[i]).collect();
+    let vectors = combine(&q, &y, l, &order);
+    Ok(EigenPairs { values, vectors: to_dense(&vectors, n) })
+}
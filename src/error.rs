@@ -5,13 +5,28 @@ use std::fmt;
 #[derive(Debug)]
 pub enum Error {
     /// Supplied matrix has invalid dimensions.
-    InvalidDimensions
+    InvalidDimensions,
+    /// An iterative method did not converge within its iteration limit.
+    NotConverged,
+    /// Supplied matrix is singular.
+    Singular,
+    /// Supplied matrix is not positive definite.
+    NotPositiveDefinite,
+    /// Supplied matrix is not symmetric.
+    NotSymmetric,
+    /// The result for a real matrix is complex.
+    NotReal,
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Error::InvalidDimensions => f.pad("Supplied matrix has invalid dimensions")
+            Error::InvalidDimensions => f.pad("Supplied matrix has invalid dimensions"),
+            Error::NotConverged => f.pad("Iterative method did not converge"),
+            Error::Singular => f.pad("Supplied matrix is singular"),
+            Error::NotPositiveDefinite => f.pad("Supplied matrix is not positive definite"),
+            Error::NotSymmetric => f.pad("Supplied matrix is not symmetric"),
+            Error::NotReal => f.pad("Result for a real matrix is complex"),
         }
     }
 }
@@ -19,13 +34,23 @@ impl fmt::Display for Error {
 impl StdError for Error {
     fn description(&self) -> &str {
         match *self {
-            Error::InvalidDimensions => "Supplied matrix has invalid dimensions"
+            Error::InvalidDimensions => "Supplied matrix has invalid dimensions",
+            Error::NotConverged => "Iterative method did not converge",
+            Error::Singular => "Supplied matrix is singular",
+            Error::NotPositiveDefinite => "Supplied matrix is not positive definite",
+            Error::NotSymmetric => "Supplied matrix is not symmetric",
+            Error::NotReal => "Result for a real matrix is complex",
         }
     }
 
     fn cause(&self) -> Option<&StdError> {
         match *self {
-            Error::InvalidDimensions => None
+            Error::InvalidDimensions => None,
+            Error::NotConverged => None,
+            Error::Singular => None,
+            Error::NotPositiveDefinite => None,
+            Error::NotSymmetric => None,
+            Error::NotReal => None,
         }
     }
 }
@@ -0,0 +1,60 @@
+use ::{Float, Num, SparseMatrix};
+use super::stencil::assemble;
+
+/// Check that every edge joins two of the `n` vertices.
+fn check_edges<T>(n: usize, edges: &[(usize, usize, T)]) -> ::Result<()> {
+    if edges.iter().any(|&(u, v, _)| u >= n || v >= n) { return Err(::Error::InvalidDimensions) }
+    Ok(())
+}
+
+/// Get the weighted degree of each vertex of an undirected graph, where a
+/// self loop counts once.
+fn degrees<T: Copy + Num>(n: usize, edges: &[(usize, usize, T)]) -> Vec<T> {
+    let mut d = vec![T::zero(); n];
+    for &(u, v, w) in edges {
+        d[u] = d[u] + w;
+        if u != v { d[v] = d[v] + w }
+    }
+    d
+}
+
+/// The adjacency matrix of a graph on `n` vertices with weighted `edges`
+/// `(u, v, w)`. An undirected edge is stored at `(u, v)` and `(v, u)`, and
+/// a directed one at `(u, v)` only. Weights of repeated edges add up. Fails
+/// if an edge has an endpoint outside `0..n`.
+pub fn adjacency<T: Copy + Num>(n: usize, edges: &[(usize, usize, T)], directed: bool)
+    -> ::Result<SparseMatrix<T>>
+{
+    check_edges(n, edges)?;
+    let reversed = edges.iter()
+        .filter(|&&(u, v, _)| !directed && u != v)
+        .map(|&(u, v, w)| (v, u, w));
+    Ok(assemble(n, n, edges.iter().cloned().chain(reversed)))
+}
+
+/// The combinatorial Laplacian `L = D - A` of an undirected graph on `n`
+/// vertices with weighted `edges`, where `D` holds the weighted degrees. It
+/// is symmetric and positive semidefinite for nonnegative weights, with one
+/// zero eigenvalue for each connected component.
+pub fn graph_laplacian<T: Copy + Num>(n: usize, edges: &[(usize, usize, T)]) -> ::Result<SparseMatrix<T>> {
+    check_edges(n, edges)?;
+    let d = degrees(n, edges);
+    let off = edges.iter()
+        .flat_map(|&(u, v, w)| {
+            let minus_w = T::zero() - w;
+            if u == v { vec![(u, u, minus_w)] } else { vec![(u, v, minus_w), (v, u, minus_w)] }
+        });
+    Ok(assemble(n, n, d.into_iter().enumerate().map(|(i, x)| (i, i, x)).chain(off)))
+}
+
+/// The normalized Laplacian `D^(-1/2) L D^(-1/2) = I - D^(-1/2) A D^(-1/2)`
+/// of an undirected graph, whose eigenvalues lie in `[0, 2]`. Rows and
+/// columns of isolated vertices are zero.
+pub fn normalized_laplacian<T: Float>(n: usize, edges: &[(usize, usize, T)]) -> ::Result<SparseMatrix<T>> {
+    let l = graph_laplacian(n, edges)?;
+    let d = degrees(n, edges);
+    Ok(assemble(n, n, l.triplets().into_iter().map(|(i, j, x)| {
+        let dd = d[i] * d[j];
+        (i, j, if dd > T::zero() { x / dd.sqrt() } else { T::zero() })
+    })))
+}
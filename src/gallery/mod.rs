@@ -0,0 +1,405 @@
+//! Classic test matrices with known properties, for exercising and
+//! benchmarking numerical code.
+//!
+//! Most constructors are generic over the element type: integer matrices
+//! are exact in any `Num` type, and those with fractions are exact with
+//! `Rational`. Finite difference operators on regular grids and the
+//! Laplacians of graphs are built directly as sparse matrices.
+
+pub use self::graph::{adjacency, graph_laplacian, normalized_laplacian};
+pub use self::stencil::{divergence, gradient, laplacian, Boundary};
+
+mod graph;
+mod stencil;
+
+use ::{DenseMatrix, Float, Num, SparseMatrix};
+
+/// Get the integer `k` as an element of `T`, by doubling.
+fn int<T: Copy + Num>(k: i64) -> T {
+    let (mut acc, mut p) = (T::zero(), T::one());
+    let mut x = k.unsigned_abs();
+    while x > 0 {
+        if x & 1 == 1 { acc = acc + p }
+        p = p + p;
+        x >>= 1;
+    }
+    if k < 0 { T::zero() - acc } else { acc }
+}
+
+/// Build an `n` by `n` dense matrix from its elements `f(i, j)`.
+fn build<T: Copy + Num, F: Fn(usize, usize) -> T>(n: usize, f: F) -> DenseMatrix<T> {
+    let mat = (0..(n*n)).map(|k| f(k / n, k % n)).collect();
+    DenseMatrix::from_vec(mat, n, n, None).unwrap()
+}
+
+/// Get the binomial coefficient `C(n, k)`, or None if it overflows `i128`.
+fn binomial(n: i128, k: i128) -> Option<i128> {
+    if k < 0 || k > n { return Some(0) }
+    (0..k).try_fold(1i128, |acc, i| Some(acc.checked_mul(n - i)? / (i + 1)))
+}
+
+/// The Hilbert matrix `H_ij = 1/(i + j + 1)`, a famously ill conditioned
+/// Hankel matrix. Use a floating point or `Rational` element type.
+pub fn hilbert<T: Copy + Num>(n: usize) -> DenseMatrix<T> {
+    build(n, |i, j| T::one() / int((i + j + 1) as i64))
+}
+
+/// The inverse of the `n` by `n` Hilbert matrix, which has integer
+/// elements. Panics if they overflow `i64`, which first happens for
+/// `n = 15`.
+pub fn inverse_hilbert<T: Copy + Num>(n: usize) -> DenseMatrix<T> {
+    let m = n as i128;
+    build(n, |i, j| {
+        let x = inverse_hilbert_element(m, i as i128, j as i128)
+            .expect("inverse Hilbert element overflows i64");
+        int(x)
+    })
+}
+
+/// Get the element i, j of the `n` by `n` inverse Hilbert matrix, or None if
+/// it overflows `i64`. The product is checked at every step, since for large
+/// `n` it overflows even `i128`.
+fn inverse_hilbert_element(n: i128, i: i128, j: i128) -> Option<i64> {
+    let c = binomial(i + j, i)?;
+    let x = (i + j + 1).checked_mul(binomial(n + i, n - j - 1)?)?
+        .checked_mul(binomial(n + j, n - i - 1)?)?
+        .checked_mul(c)?
+        .checked_mul(c)?;
+    let x = if (i + j) % 2 == 0 { x } else { -x };
+    if x < i64::MIN as i128 || x > i64::MAX as i128 { return None }
+    Some(x as i64)
+}
+
+/// The symmetric Pascal matrix `P_ij = C(i + j, i)`, which has determinant
+/// one and factors as `L L^T` with `L` the lower triangular Pascal matrix.
+pub fn pascal<T: Copy + Num>(n: usize) -> DenseMatrix<T> {
+    let mut p = vec![T::one(); n*n];
+    for i in 1..n {
+        for j in 1..n {
+            p[i*n + j] = p[(i - 1)*n + j] + p[i*n + j - 1];
+        }
+    }
+    DenseMatrix::from_vec(p, n, n, None).unwrap()
+}
+
+/// A magic square of the integers `1..n^2`, whose rows, columns and both
+/// diagonals all sum to `n (n^2 + 1)/2`, built as MATLAB's `magic` does.
+/// Fails for `n = 2`, where none exists.
+pub fn magic<T: Copy + Num>(n: usize) -> ::Result<DenseMatrix<T>> {
+    if n == 2 { return Err(::Error::InvalidDimensions) }
+    let m = magic_indices(n);
+    Ok(build(n, |i, j| int(m[i*n + j] as i64)))
+}
+
+/// Get the row major magic square of order `n` as indices `1..n^2`.
+fn magic_indices(n: usize) -> Vec<usize> {
+    let mut m = vec![0; n*n];
+    if n % 2 == 1 {
+        // The siamese method.
+        for i in 0..n {
+            for j in 0..n {
+                let a = (i + j + n + 2 - (n + 3)/2) % n;
+                let b = (i + 2*j + 1) % n;
+                m[i*n + j] = n*a + b + 1;
+            }
+        }
+    } else if n % 4 == 2 {
+        // Four copies of an odd square of order p, with rows of the left
+        // and right columns exchanged between the top and bottom halves.
+        let p = n/2;
+        let q = magic_indices(p);
+        let offsets = [0, 2, 3, 1];
+        for i in 0..n {
+            for j in 0..n {
+                let block = 2*(i / p) + j / p;
+                m[i*n + j] = q[(i % p)*p + j % p] + offsets[block]*p*p;
+            }
+        }
+        let k = (n - 2)/4;
+        let swap = |m: &mut Vec<usize>, r: usize, j: usize| m.swap(r*n + j, (r + p)*n + j);
+        for j in (0..k).chain((n - k + 1)..n) {
+            for r in 0..p {
+                swap(&mut m, r, j);
+            }
+        }
+        swap(&mut m, k, 0);
+        swap(&mut m, k, k);
+    } else {
+        // Complement the elements on the diagonals of each 4 by 4 block.
+        for i in 0..n {
+            for j in 0..n {
+                let x = i*n + j + 1;
+                m[i*n + j] = if ((i + 1) % 4)/2 == ((j + 1) % 4)/2 { n*n + 1 - x } else { x };
+            }
+        }
+    }
+    m
+}
+
+/// The symmetric tridiagonal Wilkinson matrix `W_n^+` with diagonal
+/// `|(n - 1)/2 - i|` and ones beside it. Its largest eigenvalues come in
+/// pairs that agree to many digits.
+pub fn wilkinson<T: Copy + Num>(n: usize) -> DenseMatrix<T> {
+    let two = int::<T>(2);
+    build(n, |i, j| {
+        if i == j {
+            int::<T>((n as i64 - 1 - 2*i as i64).abs()) / two
+        } else if i + 1 == j || j + 1 == i {
+            T::one()
+        } else {
+            T::zero()
+        }
+    })
+}
+
+/// The upper Hessenberg Frank matrix with `F_ij = n - max(i, j)` on and
+/// above the subdiagonal. It has determinant one, and its small
+/// eigenvalues are ill conditioned.
+pub fn frank<T: Copy + Num>(n: usize) -> DenseMatrix<T> {
+    build(n, |i, j| {
+        if j + 1 >= i { int((n - if i > j { i } else { j }) as i64) } else { T::zero() }
+    })
+}
+
+/// The upper triangular Kahan matrix `diag(1, s, ..., s^(n-1)) (I - c N)`
+/// with `s = sin(theta)`, `c = cos(theta)` and `N` the strictly upper
+/// triangular matrix of ones. Adding `pert * eps * (n - i)` to the
+/// diagonal, 25 in MATLAB, defeats QR with column pivoting as a rank
+/// revealing factorization.
+pub fn kahan<T: Float>(n: usize, theta: T, pert: T) -> DenseMatrix<T> {
+    let (s, c) = theta.sin_cos();
+    build(n, |i, j| {
+        let scale = s.powi(i as i32);
+        if i == j {
+            scale + pert * T::epsilon() * T::from(n - i).unwrap()
+        } else if i < j {
+            -c * scale
+        } else {
+            T::zero()
+        }
+    })
+}
+
+/// The symmetric positive definite Lehmer matrix
+/// `L_ij = (min(i, j) + 1)/(max(i, j) + 1)`, whose inverse is tridiagonal.
+pub fn lehmer<T: Copy + Num>(n: usize) -> DenseMatrix<T> {
+    build(n, |i, j| {
+        let (lo, hi) = if i < j { (i, j) } else { (j, i) };
+        int::<T>(lo as i64 + 1) / int(hi as i64 + 1)
+    })
+}
+
+/// The symmetric positive definite Moler matrix `U^T U`, where `U` is unit
+/// upper triangular with `alpha` above the diagonal. For `alpha = -1` it
+/// has one tiny eigenvalue.
+pub fn moler<T: Copy + Num>(n: usize, alpha: T) -> DenseMatrix<T> {
+    build(n, |i, j| {
+        let lo = if i < j { i } else { j };
+        let x = int::<T>(lo as i64) * alpha * alpha;
+        if i == j { x + T::one() } else { x + alpha }
+    })
+}
+
+/// The 1-D Laplacian `tridiag(-1, 2, -1)` of order `n`, the second
+/// difference matrix with Dirichlet boundaries.
+pub fn laplacian_1d<T: Copy + Num>(n: usize) -> SparseMatrix<T> {
+    let mut tuples = Vec::with_capacity(3*n);
+    for i in 0..n {
+        if i > 0 { tuples.push((i, i - 1, T::zero() - T::one())) }
+        tuples.push((i, i, int(2)));
+        if i + 1 < n { tuples.push((i, i + 1, T::zero() - T::one())) }
+    }
+    SparseMatrix::from_tuple(tuples, n, n)
+}
+
+/// The 2-D Poisson matrix of order `n^2`, the five point Laplacian on an
+/// `n` by `n` grid with Dirichlet boundaries and grid points numbered by
+/// rows. It equals `kron(I, T) + kron(T, I)` for `T = laplacian_1d(n)`.
+pub fn poisson_2d<T: Copy + Num>(n: usize) -> SparseMatrix<T> {
+    let (four, minus_one) = (int::<T>(4), T::zero() - T::one());
+    let mut tuples = Vec::with_capacity(5*n*n);
+    for r in 0..n {
+        for c in 0..n {
+            let k = r*n + c;
+            if r > 0 { tuples.push((k, k - n, minus_one)) }
+            if c > 0 { tuples.push((k, k - 1, minus_one)) }
+            tuples.push((k, k, four));
+            if c + 1 < n { tuples.push((k, k + 1, minus_one)) }
+            if r + 1 < n { tuples.push((k, k + n, minus_one)) }
+        }
+    }
+    SparseMatrix::from_tuple(tuples, n*n, n*n)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+    use ::{DenseMatrix, LinearOperator, Matrix, Rational, SparseMatrix};
+    use super::{frank, hilbert, inverse_hilbert, kahan, laplacian_1d, lehmer, magic, moler};
+    use super::{pascal, poisson_2d, wilkinson};
+    use super::{adjacency, divergence, gradient, graph_laplacian, laplacian, normalized_laplacian, Boundary};
+
+    fn matmul(A: &DenseMatrix<Rational>, B: &DenseMatrix<Rational>) -> DenseMatrix<Rational> {
+        let n = A.rows();
+        DenseMatrix::from_vec((0..(n*n)).map(|k| {
+            (0..n).fold(Rational::from_integer(0), |acc, l| {
+                acc + A.get(k / n, l).unwrap() * B.get(l, k % n).unwrap()
+            })
+        }).collect(), n, n, None).unwrap()
+    }
+
+    #[test]
+    #[should_panic(expected = "inverse Hilbert element overflows i64")]
+    fn test_inverse_hilbert_overflow() {
+        // Large enough that the product overflows i128, not just i64.
+        let _: DenseMatrix<i64> = inverse_hilbert(40);
+    }
+
+    #[test]
+    fn test_gallery() {
+        let H: DenseMatrix<Rational> = hilbert(6);
+        assert_eq!(H.get(2, 3).unwrap(), Rational::new(1, 6));
+        let I: DenseMatrix<Rational> = DenseMatrix::identity(6);
+        assert_eq!(matmul(&H, &inverse_hilbert(6)).elements(), I.elements());
+        let Hinv: DenseMatrix<i64> = inverse_hilbert(4);
+        assert_eq!(Hinv.get(0, 0).unwrap(), 16);
+        assert_eq!(Hinv.get(3, 3).unwrap(), 2800);
+        assert_eq!(Hinv.get(0, 1).unwrap(), -120);
+        let Hinv: DenseMatrix<i64> = inverse_hilbert(14);
+        assert_eq!(Hinv.get(0, 0).unwrap(), 196);
+
+        let P: DenseMatrix<i64> = pascal(5);
+        assert_eq!(P.get(4, 4).unwrap(), 70);
+        assert_eq!(P.det_bareiss().unwrap(), 1);
+
+        for &n in [1, 3, 4, 5, 6, 8, 10, 12, 14].iter() {
+            let M: DenseMatrix<i64> = magic(n).unwrap();
+            let sum = (n*(n*n + 1)/2) as i64;
+            for i in 0..n {
+                assert_eq!((0..n).map(|j| M.get(i, j).unwrap()).sum::<i64>(), sum);
+                assert_eq!((0..n).map(|j| M.get(j, i).unwrap()).sum::<i64>(), sum);
+            }
+            assert_eq!((0..n).map(|i| M.get(i, i).unwrap()).sum::<i64>(), sum);
+            assert_eq!((0..n).map(|i| M.get(i, n - 1 - i).unwrap()).sum::<i64>(), sum);
+            let mut values = M.elements();
+            values.sort();
+            assert_eq!(values, (1..(n*n + 1) as i64).collect::<Vec<_>>());
+        }
+        assert!(magic::<i64>(2).is_err());
+
+        let W: DenseMatrix<f64> = wilkinson(7);
+        assert_eq!((W.get(0, 0).unwrap(), W.get(3, 3).unwrap(), W.get(3, 4).unwrap()), (3.0, 0.0, 1.0));
+        let W: DenseMatrix<Rational> = wilkinson(4);
+        assert_eq!(W.get(0, 0).unwrap(), Rational::new(3, 2));
+
+        let F: DenseMatrix<i64> = frank(6);
+        assert_eq!((F.get(0, 0).unwrap(), F.get(1, 0).unwrap(), F.get(2, 0).unwrap()), (6, 5, 0));
+        assert_eq!(F.det_bareiss().unwrap(), 1);
+
+        let K = kahan(4, 1.2f64, 0.0);
+        let (s, c) = 1.2f64.sin_cos();
+        assert_eq!((K.get(2, 2).unwrap(), K.get(1, 3).unwrap(), K.get(3, 0).unwrap()), (s*s, -c*s, 0.0));
+
+        let L: DenseMatrix<Rational> = lehmer(4);
+        assert_eq!(L.get(1, 3).unwrap(), Rational::new(1, 2));
+        assert!(L.is_symmetric());
+
+        // Moler is U^T U.
+        let A: DenseMatrix<i64> = moler(5, -1);
+        let U = DenseMatrix::from_vec((0..25).map(|k| {
+            if k / 5 == k % 5 { 1 } else if k / 5 < k % 5 { -1 } else { 0 }
+        }).collect(), 5, 5, None).unwrap();
+        let UtU: Vec<i64> = (0..25).map(|k| {
+            (0..5).map(|l| U.get(l, k / 5).unwrap() * U.get(l, k % 5).unwrap()).sum()
+        }).collect();
+        assert_eq!(A.elements(), UtU);
+
+        let T: SparseMatrix<f64> = laplacian_1d(5);
+        assert_eq!(T.nnz(), 13);
+        assert_eq!((T.get(2, 2).unwrap(), T.get(2, 1).unwrap()), (2.0, -1.0));
+        let A: SparseMatrix<f64> = poisson_2d(4);
+        assert_eq!(A.dims(), (16, 16));
+        assert_eq!(A.nnz(), 16 + 4*4*3);
+        assert_eq!((A.get(5, 5).unwrap(), A.get(5, 1).unwrap(), A.get(3, 4).unwrap()), (4.0, -1.0, 0.0));
+        assert!(A.triplets().iter().all(|&(i, j, x)| A.get(j, i) == Some(x)));
+    }
+
+    /// Get a sparse matrix as a row major dense array.
+    fn dense(A: &SparseMatrix<f64>) -> Vec<f64> {
+        let (m, n) = A.dims();
+        let mut a = vec![0.0; m*n];
+        for (i, j, x) in A.triplets() {
+            a[i*n + j] = x;
+        }
+        a
+    }
+
+    #[test]
+    fn test_stencils() {
+        let L = laplacian(&[5], &[1.0], Boundary::Dirichlet).unwrap();
+        assert_eq!(L.triplets(), laplacian_1d(5).triplets());
+        let L = laplacian(&[4, 4], &[1.0, 1.0], Boundary::Dirichlet).unwrap();
+        assert_eq!(L.triplets(), poisson_2d(4).triplets());
+        assert_eq!(dense(&laplacian(&[2], &[1.0], Boundary::Periodic).unwrap()), vec![2.0, -2.0, -2.0, 2.0]);
+        assert_eq!(laplacian(&[1], &[1.0], Boundary::Periodic).unwrap().nnz(), 0);
+        assert!(laplacian(&[3, 3], &[1.0], Boundary::Neumann).is_err());
+        assert!(laplacian::<f64>(&[], &[], Boundary::Neumann).is_err());
+
+        let (dims, h) = ([3, 4, 2], [0.5, 1.0, 2.0]);
+        for &bc in [Boundary::Dirichlet, Boundary::Neumann, Boundary::Periodic].iter() {
+            let L = laplacian(&dims, &h, bc).unwrap();
+            let G = gradient(&dims, &h, bc).unwrap();
+            let D = divergence(&dims, &h, bc).unwrap();
+            let edges = match bc {
+                Boundary::Dirichlet => 4*4*2 + 3*5*2 + 3*4*3,
+                Boundary::Neumann => 2*4*2 + 3*3*2 + 3*4,
+                Boundary::Periodic => 3*24,
+            };
+            assert_eq!((G.dims(), D.dims()), ((edges, 24), (24, edges)));
+            let (g, d, l) = (dense(&G), dense(&D), dense(&L));
+            for i in 0..24 {
+                for j in 0..24 {
+                    let x: f64 = (0..edges).map(|e| d[i*edges + e] * g[e*24 + j]).sum();
+                    assert!((l[i*24 + j] + x).abs() < 1e-12);
+                    assert_eq!(l[i*24 + j], l[j*24 + i]);
+                }
+            }
+            let null = L.apply(&[1.0; 24]);
+            assert_eq!(null.iter().all(|&x| x.abs() < 1e-12), bc != Boundary::Dirichlet);
+        }
+
+        // A linear function has a constant gradient away from the boundary.
+        let G = gradient(&[5], &[0.25], Boundary::Neumann).unwrap();
+        let u: Vec<f64> = (0..5).map(|i| 3.0 * 0.25 * i as f64).collect();
+        assert!(G.apply(&u).iter().all(|&x| (x - 3.0).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_graphs() {
+        // A triangle with a pendant vertex, a self loop and an isolated vertex.
+        let edges = vec![(0, 1, 1.0), (1, 2, 2.0), (2, 0, 1.0), (2, 3, 4.0), (3, 3, 5.0)];
+        let A = adjacency(5, &edges, false).unwrap();
+        assert_eq!(A.nnz(), 9);
+        assert_eq!((A.get(1, 2).unwrap(), A.get(2, 1).unwrap(), A.get(3, 3).unwrap()), (2.0, 2.0, 5.0));
+        let B = adjacency(5, &edges, true).unwrap();
+        assert_eq!((B.nnz(), B.get(2, 1).unwrap()), (5, 0.0));
+        let doubled = adjacency(2, &[(0, 1, 1), (1, 0, 2)], true).unwrap();
+        assert_eq!(doubled.triplets(), vec![(0, 1, 1), (1, 0, 2)]);
+        assert_eq!(adjacency(2, &[(0, 1, 1), (0, 1, 2)], false).unwrap().get(1, 0), Some(3));
+
+        let L = graph_laplacian(5, &edges).unwrap();
+        let l = dense(&L);
+        assert_eq!(&l[..5], &[2.0, -1.0, -1.0, 0.0, 0.0]);
+        assert_eq!(l[3*5 + 3], 4.0);
+        assert!(L.apply(&[1.0; 5]).iter().all(|&x| x == 0.0));
+
+        let N = normalized_laplacian(5, &edges).unwrap();
+        let degree = [2.0, 3.0, 7.0, 9.0, 0.0];
+        for (i, j, x) in N.triplets() {
+            let expected = if i == j { l[i*5 + i] / degree[i] } else { -A.get(i, j).unwrap() / (degree[i] * degree[j]).sqrt() };
+            assert!((x - expected).abs() < 1e-12);
+        }
+        assert_eq!((N.get(0, 0).unwrap(), N.get(4, 4).unwrap()), (1.0, 0.0));
+        assert!(graph_laplacian(3, &[(0, 3, 1.0)]).is_err());
+    }
+}
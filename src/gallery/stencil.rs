@@ -0,0 +1,143 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+
+use ::{Matrix, Num, SparseMatrix};
+
+/// The boundary condition of a finite difference grid.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Boundary {
+    /// The unknowns are interior points and the solution vanishes on the
+    /// points just outside the grid.
+    Dirichlet,
+    /// The normal derivative vanishes, so no flux crosses the boundary.
+    Neumann,
+    /// The grid wraps around in every direction.
+    Periodic,
+}
+
+impl Boundary {
+    /// The number of edges between neighbouring points along an axis of
+    /// `n` points, which are the unknowns of the gradient.
+    fn edges(&self, n: usize) -> usize {
+        match *self {
+            Boundary::Dirichlet => n + 1,
+            Boundary::Neumann => n.saturating_sub(1),
+            Boundary::Periodic => n,
+        }
+    }
+
+    /// The points `(tail, head)` at either end of edge `e` along an axis of
+    /// `n` points, where `None` is a point outside the grid.
+    fn ends(&self, e: usize, n: usize) -> (Option<usize>, Option<usize>) {
+        match *self {
+            Boundary::Dirichlet => (e.checked_sub(1), if e < n { Some(e) } else { None }),
+            Boundary::Neumann => (Some(e), Some(e + 1)),
+            Boundary::Periodic => (Some(e), Some((e + 1) % n)),
+        }
+    }
+}
+
+/// Assemble a sparse matrix straight into its storage, summing duplicate
+/// elements and dropping those that cancel.
+pub fn assemble<T, I>(m: usize, n: usize, elements: I) -> SparseMatrix<T>
+    where T: Copy + Num, I: IntoIterator<Item=(usize, usize, T)>,
+{
+    let mut map: HashMap<(usize, usize), Cell<T>> = HashMap::new();
+    for (i, j, x) in elements {
+        let cell = map.entry((i, j)).or_insert_with(|| Cell::new(T::zero()));
+        cell.set(cell.get() + x);
+    }
+    map.retain(|_, x| x.get() != T::zero());
+    let a = SparseMatrix::new(m, n);
+    *a.mat.borrow_mut() = map;
+    a
+}
+
+/// Check the grid shape and get the number of points and the stride of
+/// each axis, with the last axis varying fastest.
+fn strides<T>(dims: &[usize], h: &[T]) -> ::Result<(usize, Vec<usize>)> {
+    if dims.is_empty() || h.len() != dims.len() { return Err(::Error::InvalidDimensions) }
+    let mut strides = vec![1; dims.len()];
+    for a in (0..(dims.len() - 1)).rev() {
+        strides[a] = strides[a + 1] * dims[a + 1];
+    }
+    Ok((strides[0] * dims[0], strides))
+}
+
+/// The negative Laplacian `-Δ` on a regular grid with `dims[a]` points
+/// and spacing `h[a]` along axis `a`, in one, two, three or more
+/// dimensions. Points are numbered with the last axis varying fastest.
+///
+/// This is the standard `2d + 1` point stencil, which is symmetric and
+/// positive semidefinite, and equals `-divergence * gradient` for the same
+/// grid. It is singular for Neumann and periodic boundaries, with the
+/// constants as its null space.
+pub fn laplacian<T: Copy + Num>(dims: &[usize], h: &[T], bc: Boundary) -> ::Result<SparseMatrix<T>> {
+    let (size, strides) = strides(dims, h)?;
+    let mut elements = Vec::with_capacity((2*dims.len() + 1)*size);
+    for k in 0..size {
+        for (a, (&n, &s)) in dims.iter().zip(&strides).enumerate() {
+            let w = T::one() / (h[a] * h[a]);
+            let i = (k / s) % n;
+            let mut neighbours = Vec::with_capacity(2);
+            match bc {
+                Boundary::Dirichlet | Boundary::Neumann => {
+                    if i > 0 { neighbours.push(k - s) }
+                    if i + 1 < n { neighbours.push(k + s) }
+                },
+                Boundary::Periodic => {
+                    neighbours.push(k - i*s + ((i + n - 1) % n)*s);
+                    neighbours.push(k - i*s + ((i + 1) % n)*s);
+                },
+            }
+            let degree = if bc == Boundary::Neumann { neighbours.len() } else { 2 };
+            for _ in 0..degree {
+                elements.push((k, k, w));
+            }
+            for l in neighbours {
+                elements.push((k, l, T::zero() - w));
+            }
+        }
+    }
+    Ok(assemble(size, size, elements))
+}
+
+/// The gradient on a grid as for `laplacian`: forward differences along
+/// each axis in turn, stacked. Along an axis of `n` points there are `n + 1`
+/// differences for Dirichlet boundaries, counting those to the boundary,
+/// `n - 1` for Neumann and `n` for periodic ones. The differences along
+/// axis `a` are numbered like the points of the grid with `dims[a]`
+/// replaced by their count.
+pub fn gradient<T: Copy + Num>(dims: &[usize], h: &[T], bc: Boundary) -> ::Result<SparseMatrix<T>> {
+    let (size, strides) = strides(dims, h)?;
+    let mut rows = 0;
+    let mut elements = Vec::new();
+    for (a, (&n, &s)) in dims.iter().zip(&strides).enumerate() {
+        let inv_h = T::one() / h[a];
+        let e = bc.edges(n);
+        let outer: usize = dims[..a].iter().product();
+        for o in 0..outer {
+            for edge in 0..e {
+                for t in 0..s {
+                    let row = rows + (o*e + edge)*s + t;
+                    let (tail, head) = bc.ends(edge, n);
+                    let base = o*n*s + t;
+                    if let Some(p) = tail { elements.push((row, base + p*s, T::zero() - inv_h)) }
+                    if let Some(p) = head { elements.push((row, base + p*s, inv_h)) }
+                }
+            }
+        }
+        rows += outer*e*s;
+    }
+    Ok(assemble(rows, size, elements))
+}
+
+/// The divergence on a grid as for `laplacian`, which maps the stacked
+/// differences of `gradient` back to the points. It is the negative
+/// transpose of the gradient, so that `-divergence * gradient` is the
+/// Laplacian and summation by parts holds exactly.
+pub fn divergence<T: Copy + Num>(dims: &[usize], h: &[T], bc: Boundary) -> ::Result<SparseMatrix<T>> {
+    let g = gradient(dims, h, bc)?;
+    let (rows, cols) = g.dims();
+    Ok(assemble(cols, rows, g.triplets().into_iter().map(|(i, j, x)| (j, i, T::zero() - x))))
+}
@@ -0,0 +1,321 @@
+use std::cell::Cell;
+use std::fmt;
+
+use ::{Conjugate, Gf, Matrix, One, Zero};
+
+const WORD_BITS: usize = 64;
+
+/// A Gf2Matrix is a matrix over `GF(2)` packed 64 entries to a word.
+///
+/// Rows are stored contiguously and padded to a whole number of words, so
+/// adding one row to another is a word-wise XOR. The padding bits are always
+/// zero.
+#[derive(Clone, Debug)]
+pub struct Gf2Matrix {
+    m: usize,
+    n: usize,
+    words: usize,
+    /// The packed rows. Column `j` of row `i` is bit `j % 64` of word
+    /// `i*words_per_row() + j/64`.
+    pub mat: Vec<Cell<u64>>,
+}
+
+impl Gf2Matrix {
+    /// Create a new Gf2Matrix given its data in a slice of matrix rows in vec
+    /// form.
+    pub fn new(mat: &[Vec<bool>]) -> ::Result<Gf2Matrix> {
+        let m = mat.len();
+        let n = match mat.first() {
+            Some(row) => row.len(),
+            None => return Err(::Error::InvalidDimensions),
+        };
+        let a = Gf2Matrix::zeros(m, n);
+        for (i, row) in mat.iter().enumerate() {
+            if row.len() != n { return Err(::Error::InvalidDimensions) }
+            for (j, &bit) in row.iter().enumerate() {
+                if bit { a.set_bit(i, j, true) }
+            }
+        }
+        Ok(a)
+    }
+
+    /// Create a new matrix of zeros given the matrix dimensions m and n.
+    pub fn zeros(m: usize, n: usize) -> Gf2Matrix {
+        let words = n.div_ceil(WORD_BITS);
+        Gf2Matrix { m, n, words, mat: vec![Cell::new(0); m*words] }
+    }
+
+    /// Create a new identity matrix given its dimension.
+    pub fn identity(n: usize) -> Gf2Matrix {
+        let a = Gf2Matrix::zeros(n, n);
+        for i in 0..n {
+            a.set_bit(i, i, true);
+        }
+        a
+    }
+
+    /// Get the number of words used to store each row.
+    #[inline]
+    pub fn words_per_row(&self) -> usize {
+        self.words
+    }
+
+    /// Get the entry at i, j as a bool. Panics if i, j is out of bounds.
+    #[inline]
+    pub fn get_bit(&self, i: usize, j: usize) -> bool {
+        self.check_bounds(i, j);
+        let w = self.mat[i*self.words + j / WORD_BITS].get();
+        (w >> (j % WORD_BITS)) & 1 == 1
+    }
+
+    /// Set the entry at i, j. Panics if i, j is out of bounds, since a
+    /// column past `n` would land in the padding of the last word.
+    #[inline]
+    pub fn set_bit(&self, i: usize, j: usize, bit: bool) {
+        self.check_bounds(i, j);
+        let cell = &self.mat[i*self.words + j / WORD_BITS];
+        let mask = 1 << (j % WORD_BITS);
+        if bit {
+            cell.set(cell.get() | mask)
+        } else {
+            cell.set(cell.get() & !mask)
+        }
+    }
+
+    #[inline]
+    fn check_bounds(&self, i: usize, j: usize) {
+        if i >= self.m || j >= self.n {
+            panic!("Index ({}, {}) out of bounds for a {} by {} Gf2Matrix", i, j, self.m, self.n)
+        }
+    }
+
+    /// Add row `src` to row `dst`, one word at a time.
+    #[inline]
+    pub fn add_row(&self, src: usize, dst: usize) {
+        for k in 0..self.words {
+            let d = &self.mat[dst*self.words + k];
+            d.set(d.get() ^ self.mat[src*self.words + k].get());
+        }
+    }
+
+    /// Swap rows `i` and `j`.
+    #[inline]
+    pub fn swap_rows(&self, i: usize, j: usize) {
+        if i == j { return }
+        for k in 0..self.words {
+            self.mat[i*self.words + k].swap(&self.mat[j*self.words + k]);
+        }
+    }
+
+    /// Get the number of ones.
+    pub fn count_ones(&self) -> usize {
+        self.mat.iter().map(|w| w.get().count_ones() as usize).sum()
+    }
+
+    /// Read `k <= 64` consecutive bits of row `i` starting at column `j` as an
+    /// integer, with column `j` in the lowest bit.
+    #[inline]
+    fn bits(&self, i: usize, j: usize, k: usize) -> usize {
+        let (w, b) = (j / WORD_BITS, j % WORD_BITS);
+        let mut x = self.mat[i*self.words + w].get() >> b;
+        if b + k > WORD_BITS {
+            x |= self.mat[i*self.words + w + 1].get() << (WORD_BITS - b);
+        }
+        let mask = if k == WORD_BITS { !0 } else { (1 << k) - 1 };
+        (x & mask) as usize
+    }
+
+    /// Compute the reduced row echelon form by Gaussian elimination. Also
+    /// returns the pivot columns in increasing order.
+    pub fn rref(&self) -> (Gf2Matrix, Vec<usize>) {
+        let a = self.clone();
+        let mut pivots = Vec::new();
+        let mut r = 0;
+        for j in 0..self.n {
+            if r == self.m { break }
+            let p = match (r..self.m).find(|&i| a.get_bit(i, j)) {
+                Some(p) => p,
+                None => continue,
+            };
+            a.swap_rows(r, p);
+            for i in 0..self.m {
+                if i != r && a.get_bit(i, j) { a.add_row(r, i) }
+            }
+            pivots.push(j);
+            r += 1;
+        }
+        (a, pivots)
+    }
+
+    /// Compute the rank.
+    pub fn rank(&self) -> usize {
+        self.rref().1.len()
+    }
+
+    /// Compute a basis for the kernel. The basis vectors are the columns of
+    /// the returned `n` by `n - rank` matrix.
+    pub fn kernel(&self) -> Gf2Matrix {
+        let (r, pivots) = self.rref();
+        let free: Vec<usize> = (0..self.n).filter(|j| !pivots.contains(j)).collect();
+        let basis = Gf2Matrix::zeros(self.n, free.len());
+        for (col, &f) in free.iter().enumerate() {
+            basis.set_bit(f, col, true);
+            for (row, &p) in pivots.iter().enumerate() {
+                if r.get_bit(row, f) { basis.set_bit(p, col, true) }
+            }
+        }
+        basis
+    }
+
+    /// Multiply with the Method of the Four Russians.
+    ///
+    /// The columns of `self` are taken `k` at a time. For each group, all
+    /// `2^k` sums of the matching `k` rows of `rhs` are tabulated, and each
+    /// row of the product then needs a single table lookup and row XOR per
+    /// group instead of `k`.
+    pub fn mul_m4ri(&self, rhs: &Gf2Matrix) -> Gf2Matrix {
+        if self.n != rhs.m {
+            panic!("Cannot multiply matrices of given dimensions: lhs={:?} rhs={:?}",
+                   self.dims(), rhs.dims())
+        }
+        let c = Gf2Matrix::zeros(self.m, rhs.n);
+        let words = rhs.words;
+        let k = if self.n < 256 { 4 } else if self.n < 4096 { 6 } else { 8 };
+        let mut table = vec![0u64; (1 << k) * words];
+
+        let mut j = 0;
+        while j < self.n {
+            let kk = if j + k > self.n { self.n - j } else { k };
+            // Entry x is the sum of the rows j + b of rhs for each set bit b
+            // of x, built from the entry with the lowest bit cleared.
+            for x in 1..(1usize << kk) {
+                let low = x.trailing_zeros() as usize;
+                let prev = x & (x - 1);
+                for w in 0..words {
+                    table[x*words + w] = table[prev*words + w] ^
+                        rhs.mat[(j + low)*words + w].get();
+                }
+            }
+            for i in 0..self.m {
+                let x = self.bits(i, j, kk);
+                if x == 0 { continue }
+                for w in 0..words {
+                    let cell = &c.mat[i*words + w];
+                    cell.set(cell.get() ^ table[x*words + w]);
+                }
+            }
+            j += kk;
+        }
+        c
+    }
+}
+
+impl Matrix<Gf<2>> for Gf2Matrix {
+    fn is_symmetric(&self) -> bool {
+        if !self.is_square() { return false }
+        for i in 1..self.m {
+            for j in 0..i {
+                if self.get_bit(i, j) != self.get_bit(j, i) { return false }
+            }
+        }
+        true
+    }
+
+    fn is_orthogonal(&self) -> bool {
+        if !self.is_square() { return false }
+        let t = self.clone().transpose();
+        self.mul_m4ri(&t) == Gf2Matrix::identity(self.m)
+    }
+
+    fn is_diagonal(&self) -> bool {
+        (0..self.m).all(|i| (0..self.n).all(|j| i == j || !self.get_bit(i, j)))
+    }
+
+    fn is_lower_triangular(&self) -> bool {
+        (0..self.m).all(|i| ((i + 1)..self.n).all(|j| !self.get_bit(i, j)))
+    }
+
+    fn is_unilower_triangular(&self) -> bool {
+        self.is_lower_triangular() && (0..self.m.min(self.n)).all(|i| self.get_bit(i, i))
+    }
+
+    fn is_strictly_lower_triangular(&self) -> bool {
+        self.is_lower_triangular() && (0..self.m.min(self.n)).all(|i| !self.get_bit(i, i))
+    }
+
+    fn is_lower_hessenberg(&self) -> bool {
+        (0..self.m).all(|i| ((i + 2)..self.n).all(|j| !self.get_bit(i, j)))
+    }
+
+    fn is_upper_triangular(&self) -> bool {
+        (0..self.m).all(|i| (0..i.min(self.n)).all(|j| !self.get_bit(i, j)))
+    }
+
+    fn is_uniupper_triangular(&self) -> bool {
+        self.is_upper_triangular() && (0..self.m.min(self.n)).all(|i| self.get_bit(i, i))
+    }
+
+    fn is_strictly_upper_triangular(&self) -> bool {
+        self.is_upper_triangular() && (0..self.m.min(self.n)).all(|i| !self.get_bit(i, i))
+    }
+
+    fn is_upper_hessenberg(&self) -> bool {
+        (2..self.m).all(|i| (0..(i - 1).min(self.n)).all(|j| !self.get_bit(i, j)))
+    }
+
+    fn trace(&self) -> Gf<2> {
+        let ones = (0..self.m.min(self.n)).filter(|&i| self.get_bit(i, i)).count();
+        Gf::new(ones as u64)
+    }
+
+    fn transpose(self) -> Self {
+        let t = Gf2Matrix::zeros(self.n, self.m);
+        for i in 0..self.m {
+            for j in 0..self.n {
+                if self.get_bit(i, j) { t.set_bit(j, i, true) }
+            }
+        }
+        t
+    }
+
+    fn rows(&self) -> usize { self.m }
+
+    fn cols(&self) -> usize { self.n }
+
+    fn get(&self, i: usize, j: usize) -> Option<Gf<2>> {
+        if i >= self.m || j >= self.n { return None }
+        Some(if self.get_bit(i, j) { Gf::one() } else { Gf::zero() })
+    }
+
+    fn set(&self, i: usize, j: usize, val: Gf<2>) -> Option<Gf<2>> {
+        if i >= self.m || j >= self.n { return None }
+        self.set_bit(i, j, !val.is_zero());
+        Some(val)
+    }
+
+    fn elements(&self) -> Vec<Gf<2>> {
+        let mut v = Vec::with_capacity(self.m*self.n);
+        for i in 0..self.m {
+            for j in 0..self.n {
+                v.push(self.get(i, j).unwrap());
+            }
+        }
+        v
+    }
+}
+
+impl Conjugate for Gf2Matrix {
+    fn conj(&self) -> Gf2Matrix { self.clone() }
+}
+
+impl fmt::Display for Gf2Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for i in 0..self.m {
+            for j in 0..self.n {
+                write!(f, "{}", if self.get_bit(i, j) { 1 } else { 0 })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
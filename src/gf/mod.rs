@@ -0,0 +1,137 @@
+pub use self::gf2_matrix::Gf2Matrix;
+pub use self::prime_field::Gf;
+
+mod gf2_matrix;
+mod prime_field;
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+    use ::{DenseMatrix, Matrix, Num, One, Zero};
+    use super::{Gf, Gf2Matrix};
+
+    type F7 = Gf<7>;
+
+    #[test]
+    fn test_prime_field_arithmetic() {
+        let a = F7::new(3);
+        let b = F7::new(5);
+        assert_eq!(a + b, F7::new(1));
+        assert_eq!(a - b, F7::new(5));
+        assert_eq!(a * b, F7::new(1));
+        assert_eq!(a / b, F7::new(2));
+        assert_eq!(-a, F7::new(4));
+        assert_eq!(F7::from_i64(-1), F7::new(6));
+        assert_eq!(a.inv(), Some(b));
+        assert_eq!(F7::zero().inv(), None);
+        assert_eq!(a.pow(6), F7::one());
+        assert_eq!(F7::from_str_radix("-10", 10).unwrap(), F7::new(4));
+
+        // The largest 64-bit prime does not fit in an i64.
+        type Big = Gf<18446744073709551557>;
+        assert_eq!(Big::from_i64(-1), Big::new(18446744073709551556));
+        assert_eq!(Big::from_i64(i64::MIN).value(), 18446744073709551557 - (1 << 63));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_prime_field_div_zero() {
+        let _ = F7::one() / F7::zero();
+    }
+
+    #[test]
+    fn test_dense_over_prime_field() {
+        let f = |v: Vec<u64>| v.into_iter().map(F7::new).collect::<Vec<_>>();
+        let A = DenseMatrix::new(&[f(vec![1, 2, 3]),
+                                       f(vec![4, 5, 6]),
+                                       f(vec![0, 1, 1])]).unwrap();
+        // det = 1*(5-6) - 2*(4-0) + 3*(4-0) = 3 over the integers.
+        assert_eq!(A.det_bareiss().unwrap(), F7::new(3));
+        assert_eq!(A.trace(), F7::new(0));
+        let B = A.clone() + A;
+        assert_eq!(B.get(1, 1).unwrap(), F7::new(3));
+    }
+
+    fn from_rows(rows: &[&str]) -> Gf2Matrix {
+        let bits: Vec<Vec<bool>> = rows.iter()
+            .map(|r| r.chars().map(|c| c == '1').collect())
+            .collect();
+        Gf2Matrix::new(&bits).unwrap()
+    }
+
+    /// A reproducible pseudo random matrix.
+    fn scrambled(m: usize, n: usize, seed: u64) -> Gf2Matrix {
+        let A = Gf2Matrix::zeros(m, n);
+        let mut x = seed;
+        for i in 0..m {
+            for j in 0..n {
+                x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                A.set_bit(i, j, x >> 63 == 1);
+            }
+        }
+        A
+    }
+
+    #[test]
+    fn test_gf2_rank_kernel() {
+        let A = from_rows(&["1101", "0111", "1010"]);
+        assert_eq!(A.rank(), 2);
+        let K = A.kernel();
+        assert_eq!(K.dims(), (4, 2));
+        assert_eq!(A.mul_m4ri(&K), Gf2Matrix::zeros(3, 2));
+        assert_eq!(K.rank(), 2);
+
+        let I = Gf2Matrix::identity(70);
+        assert_eq!(I.rank(), 70);
+        assert_eq!(I.kernel().dims(), (70, 0));
+        assert_eq!(I.trace(), Gf::new(0));
+        assert!(I.is_orthogonal());
+
+        let B = scrambled(90, 130, 1);
+        let K = B.kernel();
+        assert_eq!(K.cols(), 130 - B.rank());
+        assert_eq!(B.mul_m4ri(&K).count_ones(), 0);
+    }
+
+    #[test]
+    fn test_gf2_mul() {
+        for &(m, k, n) in [(5, 3, 7), (65, 130, 70), (40, 300, 20)].iter() {
+            let A = scrambled(m, k, 2);
+            let B = scrambled(k, n, 3);
+            let C = A.clone() * B.clone();
+            for i in 0..m {
+                for j in 0..n {
+                    let bit = (0..k).fold(false, |acc, l| acc ^ (A.get_bit(i, l) & B.get_bit(l, j)));
+                    assert_eq!(C.get_bit(i, j), bit);
+                }
+            }
+        }
+
+        let A = from_rows(&["101", "011"]);
+        let B = from_rows(&["110", "011"]);
+        assert_eq!(A + B, from_rows(&["011", "000"]));
+    }
+
+    #[test]
+    fn test_gf2_transpose() {
+        let A = scrambled(3, 100, 4);
+        let T = A.clone().transpose();
+        assert_eq!(T.dims(), (100, 3));
+        for i in 0..3 {
+            for j in 0..100 {
+                assert_eq!(A.get(i, j), T.get(j, i));
+            }
+        }
+        assert!(from_rows(&["110", "011", "001"]).is_upper_triangular());
+        assert!(!from_rows(&["110", "011", "101"]).is_upper_triangular());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gf2_set_bit_out_of_bounds() {
+        // Column 3 of a 2 by 3 matrix is padding in the last word.
+        let A = Gf2Matrix::zeros(2, 3);
+        assert_eq!(A.set(0, 3, Gf::new(1)), None);
+        A.set_bit(0, 3, true);
+    }
+}
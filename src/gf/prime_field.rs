@@ -0,0 +1,149 @@
+use std::fmt;
+use std::num::ParseIntError;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use ::{Conjugate, Num, One, Zero};
+
+/// An element of the prime field `GF(P)`, the integers modulo `P`.
+///
+/// `P` must be prime for division to be well defined; this is not checked.
+/// Elements are kept reduced to `[0, P)`, so equality is plain equality of
+/// representatives.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Gf<const P: u64> {
+    value: u64,
+}
+
+impl<const P: u64> Gf<P> {
+    /// Create a new field element, reducing `value` modulo `P`.
+    #[inline]
+    pub fn new(value: u64) -> Gf<P> {
+        Gf { value: value % P }
+    }
+
+    /// Create a new field element from a signed integer.
+    #[inline]
+    pub fn from_i64(value: i64) -> Gf<P> {
+        Gf { value: (value as i128).rem_euclid(P as i128) as u64 }
+    }
+
+    /// Get the representative in `[0, P)`.
+    #[inline]
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// Get the characteristic `P`.
+    #[inline]
+    pub fn modulus() -> u64 {
+        P
+    }
+
+    /// Raise to the power `e` by repeated squaring.
+    pub fn pow(&self, mut e: u64) -> Gf<P> {
+        let mut base = *self;
+        let mut acc = Gf::one();
+        while e > 0 {
+            if e & 1 == 1 { acc = acc * base }
+            base = base * base;
+            e >>= 1;
+        }
+        acc
+    }
+
+    /// Get the multiplicative inverse, or `None` for zero.
+    pub fn inv(&self) -> Option<Gf<P>> {
+        if self.value == 0 { return None }
+        // Fermat's little theorem: a^(P-1) = 1.
+        Some(self.pow(P - 2))
+    }
+}
+
+impl<const P: u64> Zero for Gf<P> {
+    #[inline]
+    fn zero() -> Gf<P> { Gf { value: 0 } }
+
+    #[inline]
+    fn is_zero(&self) -> bool { self.value == 0 }
+}
+
+impl<const P: u64> One for Gf<P> {
+    #[inline]
+    fn one() -> Gf<P> { Gf { value: 1 % P } }
+}
+
+impl<const P: u64> Add for Gf<P> {
+    type Output = Gf<P>;
+
+    #[inline]
+    fn add(self, other: Gf<P>) -> Gf<P> {
+        Gf { value: ((self.value as u128 + other.value as u128) % P as u128) as u64 }
+    }
+}
+
+impl<const P: u64> Sub for Gf<P> {
+    type Output = Gf<P>;
+
+    #[inline]
+    fn sub(self, other: Gf<P>) -> Gf<P> {
+        self + (-other)
+    }
+}
+
+impl<const P: u64> Neg for Gf<P> {
+    type Output = Gf<P>;
+
+    #[inline]
+    fn neg(self) -> Gf<P> {
+        if self.value == 0 { self } else { Gf { value: P - self.value } }
+    }
+}
+
+impl<const P: u64> Mul for Gf<P> {
+    type Output = Gf<P>;
+
+    #[inline]
+    fn mul(self, other: Gf<P>) -> Gf<P> {
+        Gf { value: ((self.value as u128 * other.value as u128) % P as u128) as u64 }
+    }
+}
+
+impl<const P: u64> Div for Gf<P> {
+    type Output = Gf<P>;
+
+    #[inline]
+    fn div(self, other: Gf<P>) -> Gf<P> {
+        Mul::mul(self, other.inv().expect("Division by zero in GF(p)"))
+    }
+}
+
+/// Every nonzero element divides every other, so the remainder of a
+/// division by a nonzero element is always zero.
+impl<const P: u64> Rem for Gf<P> {
+    type Output = Gf<P>;
+
+    #[inline]
+    fn rem(self, other: Gf<P>) -> Gf<P> {
+        if other.is_zero() { panic!("Division by zero in GF(p)") }
+        Gf::zero()
+    }
+}
+
+impl<const P: u64> Num for Gf<P> {
+    type FromStrRadixErr = ParseIntError;
+
+    fn from_str_radix(s: &str, radix: u32) -> Result<Gf<P>, ParseIntError> {
+        i64::from_str_radix(s, radix).map(Gf::from_i64)
+    }
+}
+
+impl<const P: u64> Conjugate for Gf<P> {
+    #[inline]
+    fn conj(&self) -> Gf<P> { *self }
+}
+
+impl<const P: u64> fmt::Display for Gf<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
@@ -6,20 +6,47 @@ extern crate ocl;
 pub use num::traits::*;
 pub use num::{Rational, Complex};
 
+pub use self::block::{Block, BlockLU, BlockMatrix};
 pub use self::dense::{DenseMatrix, DenseRow, DenseColumn};
+pub use self::dense::{AddExpr, DenseExpr, HadamardExpr, LazyMatrix, ScaleExpr, SubExpr};
+pub use self::eigs::{arnoldi, lanczos, lobpcg, randomized_evd, randomized_svd, range_finder};
+pub use self::eigs::{ComplexEigenPairs, EigenPairs, EigsOptions, RandomizedOptions, SingularTriplets, Which};
+pub use self::eigs::{NoPreconditioner, Preconditioner};
 pub use self::error::{Error, Result};
+pub use self::gf::{Gf, Gf2Matrix};
+pub use self::linalg::{BandedLU, Cholesky, LU, QR, QZ};
+pub use self::linalg::{HermiteNormalForm, SmithNormalForm};
+pub use self::linalg::{solve_care, solve_dare, solve_discrete_lyapunov, solve_lyapunov, solve_sylvester};
 pub use self::vector::{Vector};
 pub use self::matrix::{Matrix, ReadOrder};
-pub use self::sparse::{IdentityMatrix, SparseMatrix, ZeroMatrix};
+pub use self::operator::LinearOperator;
+pub use self::ops::{kron, kron_sum, Kronecker};
+pub use self::ops::{Arithmetic, Boolean, MaxPlus, MaxTimes, MinPlus, Semiring, SparseSemiring};
+pub use self::random::{Distribution, Rng, Sketch};
+pub use self::scalar::{ComplexField, Conjugate};
+pub use self::sparse::{BandedMatrix, DiagonalMatrix, IdentityMatrix, PermutationMatrix};
+pub use self::sparse::{SparseMatrix, TridiagonalMatrix};
+pub use self::sparse::{Triangle, TriangularMatrix, ZeroMatrix};
+pub use self::structured::{CirculantMatrix, HankelMatrix, KroneckerMatrix, ToeplitzMatrix};
+pub use self::structured::VandermondeMatrix;
 pub use self::vector::Vector;
 
-mod dense;
-mod error;
 #[macro_use]
 mod macros;
+mod dense;
+mod error;
+mod gf;
+mod block;
+mod eigs;
+pub mod gallery;
+mod linalg;
 mod opencl;
 mod vector;
 mod matrix;
+mod operator;
 mod ops;
+mod random;
+mod scalar;
 mod sparse;
+mod structured;
 mod vector;
@@ -0,0 +1,140 @@
+use ::{ComplexField, DenseColumn, DenseMatrix, Float, Matrix, One, Vector, Zero};
+
+/// A Cholesky factorization `A = L L^H` of a Hermitian positive definite
+/// matrix, where `L` is lower triangular with a real positive diagonal.
+#[derive(Clone, Debug)]
+pub struct Cholesky<T> {
+    n: usize,
+    /// L on and below the diagonal, row major. The upper part is zero.
+    l: Vec<T>,
+}
+
+impl<T: ComplexField> DenseMatrix<T> {
+    /// Compute the Cholesky factorization of a Hermitian positive definite
+    /// matrix. Only the lower triangle is read. Fails with
+    /// `Error::NotPositiveDefinite` if a pivot is not positive.
+    pub fn cholesky(&self) -> ::Result<Cholesky<T>> {
+        if !self.is_square() { return Err(::Error::InvalidDimensions) }
+        let n = self.rows();
+        let a = self.elements();
+        let mut l = vec![T::zero(); n*n];
+        for j in 0..n {
+            let mut d = a[j*n + j].re();
+            for k in 0..j {
+                d = d - l[j*n + k].modulus_sqr();
+            }
+            if d <= T::Real::zero() || d.is_nan() { return Err(::Error::NotPositiveDefinite) }
+            let d = d.sqrt();
+            l[j*n + j] = T::from_real(d);
+            for i in (j + 1)..n {
+                let mut s = a[i*n + j];
+                for k in 0..j {
+                    s = s - l[i*n + k] * l[j*n + k].conj();
+                }
+                l[i*n + j] = s.scale(T::Real::one() / d);
+            }
+        }
+        Ok(Cholesky { n, l })
+    }
+}
+
+impl<T: ComplexField> Cholesky<T> {
+    /// Get the lower triangular factor `L`.
+    pub fn l(&self) -> DenseMatrix<T> {
+        DenseMatrix::from_vec(self.l.clone(), self.n, self.n, None).unwrap()
+    }
+
+    /// Compute the determinant of the factored matrix, which is real and
+    /// positive.
+    pub fn det(&self) -> T {
+        let mut det = T::one();
+        for i in 0..self.n {
+            let l_ii = self.l[i*self.n + i];
+            det = det * l_ii * l_ii;
+        }
+        det
+    }
+
+    /// Solve `L L^H x = b` for a single right hand side of length `n`.
+    fn solve_in_place(&self, b: &[T]) -> Vec<T> {
+        let n = self.n;
+        let mut x = b.to_vec();
+        for i in 0..n {
+            for j in 0..i {
+                x[i] = x[i] - self.l[i*n + j] * x[j];
+            }
+            x[i] = x[i] / self.l[i*n + i];
+        }
+        for i in (0..n).rev() {
+            for j in (i + 1)..n {
+                x[i] = x[i] - self.l[j*n + i].conj() * x[j];
+            }
+            x[i] = x[i] / self.l[i*n + i];
+        }
+        x
+    }
+
+    /// Solve `A X = B` for a matrix of right hand sides.
+    pub fn solve(&self, b: &DenseMatrix<T>) -> ::Result<DenseMatrix<T>> {
+        if b.rows() != self.n { return Err(::Error::InvalidDimensions) }
+        let x = DenseMatrix::zeros(self.n, b.cols());
+        for j in 0..b.cols() {
+            let col: Vec<T> = (0..self.n).map(|i| b.get(i, j).unwrap()).collect();
+            for (i, &a) in self.solve_in_place(&col).iter().enumerate() {
+                x.set(i, j, a);
+            }
+        }
+        Ok(x)
+    }
+
+    /// Solve `A x = b` for a single right hand side.
+    pub fn solve_vector(&self, b: &DenseColumn<T>) -> ::Result<DenseColumn<T>> {
+        if b.len() != self.n { return Err(::Error::InvalidDimensions) }
+        let col: Vec<T> = (0..self.n).map(|i| b.get(i).unwrap()).collect();
+        Ok(DenseColumn::from_vec(self.solve_in_place(&col)))
+    }
+
+    /// Compute the inverse of the factored matrix.
+    pub fn inverse(&self) -> ::Result<DenseMatrix<T>> {
+        self.solve(&DenseMatrix::identity(self.n))
+    }
+
+    /// Update the factorization to that of `A + x x^H` in O(n^2) operations
+    /// with a sequence of rotations.
+    pub fn rank_one_update(&mut self, x: &[T]) -> ::Result<()> {
+        self.rank_one(x, T::Real::one())
+    }
+
+    /// Update the factorization to that of `A - x x^H` in O(n^2) operations
+    /// with a sequence of hyperbolic rotations. Fails with
+    /// `Error::NotPositiveDefinite`, leaving the factorization unchanged, if
+    /// the downdated matrix is not positive definite.
+    pub fn rank_one_downdate(&mut self, x: &[T]) -> ::Result<()> {
+        self.rank_one(x, -T::Real::one())
+    }
+
+    /// Factor `A + sigma x x^H` for `sigma = ±1`. Column `k` of `L` and `x`
+    /// are combined so that `x_k` is zeroed and `L L^H + sigma x x^H` is
+    /// unchanged.
+    fn rank_one(&mut self, x: &[T], sigma: T::Real) -> ::Result<()> {
+        let n = self.n;
+        if x.len() != n { return Err(::Error::InvalidDimensions) }
+        let mut l = self.l.clone();
+        let mut x = x.to_vec();
+        for k in 0..n {
+            let l_kk = l[k*n + k].re();
+            let r = l_kk*l_kk + sigma * x[k].modulus_sqr();
+            if r <= T::Real::zero() || r.is_nan() { return Err(::Error::NotPositiveDefinite) }
+            let r = r.sqrt();
+            let c = r / l_kk;
+            let s = x[k].scale(T::Real::one() / l_kk);
+            l[k*n + k] = T::from_real(r);
+            for i in (k + 1)..n {
+                l[i*n + k] = (l[i*n + k] + (s.conj() * x[i]).scale(sigma)).scale(T::Real::one() / c);
+                x[i] = x[i].scale(c) - s * l[i*n + k];
+            }
+        }
+        self.l = l;
+        Ok(())
+    }
+}
@@ -0,0 +1,287 @@
+use std::cmp::Ordering;
+
+use ::{Complex, Float, Zero};
+
+/// The maximum number of Jacobi sweeps before giving up on a symmetric
+/// eigenproblem.
+static MAX_SWEEPS: usize = 100;
+
+/// The number of QR iterations allowed per eigenvalue in `complex_schur`.
+static MAX_QR_ITERS: usize = 30;
+
+/// Compute all eigenvalues and eigenvectors of a small dense symmetric
+/// matrix stored row major in `a`, using cyclic Jacobi rotations.
+///
+/// Returns the eigenvalues in ascending order together with a row major
+/// `n*n` Vec whose columns are the matching orthonormal eigenvectors.
+pub fn jacobi_eigen<T: Float>(a: &[T], n: usize) -> (Vec<T>, Vec<T>) {
+    let mut a = a.to_vec();
+    let mut v = vec![T::zero(); n*n];
+    for i in 0..n {
+        v[i*n + i] = T::one();
+    }
+
+    let two = T::one() + T::one();
+    for _ in 0..MAX_SWEEPS {
+        let mut off = T::zero();
+        let mut total = T::zero();
+        for p in 0..n {
+            for q in 0..n {
+                let a_pq = a[p*n + q];
+                total = total + a_pq * a_pq;
+                if p != q { off = off + a_pq * a_pq }
+            }
+        }
+        if off <= T::epsilon() * T::epsilon() * total { break }
+
+        for p in 0..n {
+            for q in (p + 1)..n {
+                let a_pq = a[p*n + q];
+                if a_pq == T::zero() { continue }
+                let theta = (a[q*n + q] - a[p*n + p]) / (two * a_pq);
+                let t = if theta.is_infinite() {
+                    T::one() / (two * theta)
+                } else {
+                    theta.signum() / (theta.abs() + (theta * theta + T::one()).sqrt())
+                };
+                let c = T::one() / (t * t + T::one()).sqrt();
+                let s = t * c;
+                for k in 0..n {
+                    let (a_kp, a_kq) = (a[k*n + p], a[k*n + q]);
+                    a[k*n + p] = c * a_kp - s * a_kq;
+                    a[k*n + q] = s * a_kp + c * a_kq;
+                }
+                for k in 0..n {
+                    let (a_pk, a_qk) = (a[p*n + k], a[q*n + k]);
+                    a[p*n + k] = c * a_pk - s * a_qk;
+                    a[q*n + k] = s * a_pk + c * a_qk;
+                }
+                for k in 0..n {
+                    let (v_kp, v_kq) = (v[k*n + p], v[k*n + q]);
+                    v[k*n + p] = c * v_kp - s * v_kq;
+                    v[k*n + q] = s * v_kp + c * v_kq;
+                }
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| a[i*n + i].partial_cmp(&a[j*n + j]).unwrap_or(Ordering::Equal));
+    let values = order.iter().map(|&i| a[i*n + i]).collect();
+    let mut vectors = vec![T::zero(); n*n];
+    for (col, &i) in order.iter().enumerate() {
+        for k in 0..n {
+            vectors[k*n + col] = v[k*n + i];
+        }
+    }
+    (values, vectors)
+}
+
+/// Compute a complex Givens rotation `(c, s)` with real `c` such that
+/// `[c, s; -conj(s), c] * [a; b] = [r; 0]`.
+pub fn givens<T: Float>(a: Complex<T>, b: Complex<T>) -> (T, Complex<T>) {
+    let (na, nb) = (a.norm(), b.norm());
+    if nb == T::zero() { return (T::one(), Complex::zero()) }
+    if na == T::zero() { return (T::zero(), Complex::new(T::one(), T::zero())) }
+    let r = na.hypot(nb);
+    (na / r, a.unscale(na) * b.conj().unscale(r))
+}
+
+/// Multiply the row major `n*n` matrix `mat` from the right by the
+/// Householder reflection `I - 2 v v^H` acting on columns `start..n`.
+fn reflect_columns<T: Float>(mat: &mut [Complex<T>], n: usize, start: usize, v: &[Complex<T>]) {
+    let two = T::one() + T::one();
+    for i in 0..n {
+        let mut s: Complex<T> = Complex::zero();
+        for (l, j) in (start..n).enumerate() {
+            s = s + mat[i*n + j] * v[l];
+        }
+        for (l, j) in (start..n).enumerate() {
+            mat[i*n + j] = mat[i*n + j] - (s * v[l].conj()).scale(two);
+        }
+    }
+}
+
+/// A complex Schur decomposition `A = Q T Q^H` of a small dense matrix, with
+/// both factors stored row major.
+pub struct Schur<T> {
+    /// The unitary Schur vectors.
+    pub q: Vec<Complex<T>>,
+    /// The upper triangular Schur form, with the eigenvalues on its diagonal.
+    pub t: Vec<Complex<T>>,
+}
+
+/// Compute the complex Schur decomposition `A = Q T Q^H` of a small dense
+/// matrix stored row major in `a`, where `T` is upper triangular and `Q` is
+/// unitary. The eigenvalues of `A` are the diagonal of `T`.
+pub fn complex_schur<T: Float>(a: &[Complex<T>], n: usize) -> ::Result<Schur<T>> {
+    let mut h = a.to_vec();
+    let mut q = vec![Complex::zero(); n*n];
+    for i in 0..n {
+        q[i*n + i] = Complex::new(T::one(), T::zero());
+    }
+    let two = T::one() + T::one();
+
+    // Reduce to upper Hessenberg form with Householder reflections.
+    for k in 0..n.saturating_sub(2) {
+        let mut norm = T::zero();
+        for i in (k + 1)..n {
+            norm = norm + h[i*n + k].norm_sqr();
+        }
+        let norm = norm.sqrt();
+        if norm == T::zero() { continue }
+        let x0 = h[(k + 1)*n + k];
+        let phase = if x0.norm() == T::zero() {
+            Complex::new(T::one(), T::zero())
+        } else {
+            x0.unscale(x0.norm())
+        };
+        let mut v: Vec<Complex<T>> = ((k + 1)..n).map(|i| h[i*n + k]).collect();
+        v[0] = v[0] + phase.scale(norm);
+        let vnorm = v.iter().fold(T::zero(), |acc, z| acc + z.norm_sqr()).sqrt();
+        for z in v.iter_mut() { *z = z.unscale(vnorm) }
+
+        for j in 0..n {
+            let mut s = Complex::zero();
+            for (l, i) in ((k + 1)..n).enumerate() {
+                s = s + v[l].conj() * h[i*n + j];
+            }
+            for (l, i) in ((k + 1)..n).enumerate() {
+                h[i*n + j] = h[i*n + j] - (v[l] * s).scale(two);
+            }
+        }
+        reflect_columns(&mut h, n, k + 1, &v);
+        reflect_columns(&mut q, n, k + 1, &v);
+        for i in (k + 2)..n {
+            h[i*n + k] = Complex::zero();
+        }
+    }
+
+    // Shifted QR iterations on the active block [lo, hi].
+    let mut hi = n;
+    let mut iters = 0;
+    while hi > 1 {
+        let hi_i = hi - 1;
+        let mut lo = hi_i;
+        while lo > 0 {
+            let sub = h[lo*n + lo - 1].norm();
+            let diag = h[(lo - 1)*n + lo - 1].norm() + h[lo*n + lo].norm();
+            if sub <= T::epsilon() * diag || sub < T::min_positive_value() {
+                h[lo*n + lo - 1] = Complex::zero();
+                break
+            }
+            lo -= 1;
+        }
+        if lo == hi_i {
+            hi -= 1;
+            iters = 0;
+            continue
+        }
+        iters += 1;
+        if iters > MAX_QR_ITERS * n { return Err(::Error::NotConverged) }
+
+        let mu = if iters % 10 == 0 {
+            // Exceptional shift to break cycles.
+            h[hi_i*n + hi_i] + Complex::new(h[hi_i*n + hi_i - 1].norm(), T::zero())
+        } else {
+            let (a, b) = (h[(hi_i - 1)*n + hi_i - 1], h[(hi_i - 1)*n + hi_i]);
+            let (c, d) = (h[hi_i*n + hi_i - 1], h[hi_i*n + hi_i]);
+            let half = (a - d).unscale(two);
+            let disc = (half * half + b * c).sqrt();
+            let (mu1, mu2) = (d + half + disc, d + half - disc);
+            if (mu1 - d).norm() <= (mu2 - d).norm() { mu1 } else { mu2 }
+        };
+
+        for k in lo..hi {
+            h[k*n + k] = h[k*n + k] - mu;
+        }
+        let mut rotations = Vec::with_capacity(hi_i - lo);
+        for k in lo..hi_i {
+            let (c, s) = givens(h[k*n + k], h[(k + 1)*n + k]);
+            for j in k..n {
+                let (x, y) = (h[k*n + j], h[(k + 1)*n + j]);
+                h[k*n + j] = x.scale(c) + s * y;
+                h[(k + 1)*n + j] = y.scale(c) - s.conj() * x;
+            }
+            rotations.push((c, s));
+        }
+        for (l, k) in (lo..hi_i).enumerate() {
+            let (c, s) = rotations[l];
+            let top = if k + 2 < hi { k + 2 } else { hi };
+            for i in 0..top {
+                let (x, y) = (h[i*n + k], h[i*n + k + 1]);
+                h[i*n + k] = x.scale(c) + y * s.conj();
+                h[i*n + k + 1] = y.scale(c) - x * s;
+            }
+            for i in 0..n {
+                let (x, y) = (q[i*n + k], q[i*n + k + 1]);
+                q[i*n + k] = x.scale(c) + y * s.conj();
+                q[i*n + k + 1] = y.scale(c) - x * s;
+            }
+        }
+        for k in lo..hi {
+            h[k*n + k] = h[k*n + k] + mu;
+        }
+    }
+
+    for i in 1..n {
+        for j in 0..i {
+            h[i*n + j] = Complex::zero();
+        }
+    }
+    Ok(Schur { q, t: h })
+}
+
+/// Swap the adjacent eigenvalues `t_kk` and `t_(k+1)(k+1)` of a complex
+/// Schur decomposition with a single Givens rotation, updating the Schur
+/// form `t` and the Schur vectors `q` in place, as LAPACK `ztrexc` does.
+pub fn swap_schur<T: Float>(t: &mut [Complex<T>], q: &mut [Complex<T>], n: usize, k: usize) {
+    let (t11, t22) = (t[k*n + k], t[(k + 1)*n + k + 1]);
+    let (c, s) = givens(t[k*n + k + 1], t22 - t11);
+    for j in (k + 2)..n {
+        let (x, y) = (t[k*n + j], t[(k + 1)*n + j]);
+        t[k*n + j] = x.scale(c) + s * y;
+        t[(k + 1)*n + j] = y.scale(c) - s.conj() * x;
+    }
+    for i in 0..k {
+        let (x, y) = (t[i*n + k], t[i*n + k + 1]);
+        t[i*n + k] = x.scale(c) + y * s.conj();
+        t[i*n + k + 1] = y.scale(c) - x * s;
+    }
+    for i in 0..n {
+        let (x, y) = (q[i*n + k], q[i*n + k + 1]);
+        q[i*n + k] = x.scale(c) + y * s.conj();
+        q[i*n + k + 1] = y.scale(c) - x * s;
+    }
+    t[k*n + k] = t22;
+    t[(k + 1)*n + k + 1] = t11;
+}
+
+/// Compute the eigenvectors of an upper triangular matrix stored row major
+/// in `t` by back substitution. Returns a row major `n*n` Vec whose columns
+/// are the unit eigenvectors matching the diagonal of `t`.
+pub fn triangular_eigenvectors<T: Float>(t: &[Complex<T>], n: usize)
+    -> Vec<Complex<T>>
+{
+    let norm = t.iter().fold(T::zero(), |acc, z| acc.max(z.norm()));
+    let small = T::epsilon() * if norm > T::zero() { norm } else { T::one() };
+    let mut vectors = vec![Complex::zero(); n*n];
+    for k in 0..n {
+        let mut y = vec![Complex::zero(); k + 1];
+        y[k] = Complex::new(T::one(), T::zero());
+        for i in (0..k).rev() {
+            let mut s: Complex<T> = Complex::zero();
+            for j in (i + 1)..(k + 1) {
+                s = s + t[i*n + j] * y[j];
+            }
+            let mut d = t[i*n + i] - t[k*n + k];
+            if d.norm() < small { d = Complex::new(small, T::zero()) }
+            y[i] = -s / d;
+        }
+        let ynorm = y.iter().fold(T::zero(), |acc, z| acc + z.norm_sqr()).sqrt();
+        for i in 0..(k + 1) {
+            vectors[i*n + k] = y[i].unscale(ynorm);
+        }
+    }
+    vectors
+}
@@ -0,0 +1,102 @@
+use num::Integer;
+use num::rational::Ratio;
+
+use ::{DenseMatrix, Matrix, Num, One, Zero};
+
+impl<T: Clone + Copy + Num> DenseMatrix<T> {
+    /// Compute the determinant with fraction-free Bareiss elimination.
+    ///
+    /// Every division is exact, so over the integers the intermediate values
+    /// stay integral and bounded by minors of the matrix, and over
+    /// `Rational` the result is exact.
+    pub fn det_bareiss(&self) -> ::Result<T> {
+        if !self.is_square() { return Err(::Error::InvalidDimensions) }
+        let n = self.rows();
+        if n == 0 { return Ok(T::one()) }
+        let mut a = self.elements();
+        let mut negate = false;
+        let mut prev = T::one();
+
+        for k in 0..(n - 1) {
+            if a[k*n + k] == T::zero() {
+                match ((k + 1)..n).find(|&i| a[i*n + k] != T::zero()) {
+                    Some(p) => {
+                        for j in 0..n {
+                            a.swap(k*n + j, p*n + j);
+                        }
+                        negate = !negate;
+                    },
+                    None => return Ok(T::zero()),
+                }
+            }
+            let pivot = a[k*n + k];
+            for i in (k + 1)..n {
+                for j in (k + 1)..n {
+                    a[i*n + j] = (a[i*n + j] * pivot - a[i*n + k] * a[k*n + j]) / prev;
+                }
+            }
+            prev = pivot;
+        }
+        let det = a[n*n - 1];
+        Ok(if negate { T::zero() - det } else { det })
+    }
+}
+
+impl<T: Clone + Copy + Integer> DenseMatrix<Ratio<T>> {
+    /// Compute the reduced row echelon form with exact arithmetic. Also
+    /// returns the pivot columns in increasing order.
+    pub fn rref(&self) -> (DenseMatrix<Ratio<T>>, Vec<usize>) {
+        let (m, n) = self.dims();
+        let mut a = self.elements();
+        let mut pivots = Vec::new();
+        let mut r = 0;
+
+        for j in 0..n {
+            if r == m { break }
+            let p = match (r..m).find(|&i| !a[i*n + j].is_zero()) {
+                Some(p) => p,
+                None => continue,
+            };
+            for k in 0..n {
+                a.swap(r*n + k, p*n + k);
+            }
+            let pivot = a[r*n + j];
+            for k in j..n {
+                a[r*n + k] = a[r*n + k] / pivot;
+            }
+            for i in 0..m {
+                let f = a[i*n + j];
+                if i == r || f.is_zero() { continue }
+                for k in j..n {
+                    a[i*n + k] = a[i*n + k] - f * a[r*n + k];
+                }
+            }
+            pivots.push(j);
+            r += 1;
+        }
+        (DenseMatrix::from_vec(a, m, n, None).unwrap(), pivots)
+    }
+
+    /// Compute the exact rank.
+    pub fn rank(&self) -> usize {
+        self.rref().1.len()
+    }
+
+    /// Compute a basis for the nullspace. The basis vectors are the columns
+    /// of the returned `n` by `n - rank` matrix, one per free column of the
+    /// reduced row echelon form.
+    pub fn nullspace(&self) -> DenseMatrix<Ratio<T>> {
+        let n = self.cols();
+        let (r, pivots) = self.rref();
+        let free: Vec<usize> = (0..n).filter(|j| !pivots.contains(j)).collect();
+        let k = free.len();
+        let basis = DenseMatrix::zeros(n, k);
+        for (col, &f) in free.iter().enumerate() {
+            basis.set(f, col, Ratio::one());
+            for (row, &p) in pivots.iter().enumerate() {
+                basis.set(p, col, Ratio::zero() - r.get(row, f).unwrap());
+            }
+        }
+        basis
+    }
+}
@@ -0,0 +1,482 @@
+use ::{Complex, ComplexField, DenseMatrix, Float, LinearOperator, Matrix, Num, NumCast, One,
+       SparseMatrix, ToPrimitive, Zero};
+use super::{complex_schur, swap_schur};
+use super::eigen::Schur;
+
+/// The distance below which `funm` puts two eigenvalues in the same cluster.
+static CLUSTER_DELTA: f64 = 0.1;
+
+/// The number of trapezoid points on the circle around a cluster in `funm`.
+static CONTOUR_POINTS: usize = 64;
+
+/// The number of square roots `logm` may take before giving up.
+static MAX_SQRTS: usize = 64;
+
+/// The Padé degrees used by `expm` without scaling, with the largest 1-norm
+/// for which each is accurate to double precision (Higham 2005).
+static PADE_THETA: [(usize, f64); 4] = [
+    (3, 1.495585217958292e-2),
+    (5, 2.53939833006323e-1),
+    (7, 9.504178996162932e-1),
+    (9, 2.097847961257068e0),
+];
+
+/// The largest 1-norm for the degree 13 Padé approximant.
+static PADE_THETA_13: f64 = 5.371920351148152;
+
+/// The coefficients of the degree 13 Padé approximant to `e^x`. The lower
+/// degrees are in `pade_coefficients`.
+static PADE_13: [f64; 14] = [
+    64764752532480000.0, 32382376266240000.0, 7771770303897600.0,
+    1187353796428800.0, 129060195264000.0, 10559470521600.0, 670442572800.0,
+    33522128640.0, 1323241920.0, 40840800.0, 960960.0, 16380.0, 182.0, 1.0,
+];
+
+/// The largest 1-norm for which a degree m Taylor polynomial approximates
+/// `e^x` to double precision, from Al-Mohy and Higham (2011).
+static TAYLOR_THETA: [(usize, f64); 35] = [
+    (1, 2.29e-16), (2, 2.58e-8), (3, 1.39e-5), (4, 3.40e-4), (5, 2.40e-3),
+    (6, 9.07e-3), (7, 2.38e-2), (8, 5.00e-2), (9, 8.96e-2), (10, 1.44e-1),
+    (11, 2.14e-1), (12, 3.00e-1), (13, 4.00e-1), (14, 5.14e-1), (15, 6.41e-1),
+    (16, 7.81e-1), (17, 9.31e-1), (18, 1.09), (19, 1.26), (20, 1.44),
+    (21, 1.62), (22, 1.82), (23, 2.01), (24, 2.22), (25, 2.43), (26, 2.64),
+    (27, 2.86), (28, 3.08), (29, 3.31), (30, 3.54), (35, 4.7), (40, 6.0),
+    (45, 7.2), (50, 8.5), (55, 9.9),
+];
+
+/// The positive nodes and weights of 8 point Gauss–Legendre quadrature on
+/// `[-1, 1]`. The rule is symmetric, so each pair stands for two points.
+static GAUSS_LEGENDRE_8: [(f64, f64); 4] = [
+    (0.1834346424956498, 0.362683783378362),
+    (0.525532409916329, 0.3137066458778873),
+    (0.7966664774136267, 0.2223810344533745),
+    (0.9602898564975363, 0.1012285362903763),
+];
+
+#[inline]
+fn real<R: Float>(x: f64) -> R {
+    <R as NumCast>::from(x).unwrap()
+}
+
+/// Get the coefficients of the degree m Padé approximant to `e^x`.
+fn pade_coefficients(m: usize) -> &'static [f64] {
+    match m {
+        3 => &[120.0, 60.0, 12.0, 1.0],
+        5 => &[30240.0, 15120.0, 3360.0, 420.0, 30.0, 1.0],
+        7 => &[17297280.0, 8648640.0, 1995840.0, 277200.0, 25200.0, 1512.0, 56.0, 1.0],
+        _ => &[17643225600.0, 8821612800.0, 2075673600.0, 302702400.0, 30270240.0,
+               2162160.0, 110880.0, 3960.0, 90.0, 1.0],
+    }
+}
+
+/// Get the row major `n*n` identity.
+pub fn identity<T: Copy + Num>(n: usize) -> Vec<T> {
+    let mut a = vec![T::zero(); n*n];
+    for i in 0..n {
+        a[i*n + i] = T::one();
+    }
+    a
+}
+
+/// Multiply two row major `n*n` matrices.
+pub fn matmul<T: Copy + Num>(a: &[T], b: &[T], n: usize) -> Vec<T> {
+    let mut c = vec![T::zero(); n*n];
+    for i in 0..n {
+        for k in 0..n {
+            let a_ik = a[i*n + k];
+            if a_ik == T::zero() { continue }
+            for j in 0..n {
+                c[i*n + j] = c[i*n + j] + a_ik * b[k*n + j];
+            }
+        }
+    }
+    c
+}
+
+/// Form the linear combination `sum c_k A_k` of equally sized matrices.
+fn combine<T: ComplexField>(terms: &[(f64, &[T])]) -> Vec<T> {
+    let mut c = vec![T::zero(); terms[0].1.len()];
+    for &(w, a) in terms {
+        let w = T::from_real(real(w));
+        for (c_i, &a_i) in c.iter_mut().zip(a) {
+            *c_i = *c_i + w * a_i;
+        }
+    }
+    c
+}
+
+/// Get the 1-norm of a row major `n*n` matrix.
+fn norm_1<T: ComplexField>(a: &[T], n: usize) -> T::Real {
+    (0..n).fold(T::Real::zero(), |acc, j| {
+        acc.max((0..n).fold(T::Real::zero(), |s, i| s + a[i*n + j].modulus()))
+    })
+}
+
+/// Solve the Sylvester equation `A X - X B = C` where `A` is a p by p and
+/// `B` a q by q upper triangular matrix, all row major, by substitution one
+/// column of `X` at a time. Fails with `Error::Singular` if `A` and `B`
+/// share an eigenvalue.
+pub fn triangular_sylvester<T: ComplexField>(a: &[T], p: usize, b: &[T], q: usize, c: &[T])
+    -> ::Result<Vec<T>>
+{
+    let mut x = c.to_vec();
+    for l in 0..q {
+        for k in 0..l {
+            let b_kl = b[k*q + l];
+            if b_kl == T::zero() { continue }
+            for i in 0..p {
+                x[i*q + l] = x[i*q + l] + x[i*q + k] * b_kl;
+            }
+        }
+        for i in (0..p).rev() {
+            let mut s = x[i*q + l];
+            for j in (i + 1)..p {
+                s = s - a[i*p + j] * x[j*q + l];
+            }
+            let d = a[i*p + i] - b[l*q + l];
+            if d == T::zero() { return Err(::Error::Singular) }
+            x[i*q + l] = s / d;
+        }
+    }
+    Ok(x)
+}
+
+/// Compute the principal square root of an upper triangular matrix by the
+/// Björck–Hammarling recurrence. Fails with `Error::Singular` if there is
+/// no square root, as for a nilpotent Jordan block.
+fn sqrt_triangular<R: Float>(t: &[Complex<R>], n: usize) -> ::Result<Vec<Complex<R>>> {
+    let mut r = vec![Complex::zero(); n*n];
+    for j in 0..n {
+        r[j*n + j] = t[j*n + j].sqrt();
+        for i in (0..j).rev() {
+            let mut s = t[i*n + j];
+            for k in (i + 1)..j {
+                s = s - r[i*n + k] * r[k*n + j];
+            }
+            let d = r[i*n + i] + r[j*n + j];
+            if d == Complex::zero() {
+                if s == Complex::zero() { continue }
+                return Err(::Error::Singular)
+            }
+            r[i*n + j] = s / d;
+        }
+    }
+    Ok(r)
+}
+
+/// Compute the complex Schur decomposition `A = Q T Q^H` of a square matrix.
+fn schur<T: ComplexField>(a: &DenseMatrix<T>) -> ::Result<Schur<T::Real>> {
+    if !a.is_square() { return Err(::Error::InvalidDimensions) }
+    let z: Vec<Complex<T::Real>> = a.elements().iter().map(|x| x.to_complex()).collect();
+    complex_schur(&z, a.rows())
+}
+
+/// Form `Q F Q^H` and convert it to `T`. Fails with `Error::NotReal` if `T`
+/// is real and the result has an imaginary part above rounding level.
+fn from_schur<T: ComplexField>(q: &[Complex<T::Real>], f: &[Complex<T::Real>], n: usize)
+    -> ::Result<DenseMatrix<T>>
+{
+    let qf = matmul(q, f, n);
+    let mut z = vec![Complex::zero(); n*n];
+    for i in 0..n {
+        for j in 0..n {
+            z[i*n + j] = (0..n).fold(Complex::zero(), |acc, k| acc + qf[i*n + k] * q[j*n + k].conj());
+        }
+    }
+    let is_real = T::from_complex(Complex::new(T::Real::zero(), T::Real::one())).im() == T::Real::zero();
+    if is_real {
+        let scale = z.iter().fold(T::Real::one(), |acc, w| acc.max(w.norm()));
+        let tol = T::epsilon().sqrt() * scale;
+        if z.iter().any(|w| w.im.abs() > tol) { return Err(::Error::NotReal) }
+    }
+    DenseMatrix::from_vec(z.into_iter().map(T::from_complex).collect(), n, n, None)
+}
+
+/// Reorder a complex Schur form so that eigenvalues closer than `delta`,
+/// directly or through a chain of others, are adjacent. Returns the ranges
+/// of the diagonal blocks, one per cluster.
+fn cluster_schur<R: Float>(t: &mut [Complex<R>], q: &mut [Complex<R>], n: usize, delta: R)
+    -> Vec<(usize, usize)>
+{
+    // Label each eigenvalue with the least index in its cluster.
+    let mut label: Vec<usize> = (0..n).collect();
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let (a, b) = (label[i], label[j]);
+            if a != b && (t[i*n + i] - t[j*n + j]).norm() <= delta {
+                let (keep, drop) = if a < b { (a, b) } else { (b, a) };
+                for l in label.iter_mut().filter(|l| **l == drop) { *l = keep }
+            }
+        }
+    }
+    // Bubble the eigenvalues into cluster order with adjacent swaps.
+    let mut swapped = true;
+    while swapped {
+        swapped = false;
+        for k in 0..n.saturating_sub(1) {
+            if label[k] > label[k + 1] {
+                swap_schur(t, q, n, k);
+                label.swap(k, k + 1);
+                swapped = true;
+            }
+        }
+    }
+    let mut blocks = Vec::new();
+    let mut start = 0;
+    for k in 1..(n + 1) {
+        if k == n || label[k] != label[start] {
+            blocks.push((start, k));
+            start = k;
+        }
+    }
+    blocks
+}
+
+/// Evaluate `f` on the diagonal block `t[s..e, s..e]` of a Schur form by the
+/// Cauchy integral `(1/2 pi i) int f(z) (z I - T)^-1 dz`, with the
+/// trapezoid rule on a circle around the block's eigenvalues.
+fn contour_block<R, F>(t: &[Complex<R>], n: usize, (s, e): (usize, usize), f: &F, delta: R)
+    -> Vec<Complex<R>>
+    where R: Float, F: Fn(Complex<R>) -> Complex<R>
+{
+    let p = e - s;
+    if p == 1 { return vec![f(t[s*n + s])] }
+    let tb: Vec<Complex<R>> = (s..e).flat_map(|i| (s..e).map(move |j| t[i*n + j])).collect();
+    let center = (0..p).fold(Complex::zero(), |acc, i| acc + tb[i*p + i]).unscale(real(p as f64));
+    let rho = (0..p).fold(R::zero(), |acc, i| acc.max((tb[i*p + i] - center).norm()));
+    let radius = rho + rho + delta;
+    let eye = identity(p);
+    let zero = vec![Complex::zero(); p*p];
+    let mut acc = vec![Complex::zero(); p*p];
+    for k in 0..CONTOUR_POINTS {
+        let theta = real::<R>(2.0 * ::std::f64::consts::PI * k as f64 / CONTOUR_POINTS as f64);
+        let w = Complex::from_polar(&radius, &theta);
+        let z = center + w;
+        let shifted: Vec<Complex<R>> = tb.iter().zip(&eye).map(|(&a, &i)| i * z - a).collect();
+        // The point is off the spectrum, so the resolvent exists.
+        let resolvent = triangular_sylvester(&shifted, p, &zero, p, &eye).unwrap();
+        let c = f(z) * w;
+        for (a, &r) in acc.iter_mut().zip(&resolvent) {
+            *a = *a + c * r;
+        }
+    }
+    acc.into_iter().map(|a| a.unscale(real(CONTOUR_POINTS as f64))).collect()
+}
+
+/// Matrix functions of square dense matrices. Apart from `expm`, these work
+/// from the complex Schur form, so a real matrix whose result is complex,
+/// such as the square root of a matrix with a negative eigenvalue, fails
+/// with `Error::NotReal`. Map such a matrix to `Complex` first to get the
+/// complex result.
+impl<T: ComplexField> DenseMatrix<T> {
+    /// Compute the matrix exponential `e^A` by scaling and squaring with a
+    /// Padé approximant of degree 3 to 13, chosen from the 1-norm as in
+    /// Higham (2005).
+    pub fn expm(&self) -> ::Result<DenseMatrix<T>> {
+        if !self.is_square() { return Err(::Error::InvalidDimensions) }
+        let n = self.rows();
+        let mut a = self.elements();
+        let norm = norm_1(&a, n).to_f64().unwrap();
+        let eye = identity(n);
+        let a2 = matmul(&a, &a, n);
+
+        let (u, v, s) = match PADE_THETA.iter().find(|&&(_, theta)| norm <= theta) {
+            Some(&(m, _)) => {
+                let b = pade_coefficients(m);
+                let mut pows = vec![eye, a2];
+                while 2*pows.len() <= m {
+                    let next = matmul(&pows[pows.len() - 1], &pows[1], n);
+                    pows.push(next);
+                }
+                let odd: Vec<(f64, &[T])> = pows.iter().enumerate().map(|(k, p)| (b[2*k + 1], &p[..])).collect();
+                let even: Vec<(f64, &[T])> = pows.iter().enumerate().map(|(k, p)| (b[2*k], &p[..])).collect();
+                (matmul(&a, &combine(&odd), n), combine(&even), 0)
+            },
+            None => {
+                let s = (norm / PADE_THETA_13).log2().ceil().max(0.0) as i32;
+                let scale = T::from_real(real(2f64.powi(-s)));
+                for x in a.iter_mut() { *x = *x * scale }
+                let b = &PADE_13;
+                let a2 = matmul(&a, &a, n);
+                let a4 = matmul(&a2, &a2, n);
+                let a6 = matmul(&a4, &a2, n);
+                let u_hi = combine(&[(b[13], &a6), (b[11], &a4), (b[9], &a2)]);
+                let u_lo = combine(&[(b[7], &a6), (b[5], &a4), (b[3], &a2), (b[1], &eye)]);
+                let inner = combine(&[(1.0, &matmul(&a6, &u_hi, n)), (1.0, &u_lo)]);
+                let v_hi = combine(&[(b[12], &a6), (b[10], &a4), (b[8], &a2)]);
+                let v_lo = combine(&[(b[6], &a6), (b[4], &a4), (b[2], &a2), (b[0], &eye)]);
+                let v = combine(&[(1.0, &matmul(&a6, &v_hi, n)), (1.0, &v_lo)]);
+                (matmul(&a, &inner, n), v, s)
+            },
+        };
+
+        // r = (V - U)^-1 (V + U)
+        let p = DenseMatrix::from_vec(combine(&[(1.0, &v), (-1.0, &u)]), n, n, None)?;
+        let q = DenseMatrix::from_vec(combine(&[(1.0, &v), (1.0, &u)]), n, n, None)?;
+        let mut r = p.lu()?.solve(&q)?.elements();
+        for _ in 0..s {
+            r = matmul(&r, &r, n);
+        }
+        DenseMatrix::from_vec(r, n, n, None)
+    }
+
+    /// Compute the principal square root, the square root whose eigenvalues
+    /// have positive real part, by the Schur method. Fails with
+    /// `Error::Singular` if the matrix has no square root.
+    pub fn sqrtm(&self) -> ::Result<DenseMatrix<T>> {
+        let Schur { q, t } = schur(self)?;
+        let n = self.rows();
+        from_schur(&q, &sqrt_triangular(&t, n)?, n)
+    }
+
+    /// Compute the principal logarithm by inverse scaling and squaring: take
+    /// square roots of the Schur form until it is close to `I`, apply a
+    /// Padé approximant to `log(I + X)`, and scale back. Fails with
+    /// `Error::Singular` if the matrix is singular.
+    pub fn logm(&self) -> ::Result<DenseMatrix<T>> {
+        let Schur { q, mut t } = schur(self)?;
+        let n = self.rows();
+        if (0..n).any(|i| t[i*n + i] == Complex::zero()) { return Err(::Error::Singular) }
+        let eye: Vec<Complex<T::Real>> = identity(n);
+        let mut k = 0;
+        let mut x: Vec<Complex<T::Real>> = t.iter().zip(&eye).map(|(&a, &i)| a - i).collect();
+        while norm_1(&x, n) > real(0.25) {
+            if k == MAX_SQRTS { return Err(::Error::NotConverged) }
+            t = sqrt_triangular(&t, n)?;
+            x = t.iter().zip(&eye).map(|(&a, &i)| a - i).collect();
+            k += 1;
+        }
+
+        // The m point Gauss–Legendre rule applied to
+        // log(I + X) = int_0^1 X (I + s X)^-1 ds is the [m/m] Padé approximant.
+        let zero = vec![Complex::zero(); n*n];
+        let mut l = vec![Complex::zero(); n*n];
+        for &(node, weight) in GAUSS_LEGENDRE_8.iter() {
+            for &sign in &[-1.0, 1.0] {
+                let s: T::Real = real((1.0 + sign * node) / 2.0);
+                let m: Vec<Complex<T::Real>> = x.iter().zip(&eye).map(|(&a, &i)| i + a.scale(s)).collect();
+                let y = triangular_sylvester(&m, n, &zero, n, &x)?;
+                for (l_i, y_i) in l.iter_mut().zip(y) {
+                    *l_i = *l_i + y_i.scale(real(weight / 2.0));
+                }
+            }
+        }
+        let scale: T::Real = real(2f64.powi(k as i32));
+        let l: Vec<Complex<T::Real>> = l.into_iter().map(|a| a.scale(scale)).collect();
+        from_schur(&q, &l, n)
+    }
+
+    /// Compute `f(A)` for a function `f` analytic on a neighbourhood of the
+    /// spectrum by the Schur–Parlett method of Davies and Higham (2003).
+    ///
+    /// The Schur form is reordered so that close eigenvalues form diagonal
+    /// blocks. `f` of each block comes from a contour integral around its
+    /// eigenvalues, so `f` must be analytic on a disc around each cluster,
+    /// and the blocks are coupled by the block Parlett recurrence.
+    pub fn funm<F>(&self, f: F) -> ::Result<DenseMatrix<T>>
+        where F: Fn(Complex<T::Real>) -> Complex<T::Real>
+    {
+        let Schur { mut q, mut t } = schur(self)?;
+        let n = self.rows();
+        let delta = real(CLUSTER_DELTA);
+        let blocks = cluster_schur(&mut t, &mut q, n, delta);
+
+        let mut fm: Vec<Complex<T::Real>> = vec![Complex::zero(); n*n];
+        for &(s, e) in &blocks {
+            let p = e - s;
+            let fb = contour_block(&t, n, (s, e), &f, delta);
+            for i in 0..p {
+                for j in i..p {
+                    fm[(s + i)*n + s + j] = fb[i*p + j];
+                }
+            }
+        }
+
+        let sub = |a: &[Complex<T::Real>], (r0, r1): (usize, usize), (c0, c1): (usize, usize)| -> Vec<Complex<T::Real>> {
+            (r0..r1).flat_map(|i| (c0..c1).map(move |j| a[i*n + j])).collect()
+        };
+        for (jb, &bj) in blocks.iter().enumerate() {
+            for &bi in blocks[..jb].iter().rev() {
+                // T_ii F_ij - F_ij T_jj = sum_k F_ik T_kj - T_ik F_kj over
+                // the blocks k other than F_ij itself.
+                let mut c = Vec::with_capacity((bi.1 - bi.0)*(bj.1 - bj.0));
+                for r in bi.0..bi.1 {
+                    for col in bj.0..bj.1 {
+                        let left = (bi.0..bj.0).fold(Complex::<T::Real>::zero(), |acc, l| acc + fm[r*n + l] * t[l*n + col]);
+                        let right = (bi.1..bj.1).fold(Complex::<T::Real>::zero(), |acc, l| acc + t[r*n + l] * fm[l*n + col]);
+                        c.push(left - right);
+                    }
+                }
+                let (p, w) = (bi.1 - bi.0, bj.1 - bj.0);
+                let x = triangular_sylvester(&sub(&t, bi, bi), p, &sub(&t, bj, bj), w, &c)?;
+                for i in 0..p {
+                    for j in 0..w {
+                        fm[(bi.0 + i)*n + bj.0 + j] = x[i*w + j];
+                    }
+                }
+            }
+        }
+        from_schur(&q, &fm, n)
+    }
+}
+
+impl<T: ComplexField> SparseMatrix<T> {
+    /// Compute `e^A v` without forming `e^A`, using only products with `A`.
+    ///
+    /// This is the truncated Taylor method of Al-Mohy and Higham (2011):
+    /// `A` is shifted by `trace(A)/n`, the interval is split into `s` steps
+    /// and each step sums a Taylor series of degree at most `m`, stopping
+    /// early once the terms are negligible. `m` and `s` are chosen from the
+    /// 1-norm to minimize the number of products.
+    pub fn expm_multiply(&self, v: &[T]) -> ::Result<Vec<T>> {
+        if !self.is_square() || v.len() != self.rows() { return Err(::Error::InvalidDimensions) }
+        let n = self.rows();
+        if n == 0 { return Ok(Vec::new()) }
+        let triplets = self.triplets();
+        let trace = triplets.iter().filter(|&&(i, j, _)| i == j).fold(T::zero(), |acc, &(_, _, a)| acc + a);
+        let mu = trace.scale(T::Real::one() / real(n as f64));
+
+        // The 1-norm of A - mu I, counting mu on unstored diagonal elements.
+        let mut cols = vec![mu.modulus(); n];
+        for &(i, j, a) in &triplets {
+            if i == j {
+                cols[j] = cols[j] - mu.modulus() + (a - mu).modulus();
+            } else {
+                cols[j] = cols[j] + a.modulus();
+            }
+        }
+        let norm = cols.iter().fold(T::Real::zero(), |acc, &c| acc.max(c)).to_f64().unwrap();
+
+        let norm_inf = |x: &[T]| x.iter().fold(T::Real::zero(), |acc, z| acc.max(z.modulus()));
+        let (m, s) = if norm == 0.0 {
+            (0, 1)
+        } else {
+            TAYLOR_THETA.iter()
+                .map(|&(m, theta)| (m, (norm / theta).ceil().max(1.0) as usize))
+                .min_by_key(|&(m, s)| m*s)
+                .unwrap()
+        };
+        let tol = T::epsilon() / real(2.0);
+        let eta = T::from_complex(mu.to_complex().unscale(real(s as f64)).exp());
+        let mut f = v.to_vec();
+        let mut b = v.to_vec();
+        for _ in 0..s {
+            let mut c1 = norm_inf(&b);
+            for j in 1..(m + 1) {
+                let scale = T::Real::one() / real((s*j) as f64);
+                b = self.apply(&b).iter().zip(&b).map(|(&ab, &b)| (ab - mu * b).scale(scale)).collect();
+                let c2 = norm_inf(&b);
+                for (f_i, &b_i) in f.iter_mut().zip(&b) {
+                    *f_i = *f_i + b_i;
+                }
+                if c1 + c2 <= tol * norm_inf(&f) { break }
+                c1 = c2;
+            }
+            for f_i in f.iter_mut() {
+                *f_i = eta * *f_i;
+            }
+            b = f.clone();
+        }
+        Ok(f)
+    }
+}
@@ -0,0 +1,759 @@
+pub use self::cholesky::Cholesky;
+pub use self::eigen::{complex_schur, jacobi_eigen, swap_schur, triangular_eigenvectors};
+pub use self::lu::{BandedLU, LU};
+pub use self::norm::{norm_2, scaled_norm_2, to_dense};
+pub use self::normal_form::{HermiteNormalForm, SmithNormalForm};
+pub use self::qr::QR;
+pub use self::qz::QZ;
+pub use self::sylvester::{solve_care, solve_dare, solve_discrete_lyapunov, solve_lyapunov, solve_sylvester};
+
+mod cholesky;
+mod eigen;
+mod exact;
+mod lu;
+mod matfun;
+mod norm;
+mod normal_form;
+mod poly;
+mod qr;
+mod qz;
+mod sylvester;
+mod update;
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+    use ::{Complex, DenseColumn, DenseMatrix, Error, Matrix, Num, Rational, Vector};
+
+    fn c(re: f64, im: f64) -> Complex<f64> {
+        Complex::new(re, im)
+    }
+
+    fn matmul<T: Copy + Num>(A: &DenseMatrix<T>, B: &DenseMatrix<T>) -> DenseMatrix<T> {
+        let C = DenseMatrix::zeros(A.rows(), B.cols());
+        for i in 0..A.rows() {
+            for j in 0..B.cols() {
+                let c = (0..A.cols()).fold(T::zero(), |acc, k| {
+                    acc + A.get(i, k).unwrap() * B.get(k, j).unwrap()
+                });
+                C.set(i, j, c);
+            }
+        }
+        C
+    }
+
+    fn assert_close(A: &DenseMatrix<Complex<f64>>, B: &DenseMatrix<Complex<f64>>) {
+        assert_eq!(A.dims(), B.dims());
+        for i in 0..A.rows() {
+            for j in 0..A.cols() {
+                assert!((A.get(i, j).unwrap() - B.get(i, j).unwrap()).norm() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_complex_lu() {
+        let A = DenseMatrix::new(&[vec![c(1.0, 1.0), c(2.0, 0.0), c(0.0, -1.0)],
+                                       vec![c(0.0, 2.0), c(1.0, -1.0), c(3.0, 0.0)],
+                                       vec![c(4.0, 0.0), c(0.0, 1.0), c(1.0, 1.0)]]).unwrap();
+        let lu = A.lu().unwrap();
+        assert_close(&matmul(&lu.p(), &A), &matmul(&lu.l(), &lu.u()));
+
+        let x = DenseColumn::from_vec(vec![c(1.0, 0.0), c(0.0, 1.0), c(-1.0, 2.0)]);
+        let b: Vec<Complex<f64>> = (0..3).map(|i| {
+            (0..3).fold(c(0.0, 0.0), |acc, j| acc + A.get(i, j).unwrap() * x.get(j).unwrap())
+        }).collect();
+        let y = lu.solve_vector(&DenseColumn::from_vec(b)).unwrap();
+        for i in 0..3 {
+            assert!((y.get(i).unwrap() - x.get(i).unwrap()).norm() < 1e-10);
+        }
+        assert_close(&matmul(&A, &lu.inverse().unwrap()), &DenseMatrix::identity(3));
+
+        let S = DenseMatrix::new(&[vec![1.0, 2.0], vec![2.0, 4.0]]).unwrap();
+        let lu = S.lu().unwrap();
+        assert!(lu.is_singular());
+        assert_eq!(lu.det(), 0.0);
+        match lu.inverse() {
+            Err(Error::Singular) => {},
+            _ => panic!("expected a singular matrix error"),
+        }
+    }
+
+    #[test]
+    fn test_complex_cholesky() {
+        let A = DenseMatrix::new(&[vec![c(4.0, 0.0), c(1.0, -2.0), c(0.0, 1.0)],
+                                       vec![c(1.0, 2.0), c(6.0, 0.0), c(1.0, 0.0)],
+                                       vec![c(0.0, -1.0), c(1.0, 0.0), c(3.0, 0.0)]]).unwrap();
+        assert!(A.is_hermitian());
+        let chol = A.cholesky().unwrap();
+        let L = chol.l();
+        assert_close(&matmul(&L, &L.conj_transpose()), &A);
+        assert!((chol.det() - A.lu().unwrap().det()).norm() < 1e-10);
+        assert_close(&matmul(&A, &chol.inverse().unwrap()), &DenseMatrix::identity(3));
+
+        let B = DenseMatrix::new(&[vec![1.0, 2.0], vec![2.0, 1.0]]).unwrap();
+        match B.cholesky() {
+            Err(Error::NotPositiveDefinite) => {},
+            _ => panic!("expected a not positive definite error"),
+        }
+    }
+
+    #[test]
+    fn test_complex_qr() {
+        let A = DenseMatrix::new(&[vec![c(1.0, 1.0), c(2.0, 0.0)],
+                                       vec![c(0.0, 2.0), c(1.0, -1.0)],
+                                       vec![c(4.0, 0.0), c(0.0, 1.0)],
+                                       vec![c(1.0, 0.0), c(1.0, 1.0)]]).unwrap();
+        let qr = A.qr();
+        let (Q, R) = (qr.q(), qr.r());
+        assert!(Q.is_unitary());
+        assert_close(&matmul(&Q, &R), &A);
+        for i in 0..4 {
+            for j in 0..i.min(2) {
+                assert_eq!(R.get(i, j).unwrap(), c(0.0, 0.0));
+            }
+        }
+
+        // An exactly consistent system is recovered by least squares.
+        let x = [c(2.0, -1.0), c(0.5, 0.5)];
+        let b: Vec<Complex<f64>> = (0..4).map(|i| {
+            (0..2).fold(c(0.0, 0.0), |acc, j| acc + A.get(i, j).unwrap() * x[j])
+        }).collect();
+        let y = qr.solve_vector(&DenseColumn::from_vec(b)).unwrap();
+        for (i, &xi) in x.iter().enumerate() {
+            assert!((y.get(i).unwrap() - xi).norm() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_hermitian_predicates() {
+        let H = DenseMatrix::new(&[vec![c(2.0, 0.0), c(1.0, 1.0)],
+                                       vec![c(1.0, -1.0), c(3.0, 0.0)]]).unwrap();
+        assert!(H.is_hermitian());
+        assert!(!H.is_skew_hermitian());
+        assert!(!H.is_symmetric());
+
+        let K = DenseMatrix::new(&[vec![c(0.0, 1.0), c(2.0, 1.0)],
+                                       vec![c(-2.0, 1.0), c(0.0, 0.0)]]).unwrap();
+        assert!(K.is_skew_hermitian());
+        assert!(!K.is_hermitian());
+
+        let s = 0.5f64.sqrt();
+        let U = DenseMatrix::new(&[vec![c(s, 0.0), c(0.0, s)],
+                                       vec![c(0.0, s), c(s, 0.0)]]).unwrap();
+        assert!(U.is_unitary());
+        assert!(!H.is_unitary());
+    }
+
+    #[test]
+    fn test_conj_transpose() {
+        let A = DenseMatrix::new(&[vec![c(1.0, 2.0), c(3.0, 0.0), c(0.0, -1.0)],
+                                       vec![c(0.0, 1.0), c(2.0, 2.0), c(5.0, 0.0)]]).unwrap();
+        let B = DenseMatrix::new(&[vec![c(1.0, -2.0), c(0.0, -1.0)],
+                                       vec![c(3.0, 0.0), c(2.0, -2.0)],
+                                       vec![c(0.0, 1.0), c(5.0, 0.0)]]).unwrap();
+        assert_eq!(A.conj_transpose(), B);
+
+        let tuples = vec![(0, 2, c(1.0, 1.0)), (1, 0, c(0.0, -3.0))];
+        let S = sparse![tuples; 2, 3];
+        let T = S.conj_transpose();
+        assert_eq!(T.dims(), (3, 2));
+        assert_eq!(T.get(2, 0).unwrap(), c(1.0, -1.0));
+        assert_eq!(T.get(0, 1).unwrap(), c(0.0, 3.0));
+    }
+
+    fn q(n: isize) -> Rational {
+        Rational::from_integer(n)
+    }
+
+    #[test]
+    fn test_det_bareiss() {
+        let A = DenseMatrix::new(&[vec![2, -1, 0, 3],
+                                       vec![1, 3, 2, -2],
+                                       vec![0, 1, 1, 4],
+                                       vec![5, 0, -3, 1]]).unwrap();
+        assert_eq!(A.det_bareiss().unwrap(), 143);
+
+        // A zero leading pivot forces a row swap.
+        let B = DenseMatrix::new(&[vec![0, 1], vec![1, 0]]).unwrap();
+        assert_eq!(B.det_bareiss().unwrap(), -1);
+
+        let S = DenseMatrix::new(&[vec![1, 2, 3], vec![2, 4, 6], vec![0, 1, 1]]).unwrap();
+        assert_eq!(S.det_bareiss().unwrap(), 0);
+
+        let R = DenseMatrix::new(&[vec![Rational::new(1, 2), Rational::new(1, 3)],
+                                       vec![Rational::new(1, 4), Rational::new(1, 5)]]).unwrap();
+        assert_eq!(R.det_bareiss().unwrap(), Rational::new(1, 60));
+    }
+
+    #[test]
+    fn test_rref_rank_nullspace() {
+        let A = DenseMatrix::new(&[vec![q(1), q(2), q(1), q(1)],
+                                       vec![q(2), q(4), q(0), q(6)],
+                                       vec![q(3), q(6), q(1), q(7)]]).unwrap();
+        let (R, pivots) = A.rref();
+        let expected = DenseMatrix::new(&[vec![q(1), q(2), q(0), q(3)],
+                                              vec![q(0), q(0), q(1), q(-2)],
+                                              vec![q(0), q(0), q(0), q(0)]]).unwrap();
+        assert_eq!(R, expected);
+        assert_eq!(pivots, vec![0, 2]);
+        assert_eq!(A.rank(), 2);
+
+        let N = A.nullspace();
+        assert_eq!(N.dims(), (4, 2));
+        assert_eq!(matmul(&A, &N), DenseMatrix::zeros(3, 2));
+
+        let H = DenseMatrix::new(&[vec![Rational::new(1, 1), Rational::new(1, 2)],
+                                       vec![Rational::new(1, 2), Rational::new(1, 3)]]).unwrap();
+        assert_eq!(H.rank(), 2);
+        assert_eq!(H.nullspace().dims(), (2, 0));
+    }
+
+    #[test]
+    fn test_hermite_normal_form() {
+        let A: DenseMatrix<i64> = DenseMatrix::new(&[vec![2, 3, 6, 2],
+                                                         vec![5, 6, 1, 6],
+                                                         vec![8, 3, 1, 1]]).unwrap();
+        let hnf = A.hermite_normal_form();
+        assert_eq!(matmul(&hnf.u, &A), hnf.h);
+        assert_eq!(hnf.u.det_bareiss().unwrap().abs(), 1);
+        let expected = DenseMatrix::new(&[vec![1, 0, 50, -11],
+                                              vec![0, 3, 28, -2],
+                                              vec![0, 0, 61, -13]]).unwrap();
+        assert_eq!(hnf.h, expected);
+    }
+
+    #[test]
+    fn test_smith_normal_form() {
+        let A: DenseMatrix<i64> = DenseMatrix::new(&[vec![2, 4, 4],
+                                                         vec![-6, 6, 12],
+                                                         vec![10, -4, -16]]).unwrap();
+        let snf = A.smith_normal_form();
+        assert_eq!(matmul(&matmul(&snf.u, &A), &snf.v), snf.d);
+        assert_eq!(snf.u.det_bareiss().unwrap().abs(), 1);
+        assert_eq!(snf.v.det_bareiss().unwrap().abs(), 1);
+        let expected = DenseMatrix::new(&[vec![2, 0, 0],
+                                              vec![0, 6, 0],
+                                              vec![0, 0, 12]]).unwrap();
+        assert_eq!(snf.d, expected);
+
+        let B = DenseMatrix::new(&[vec![6, 4], vec![4, 6], vec![2, 2]]).unwrap();
+        let snf = B.smith_normal_form();
+        assert_eq!(matmul(&matmul(&snf.u, &B), &snf.v), snf.d);
+        assert_eq!(snf.d, DenseMatrix::new(&[vec![2, 0], vec![0, 2], vec![0, 0]]).unwrap());
+    }
+
+    #[test]
+    fn test_banded_lu() {
+        use ::BandedMatrix;
+
+        let A: DenseMatrix<f64> = DenseMatrix::new(&[vec![1e-3, 1.0, 0.0, 0.0, 0.0],
+                                                         vec![2.0, 1.0, 1.0, 0.0, 0.0],
+                                                         vec![0.0, 3.0, 1e-3, 1.0, 0.0],
+                                                         vec![0.0, 0.0, 4.0, 1.0, 2.0],
+                                                         vec![0.0, 0.0, 0.0, 5.0, 1.0]]).unwrap();
+        let B = BandedMatrix::from_dense(&A, 1, 1);
+        let lu = B.lu().unwrap();
+        assert!(lu.ipiv().iter().enumerate().any(|(k, &p)| p != k));
+        assert!((lu.det() - A.lu().unwrap().det()).abs() < 1e-10);
+
+        let x = [1.0, -2.0, 3.0, 0.5, 4.0];
+        let b: Vec<f64> = (0..5).map(|i| (0..5).fold(0.0, |acc, j| acc + A.get(i, j).unwrap() * x[j])).collect();
+        let y = lu.solve_vector(&b).unwrap();
+        for i in 0..5 {
+            assert!((y[i] - x[i]).abs() < 1e-10);
+        }
+
+        let W = BandedMatrix::from_dense(&A, 2, 1);
+        assert!((W.lu().unwrap().det() - lu.det()).abs() < 1e-10);
+        let S = BandedMatrix::<f64>::zeros(3, 3, 1, 1);
+        match S.solve_vector(&[1.0, 1.0, 1.0]) {
+            Err(Error::Singular) => (),
+            _ => panic!("expected a singular matrix"),
+        }
+        assert!(BandedMatrix::<f64>::zeros(3, 2, 1, 1).lu().is_err());
+    }
+
+    #[test]
+    fn test_norms_det() {
+        use ::{DiagonalMatrix, IdentityMatrix, ZeroMatrix};
+
+        let A: DenseMatrix<f64> = DenseMatrix::new(&[vec![3.0, 0.0], vec![-4.0, 5.0]]).unwrap();
+        assert_eq!(A.norm_1(), 7.0);
+        assert_eq!(A.norm_inf(), 9.0);
+        assert_eq!(A.norm_max(), 5.0);
+        assert!((A.norm_fro() - 50.0f64.sqrt()).abs() < 1e-12);
+        // The singular values are sqrt(45) and sqrt(5).
+        assert!((A.norm_2().unwrap() - 45.0f64.sqrt()).abs() < 1e-10);
+        assert!((A.clone().transpose().norm_2().unwrap() - 45.0f64.sqrt()).abs() < 1e-10);
+        let B: DenseMatrix<f64> = DenseMatrix::new(&[vec![1.0, 2.0, 2.0]]).unwrap();
+        assert!((B.norm_2().unwrap() - 3.0).abs() < 1e-12);
+        assert!((A.det().unwrap() - 15.0).abs() < 1e-12);
+
+        // The estimate matches ||A||_1 ||A^-1||_1 on small matrices.
+        let C = DenseMatrix::new(&[vec![4.0, -1.0, 0.0, 2.0],
+                                       vec![1.0, 5.0, -2.0, 0.0],
+                                       vec![0.0, 3.0, 6.0, 1.0],
+                                       vec![2.0, 0.0, 1.0, 0.5]]).unwrap();
+        let exact = C.norm_1() * C.lu().unwrap().inverse().unwrap().norm_1();
+        let est = C.cond_1_est().unwrap();
+        assert!(est <= exact * (1.0 + 1e-12) && est >= exact / 3.0, "{} vs {}", est, exact);
+        let S = DenseMatrix::new(&[vec![1.0, 2.0], vec![2.0, 4.0]]).unwrap();
+        match S.cond_1_est() {
+            Err(Error::Singular) => (),
+            _ => panic!("expected a singular matrix"),
+        }
+
+        // A determinant that overflows still has a finite log.
+        let D: DiagonalMatrix<f64> = DiagonalMatrix::new(vec![-10.0; 400]);
+        assert!(D.to_dense().lu().unwrap().det().is_infinite());
+        let (sign, log_abs) = D.log_det().unwrap();
+        assert_eq!(sign, 1.0);
+        assert!((log_abs - 400.0 * 10.0f64.ln()).abs() < 1e-9);
+        let (sign, log_abs) = S.log_det().unwrap();
+        assert_eq!((sign, log_abs), (0.0, f64::NEG_INFINITY));
+
+        let Z = DenseMatrix::new(&[vec![c(0.0, 1.0), c(3.0, 4.0)], vec![c(1.0, 0.0), c(0.0, 0.0)]]).unwrap();
+        assert_eq!(Z.norm_1(), 5.0);
+        assert_eq!(Z.norm_inf(), 6.0);
+        assert!((Z.det().unwrap() - c(-3.0, -4.0)).norm() < 1e-12);
+        let (sign, log_abs) = Z.log_det().unwrap();
+        assert!((sign - c(-0.6, -0.8)).norm() < 1e-12);
+        assert!((log_abs - 5.0f64.ln()).abs() < 1e-12);
+
+        let I: IdentityMatrix<f64> = IdentityMatrix::new(9);
+        assert_eq!((I.norm_1(), I.norm_fro(), I.norm_2().unwrap()), (1.0, 3.0, 1.0));
+        assert_eq!((I.det().unwrap(), I.cond_1_est().unwrap()), (1.0, 1.0));
+        let O: ZeroMatrix<f64> = ZeroMatrix::new(3, 3);
+        assert_eq!((O.norm_inf(), O.norm_2().unwrap(), O.det().unwrap()), (0.0, 0.0, 0.0));
+        assert!(ZeroMatrix::<f64>::new(2, 3).det().is_err());
+    }
+
+    #[test]
+    fn test_vector_norms() {
+        let x: DenseColumn<f64> = DenseColumn::from_vec(vec![3.0, -4.0, 0.0]);
+        assert_eq!(x.norm_1(), 7.0);
+        assert_eq!(x.norm_2(), 5.0);
+        assert_eq!(x.norm_inf(), 4.0);
+        assert!((x.norm_p(2.0) - 5.0).abs() < 1e-12);
+        assert!((x.norm_p(3.0) - 91.0f64.cbrt()).abs() < 1e-12);
+        assert_eq!(x.norm_p(f64::INFINITY), 4.0);
+        let w = DenseColumn::from_vec(vec![1.0, f64::NAN, f64::INFINITY]);
+        assert!(w.norm_inf().is_nan() && w.norm_p(2.0).is_nan() && w.norm_p(f64::INFINITY).is_nan());
+        let v = DenseColumn::from_vec(vec![f64::NAN; 2]).transpose();
+        assert!(v.norm_inf().is_nan() && v.norm_p(1.5).is_nan() && v.norm_2().is_nan());
+        // Scaling keeps huge and tiny elements representable.
+        let y = DenseColumn::from_vec(vec![3e200f64, 4e200]).transpose();
+        assert!((y.norm_2() / 5e200 - 1.0).abs() < 1e-12);
+        let z = DenseColumn::from_vec(vec![c(3.0, 4.0), c(0.0, 0.0)]);
+        assert_eq!(z.norm_2(), 5.0);
+    }
+
+    #[test]
+    fn test_matrix_functions() {
+        use ::SparseMatrix;
+
+        let close = |A: &DenseMatrix<f64>, B: &DenseMatrix<f64>, tol: f64| {
+            assert_eq!(A.dims(), B.dims());
+            for (a, b) in A.elements().iter().zip(B.elements()) {
+                assert!((a - b).abs() <= tol * (1.0 + b.abs()), "{} vs {}", a, b);
+            }
+        };
+        let e = 1.0f64.exp();
+
+        let D: DenseMatrix<f64> = DenseMatrix::new(&[vec![1.0, 0.0], vec![0.0, -2.0]]).unwrap();
+        close(&D.expm().unwrap(), &DenseMatrix::new(&[vec![e, 0.0], vec![0.0, (-2.0f64).exp()]]).unwrap(), 1e-14);
+        let N: DenseMatrix<f64> = DenseMatrix::new(&[vec![0.0, 1.0], vec![0.0, 0.0]]).unwrap();
+        close(&N.expm().unwrap(), &DenseMatrix::new(&[vec![1.0, 1.0], vec![0.0, 1.0]]).unwrap(), 1e-15);
+        let R: DenseMatrix<f64> = DenseMatrix::new(&[vec![0.0, -3.0], vec![3.0, 0.0]]).unwrap();
+        let (cos, sin) = (3.0f64.cos(), 3.0f64.sin());
+        close(&R.expm().unwrap(), &DenseMatrix::new(&[vec![cos, -sin], vec![sin, cos]]).unwrap(), 1e-13);
+        // A large norm needs scaling and squaring (Moler and Van Loan).
+        let M: DenseMatrix<f64> = DenseMatrix::new(&[vec![-49.0, 24.0], vec![-64.0, 31.0]]).unwrap();
+        let (e1, e17) = ((-1.0f64).exp(), (-17.0f64).exp());
+        let expected = DenseMatrix::new(&[vec![3.0 * e17 - 2.0 * e1, 1.5 * (e1 - e17)],
+                                              vec![4.0 * (e17 - e1), 3.0 * e1 - 2.0 * e17]]).unwrap();
+        close(&M.expm().unwrap(), &expected, 1e-12);
+        assert!(DenseMatrix::<f64>::zeros(2, 3).expm().is_err());
+
+        let A: DenseMatrix<f64> = DenseMatrix::new(&[vec![4.0, 1.0, 0.0],
+                                                         vec![1.0, 3.0, 1.0],
+                                                         vec![0.0, 2.0, 5.0]]).unwrap();
+        let S = A.sqrtm().unwrap();
+        close(&matmul(&S, &S), &A, 1e-12);
+        close(&A.logm().unwrap().expm().unwrap(), &A, 1e-12);
+        let B: DenseMatrix<f64> = DenseMatrix::new(&[vec![0.1, 0.5], vec![-0.2, 0.3]]).unwrap();
+        close(&B.expm().unwrap().logm().unwrap(), &B, 1e-12);
+        close(&A.funm(|z| z.exp()).unwrap(), &A.expm().unwrap(), 1e-12);
+
+        // A Jordan block is a single cluster, evaluated by a contour integral.
+        let J: DenseMatrix<f64> = DenseMatrix::new(&[vec![2.0, 1.0, 0.0],
+                                                         vec![0.0, 2.0, 1.0],
+                                                         vec![0.0, 0.0, 2.0]]).unwrap();
+        let e2 = 2.0f64.exp();
+        let expected = DenseMatrix::new(&[vec![e2, e2, e2 / 2.0],
+                                              vec![0.0, e2, e2],
+                                              vec![0.0, 0.0, e2]]).unwrap();
+        close(&J.funm(|z| z.exp()).unwrap(), &expected, 1e-12);
+        close(&J.expm().unwrap(), &expected, 1e-13);
+        // Close and distant eigenvalues together.
+        let K: DenseMatrix<f64> = DenseMatrix::new(&[vec![1.0, 2.0, 0.5, 1.0],
+                                                         vec![0.0, 1.01, 1.0, -1.0],
+                                                         vec![0.0, 0.0, 3.0, 2.0],
+                                                         vec![0.0, 0.0, 0.0, 1.02]]).unwrap();
+        close(&K.funm(|z| z.exp()).unwrap(), &K.expm().unwrap(), 1e-11);
+        close(&K.funm(|z| z.sqrt()).unwrap(), &K.sqrtm().unwrap(), 1e-11);
+
+        // The square root of a real matrix with a negative eigenvalue is complex.
+        let P: DenseMatrix<f64> = DenseMatrix::new(&[vec![-4.0, 0.0], vec![0.0, 9.0]]).unwrap();
+        match P.sqrtm() {
+            Err(Error::NotReal) => (),
+            _ => panic!("expected a complex result"),
+        }
+        let Q = P.map(|x| c(x, 0.0)).sqrtm().unwrap();
+        assert_close(&Q, &DenseMatrix::new(&[vec![c(0.0, 2.0), c(0.0, 0.0)], vec![c(0.0, 0.0), c(3.0, 0.0)]]).unwrap());
+        match DenseMatrix::new(&[vec![0.0, 1.0], vec![0.0, 0.0]]).unwrap().logm() {
+            Err(Error::Singular) => (),
+            _ => panic!("expected a singular matrix"),
+        }
+
+        // e^A v for the 1-D Laplacian matches the dense exponential.
+        let n = 30;
+        let mut tuples = Vec::new();
+        for i in 0..n {
+            tuples.push((i, i, -20.0));
+            if i > 0 { tuples.push((i, i - 1, 10.0)) }
+            if i + 1 < n { tuples.push((i, i + 1, 10.0)) }
+        }
+        let L: SparseMatrix<f64> = SparseMatrix::from_tuple(tuples, n, n);
+        let v: Vec<f64> = (0..n).map(|i| (i as f64 * 0.3).sin()).collect();
+        let w = L.expm_multiply(&v).unwrap();
+        let Ld = DenseMatrix::from_vec(L.elements(), n, n, None).unwrap();
+        let E = Ld.expm().unwrap();
+        for (i, &wi) in w.iter().enumerate() {
+            let expected = (0..n).fold(0.0, |acc, j| acc + E.get(i, j).unwrap() * v[j]);
+            assert!((wi - expected).abs() < 1e-12, "{} vs {}", wi, expected);
+        }
+        assert!(L.expm_multiply(&v[1..]).is_err());
+    }
+
+    #[test]
+    fn test_powers() {
+        use ::{IdentityMatrix, ZeroMatrix};
+
+        // Fibonacci numbers from the linear recurrence.
+        let F: DenseMatrix<i64> = DenseMatrix::new(&[vec![1, 1], vec![1, 0]]).unwrap();
+        assert_eq!(F.pow(10).unwrap().elements(), vec![89, 55, 55, 34]);
+        assert_eq!(F.pow(0).unwrap().elements(), vec![1, 0, 0, 1]);
+        assert_eq!(F.pow(1).unwrap().elements(), F.elements());
+        // det(F) = -1, so the inverse is integral.
+        assert_eq!(F.pow(-3).unwrap().elements(), vec![-1, 2, 2, -3]);
+        let G: DenseMatrix<i64> = DenseMatrix::new(&[vec![2, 0], vec![0, 1]]).unwrap();
+        match G.pow(-1) {
+            Err(Error::Singular) => (),
+            _ => panic!("expected no integral inverse"),
+        }
+        assert!(DenseMatrix::<i64>::zeros(2, 3).pow(2).is_err());
+
+        let r = |a: isize, b: isize| Rational::new(a, b);
+        let R = DenseMatrix::new(&[vec![r(2, 1), r(1, 3)], vec![r(0, 1), r(4, 1)]]).unwrap();
+        let P = matmul(&R.pow(5).unwrap(), &R.pow(-5).unwrap());
+        assert_eq!(P.elements(), vec![r(1, 1), r(0, 1), r(0, 1), r(1, 1)]);
+        assert_eq!(R.pow(-1).unwrap().elements(), vec![r(1, 2), r(-1, 24), r(0, 1), r(1, 4)]);
+        match DenseMatrix::new(&[vec![r(1, 1), r(2, 1)], vec![r(2, 1), r(4, 1)]]).unwrap().pow(-2) {
+            Err(Error::Singular) => (),
+            _ => panic!("expected a singular matrix"),
+        }
+
+        // Paterson–Stockmeyer agrees with Horner's rule.
+        let coeffs: Vec<i64> = vec![3, -1, 4, 1, -5, 9, 2, -6, 5, 3];
+        let expected = coeffs.iter().rev().fold(DenseMatrix::zeros(2, 2), |acc, &c| {
+            let next = matmul(&acc, &F);
+            next.set(0, 0, next.get(0, 0).unwrap() + c);
+            next.set(1, 1, next.get(1, 1).unwrap() + c);
+            next
+        });
+        assert_eq!(F.polyval(&coeffs).unwrap().elements(), expected.elements());
+        assert_eq!(F.polyval(&[7]).unwrap().elements(), vec![7, 0, 0, 7]);
+        assert_eq!(F.polyval(&[]).unwrap().elements(), vec![0, 0, 0, 0]);
+
+        // The characteristic polynomial, exactly and in floating point, and
+        // Cayley–Hamilton.
+        let A: DenseMatrix<i64> = DenseMatrix::new(&[vec![2, -1, 0, 3],
+                                                         vec![1, 0, 4, -2],
+                                                         vec![0, 5, 1, 1],
+                                                         vec![-3, 2, 0, 6]]).unwrap();
+        let q = A.charpoly_leverrier().unwrap();
+        assert_eq!(q[4], 1);
+        assert_eq!(q[3], -9);
+        assert_eq!(q[0], A.det_bareiss().unwrap());
+        assert!(A.polyval(&q).unwrap().elements().iter().all(|&x| x == 0));
+        let Af = A.map(|x| x as f64);
+        for (x, &y) in Af.charpoly().unwrap().iter().zip(&q) {
+            assert!((x - y as f64).abs() < 1e-10 * (1.0 + (y as f64).abs()), "{} vs {}", x, y);
+        }
+        let Z = DenseMatrix::new(&[vec![c(0.0, 1.0), c(2.0, 0.0), c(1.0, 1.0)],
+                                       vec![c(1.0, 0.0), c(0.0, -1.0), c(3.0, 0.0)],
+                                       vec![c(0.0, 2.0), c(1.0, 0.0), c(1.0, 0.0)]]).unwrap();
+        let p = Z.charpoly().unwrap();
+        assert!((p[0] + Z.det().unwrap()).norm() < 1e-12);
+        assert_close(&Z.polyval(&p).unwrap(), &DenseMatrix::zeros(3, 3));
+        assert_eq!(DenseMatrix::<f64>::zeros(0, 0).charpoly().unwrap(), vec![1.0]);
+
+        let I: IdentityMatrix<i64> = IdentityMatrix::new(4);
+        assert_eq!(I.pow(-7).rows(), 4);
+        let O: ZeroMatrix<i64> = ZeroMatrix::new(3, 3);
+        assert_eq!(O.pow(2).unwrap().nnz(), 0);
+        assert_eq!(O.pow(0).unwrap().elements(), DenseMatrix::<i64>::identity(3).elements());
+        assert!(O.pow(-1).is_err());
+        assert!(ZeroMatrix::<i64>::new(2, 3).pow(1).is_err());
+    }
+
+    #[test]
+    fn test_matrix_equations() {
+        use ::{solve_care, solve_dare, solve_discrete_lyapunov, solve_lyapunov, solve_sylvester};
+
+        let small = |A: &DenseMatrix<f64>, tol: f64| {
+            assert!(A.elements().iter().all(|x| x.abs() < tol), "{:?}", A.elements());
+        };
+        let sub = |A: &DenseMatrix<f64>, B: &DenseMatrix<f64>| {
+            DenseMatrix::from_vec(A.elements().iter().zip(B.elements()).map(|(a, b)| a - b).collect(),
+                                  A.rows(), A.cols(), None).unwrap()
+        };
+
+        let A: DenseMatrix<f64> = DenseMatrix::new(&[vec![-3.0, 1.0, 0.5],
+                                                         vec![0.2, -2.0, 1.0],
+                                                         vec![1.0, 0.0, -4.0]]).unwrap();
+        let B: DenseMatrix<f64> = DenseMatrix::new(&[vec![1.0, 2.0], vec![-1.0, 3.0]]).unwrap();
+        let C: DenseMatrix<f64> = DenseMatrix::new(&[vec![1.0, 0.0], vec![2.0, -1.0], vec![0.0, 3.0]]).unwrap();
+        let X = solve_sylvester(&A, &B, &C).unwrap();
+        let AX = matmul(&A, &X);
+        let residual = DenseMatrix::from_vec(AX.elements().iter().zip(matmul(&X, &B).elements())
+                                             .map(|(a, b)| a + b).collect(), 3, 2, None).unwrap();
+        small(&sub(&residual, &C), 1e-12);
+        assert!(solve_sylvester(&A, &B, &B).is_err());
+        // A and -A share the eigenvalue 0.
+        let N = DenseMatrix::new(&[vec![0.0, 1.0], vec![0.0, 0.0]]).unwrap();
+        match solve_sylvester(&N, &N, &B) {
+            Err(Error::Singular) => (),
+            _ => panic!("expected a singular equation"),
+        }
+
+        let Q: DenseMatrix<f64> = DenseMatrix::new(&[vec![2.0, 1.0, 0.0],
+                                                         vec![1.0, 3.0, 0.0],
+                                                         vec![0.0, 0.0, 1.0]]).unwrap();
+        let At = A.clone().transpose();
+        let X = solve_lyapunov(&A, &Q).unwrap();
+        let lhs = DenseMatrix::from_vec(matmul(&A, &X).elements().iter().zip(matmul(&X, &At).elements())
+                                        .map(|(a, b)| a + b).collect(), 3, 3, None).unwrap();
+        small(&sub(&lhs, &Q), 1e-12);
+
+        let S: DenseMatrix<f64> = DenseMatrix::new(&[vec![0.5, 0.3, 0.0],
+                                                         vec![-0.2, 0.4, 0.1],
+                                                         vec![0.1, 0.0, -0.6]]).unwrap();
+        let X = solve_discrete_lyapunov(&S, &Q).unwrap();
+        let SXS = matmul(&matmul(&S, &X), &S.clone().transpose());
+        small(&sub(&sub(&SXS, &X), &Q.map(|x| -x)), 1e-12);
+        let Z = DenseMatrix::new(&[vec![c(0.5, 0.5), c(1.0, 0.0)], vec![c(0.0, 0.0), c(0.0, -0.3)]]).unwrap();
+        let W = DenseMatrix::new(&[vec![c(1.0, 0.0), c(0.0, 1.0)], vec![c(0.0, -1.0), c(2.0, 0.0)]]).unwrap();
+        let X = solve_discrete_lyapunov(&Z, &W).unwrap();
+        let ZXZ = matmul(&matmul(&Z, &X), &Z.conj_transpose());
+        let residual = DenseMatrix::from_vec(ZXZ.elements().iter().zip(X.elements()).zip(W.elements())
+                                             .map(|((a, b), w)| a - b + w).collect(), 2, 2, None).unwrap();
+        assert_close(&residual, &DenseMatrix::zeros(2, 2));
+
+        // The double integrator with unit weights has X = [sqrt 3, 1; 1, sqrt 3].
+        let A: DenseMatrix<f64> = DenseMatrix::new(&[vec![0.0, 1.0], vec![0.0, 0.0]]).unwrap();
+        let B: DenseMatrix<f64> = DenseMatrix::new(&[vec![0.0], vec![1.0]]).unwrap();
+        let Q: DenseMatrix<f64> = DenseMatrix::identity(2);
+        let R: DenseMatrix<f64> = DenseMatrix::identity(1);
+        let X = solve_care(&A, &B, &Q, &R).unwrap();
+        let r3 = 3.0f64.sqrt();
+        small(&sub(&X, &DenseMatrix::new(&[vec![r3, 1.0], vec![1.0, r3]]).unwrap()), 1e-10);
+        assert!(solve_care(&A, &B, &Q, &Q).is_err());
+
+        // Check the DARE residual for a discretized double integrator.
+        let A: DenseMatrix<f64> = DenseMatrix::new(&[vec![1.0, 0.1], vec![0.0, 1.0]]).unwrap();
+        let B: DenseMatrix<f64> = DenseMatrix::new(&[vec![0.005], vec![0.1]]).unwrap();
+        let R: DenseMatrix<f64> = DenseMatrix::new(&[vec![0.5]]).unwrap();
+        let X = solve_dare(&A, &B, &Q, &R).unwrap();
+        let (At, Bt) = (A.clone().transpose(), B.clone().transpose());
+        let AXA = matmul(&matmul(&At, &X), &A);
+        let AXB = matmul(&matmul(&At, &X), &B);
+        let g = R.get(0, 0).unwrap() + matmul(&matmul(&Bt, &X), &B).get(0, 0).unwrap();
+        let K = matmul(&AXB, &AXB.clone().transpose()).map(|x| x / g);
+        small(&sub(&sub(&sub(&AXA, &X), &K), &Q.map(|x| -x)), 1e-9);
+        assert!(X.is_symmetric() && X.get(0, 0).unwrap() > 0.0 && X.det().unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_qz() {
+        let A = DenseMatrix::new(&[vec![1.0, 2.0, 0.0, -1.0],
+                                       vec![3.0, -1.0, 2.0, 0.5],
+                                       vec![0.0, 4.0, 1.0, 2.0],
+                                       vec![2.0, 0.0, -3.0, 1.0]]).unwrap();
+        let B = DenseMatrix::new(&[vec![2.0, 1.0, 0.0, 0.0],
+                                       vec![0.0, 1.0, 1.0, 0.0],
+                                       vec![1.0, 0.0, 3.0, 1.0],
+                                       vec![0.0, 0.0, 0.0, 0.0]]).unwrap();
+        let qz = A.qz(&B).unwrap();
+        let (Q, Z, S, T) = (qz.q(), qz.z(), qz.s(), qz.t());
+        assert!(Q.is_unitary() && Z.is_unitary());
+        for i in 0..4 {
+            for j in 0..i {
+                assert_eq!((S.get(i, j).unwrap(), T.get(i, j).unwrap()), (c(0.0, 0.0), c(0.0, 0.0)));
+            }
+        }
+        let complex = |M: &DenseMatrix<f64>| M.map(|x| c(x, 0.0));
+        assert_close(&matmul(&matmul(&Q, &S), &Z.conj_transpose()), &complex(&A));
+        assert_close(&matmul(&matmul(&Q, &T), &Z.conj_transpose()), &complex(&B));
+        assert!(qz.alpha_beta().iter().all(|&(_, beta)| beta >= 0.0));
+
+        // B has rank 3, so one eigenvalue is infinite and the others make
+        // A - lambda B singular.
+        let values = qz.eigenvalues();
+        assert_eq!(values.iter().filter(|z| z.re.is_infinite()).count(), 1);
+        for &lambda in values.iter().filter(|z| z.re.is_finite()) {
+            let P = DenseMatrix::from_vec(A.elements().iter().zip(B.elements())
+                                          .map(|(&a, b)| c(a, 0.0) - lambda * b).collect(), 4, 4, None).unwrap();
+            assert!(P.det().unwrap().norm() < 1e-9, "{}", lambda);
+        }
+
+        // With B = I the generalized eigenvalues are the eigenvalues.
+        let I: DenseMatrix<f64> = DenseMatrix::identity(4);
+        let sort = |mut v: Vec<Complex<f64>>| {
+            v.sort_by(|a, b| (a.re, a.im).partial_cmp(&(b.re, b.im)).unwrap());
+            v
+        };
+        let values = sort(A.qz(&I).unwrap().eigenvalues());
+        let schur = super::complex_schur(&complex(&A).elements(), 4).unwrap();
+        let expected = sort((0..4).map(|i| schur.t[i*4 + i]).collect());
+        for (x, y) in values.iter().zip(&expected) {
+            assert!((x - y).norm() < 1e-10, "{} vs {}", x, y);
+        }
+        assert!(A.qz(&DenseMatrix::zeros(3, 3)).is_err());
+
+        // K x = w^2 M x for a chain of three unit springs and masses 2, 1, 1.
+        let K = DenseMatrix::new(&[vec![2.0, -1.0, 0.0],
+                                       vec![-1.0, 2.0, -1.0],
+                                       vec![0.0, -1.0, 1.0]]).unwrap();
+        let M = DenseMatrix::new(&[vec![2.0, 0.0, 0.0],
+                                       vec![0.0, 1.0, 0.0],
+                                       vec![0.0, 0.0, 1.0]]).unwrap();
+        let pairs = K.symmetric_definite_eigen(&M).unwrap();
+        assert!(pairs.values.windows(2).all(|w| w[0] <= w[1]));
+        let X = pairs.vectors;
+        let KX = matmul(&K, &X);
+        let MX = matmul(&M, &X);
+        for j in 0..3 {
+            for i in 0..3 {
+                let r: f64 = KX.get(i, j).unwrap() - pairs.values[j] * MX.get(i, j).unwrap();
+                assert!(r.abs() < 1e-12);
+            }
+        }
+        let G = matmul(&X.clone().transpose(), &MX);
+        for i in 0..3 {
+            for j in 0..3 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((G.get(i, j).unwrap() - expected).abs() < 1e-12);
+            }
+        }
+        // The product of the eigenvalues is det(K) / det(M).
+        let product = pairs.values.iter().fold(1.0, |acc, x| acc * x);
+        assert!((product - 0.5).abs() < 1e-12);
+        match K.symmetric_definite_eigen(&K.map(|x| -x)) {
+            Err(Error::NotPositiveDefinite) => (),
+            _ => panic!("expected an indefinite matrix"),
+        }
+        let N = DenseMatrix::new(&[vec![1.0, 2.0, 0.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 1.0]]).unwrap();
+        match N.symmetric_definite_eigen(&M) {
+            Err(Error::NotSymmetric) => (),
+            _ => panic!("expected a nonsymmetric matrix"),
+        }
+    }
+
+    #[test]
+    fn test_low_rank_updates() {
+        let A = DenseMatrix::new(&[vec![c(4.0, 0.0), c(1.0, 1.0), c(0.0, 0.0)],
+                                       vec![c(1.0, -1.0), c(3.0, 0.0), c(0.0, 1.0)],
+                                       vec![c(0.0, 0.0), c(0.0, -1.0), c(2.0, 0.0)]]).unwrap();
+        let x = vec![c(1.0, 0.5), c(-1.0, 0.0), c(0.0, 2.0)];
+        let outer = |sign: f64| DenseMatrix::from_vec(A.elements().iter().enumerate()
+                                                      .map(|(k, &a)| a + (x[k / 3] * x[k % 3].conj()).scale(sign))
+                                                      .collect(), 3, 3, None).unwrap();
+        let mut chol = A.cholesky().unwrap();
+        chol.rank_one_update(&x).unwrap();
+        assert_close(&chol.l(), &outer(1.0).cholesky().unwrap().l());
+        chol.rank_one_downdate(&x).unwrap();
+        assert_close(&chol.l(), &A.cholesky().unwrap().l());
+        let big: Vec<_> = x.iter().map(|z| z.scale(3.0)).collect();
+        match chol.rank_one_downdate(&big) {
+            Err(Error::NotPositiveDefinite) => (),
+            _ => panic!("expected an indefinite matrix"),
+        }
+        assert_close(&chol.l(), &A.cholesky().unwrap().l());
+
+        // (A + U V^H) X = B by Sherman–Morrison–Woodbury.
+        let U = DenseMatrix::new(&[vec![c(1.0, 0.0), c(0.0, 1.0)],
+                                       vec![c(2.0, -1.0), c(0.0, 0.0)],
+                                       vec![c(0.0, 0.0), c(1.0, 1.0)]]).unwrap();
+        let V = DenseMatrix::new(&[vec![c(0.5, 0.0), c(1.0, 0.0)],
+                                       vec![c(0.0, 0.0), c(-1.0, 2.0)],
+                                       vec![c(1.0, -1.0), c(0.0, 0.0)]]).unwrap();
+        let B = DenseMatrix::new(&[vec![c(1.0, 0.0), c(0.0, 0.0)],
+                                       vec![c(0.0, 2.0), c(1.0, 0.0)],
+                                       vec![c(-1.0, 0.0), c(3.0, 1.0)]]).unwrap();
+        let updated = DenseMatrix::from_vec(A.elements().iter().zip(matmul(&U, &V.conj_transpose()).elements())
+                                            .map(|(&a, b)| a + b).collect(), 3, 3, None).unwrap();
+        let X = A.lu().unwrap().solve_woodbury(&U, &V, &B).unwrap();
+        assert_close(&matmul(&updated, &X), &B);
+        assert_close(&A.cholesky().unwrap().solve_woodbury(&U, &V, &B).unwrap(), &X);
+        let u = DenseMatrix::from_vec((0..3).map(|i| -A.get(i, 0).unwrap()).collect(), 3, 1, None).unwrap();
+        let e = DenseMatrix::from_vec(vec![c(1.0, 0.0), c(0.0, 0.0), c(0.0, 0.0)], 3, 1, None).unwrap();
+        match A.lu().unwrap().solve_woodbury(&u, &e, &B) {
+            Err(Error::Singular) => (),
+            _ => panic!("expected a singular update"),
+        }
+
+        // QR updates agree with the updated matrix, and keep Q unitary and R
+        // upper triangular.
+        let check = |qr: &super::QR<Complex<f64>>, rows: &[Vec<Complex<f64>>]| {
+            let (Q, R) = (qr.q(), qr.r());
+            assert!(Q.is_unitary());
+            for i in 0..R.rows() {
+                for j in 0..i.min(R.cols()) {
+                    assert_eq!(R.get(i, j).unwrap(), c(0.0, 0.0));
+                }
+            }
+            assert_close(&matmul(&Q, &R), &DenseMatrix::new(rows).unwrap());
+        };
+        let mut rows = vec![vec![c(1.0, 0.0), c(2.0, 1.0), c(0.0, -1.0)],
+                            vec![c(0.0, 1.0), c(1.0, 0.0), c(3.0, 0.0)],
+                            vec![c(2.0, 0.0), c(-1.0, 0.0), c(1.0, 1.0)],
+                            vec![c(1.0, -2.0), c(0.0, 0.0), c(2.0, 0.0)]];
+        let mut qr = DenseMatrix::new(&rows).unwrap().qr();
+        let row = vec![c(0.5, 0.5), c(-2.0, 0.0), c(1.0, 3.0)];
+        qr.insert_row(2, &row).unwrap();
+        rows.insert(2, row);
+        check(&qr, &rows);
+        qr.delete_row(0).unwrap();
+        rows.remove(0);
+        check(&qr, &rows);
+        let col = vec![c(1.0, 0.0), c(0.0, -1.0), c(2.0, 2.0), c(-1.0, 0.0)];
+        qr.insert_col(1, &col).unwrap();
+        for (row, &z) in rows.iter_mut().zip(&col) {
+            row.insert(1, z);
+        }
+        check(&qr, &rows);
+        qr.delete_col(0).unwrap();
+        for row in rows.iter_mut() {
+            row.remove(0);
+        }
+        check(&qr, &rows);
+        qr.delete_row(3).unwrap();
+        rows.remove(3);
+        check(&qr, &rows);
+        assert!(qr.insert_row(5, &[c(0.0, 0.0); 3]).is_err());
+        assert!(qr.delete_col(3).is_err());
+    }
+}
@@ -0,0 +1,46 @@
+use ::{Complex, ComplexField, DenseMatrix, Float, Matrix, One, Zero};
+use super::complex_schur;
+
+/// Compute `sqrt(sum |x_i|^2)` from the moduli `|x_i|`, scaling by the
+/// largest so that squaring cannot overflow or underflow.
+pub fn scaled_norm_2<R: Float, I: Iterator<Item = R> + Clone>(moduli: I) -> R {
+    let scale = moduli.clone().fold(R::zero(), |acc, r| if r.is_nan() || r > acc { r } else { acc });
+    if scale == R::zero() || !scale.is_finite() { return scale }
+    let sum = moduli.fold(R::zero(), |acc, r| { let q = r / scale; acc + q * q });
+    scale * sum.sqrt()
+}
+
+/// Copy any matrix into a row major dense matrix.
+pub fn to_dense<T: ComplexField, M: Matrix<T>>(a: &M) -> DenseMatrix<T> {
+    DenseMatrix::from_vec(a.elements(), a.rows(), a.cols(), None).unwrap()
+}
+
+/// Compute the spectral norm, the largest singular value, as the square root
+/// of the largest eigenvalue of the smaller of the Gram matrices `A^H A` and
+/// `A A^H`. The matrix is scaled by its largest modulus first, so that the
+/// Gram matrix cannot overflow.
+pub fn norm_2<T: ComplexField, M: Matrix<T>>(a: &M) -> ::Result<T::Real> {
+    let (m, n) = a.dims();
+    let k = if m < n { m } else { n };
+    if k == 0 { return Ok(T::Real::zero()) }
+    let mut a = a.elements();
+    let scale = a.iter().fold(T::Real::zero(), |acc, z| acc.max(z.modulus()));
+    if scale == T::Real::zero() || !scale.is_finite() { return Ok(scale) }
+    for z in a.iter_mut() { *z = z.scale(T::Real::one() / scale) }
+    let mut gram: Vec<Complex<T::Real>> = vec![Complex::zero(); k*k];
+    for i in 0..k {
+        for j in i..k {
+            let mut acc = T::zero();
+            if m < n {
+                for l in 0..n { acc = acc + a[i*n + l] * a[j*n + l].conj() }
+            } else {
+                for l in 0..m { acc = acc + a[l*n + i].conj() * a[l*n + j] }
+            }
+            gram[i*k + j] = acc.to_complex();
+            gram[j*k + i] = acc.conj().to_complex();
+        }
+    }
+    let schur = complex_schur(&gram, k)?;
+    let lambda = (0..k).fold(T::Real::zero(), |acc, i| acc.max(schur.t[i*k + i].re));
+    Ok(scale * lambda.sqrt())
+}
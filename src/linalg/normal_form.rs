@@ -0,0 +1,215 @@
+use num::Integer;
+
+use ::{DenseMatrix, Matrix, Signed};
+
+/// The Hermite normal form `H = U A` of an integer matrix.
+///
+/// `H` is in row echelon form with positive pivots, every entry above a
+/// pivot lies in `[0, pivot)`, and `U` is unimodular.
+#[derive(Clone, Debug)]
+pub struct HermiteNormalForm<T> where T: Copy {
+    pub h: DenseMatrix<T>,
+    pub u: DenseMatrix<T>,
+}
+
+/// The Smith normal form `D = U A V` of an integer matrix.
+///
+/// `D` is diagonal with nonnegative entries, each dividing the next, and `U`
+/// and `V` are unimodular.
+#[derive(Clone, Debug)]
+pub struct SmithNormalForm<T> where T: Copy {
+    pub d: DenseMatrix<T>,
+    pub u: DenseMatrix<T>,
+    pub v: DenseMatrix<T>,
+}
+
+/// Get `(g, x, y)` with `x a + y b = g = gcd(a, b)` and `g >= 0`.
+fn extended_gcd<T: Copy + Integer + Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (T::one(), T::zero());
+    let (mut y0, mut y1) = (T::zero(), T::one());
+    while !r1.is_zero() {
+        let q = r0 / r1;
+        let r = r0 - q * r1;
+        r0 = r1;
+        r1 = r;
+        let x = x0 - q * x1;
+        x0 = x1;
+        x1 = x;
+        let y = y0 - q * y1;
+        y0 = y1;
+        y1 = y;
+    }
+    if r0.is_negative() { (-r0, -x0, -y0) } else { (r0, x0, y0) }
+}
+
+fn identity<T: Copy + Integer>(n: usize) -> Vec<T> {
+    let mut mat = vec![T::zero(); n*n];
+    for i in 0..n {
+        mat[i*n + i] = T::one();
+    }
+    mat
+}
+
+/// Replace rows `r` and `s` of the row major `mat` with `n` columns by
+/// `(a row_r + b row_s, c row_r + d row_s)`.
+fn combine_rows<T: Copy + Integer>(mat: &mut [T], n: usize, r: usize, s: usize,
+                                   (a, b, c, d): (T, T, T, T)) {
+    for j in 0..n {
+        let (x, y) = (mat[r*n + j], mat[s*n + j]);
+        mat[r*n + j] = a * x + b * y;
+        mat[s*n + j] = c * x + d * y;
+    }
+}
+
+fn negate_row<T: Copy + Signed>(mat: &mut [T], n: usize, r: usize) {
+    for x in mat[r*n..(r + 1)*n].iter_mut() {
+        *x = -*x;
+    }
+}
+
+/// Subtract `q` times column `c` from column `j`.
+fn sub_col<T: Copy + Integer>(mat: &mut [T], m: usize, n: usize, j: usize, c: usize, q: T) {
+    for i in 0..m {
+        mat[i*n + j] = mat[i*n + j] - q * mat[i*n + c];
+    }
+}
+
+fn swap_rows<T>(mat: &mut [T], n: usize, r: usize, s: usize) {
+    if r == s { return }
+    for j in 0..n {
+        mat.swap(r*n + j, s*n + j);
+    }
+}
+
+fn swap_cols<T>(mat: &mut [T], m: usize, n: usize, c: usize, d: usize) {
+    if c == d { return }
+    for i in 0..m {
+        mat.swap(i*n + c, i*n + d);
+    }
+}
+
+impl<T: Clone + Copy + Integer + Signed> DenseMatrix<T> {
+    /// Compute the row-style Hermite normal form with exact integer
+    /// arithmetic.
+    pub fn hermite_normal_form(&self) -> HermiteNormalForm<T> {
+        let (m, n) = self.dims();
+        let mut a = self.elements();
+        let mut u = identity(m);
+        let one = T::one();
+        let zero = T::zero();
+        let mut r = 0;
+
+        for j in 0..n {
+            if r == m { break }
+            for i in (r + 1)..m {
+                let (p, b) = (a[r*n + j], a[i*n + j]);
+                if b.is_zero() { continue }
+                // The 2x2 transform has determinant one, so it is unimodular.
+                let (g, x, y) = extended_gcd(p, b);
+                let op = (x, y, zero - b / g, p / g);
+                combine_rows(&mut a, n, r, i, op);
+                combine_rows(&mut u, m, r, i, op);
+            }
+            let pivot = a[r*n + j];
+            if pivot.is_zero() { continue }
+            if pivot.is_negative() {
+                negate_row(&mut a, n, r);
+                negate_row(&mut u, m, r);
+            }
+            let pivot = a[r*n + j];
+            for i in 0..r {
+                let q = a[i*n + j].div_floor(&pivot);
+                if q.is_zero() { continue }
+                let op = (one, zero - q, zero, one);
+                combine_rows(&mut a, n, i, r, op);
+                combine_rows(&mut u, m, i, r, op);
+            }
+            r += 1;
+        }
+        HermiteNormalForm {
+            h: DenseMatrix::from_vec(a, m, n, None).unwrap(),
+            u: DenseMatrix::from_vec(u, m, m, None).unwrap(),
+        }
+    }
+
+    /// Compute the Smith normal form with exact integer arithmetic.
+    pub fn smith_normal_form(&self) -> SmithNormalForm<T> {
+        let (m, n) = self.dims();
+        let mut a = self.elements();
+        let mut u = identity(m);
+        let mut v = identity(n);
+        let one = T::one();
+        let zero = T::zero();
+
+        for t in 0..(if m < n { m } else { n }) {
+            loop {
+                // Move the smallest nonzero entry of the trailing block to
+                // the pivot position.
+                let mut best = None;
+                for i in t..m {
+                    for j in t..n {
+                        let x = a[i*n + j];
+                        if x.is_zero() { continue }
+                        match best {
+                            Some((_, _, b)) if x.abs() >= b => {},
+                            _ => best = Some((i, j, x.abs())),
+                        }
+                    }
+                }
+                let (pi, pj) = match best {
+                    Some((i, j, _)) => (i, j),
+                    None => break,
+                };
+                swap_rows(&mut a, n, t, pi);
+                swap_rows(&mut u, m, t, pi);
+                swap_cols(&mut a, m, n, t, pj);
+                swap_cols(&mut v, n, n, t, pj);
+
+                let pivot = a[t*n + t];
+                let mut done = true;
+                for i in (t + 1)..m {
+                    let q = a[i*n + t] / pivot;
+                    if !q.is_zero() {
+                        let op = (one, zero, zero - q, one);
+                        combine_rows(&mut a, n, t, i, op);
+                        combine_rows(&mut u, m, t, i, op);
+                    }
+                    if !a[i*n + t].is_zero() { done = false }
+                }
+                for j in (t + 1)..n {
+                    let q = a[t*n + j] / pivot;
+                    if !q.is_zero() {
+                        sub_col(&mut a, m, n, j, t, q);
+                        sub_col(&mut v, n, n, j, t, q);
+                    }
+                    if !a[t*n + j].is_zero() { done = false }
+                }
+                if done {
+                    // The pivot must divide the whole trailing block. If it
+                    // does not, fold an offending row in and reduce again.
+                    let bad = ((t + 1)..m).find(|&i| {
+                        ((t + 1)..n).any(|j| !a[i*n + j].is_multiple_of(&pivot))
+                    });
+                    match bad {
+                        Some(i) => {
+                            let op = (one, one, zero, one);
+                            combine_rows(&mut a, n, t, i, op);
+                            combine_rows(&mut u, m, t, i, op);
+                        },
+                        None => break,
+                    }
+                }
+            }
+            if a[t*n + t].is_negative() {
+                negate_row(&mut a, n, t);
+                negate_row(&mut u, m, t);
+            }
+        }
+        SmithNormalForm {
+            d: DenseMatrix::from_vec(a, m, n, None).unwrap(),
+            u: DenseMatrix::from_vec(u, m, m, None).unwrap(),
+            v: DenseMatrix::from_vec(v, n, n, None).unwrap(),
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use ::{ComplexField, DenseMatrix, Matrix, Num, One, Zero};
+use super::matfun::{identity, matmul};
+use super::scaled_norm_2;
+
+/// Get the integer `k` as an element of `T`.
+fn from_usize<T: Num>(k: usize) -> T {
+    (0..k).fold(T::zero(), |acc, _| acc + T::one())
+}
+
+/// Invert a row major `n*n` matrix by fraction-free Gauss–Jordan
+/// elimination on `[A | I]`, which leaves `[d I | d A^-1]` with `d = det(A)`
+/// after divisions that are all exact. Fails with `Error::Singular` if the
+/// matrix is singular, or if `T` divides like an integer and `A^-1` is not
+/// integral.
+fn exact_inverse<T: Copy + Num>(a: &[T], n: usize) -> ::Result<Vec<T>> {
+    let w = 2*n;
+    let mut m = vec![T::zero(); n*w];
+    for i in 0..n {
+        m[i*w..(i*w + n)].copy_from_slice(&a[i*n..(i*n + n)]);
+        m[i*w + n + i] = T::one();
+    }
+    let mut prev = T::one();
+    for k in 0..n {
+        let p = match (k..n).find(|&i| m[i*w + k] != T::zero()) {
+            Some(p) => p,
+            None => return Err(::Error::Singular),
+        };
+        for j in 0..w {
+            m.swap(k*w + j, p*w + j);
+        }
+        let pivot = m[k*w + k];
+        for i in (0..n).filter(|&i| i != k) {
+            let f = m[i*w + k];
+            for j in (0..w).filter(|&j| j != k) {
+                m[i*w + j] = (m[i*w + j] * pivot - f * m[k*w + j]) / prev;
+            }
+            m[i*w + k] = T::zero();
+        }
+        prev = pivot;
+    }
+
+    let integral = T::one() / (T::one() + T::one()) == T::zero();
+    let mut inv = Vec::with_capacity(n*n);
+    for i in 0..n {
+        for j in 0..n {
+            let x = m[i*w + n + j];
+            let q = x / prev;
+            if integral && q * prev != x { return Err(::Error::Singular) }
+            inv.push(q);
+        }
+    }
+    Ok(inv)
+}
+
+impl<T: Clone + Copy + Num> DenseMatrix<T> {
+    /// Raise a square matrix to the power `k` by repeated squaring, with
+    /// `A^0 = I`. A negative `k` raises the inverse, which is computed
+    /// exactly by fraction-free elimination. That suits integer and
+    /// `Rational` matrices; for floating point matrices
+    /// `lu()?.inverse()?.pow(-k)` pivots for stability instead.
+    ///
+    /// Fails with `Error::Singular` if `k < 0` and the matrix has no inverse
+    /// over `T`. Over the integers that means unless the determinant is ±1.
+    pub fn pow(&self, k: i32) -> ::Result<DenseMatrix<T>> {
+        if !self.is_square() { return Err(::Error::InvalidDimensions) }
+        let n = self.rows();
+        let mut base = if k < 0 { exact_inverse(&self.elements(), n)? } else { self.elements() };
+        let mut e = k.unsigned_abs();
+        let mut acc: Option<Vec<T>> = None;
+        while e > 0 {
+            if e & 1 == 1 {
+                acc = Some(match acc {
+                    Some(a) => matmul(&a, &base, n),
+                    None => base.clone(),
+                });
+            }
+            e >>= 1;
+            if e > 0 { base = matmul(&base, &base, n) }
+        }
+        DenseMatrix::from_vec(acc.unwrap_or_else(|| identity(n)), n, n, None)
+    }
+
+    /// Evaluate the polynomial `c_0 I + c_1 A + ... + c_d A^d` for `coeffs`
+    /// `[c_0, ..., c_d]` by the Paterson–Stockmeyer method. With
+    /// `s ~ sqrt(d)`, the powers up to `A^s` are formed once and the
+    /// polynomial is evaluated by Horner's rule in `A^s`, for about
+    /// `2 sqrt(d)` matrix products instead of `d`.
+    pub fn polyval(&self, coeffs: &[T]) -> ::Result<DenseMatrix<T>> {
+        if !self.is_square() { return Err(::Error::InvalidDimensions) }
+        let n = self.rows();
+        let d = coeffs.len();
+        if d == 0 { return Ok(DenseMatrix::zeros(n, n)) }
+        let mut s = 1;
+        while s*s < d { s += 1 }
+        let a = self.elements();
+        let mut pows = vec![identity(n)];
+        for j in 1..(s + 1) {
+            let next = matmul(&pows[j - 1], &a, n);
+            pows.push(next);
+        }
+
+        // The polynomial is sum_k B_k (A^s)^k with B_k of degree below s.
+        let block = |k: usize| {
+            let mut b = vec![T::zero(); n*n];
+            for (j, &c) in coeffs.iter().enumerate().skip(k*s).take(s) {
+                if c == T::zero() { continue }
+                for (b_i, &p_i) in b.iter_mut().zip(&pows[j - k*s]) {
+                    *b_i = *b_i + c * p_i;
+                }
+            }
+            b
+        };
+        let r = (d - 1)/s + 1;
+        let mut p = block(r - 1);
+        for k in (0..(r - 1)).rev() {
+            p = matmul(&p, &pows[s], n);
+            for (p_i, b_i) in p.iter_mut().zip(block(k)) {
+                *p_i = *p_i + b_i;
+            }
+        }
+        DenseMatrix::from_vec(p, n, n, None)
+    }
+
+    /// Compute the characteristic polynomial `det(x I - A)` as `charpoly`
+    /// does, with the Faddeev–LeVerrier recurrence. Every division is by an
+    /// integer `k <= n` and exact, so the result is exact over the integers
+    /// and `Rational`, at the cost of `n` matrix products.
+    pub fn charpoly_leverrier(&self) -> ::Result<Vec<T>> {
+        if !self.is_square() { return Err(::Error::InvalidDimensions) }
+        let n = self.rows();
+        let a = self.elements();
+        let mut c = vec![T::zero(); n + 1];
+        c[n] = T::one();
+        // M_k = A M_(k-1) + c_(n-k+1) I, c_(n-k) = -tr(A M_k)/k, M_0 = 0
+        let mut m = vec![T::zero(); n*n];
+        for k in 1..(n + 1) {
+            for i in 0..n {
+                m[i*n + i] = m[i*n + i] + c[n + 1 - k];
+            }
+            let am = matmul(&a, &m, n);
+            let trace = (0..n).fold(T::zero(), |acc, i| acc + am[i*n + i]);
+            c[n - k] = (T::zero() - trace) / from_usize(k);
+            m = am;
+        }
+        Ok(c)
+    }
+}
+
+impl<T: ComplexField> DenseMatrix<T> {
+    /// Compute the characteristic polynomial `det(x I - A)` of a square
+    /// matrix as its coefficients `[c_0, ..., c_n]` in increasing degree, so
+    /// that `c_n = 1` and `polyval` of the result is zero.
+    ///
+    /// The matrix is reduced to upper Hessenberg form `H` by Householder
+    /// reflections, and the characteristic polynomials of the leading
+    /// principal submatrices of `H` follow from a recurrence, in O(n^3)
+    /// operations.
+    pub fn charpoly(&self) -> ::Result<Vec<T>> {
+        if !self.is_square() { return Err(::Error::InvalidDimensions) }
+        let n = self.rows();
+        let mut h = self.elements();
+        let two = T::Real::one() + T::Real::one();
+        for k in 0..n.saturating_sub(2) {
+            let norm = scaled_norm_2(((k + 1)..n).map(|i| h[i*n + k].modulus()));
+            if norm == T::Real::zero() { continue }
+            let x0 = h[(k + 1)*n + k];
+            let phase = if x0 == T::zero() { T::one() } else { x0.scale(T::Real::one() / x0.modulus()) };
+            let mut v: Vec<T> = ((k + 1)..n).map(|i| h[i*n + k]).collect();
+            v[0] = v[0] + phase.scale(norm);
+            let vnorm = scaled_norm_2(v.iter().map(|z| z.modulus()));
+            for z in v.iter_mut() { *z = z.scale(T::Real::one() / vnorm) }
+            // H = P H P with P = I - 2 v v^H acting on rows and columns k+1..n.
+            for j in 0..n {
+                let s = v.iter().enumerate().fold(T::zero(), |acc, (l, v_l)| acc + v_l.conj() * h[(k + 1 + l)*n + j]);
+                for (l, v_l) in v.iter().enumerate() {
+                    h[(k + 1 + l)*n + j] = h[(k + 1 + l)*n + j] - (*v_l * s).scale(two);
+                }
+            }
+            for i in 0..n {
+                let s = v.iter().enumerate().fold(T::zero(), |acc, (l, &v_l)| acc + h[i*n + k + 1 + l] * v_l);
+                for (l, v_l) in v.iter().enumerate() {
+                    h[i*n + k + 1 + l] = h[i*n + k + 1 + l] - (s * v_l.conj()).scale(two);
+                }
+            }
+        }
+
+        // p_k = (x - h_kk) p_(k-1)
+        //       - sum_i h_(k-i),k h_k,(k-1) ... h_(k-i+1),(k-i) p_(k-i-1)
+        let mut polys: Vec<Vec<T>> = vec![vec![T::one()]];
+        for k in 1..(n + 1) {
+            let prev = &polys[k - 1];
+            let mut p = vec![T::zero(); k + 1];
+            for (d, &c) in prev.iter().enumerate() {
+                p[d + 1] = p[d + 1] + c;
+                p[d] = p[d] - h[(k - 1)*n + k - 1] * c;
+            }
+            let mut beta = T::one();
+            for i in 1..k {
+                beta = beta * h[(k - i)*n + k - i - 1];
+                let f = h[(k - i - 1)*n + k - 1] * beta;
+                if f == T::zero() { continue }
+                for (d, &c) in polys[k - i - 1].iter().enumerate() {
+                    p[d] = p[d] - f * c;
+                }
+            }
+            polys.push(p);
+        }
+        Ok(polys.pop().unwrap())
+    }
+}
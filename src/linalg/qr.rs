@@ -0,0 +1,247 @@
+use std::cmp;
+
+use ::{ComplexField, DenseColumn, DenseMatrix, Float, Matrix, One, Vector, Zero};
+use super::update::rotation;
+
+/// A QR factorization `A = Q R` of an `m` by `n` matrix, where `Q` is an
+/// `m` by `m` unitary matrix and `R` is `m` by `n` upper triangular.
+#[derive(Clone, Debug)]
+pub struct QR<T> {
+    m: usize,
+    n: usize,
+    /// Row major `m*m`.
+    q: Vec<T>,
+    /// Row major `m*n`.
+    r: Vec<T>,
+}
+
+impl<T: ComplexField> DenseMatrix<T> {
+    /// Compute the QR factorization with Householder reflections.
+    pub fn qr(&self) -> QR<T> {
+        let (m, n) = self.dims();
+        let mut r = self.elements();
+        let mut q = vec![T::zero(); m*m];
+        for i in 0..m {
+            q[i*m + i] = T::one();
+        }
+        let two = T::one() + T::one();
+
+        for k in 0..(if m > n { n } else { m }) {
+            let mut norm = T::Real::zero();
+            for i in k..m {
+                norm = norm + r[i*n + k].modulus_sqr();
+            }
+            let norm = norm.sqrt();
+            if norm == T::Real::zero() { continue }
+
+            // Reflect x onto alpha e_1 with alpha = -phase(x_0) |x|, which
+            // avoids cancellation in x - alpha e_1.
+            let x0 = r[k*n + k];
+            let phase = if x0.modulus() == T::Real::zero() {
+                T::one()
+            } else {
+                x0.scale(T::Real::one() / x0.modulus())
+            };
+            let alpha = -phase.scale(norm);
+            let mut v: Vec<T> = (k..m).map(|i| r[i*n + k]).collect();
+            v[0] = v[0] - alpha;
+            let vnorm = v.iter().fold(T::Real::zero(), |acc, z| acc + z.modulus_sqr()).sqrt();
+            if vnorm == T::Real::zero() { continue }
+            for z in v.iter_mut() { *z = z.scale(T::Real::one() / vnorm) }
+
+            for j in k..n {
+                let mut s = T::zero();
+                for i in k..m {
+                    s = s + v[i - k].conj() * r[i*n + j];
+                }
+                for i in k..m {
+                    r[i*n + j] = r[i*n + j] - two * v[i - k] * s;
+                }
+            }
+            for i in 0..m {
+                let mut s = T::zero();
+                for j in k..m {
+                    s = s + q[i*m + j] * v[j - k];
+                }
+                for j in k..m {
+                    q[i*m + j] = q[i*m + j] - two * s * v[j - k].conj();
+                }
+            }
+            for i in (k + 1)..m {
+                r[i*n + k] = T::zero();
+            }
+        }
+        QR { m, n, q, r }
+    }
+}
+
+impl<T: ComplexField> QR<T> {
+    /// Get the unitary factor `Q`.
+    pub fn q(&self) -> DenseMatrix<T> {
+        DenseMatrix::from_vec(self.q.clone(), self.m, self.m, None).unwrap()
+    }
+
+    /// Get the upper triangular factor `R`.
+    pub fn r(&self) -> DenseMatrix<T> {
+        DenseMatrix::from_vec(self.r.clone(), self.m, self.n, None).unwrap()
+    }
+
+    /// Returns true if `R` has no zero on its diagonal.
+    pub fn is_full_rank(&self) -> bool {
+        let k = if self.m > self.n { self.n } else { self.m };
+        (0..k).all(|i| self.r[i*self.n + i] != T::zero())
+    }
+
+    /// Compute the least squares solution of `A x = b` for `b` of length `m`.
+    fn solve_in_place(&self, b: &[T]) -> Vec<T> {
+        let (m, n) = (self.m, self.n);
+        let mut y = vec![T::zero(); n];
+        for (i, y_i) in y.iter_mut().enumerate() {
+            for (k, &b_k) in b.iter().enumerate() {
+                *y_i = *y_i + self.q[k*m + i].conj() * b_k;
+            }
+        }
+        for i in (0..n).rev() {
+            for j in (i + 1)..n {
+                y[i] = y[i] - self.r[i*n + j] * y[j];
+            }
+            y[i] = y[i] / self.r[i*n + i];
+        }
+        y
+    }
+
+    /// Solve `A X = B` in the least squares sense. Requires `m >= n` and `A`
+    /// to have full column rank.
+    pub fn solve(&self, b: &DenseMatrix<T>) -> ::Result<DenseMatrix<T>> {
+        if b.rows() != self.m || self.m < self.n { return Err(::Error::InvalidDimensions) }
+        if !self.is_full_rank() { return Err(::Error::Singular) }
+        let x = DenseMatrix::zeros(self.n, b.cols());
+        for j in 0..b.cols() {
+            let col: Vec<T> = (0..self.m).map(|i| b.get(i, j).unwrap()).collect();
+            for (i, &a) in self.solve_in_place(&col).iter().enumerate() {
+                x.set(i, j, a);
+            }
+        }
+        Ok(x)
+    }
+
+    /// Solve `A x = b` in the least squares sense for a single right hand
+    /// side.
+    pub fn solve_vector(&self, b: &DenseColumn<T>) -> ::Result<DenseColumn<T>> {
+        if b.len() != self.m || self.m < self.n { return Err(::Error::InvalidDimensions) }
+        if !self.is_full_rank() { return Err(::Error::Singular) }
+        let col: Vec<T> = (0..self.m).map(|i| b.get(i).unwrap()).collect();
+        Ok(DenseColumn::from_vec(self.solve_in_place(&col)))
+    }
+
+    /// Apply `G = [c, s; -conj(s), c]` to rows `i` and `i + 1` of `R` and
+    /// `G^H` to columns `i` and `i + 1` of `Q`, leaving `Q R` unchanged.
+    fn rotate(&mut self, i: usize, c: T::Real, s: T) {
+        let (m, n) = (self.m, self.n);
+        for j in 0..n {
+            let (x, y) = (self.r[i*n + j], self.r[(i + 1)*n + j]);
+            self.r[i*n + j] = x.scale(c) + s * y;
+            self.r[(i + 1)*n + j] = y.scale(c) - s.conj() * x;
+        }
+        for k in 0..m {
+            let (x, y) = (self.q[k*m + i], self.q[k*m + i + 1]);
+            self.q[k*m + i] = x.scale(c) + s.conj() * y;
+            self.q[k*m + i + 1] = y.scale(c) - s * x;
+        }
+    }
+
+    /// Zero `R[i + 1][j]` against `R[i][j]`.
+    fn annihilate(&mut self, i: usize, j: usize) {
+        let n = self.n;
+        let (c, s) = rotation(self.r[i*n + j], self.r[(i + 1)*n + j]);
+        self.rotate(i, c, s);
+        self.r[(i + 1)*n + j] = T::zero();
+    }
+
+    /// Update the factorization to that of `A` with `row` inserted before
+    /// row `i`, in O(m^2 + mn) operations.
+    pub fn insert_row(&mut self, i: usize, row: &[T]) -> ::Result<()> {
+        let (m, n) = (self.m, self.n);
+        if i > m || row.len() != n { return Err(::Error::InvalidDimensions) }
+        // [row; A] = diag(1, Q) [row; R], whose new first row is then moved
+        // to row i of Q. The rotations restore [row; R] from Hessenberg form.
+        let mut r = row.to_vec();
+        r.extend_from_slice(&self.r);
+        let mut q = vec![T::zero(); (m + 1)*(m + 1)];
+        for k in 0..(m + 1) {
+            if k == i {
+                q[k*(m + 1)] = T::one();
+                continue
+            }
+            let src = if k < i { k } else { k - 1 };
+            q[(k*(m + 1) + 1)..((k + 1)*(m + 1))].copy_from_slice(&self.q[src*m..(src + 1)*m]);
+        }
+        self.m = m + 1;
+        self.q = q;
+        self.r = r;
+        for j in 0..cmp::min(m, n) {
+            self.annihilate(j, j);
+        }
+        Ok(())
+    }
+
+    /// Update the factorization to that of `A` with row `i` removed, in
+    /// O(m^2 + mn) operations.
+    pub fn delete_row(&mut self, i: usize) -> ::Result<()> {
+        let (m, n) = (self.m, self.n);
+        if i >= m { return Err(::Error::InvalidDimensions) }
+        // Rotate row i of Q onto a multiple of e_1 from the bottom up, which
+        // leaves R upper Hessenberg and the first column of Q a multiple of
+        // e_i. Dropping both and the first row of R leaves the factors.
+        for k in (0..(m - 1)).rev() {
+            let (x, y) = (self.q[i*m + k], self.q[i*m + k + 1]);
+            let (c, s) = rotation(x.conj(), y.conj());
+            self.rotate(k, c, s);
+        }
+        let mut q = Vec::with_capacity((m - 1)*(m - 1));
+        for k in (0..m).filter(|&k| k != i) {
+            q.extend_from_slice(&self.q[(k*m + 1)..((k + 1)*m)]);
+        }
+        self.r.drain(0..n);
+        self.m = m - 1;
+        self.q = q;
+        Ok(())
+    }
+
+    /// Update the factorization to that of `A` with `col` inserted before
+    /// column `j`, in O(m^2 + mn) operations.
+    pub fn insert_col(&mut self, j: usize, col: &[T]) -> ::Result<()> {
+        let (m, n) = (self.m, self.n);
+        if j > n || col.len() != m { return Err(::Error::InvalidDimensions) }
+        let mut r = Vec::with_capacity(m*(n + 1));
+        for i in 0..m {
+            r.extend_from_slice(&self.r[i*n..(i*n + j)]);
+            r.push((0..m).fold(T::zero(), |acc, k| acc + self.q[k*m + i].conj() * col[k]));
+            r.extend_from_slice(&self.r[(i*n + j)..((i + 1)*n)]);
+        }
+        self.n = n + 1;
+        self.r = r;
+        for i in (j..m.saturating_sub(1)).rev() {
+            self.annihilate(i, j);
+        }
+        Ok(())
+    }
+
+    /// Update the factorization to that of `A` with column `j` removed, in
+    /// O(m^2 + mn) operations.
+    pub fn delete_col(&mut self, j: usize) -> ::Result<()> {
+        let (m, n) = (self.m, self.n);
+        if j >= n { return Err(::Error::InvalidDimensions) }
+        let mut r = Vec::with_capacity(m*(n - 1));
+        for i in 0..m {
+            r.extend_from_slice(&self.r[i*n..(i*n + j)]);
+            r.extend_from_slice(&self.r[(i*n + j + 1)..((i + 1)*n)]);
+        }
+        self.n = n - 1;
+        self.r = r;
+        for k in j..cmp::min(m.saturating_sub(1), n - 1) {
+            self.annihilate(k, k);
+        }
+        Ok(())
+    }
+}
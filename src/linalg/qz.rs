@@ -0,0 +1,334 @@
+use ::{Complex, ComplexField, DenseMatrix, EigenPairs, Float, Matrix, One, Zero};
+use super::eigen::givens;
+use super::jacobi_eigen;
+
+/// The number of QZ iterations allowed per eigenvalue.
+static MAX_QZ_ITERS: usize = 30;
+
+/// A complex generalized Schur (QZ) decomposition `A = Q S Z^H`,
+/// `B = Q T Z^H` of a square pencil `(A, B)`, where `Q` and `Z` are unitary
+/// and `S` and `T` are upper triangular with `T` having a real nonnegative
+/// diagonal. The generalized eigenvalues, the `lambda` for which
+/// `A - lambda B` is singular, are the ratios `s_ii / t_ii`.
+#[derive(Clone, Debug)]
+pub struct QZ<R> {
+    n: usize,
+    /// All four factors are row major `n*n`.
+    q: Vec<Complex<R>>,
+    z: Vec<Complex<R>>,
+    s: Vec<Complex<R>>,
+    t: Vec<Complex<R>>,
+}
+
+/// Multiply rows p and p+1 of the row major `n*n` matrix `m` from the left
+/// by the rotation `[c, s; -conj(s), c]`.
+fn rotate_rows<R: Float>(m: &mut [Complex<R>], n: usize, p: usize, c: R, s: Complex<R>) {
+    for j in 0..n {
+        let (x, y) = (m[p*n + j], m[(p + 1)*n + j]);
+        m[p*n + j] = x.scale(c) + s * y;
+        m[(p + 1)*n + j] = y.scale(c) - s.conj() * x;
+    }
+}
+
+/// Multiply columns p and p+1 of the row major `n*n` matrix `m` from the
+/// right by the conjugate transpose of the rotation `[c, s; -conj(s), c]`.
+fn rotate_cols<R: Float>(m: &mut [Complex<R>], n: usize, p: usize, c: R, s: Complex<R>) {
+    for i in 0..n {
+        let (x, y) = (m[i*n + p], m[i*n + p + 1]);
+        m[i*n + p] = x.scale(c) + y * s.conj();
+        m[i*n + p + 1] = y.scale(c) - x * s;
+    }
+}
+
+/// Compute a rotation `(c, s)` for `rotate_cols` that maps the row `[x, y]`
+/// to `[0, r]`.
+fn col_givens<R: Float>(x: Complex<R>, y: Complex<R>) -> (R, Complex<R>) {
+    let (nx, ny) = (x.norm(), y.norm());
+    if nx == R::zero() { return (R::one(), Complex::zero()) }
+    if ny == R::zero() { return (R::zero(), Complex::new(R::one(), R::zero())) }
+    let r = nx.hypot(ny);
+    (ny / r, -(x.conj() * y).unscale(r * ny))
+}
+
+/// The state of a QZ iteration: the pencil `(H, T)` being reduced and the
+/// accumulated transformations.
+struct Pencil<R> {
+    n: usize,
+    h: Vec<Complex<R>>,
+    t: Vec<Complex<R>>,
+    q: Vec<Complex<R>>,
+    z: Vec<Complex<R>>,
+}
+
+impl<R: Float> Pencil<R> {
+    /// Rotate rows p and p+1 of both matrices to zero `b` in `[a; b]`.
+    fn left(&mut self, p: usize, a: Complex<R>, b: Complex<R>) {
+        let (c, s) = givens(a, b);
+        rotate_rows(&mut self.h, self.n, p, c, s);
+        rotate_rows(&mut self.t, self.n, p, c, s);
+        rotate_cols(&mut self.q, self.n, p, c, s);
+    }
+
+    /// Rotate columns p and p+1 of both matrices to zero `x` in `[x, y]`.
+    fn right(&mut self, p: usize, x: Complex<R>, y: Complex<R>) {
+        let (c, s) = col_givens(x, y);
+        rotate_cols(&mut self.h, self.n, p, c, s);
+        rotate_cols(&mut self.t, self.n, p, c, s);
+        rotate_cols(&mut self.z, self.n, p, c, s);
+    }
+
+    fn h(&self, i: usize, j: usize) -> Complex<R> { self.h[i*self.n + j] }
+
+    fn t(&self, i: usize, j: usize) -> Complex<R> { self.t[i*self.n + j] }
+
+    /// Move a zero `t_jj` down to `t_(hi-1)(hi-1)` and zero `h_(hi-1)(hi-2)`,
+    /// which deflates an infinite eigenvalue at the bottom of `[lo, hi)`.
+    fn chase_infinite(&mut self, lo: usize, hi: usize, j: usize) {
+        let n = self.n;
+        self.t[j*n + j] = Complex::zero();
+        for k in j..(hi - 1) {
+            let (a, b) = (self.t(k, k + 1), self.t(k + 1, k + 1));
+            self.left(k, a, b);
+            self.t[(k + 1)*n + k + 1] = Complex::zero();
+            if k > lo {
+                let (x, y) = (self.h(k + 1, k - 1), self.h(k + 1, k));
+                self.right(k - 1, x, y);
+                self.h[(k + 1)*n + k - 1] = Complex::zero();
+            }
+        }
+        let (x, y) = (self.h(hi - 1, hi - 2), self.h(hi - 1, hi - 1));
+        self.right(hi - 2, x, y);
+        self.h[(hi - 1)*n + hi - 2] = Complex::zero();
+    }
+
+    /// Perform a single shift QZ step on the active block `[lo, hi)`.
+    fn step(&mut self, lo: usize, hi: usize, mu: Complex<R>) {
+        let n = self.n;
+        let (x, y) = (self.h(lo, lo) - mu * self.t(lo, lo), self.h(lo + 1, lo));
+        self.left(lo, x, y);
+        for k in lo..(hi - 1) {
+            let (x, y) = (self.t(k + 1, k), self.t(k + 1, k + 1));
+            self.right(k, x, y);
+            self.t[(k + 1)*n + k] = Complex::zero();
+            if k + 2 < hi {
+                let (a, b) = (self.h(k + 1, k), self.h(k + 2, k));
+                self.left(k + 1, a, b);
+                self.h[(k + 2)*n + k] = Complex::zero();
+            }
+        }
+    }
+
+    /// Get the eigenvalue of the trailing 2 by 2 block of `H T^-1` closest to
+    /// the trailing ratio `h_(hi-1)(hi-1) / t_(hi-1)(hi-1)`.
+    fn wilkinson_shift(&self, hi: usize) -> Complex<R> {
+        let (i, j) = (hi - 2, hi - 1);
+        let two = R::one() + R::one();
+        let (t11, t12, t22) = (self.t(i, i), self.t(i, j), self.t(j, j));
+        // M = H_b T_b^-1 for the upper triangular T_b.
+        let one = Complex::new(R::one(), R::zero());
+        let inv = [one / t11, -t12 / (t11 * t22), one / t22];
+        let m = |r: usize| {
+            let (h1, h2) = (self.h(r, i), self.h(r, j));
+            (h1 * inv[0], h1 * inv[1] + h2 * inv[2])
+        };
+        let ((a, b), (c, d)) = (m(i), m(j));
+        let half = (a - d).unscale(two);
+        let disc = (half * half + b * c).sqrt();
+        let (mu1, mu2) = (d + half + disc, d + half - disc);
+        if (mu1 - d).norm() <= (mu2 - d).norm() { mu1 } else { mu2 }
+    }
+}
+
+impl<T: ComplexField> DenseMatrix<T> {
+    /// Compute the complex generalized Schur decomposition of the pencil
+    /// `(A, B)` by the QZ algorithm of Moler and Stewart. `B` is reduced to
+    /// triangular and `A` to Hessenberg form with rotations, followed by
+    /// single shift QZ steps. A singular `B` is allowed and gives infinite
+    /// eigenvalues.
+    ///
+    /// Fails with `Error::InvalidDimensions` unless both matrices are square
+    /// and the same size, or `Error::NotConverged` if the iteration does not
+    /// converge.
+    pub fn qz(&self, b: &DenseMatrix<T>) -> ::Result<QZ<T::Real>> {
+        let n = self.rows();
+        if !self.is_square() || b.dims() != (n, n) { return Err(::Error::InvalidDimensions) }
+        let mut eye = vec![Complex::zero(); n*n];
+        for i in 0..n {
+            eye[i*n + i] = Complex::new(T::Real::one(), T::Real::zero());
+        }
+        let mut p = Pencil {
+            n,
+            h: self.elements().iter().map(|x| x.to_complex()).collect(),
+            t: b.elements().iter().map(|x| x.to_complex()).collect(),
+            q: eye.clone(),
+            z: eye,
+        };
+        let eps = T::epsilon();
+        let t_tol = eps * p.t.iter().fold(T::Real::zero(), |acc, x| acc + x.norm_sqr()).sqrt();
+        let h_tol = eps * p.h.iter().fold(T::Real::zero(), |acc, x| acc + x.norm_sqr()).sqrt();
+
+        // Triangularize T, then reduce H to Hessenberg form keeping T
+        // triangular.
+        for j in 0..n {
+            for i in ((j + 1)..n).rev() {
+                let (a, b) = (p.t(i - 1, j), p.t(i, j));
+                p.left(i - 1, a, b);
+                p.t[i*n + j] = Complex::zero();
+            }
+        }
+        for j in 0..n.saturating_sub(2) {
+            for i in ((j + 2)..n).rev() {
+                let (a, b) = (p.h(i - 1, j), p.h(i, j));
+                p.left(i - 1, a, b);
+                p.h[i*n + j] = Complex::zero();
+                let (x, y) = (p.t(i, i - 1), p.t(i, i));
+                p.right(i - 1, x, y);
+                p.t[i*n + i - 1] = Complex::zero();
+            }
+        }
+
+        let mut hi = n;
+        let mut iters = 0;
+        while hi > 1 {
+            let mut lo = hi - 1;
+            while lo > 0 {
+                let sub = p.h(lo, lo - 1).norm();
+                let diag = p.h(lo - 1, lo - 1).norm() + p.h(lo, lo).norm();
+                if sub <= eps * diag || sub <= h_tol {
+                    p.h[lo*n + lo - 1] = Complex::zero();
+                    break
+                }
+                lo -= 1;
+            }
+            if lo == hi - 1 {
+                hi -= 1;
+                iters = 0;
+                continue
+            }
+            if let Some(j) = (lo..hi).find(|&j| p.t(j, j).norm() <= t_tol) {
+                p.chase_infinite(lo, hi, j);
+                hi -= 1;
+                iters = 0;
+                continue
+            }
+            iters += 1;
+            if iters > MAX_QZ_ITERS * n { return Err(::Error::NotConverged) }
+            let mu = if iters % 10 == 0 {
+                // Exceptional shift to break cycles.
+                p.h(hi - 1, hi - 1) / p.t(hi - 1, hi - 1) + Complex::new(p.h(hi - 1, hi - 2).norm(), T::Real::zero())
+            } else {
+                p.wilkinson_shift(hi)
+            };
+            p.step(lo, hi, mu);
+        }
+
+        // Make the diagonal of T real and nonnegative, as LAPACK does.
+        for i in 0..n {
+            let t_ii = p.t(i, i);
+            if t_ii.norm() == T::Real::zero() { continue }
+            let d = t_ii.unscale(t_ii.norm());
+            for j in 0..n {
+                p.h[i*n + j] = p.h[i*n + j] * d.conj();
+                p.t[i*n + j] = p.t[i*n + j] * d.conj();
+                p.q[j*n + i] = p.q[j*n + i] * d;
+            }
+            p.t[i*n + i] = Complex::new(t_ii.norm(), T::Real::zero());
+        }
+        for i in 1..n {
+            for j in 0..i {
+                p.h[i*n + j] = Complex::zero();
+                p.t[i*n + j] = Complex::zero();
+            }
+        }
+        Ok(QZ { n, q: p.q, z: p.z, s: p.h, t: p.t })
+    }
+}
+
+impl<R: Float> QZ<R> {
+    fn matrix(&self, a: &[Complex<R>]) -> DenseMatrix<Complex<R>> {
+        DenseMatrix::from_vec(a.to_vec(), self.n, self.n, None).unwrap()
+    }
+
+    /// Get the unitary left factor `Q`.
+    pub fn q(&self) -> DenseMatrix<Complex<R>> { self.matrix(&self.q) }
+
+    /// Get the unitary right factor `Z`.
+    pub fn z(&self) -> DenseMatrix<Complex<R>> { self.matrix(&self.z) }
+
+    /// Get the upper triangular factor `S` of `A`.
+    pub fn s(&self) -> DenseMatrix<Complex<R>> { self.matrix(&self.s) }
+
+    /// Get the upper triangular factor `T` of `B`.
+    pub fn t(&self) -> DenseMatrix<Complex<R>> { self.matrix(&self.t) }
+
+    /// Get the generalized eigenvalues as pairs `(alpha, beta)` with
+    /// `lambda = alpha / beta`, in the order they appear on the diagonals.
+    /// `beta` is real and zero for an infinite eigenvalue.
+    pub fn alpha_beta(&self) -> Vec<(Complex<R>, R)> {
+        (0..self.n).map(|i| (self.s[i*self.n + i], self.t[i*self.n + i].re)).collect()
+    }
+
+    /// Get the generalized eigenvalues `alpha / beta`. An infinite
+    /// eigenvalue is `inf`, and is `NaN` if `alpha` is zero too, when the
+    /// pencil is singular and every `lambda` is an eigenvalue.
+    pub fn eigenvalues(&self) -> Vec<Complex<R>> {
+        self.alpha_beta().into_iter().map(|(alpha, beta)| {
+            if beta != R::zero() { return alpha.unscale(beta) }
+            let x = if alpha == Complex::zero() { R::nan() } else { R::infinity() };
+            Complex::new(x, R::zero())
+        }).collect()
+    }
+}
+
+impl<T: Float + ComplexField> DenseMatrix<T> {
+    /// Solve the symmetric-definite generalized eigenproblem `A x = lambda B x`
+    /// for symmetric `A` and symmetric positive definite `B`. With the
+    /// Cholesky factorization `B = L L^T` this is the symmetric eigenproblem
+    /// for `L^-1 A L^-T`, whose eigenvectors `y` give `x = L^-T y`.
+    ///
+    /// The eigenvalues are real and returned in ascending order, and the
+    /// eigenvectors are normalized so that `X^T B X = I`. Fails with
+    /// `Error::NotSymmetric` unless both matrices are symmetric, or
+    /// `Error::NotPositiveDefinite` unless `B` is positive definite.
+    pub fn symmetric_definite_eigen(&self, b: &DenseMatrix<T>) -> ::Result<EigenPairs<T>> {
+        let n = self.rows();
+        if !self.is_square() || b.dims() != (n, n) { return Err(::Error::InvalidDimensions) }
+        if !self.is_symmetric() || !b.is_symmetric() { return Err(::Error::NotSymmetric) }
+        let l = b.cholesky()?.l().elements();
+        let a = self.elements();
+
+        // Solve L W = A, then L C = W^T, which is C = L^-1 A L^-T.
+        let forward = |rhs: &[T]| {
+            let mut x = rhs.to_vec();
+            for j in 0..n {
+                for i in 0..n {
+                    let s = (0..i).fold(x[i*n + j], |acc, k| acc - l[i*n + k] * x[k*n + j]);
+                    x[i*n + j] = s / l[i*n + i];
+                }
+            }
+            x
+        };
+        let w = forward(&a);
+        let w_t: Vec<T> = (0..n*n).map(|k| w[(k % n)*n + k / n]).collect();
+        let mut c = forward(&w_t);
+        let half = T::one() / (T::one() + T::one());
+        for i in 0..n {
+            for j in 0..i {
+                let avg = (c[i*n + j] + c[j*n + i]) * half;
+                c[i*n + j] = avg;
+                c[j*n + i] = avg;
+            }
+        }
+        let (values, y) = jacobi_eigen(&c, n);
+
+        // x = L^-T y by back substitution.
+        let mut x = y;
+        for j in 0..n {
+            for i in (0..n).rev() {
+                let s = ((i + 1)..n).fold(x[i*n + j], |acc, k| acc - l[k*n + i] * x[k*n + j]);
+                x[i*n + j] = s / l[i*n + i];
+            }
+        }
+        Ok(EigenPairs { values, vectors: DenseMatrix::from_vec(x, n, n, None)? })
+    }
+}
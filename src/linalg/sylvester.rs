@@ -0,0 +1,244 @@
+use ::{Complex, ComplexField, DenseMatrix, Float, Matrix, One, Zero};
+use super::{complex_schur, swap_schur};
+use super::eigen::Schur;
+use super::matfun::triangular_sylvester;
+
+/// Copy a dense matrix into a row major Vec of complex numbers.
+fn to_complex<T: ComplexField>(a: &DenseMatrix<T>) -> Vec<Complex<T::Real>> {
+    a.elements().iter().map(|x| x.to_complex()).collect()
+}
+
+/// Convert a row major complex `m*n` Vec to `T`. For real `T` the imaginary
+/// parts, which are rounding errors for real data, are dropped.
+fn from_complex<T: ComplexField>(z: Vec<Complex<T::Real>>, m: usize, n: usize) -> DenseMatrix<T> {
+    DenseMatrix::from_vec(z.into_iter().map(T::from_complex).collect(), m, n, None).unwrap()
+}
+
+/// Multiply the row major `m*k` matrix `a` by the `k*n` matrix `b`.
+fn mul<R: Float>(a: &[Complex<R>], b: &[Complex<R>], m: usize, k: usize, n: usize) -> Vec<Complex<R>> {
+    let mut c = vec![Complex::zero(); m*n];
+    for i in 0..m {
+        for l in 0..k {
+            let a_il = a[i*k + l];
+            if a_il == Complex::zero() { continue }
+            for j in 0..n {
+                c[i*n + j] = c[i*n + j] + a_il * b[l*n + j];
+            }
+        }
+    }
+    c
+}
+
+/// Get the conjugate transpose of the row major `m*n` matrix `a`.
+fn conj_transpose<R: Float>(a: &[Complex<R>], m: usize, n: usize) -> Vec<Complex<R>> {
+    (0..n).flat_map(|j| (0..m).map(move |i| a[i*n + j].conj())).collect()
+}
+
+/// Solve `A X = B` for the `n*n` matrix `a` and `n*k` matrix `b` by LU
+/// factorization. Fails with `Error::Singular` if `A` is singular.
+fn lu_solve<R: Float>(a: Vec<Complex<R>>, b: Vec<Complex<R>>, n: usize, k: usize) -> ::Result<Vec<Complex<R>>> {
+    let lu = DenseMatrix::from_vec(a, n, n, None)?.lu()?;
+    Ok(lu.solve(&DenseMatrix::from_vec(b, n, k, None)?)?.elements())
+}
+
+/// Move the eigenvalues picked by `select` to the top left of a complex
+/// Schur decomposition, keeping their order, and return how many there are.
+fn reorder_schur<R, F>(t: &mut [Complex<R>], q: &mut [Complex<R>], n: usize, select: F) -> usize
+    where R: Float, F: Fn(Complex<R>) -> bool
+{
+    let mut k = 0;
+    for j in 0..n {
+        if select(t[j*n + j]) {
+            for i in (k..j).rev() {
+                swap_schur(t, q, n, i);
+            }
+            k += 1;
+        }
+    }
+    k
+}
+
+/// Get the solution `X = U21 U11^-1` of an algebraic Riccati equation from
+/// the Schur vectors of the `2n*2n` Hamiltonian or symplectic matrix `z`,
+/// whose stable invariant subspace is spanned by `[U11; U21]`. The result
+/// is made exactly Hermitian.
+fn riccati_from_schur<T, F>(z: Vec<Complex<T::Real>>, n: usize, stable: F) -> ::Result<DenseMatrix<T>>
+    where T: ComplexField, F: Fn(Complex<T::Real>) -> bool
+{
+    let w = 2*n;
+    let Schur { mut q, mut t } = complex_schur(&z, w)?;
+    // Without n stable eigenvalues there is no stabilizing solution.
+    if reorder_schur(&mut t, &mut q, w, stable) != n { return Err(::Error::Singular) }
+
+    // X U11 = U21 is U11^T X^T = U21^T.
+    let (mut u11_t, mut u21_t) = (Vec::with_capacity(n*n), Vec::with_capacity(n*n));
+    for j in 0..n {
+        for i in 0..n {
+            u11_t.push(q[i*w + j]);
+            u21_t.push(q[(n + i)*w + j]);
+        }
+    }
+    let x_t = lu_solve(u11_t, u21_t, n, n)?;
+    let half: T::Real = T::Real::one() / (T::Real::one() + T::Real::one());
+    let mut x = vec![Complex::zero(); n*n];
+    for i in 0..n {
+        for j in 0..n {
+            x[i*n + j] = (x_t[j*n + i] + x_t[i*n + j].conj()).scale(half);
+        }
+    }
+    Ok(from_complex(x, n, n))
+}
+
+/// Solve the Sylvester equation `A X + X B = C` for `X` by the
+/// Bartels–Stewart method. `A` and `B` are reduced to complex Schur form,
+/// the transformed equation is solved by substitution, and the solution is
+/// transformed back, in O(m^3 + n^3) operations.
+///
+/// `A` is m by m, `B` is n by n and `C` is m by n. Fails with
+/// `Error::Singular` if `A` and `-B` share an eigenvalue, when the solution
+/// is not unique.
+pub fn solve_sylvester<T: ComplexField>(a: &DenseMatrix<T>, b: &DenseMatrix<T>, c: &DenseMatrix<T>)
+    -> ::Result<DenseMatrix<T>>
+{
+    let (m, n) = (a.rows(), b.rows());
+    if !a.is_square() || !b.is_square() || c.dims() != (m, n) { return Err(::Error::InvalidDimensions) }
+    let sa = complex_schur(&to_complex(a), m)?;
+    let sb = complex_schur(&to_complex(b), n)?;
+    // T Y + Y S = U^H C V with A = U T U^H, B = V S V^H and X = U Y V^H.
+    let f = mul(&mul(&conj_transpose(&sa.q, m, m), &to_complex(c), m, m, n), &sb.q, m, n, n);
+    let neg_s: Vec<Complex<T::Real>> = sb.t.iter().map(|&s| -s).collect();
+    let y = triangular_sylvester(&sa.t, m, &neg_s, n, &f)?;
+    let x = mul(&mul(&sa.q, &y, m, m, n), &conj_transpose(&sb.q, n, n), m, n, n);
+    Ok(from_complex(x, m, n))
+}
+
+/// Solve the continuous Lyapunov equation `A X + X A^H = Q` for `X`, as a
+/// Sylvester equation. Fails with `Error::Singular` if `A` and `-A^H` share
+/// an eigenvalue, which cannot happen when `A` is stable.
+pub fn solve_lyapunov<T: ComplexField>(a: &DenseMatrix<T>, q: &DenseMatrix<T>) -> ::Result<DenseMatrix<T>> {
+    solve_sylvester(a, &a.conj_transpose(), q)
+}
+
+/// Solve the discrete Lyapunov (Stein) equation `A X A^H - X + Q = 0` for
+/// `X`. With `A = U T U^H` in complex Schur form the transformed equation
+/// `T Y T^H - Y = -U^H Q U` is solved one column at a time, from the last,
+/// in O(n^3) operations.
+///
+/// Fails with `Error::Singular` if two eigenvalues of `A` have a product
+/// `lambda_i conj(lambda_j) = 1`, which cannot happen when every eigenvalue
+/// lies inside the unit circle.
+pub fn solve_discrete_lyapunov<T: ComplexField>(a: &DenseMatrix<T>, q: &DenseMatrix<T>)
+    -> ::Result<DenseMatrix<T>>
+{
+    let n = a.rows();
+    if !a.is_square() || q.dims() != (n, n) { return Err(::Error::InvalidDimensions) }
+    let Schur { q: u, t } = complex_schur(&to_complex(a), n)?;
+    let uh = conj_transpose(&u, n, n);
+    let f = mul(&mul(&uh, &to_complex(q), n, n, n), &u, n, n, n);
+
+    let one = [Complex::one()];
+    let mut y = vec![Complex::zero(); n*n];
+    for j in (0..n).rev() {
+        // (conj(t_jj) T - I) y_j = -f_j - T sum_(l > j) conj(t_jl) y_l
+        let mut w = vec![Complex::zero(); n];
+        for l in (j + 1)..n {
+            let t_jl = t[j*n + l].conj();
+            for (i, w_i) in w.iter_mut().enumerate() {
+                *w_i = *w_i + t_jl * y[i*n + l];
+            }
+        }
+        let tw = mul(&t, &w, n, n, 1);
+        let rhs: Vec<Complex<T::Real>> = (0..n).map(|i| -f[i*n + j] - tw[i]).collect();
+        let t_jj = t[j*n + j].conj();
+        let shifted: Vec<Complex<T::Real>> = t.iter().map(|&x| x * t_jj).collect();
+        let y_j = triangular_sylvester(&shifted, n, &one, 1, &rhs)?;
+        for (i, y_ij) in y_j.into_iter().enumerate() {
+            y[i*n + j] = y_ij;
+        }
+    }
+    Ok(from_complex(mul(&mul(&u, &y, n, n, n), &uh, n, n, n), n, n))
+}
+
+/// Check the operands of an algebraic Riccati equation and form
+/// `G = B R^-1 B^H`.
+fn riccati_gain<T: ComplexField>(a: &DenseMatrix<T>, b: &DenseMatrix<T>, q: &DenseMatrix<T>, r: &DenseMatrix<T>)
+    -> ::Result<Vec<Complex<T::Real>>>
+{
+    let (n, m) = b.dims();
+    if a.dims() != (n, n) || q.dims() != (n, n) || r.dims() != (m, m) {
+        return Err(::Error::InvalidDimensions)
+    }
+    let bc = to_complex(b);
+    let bh = conj_transpose(&bc, n, m);
+    let k = lu_solve(to_complex(r), bh, m, n)?;
+    Ok(mul(&bc, &k, n, m, n))
+}
+
+/// Solve the continuous algebraic Riccati equation
+/// `A^H X + X A - X B R^-1 B^H X + Q = 0` for the stabilizing solution `X`,
+/// the one for which `A - B R^-1 B^H X` is stable, by the Schur method of
+/// Laub (1979). The eigenvalues of the Hamiltonian matrix
+/// `[A, -B R^-1 B^H; -Q, -A^H]` in the left half plane are moved to the top
+/// of its Schur form, whose leading Schur vectors `[U11; U21]` give
+/// `X = U21 U11^-1`.
+///
+/// `A` and `Q` are n by n, `B` is n by m and `R` is m by m. Fails with
+/// `Error::Singular` if `R` is singular or there is no stabilizing
+/// solution.
+pub fn solve_care<T: ComplexField>(a: &DenseMatrix<T>, b: &DenseMatrix<T>, q: &DenseMatrix<T>, r: &DenseMatrix<T>)
+    -> ::Result<DenseMatrix<T>>
+{
+    let g = riccati_gain(a, b, q, r)?;
+    let n = a.rows();
+    let (ac, qc) = (to_complex(a), to_complex(q));
+    let ah = conj_transpose(&ac, n, n);
+    let w = 2*n;
+    let mut h = vec![Complex::zero(); w*w];
+    for i in 0..n {
+        for j in 0..n {
+            h[i*w + j] = ac[i*n + j];
+            h[i*w + n + j] = -g[i*n + j];
+            h[(n + i)*w + j] = -qc[i*n + j];
+            h[(n + i)*w + n + j] = -ah[i*n + j];
+        }
+    }
+    riccati_from_schur(h, n, |z| z.re < T::Real::zero())
+}
+
+/// Solve the discrete algebraic Riccati equation
+/// `A^H X A - X - A^H X B (R + B^H X B)^-1 B^H X A + Q = 0` for the
+/// stabilizing solution `X` by the Schur method of Laub (1979). The
+/// eigenvalues of the symplectic matrix
+/// `[A + G A^-H Q, -G A^-H; -A^-H Q, A^-H]`, with `G = B R^-1 B^H`, inside
+/// the unit circle are moved to the top of its Schur form, whose leading
+/// Schur vectors `[U11; U21]` give `X = U21 U11^-1`.
+///
+/// The operands are shaped as for `solve_care`. Fails with
+/// `Error::Singular` if `A` or `R` is singular or there is no stabilizing
+/// solution.
+pub fn solve_dare<T: ComplexField>(a: &DenseMatrix<T>, b: &DenseMatrix<T>, q: &DenseMatrix<T>, r: &DenseMatrix<T>)
+    -> ::Result<DenseMatrix<T>>
+{
+    let g = riccati_gain(a, b, q, r)?;
+    let n = a.rows();
+    let (ac, qc) = (to_complex(a), to_complex(q));
+    let mut eye = vec![Complex::zero(); n*n];
+    for i in 0..n {
+        eye[i*n + i] = Complex::one();
+    }
+    let a_inv_h = lu_solve(conj_transpose(&ac, n, n), eye, n, n)?;
+    let a_inv_h_q = mul(&a_inv_h, &qc, n, n, n);
+    let g_a_inv_h = mul(&g, &a_inv_h, n, n, n);
+    let g_a_inv_h_q = mul(&g, &a_inv_h_q, n, n, n);
+    let w = 2*n;
+    let mut z = vec![Complex::zero(); w*w];
+    for i in 0..n {
+        for j in 0..n {
+            z[i*w + j] = ac[i*n + j] + g_a_inv_h_q[i*n + j];
+            z[i*w + n + j] = -g_a_inv_h[i*n + j];
+            z[(n + i)*w + j] = -a_inv_h_q[i*n + j];
+            z[(n + i)*w + n + j] = a_inv_h[i*n + j];
+        }
+    }
+    riccati_from_schur(z, n, |z| z.norm() < T::Real::one())
+}
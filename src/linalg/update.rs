@@ -0,0 +1,77 @@
+use ::{ComplexField, DenseMatrix, Float, Matrix, One, Zero};
+use super::{Cholesky, LU};
+
+/// Compute a Givens rotation `(c, s)` with real `c` such that
+/// `[c, s; -conj(s), c] * [a; b] = [r; 0]`.
+pub fn rotation<T: ComplexField>(a: T, b: T) -> (T::Real, T) {
+    let (na, nb) = (a.modulus(), b.modulus());
+    if nb == T::Real::zero() { return (T::Real::one(), T::zero()) }
+    if na == T::Real::zero() { return (T::Real::zero(), T::one()) }
+    let r = na.hypot(nb);
+    (na / r, a.scale(T::Real::one() / na) * b.conj().scale(T::Real::one() / r))
+}
+
+/// Get the row major product of `a` and `b`.
+fn mul<T: ComplexField>(a: &[T], b: &[T], m: usize, k: usize, n: usize) -> Vec<T> {
+    let mut c = vec![T::zero(); m*n];
+    for i in 0..m {
+        for l in 0..k {
+            let a_il = a[i*k + l];
+            if a_il == T::zero() { continue }
+            for j in 0..n {
+                c[i*n + j] = c[i*n + j] + a_il * b[l*n + j];
+            }
+        }
+    }
+    c
+}
+
+/// Solve `(A + U V^H) X = B` with the Sherman–Morrison–Woodbury formula
+/// `X = A^-1 B - A^-1 U (I + V^H A^-1 U)^-1 V^H A^-1 B`, given `solve` for
+/// `A`. Only a `k*k` system is factored for `n` by `k` updates `U` and `V`.
+fn woodbury<T, F>(solve: F, u: &DenseMatrix<T>, v: &DenseMatrix<T>, b: &DenseMatrix<T>)
+    -> ::Result<DenseMatrix<T>>
+    where T: ComplexField, F: Fn(&DenseMatrix<T>) -> ::Result<DenseMatrix<T>>
+{
+    let (n, k) = u.dims();
+    if v.dims() != (n, k) || b.rows() != n { return Err(::Error::InvalidDimensions) }
+    let p = b.cols();
+    let ainv_u = solve(u)?.elements();
+    let ainv_b = solve(b)?.elements();
+    let vh = v.conj_transpose().elements();
+
+    let mut cap = mul(&vh, &ainv_u, k, n, k);
+    for i in 0..k {
+        cap[i*k + i] = cap[i*k + i] + T::one();
+    }
+    let rhs = DenseMatrix::from_vec(mul(&vh, &ainv_b, k, n, p), k, p, None)?;
+    let y = DenseMatrix::from_vec(cap, k, k, None)?.lu()?.solve(&rhs)?.elements();
+    let x = ainv_b.iter()
+        .zip(mul(&ainv_u, &y, n, k, p))
+        .map(|(&z, w)| z - w)
+        .collect();
+    DenseMatrix::from_vec(x, n, p, None)
+}
+
+impl<T: ComplexField> LU<T> {
+    /// Solve `(A + U V^H) X = B` for the factored `A` and an `n` by `k`
+    /// update, in O(n^2 k) operations rather than refactoring. For `k = 1`
+    /// this is the Sherman–Morrison formula. Fails with `Error::Singular` if
+    /// the updated matrix is singular.
+    pub fn solve_woodbury(&self, u: &DenseMatrix<T>, v: &DenseMatrix<T>, b: &DenseMatrix<T>)
+        -> ::Result<DenseMatrix<T>>
+    {
+        woodbury(|x| self.solve(x), u, v, b)
+    }
+}
+
+impl<T: ComplexField> Cholesky<T> {
+    /// Solve `(A + U V^H) X = B` for the factored `A` and an `n` by `k`
+    /// update, as `LU::solve_woodbury` does. The updated matrix need not be
+    /// Hermitian or definite.
+    pub fn solve_woodbury(&self, u: &DenseMatrix<T>, v: &DenseMatrix<T>, b: &DenseMatrix<T>)
+        -> ::Result<DenseMatrix<T>>
+    {
+        woodbury(|x| self.solve(x), u, v, b)
+    }
+}
@@ -44,3 +44,10 @@ macro_rules! vector {
         $crate::DenseColumn::from_vec(vec![$($elem),+])
     };
 }
+
+#[macro_export]
+macro_rules! block {
+    ($($($b:expr),+);+) => {
+        $crate::BlockMatrix::new(vec![$(vec![$(Box::new($b) as Box<dyn $crate::Block<_>>,)+],)+])
+    };
+}
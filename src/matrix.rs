@@ -1,10 +1,23 @@
 use std::cmp;
 
+use ::{ComplexField, Conjugate, Float, Zero};
+use ::linalg::{norm_2, scaled_norm_2, to_dense};
+
 pub trait Matrix<T>: Sized where T: ::Num + Clone {
 
-    // TODO: allow num::Complex.
-    ///// Check if the matrix is Hermitian.
-    //fn is_hermitian(&self) -> bool;
+    /// Check if the matrix is Hermitian, equal to its conjugate transpose.
+    #[inline]
+    fn is_hermitian(&self) -> bool where T: Conjugate {
+        if !self.is_square() { return false }
+        for i in 0..self.rows() {
+            for j in 0..(i + 1) {
+                if self.get(i, j).unwrap() != self.get(j, i).unwrap().conj() {
+                    return false
+                }
+            }
+        }
+        true
+    }
 
     /// Check if the matrix is symmetric.
     #[inline]
@@ -16,17 +29,45 @@ pub trait Matrix<T>: Sized where T: ::Num + Clone {
     /// Check if the matrix is symmetric positive semi-definite.
     fn is_symmetric_positive_semi_definite(&self) -> bool;
 
-    /// Check if the matrix is skew Hermitian.
-    fn is_skew_hermitian(&self) -> bool;
-
     /// Check if the matrix is skew Symmetric.
     fn is_skew_symmetric(&self) -> bool;
-
-    // Needs num::Complex
-    /// Check if the matrix is unitary.
-    fn is_unitary(&self) -> bool;
     */
 
+    /// Check if the matrix is skew Hermitian, equal to the negative of its
+    /// conjugate transpose.
+    #[inline]
+    fn is_skew_hermitian(&self) -> bool where T: Conjugate {
+        if !self.is_square() { return false }
+        for i in 0..self.rows() {
+            for j in 0..(i + 1) {
+                let a_ji = self.get(j, i).unwrap().conj();
+                if self.get(i, j).unwrap() != T::zero() - a_ji {
+                    return false
+                }
+            }
+        }
+        true
+    }
+
+    /// Check if the matrix is unitary, that is `A^H A = I` up to rounding.
+    #[inline]
+    fn is_unitary(&self) -> bool where T: ComplexField {
+        if !self.is_square() { return false }
+        let n = self.rows();
+        let tol = T::epsilon().sqrt() * <T::Real as ::NumCast>::from(n).unwrap();
+        for i in 0..n {
+            for j in 0..n {
+                let mut acc = T::zero();
+                for k in 0..n {
+                    acc = acc + self.get(k, i).unwrap().conj() * self.get(k, j).unwrap();
+                }
+                if i == j { acc = acc - T::one() }
+                if acc.modulus() > tol { return false }
+            }
+        }
+        true
+    }
+
     /// Check if the matrix is orthogonal.
     #[inline]
     fn is_orthogonal(&self) -> bool;
@@ -111,6 +152,58 @@ pub trait Matrix<T>: Sized where T: ::Num + Clone {
         (self.rows(), self.cols())
     }
 
+    /// Compute the 1-norm, the largest column sum of moduli.
+    fn norm_1(&self) -> T::Real where T: ComplexField {
+        (0..self.cols()).fold(T::Real::zero(), |acc, j| {
+            acc.max((0..self.rows()).fold(T::Real::zero(), |s, i| s + self.get(i, j).unwrap().modulus()))
+        })
+    }
+
+    /// Compute the infinity norm, the largest row sum of moduli.
+    fn norm_inf(&self) -> T::Real where T: ComplexField {
+        (0..self.rows()).fold(T::Real::zero(), |acc, i| {
+            acc.max((0..self.cols()).fold(T::Real::zero(), |s, j| s + self.get(i, j).unwrap().modulus()))
+        })
+    }
+
+    /// Compute the Frobenius norm, the square root of the sum of squared
+    /// moduli.
+    fn norm_fro(&self) -> T::Real where T: ComplexField {
+        scaled_norm_2(self.elements().iter().map(|z| z.modulus()))
+    }
+
+    /// Compute the largest modulus of any element. This is not a
+    /// submultiplicative norm.
+    fn norm_max(&self) -> T::Real where T: ComplexField {
+        self.elements().iter().fold(T::Real::zero(), |acc, z| acc.max(z.modulus()))
+    }
+
+    /// Compute the spectral norm, the largest singular value. Fails if the
+    /// eigenvalue iteration does not converge.
+    fn norm_2(&self) -> ::Result<T::Real> where T: ComplexField {
+        norm_2(self)
+    }
+
+    /// Estimate the 1-norm condition number `||A||_1 ||A^-1||_1` with the
+    /// Hager–Higham estimator, at the cost of an LU factorization and a few
+    /// solves. Fails with `Error::Singular` if the matrix is singular.
+    fn cond_1_est(&self) -> ::Result<T::Real> where T: ComplexField {
+        Ok(self.norm_1() * to_dense(self).lu()?.inverse_norm_1_est()?)
+    }
+
+    /// Compute the determinant by LU factorization. Fails unless the matrix
+    /// is square.
+    fn det(&self) -> ::Result<T> where T: ComplexField {
+        Ok(to_dense(self).lu()?.det())
+    }
+
+    /// Compute the sign and the natural logarithm of the modulus of the
+    /// determinant, so that `det = sign * exp(log_abs)` without overflow.
+    /// See `LU::log_det`. Fails unless the matrix is square.
+    fn log_det(&self) -> ::Result<(T, T::Real)> where T: ComplexField {
+        Ok(to_dense(self).lu()?.log_det())
+    }
+
     /// Get the matrix diagonals.
     #[inline]
     fn diags(&self) -> Vec<T> {
@@ -137,6 +230,9 @@ impl Default for ReadOrder {
 
 /// Flags are also used to give guarantees. (ie. a matrix flagged with
 /// Flag::Invertible will never actually check if it is invertible.)
+///
+/// Some flags have a structured matrix type that stores only what the flag
+/// allows, such as `DiagonalMatrix` for `Flag::Diagonal`.
 pub enum Flag {
     Symmetric,
     Orthogonal,
@@ -0,0 +1,13 @@
+/// A LinearOperator is anything that can be applied to a vector, whether or
+/// not it stores its elements. Iterative methods only ever need the action of
+/// a matrix on a vector, so they are written against this trait.
+pub trait LinearOperator<T> {
+    /// Get the operator dimensions `(m, n)`.
+    fn shape(&self) -> (usize, usize);
+
+    /// Compute the product `A x`. The slice `x` must have length `n`.
+    fn apply(&self, x: &[T]) -> Vec<T>;
+
+    /// Compute the product `A^T x`. The slice `x` must have length `m`.
+    fn apply_transpose(&self, x: &[T]) -> Vec<T>;
+}
@@ -1,13 +1,15 @@
+use std::collections::HashMap;
 use std::fmt::Debug;
-use std::ops::Add;
-use std::ops::Sub;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
 
-use ::{FromPrimitive, Num, One, ToPrimitive};
-use ::{DenseMatrix, IdentityMatrix, Matrix, SparseMatrix, ZeroMatrix};
+use ::{Num};
+use ::{DenseMatrix, DiagonalMatrix, Gf2Matrix, IdentityMatrix, Matrix, PermutationMatrix};
+use ::{ReadOrder, SparseMatrix, TriangularMatrix, ZeroMatrix};
 use ::{Vector};
 
 
 static ADD_DIM_ERROR: &str = "Cannot add matrices of given dimensions";
+static ADD_TRIANGLE_ERROR: &str = "Cannot add a triangular matrix of the opposite triangle in place";
 
 macro_rules! check_vec_dims {
     ($self:expr, $other:expr) => (
@@ -25,27 +27,314 @@ macro_rules! check_add_dims {
     )
 }
 
-macro_rules! zero_add_impl {
-    ($($t:ty)*) => ($(
-        impl<T: Clone + Num + FromPrimitive + ToPrimitive>
-            Add<$t> for ZeroMatrix<T>
-            where T: Copy + Debug,
+/// Combine two matrices of equal dimensions element-wise into a new dense
+/// matrix.
+fn dense_zip<T, A, B, F>(a: &A, b: &B, f: F) -> DenseMatrix<T>
+    where T: Clone + Copy + Num, A: Matrix<T>, B: Matrix<T>, F: Fn(T, T) -> T
+{
+    let mut mat = Vec::with_capacity(a.rows()*a.cols());
+    for i in 0..a.rows() {
+        for j in 0..a.cols() {
+            mat.push(f(a.get(i, j).unwrap(), b.get(i, j).unwrap()));
+        }
+    }
+    DenseMatrix::from_vec(mat, a.rows(), a.cols(), None).unwrap()
+}
+
+/// Combine a dense matrix with another matrix element-wise into a new dense
+/// matrix with the same read order as the dense one.
+fn dense_zip_ordered<T, B, F>(a: &DenseMatrix<T>, b: &B, f: F) -> DenseMatrix<T>
+    where T: Clone + Copy + Num, B: Matrix<T>, F: Fn(T, T) -> T
+{
+    match a.read_order {
+        ReadOrder::RowMajor => dense_zip(a, b, f),
+        ReadOrder::ColMajor => {
+            // Store the columns in turn, which column major order reads as
+            // the rows of the transpose.
+            let (m, n) = (a.rows(), a.cols());
+            let mut mat = Vec::with_capacity(m*n);
+            for j in 0..n {
+                for i in 0..m {
+                    mat.push(f(a.get(i, j).unwrap(), b.get(i, j).unwrap()));
+                }
+            }
+            DenseMatrix::from_vec(mat, n, m, Some(ReadOrder::ColMajor)).unwrap()
+        },
+    }
+}
+
+/// Combine the stored elements of two sparse operands given as triplets. An
+/// element stored in only one operand is zero in the other.
+fn sparse_zip<T, F>(a: Vec<(usize, usize, T)>,
+                    b: Vec<(usize, usize, T)>,
+                    m: usize,
+                    n: usize,
+                    f: F) -> SparseMatrix<T>
+    where T: Clone + Copy + Num, F: Fn(T, T) -> T
+{
+    let mut map: HashMap<(usize, usize), T> = HashMap::new();
+    for (i, j, x) in a {
+        map.insert((i, j), f(x, T::zero()));
+    }
+    for (i, j, y) in b {
+        let x = map.get(&(i, j)).cloned().unwrap_or_else(T::zero);
+        map.insert((i, j), f(x, y));
+    }
+    SparseMatrix::from_tuple(map.into_iter().map(|((i, j), x)| (i, j, x)).collect(), m, n)
+}
+
+fn identity_triplets<T: Num>(n: usize) -> Vec<(usize, usize, T)> {
+    (0..n).map(|i| (i, i, T::one())).collect()
+}
+
+/// Implement `Add` and `Sub` for a pair of matrix types. The body sees the
+/// two operands by reference and the scalar operation as a closure.
+macro_rules! additive_impl {
+    ($lhs:ty, $rhs:ty, $out:ty, |$a:ident, $b:ident, $f:ident| $body:expr) => (
+        additive_impl!(@op Add, add, +, $lhs, $rhs, $out, |$a, $b, $f| $body);
+        additive_impl!(@op Sub, sub, -, $lhs, $rhs, $out, |$a, $b, $f| $body);
+    );
+    (@op $imp:ident, $method:ident, $op:tt, $lhs:ty, $rhs:ty, $out:ty,
+     |$a:ident, $b:ident, $f:ident| $body:expr) => (
+        impl<'a, 'b, T> $imp<&'b $rhs> for &'a $lhs
+            where T: Clone + Copy + Num + Debug,
         {
-            type Output = $t;
+            type Output = $out;
 
-            #[inline]
-            fn add(self, other: $t) -> $t {
-                check_add_dims! { self, other }
-                other
+            fn $method(self, other: &'b $rhs) -> $out {
+                check_add_dims!(self, other);
+                let ($a, $b) = (self, other);
+                let $f = |x: T, y: T| x $op y;
+                $body
             }
         }
-    )*)
+
+        forward_binop!($imp, $method, $lhs, $rhs, $out);
+    );
 }
 
-zero_add_impl! { DenseMatrix<T> IdentityMatrix<T> SparseMatrix<T> ZeroMatrix<T> }
+// These are eager, with one allocation per operator. `DenseMatrix::lazy`
+// fuses a chain of element-wise operations into a single pass.
+additive_impl! { DenseMatrix<T>, DenseMatrix<T>, DenseMatrix<T>, |a, b, f| dense_zip(a, b, f) }
+additive_impl! { DenseMatrix<T>, SparseMatrix<T>, DenseMatrix<T>, |a, b, f| dense_zip_ordered(a, b, f) }
+additive_impl! { DenseMatrix<T>, IdentityMatrix<T>, DenseMatrix<T>, |a, b, f| dense_zip_ordered(a, b, f) }
+additive_impl! { SparseMatrix<T>, DenseMatrix<T>, DenseMatrix<T>, |a, b, f| dense_zip(a, b, f) }
+additive_impl! { IdentityMatrix<T>, DenseMatrix<T>, DenseMatrix<T>, |a, b, f| dense_zip(a, b, f) }
+
+additive_impl! { SparseMatrix<T>, SparseMatrix<T>, SparseMatrix<T>, |a, b, f| {
+    sparse_zip(a.triplets(), b.triplets(), a.rows(), a.cols(), f)
+}}
+additive_impl! { SparseMatrix<T>, IdentityMatrix<T>, SparseMatrix<T>, |a, b, f| {
+    sparse_zip(a.triplets(), identity_triplets(b.rows()), a.rows(), a.cols(), f)
+}}
+additive_impl! { IdentityMatrix<T>, SparseMatrix<T>, SparseMatrix<T>, |a, b, f| {
+    sparse_zip(identity_triplets(a.rows()), b.triplets(), a.rows(), a.cols(), f)
+}}
+
+// Adding or subtracting a zero matrix copies the other operand.
+additive_impl! { ZeroMatrix<T>, ZeroMatrix<T>, ZeroMatrix<T>, |a, _b, _f| a.clone() }
+additive_impl! { DenseMatrix<T>, ZeroMatrix<T>, DenseMatrix<T>, |a, _b, _f| a.clone() }
+additive_impl! { SparseMatrix<T>, ZeroMatrix<T>, SparseMatrix<T>, |a, _b, _f| a.deep_clone() }
+additive_impl! { IdentityMatrix<T>, ZeroMatrix<T>, IdentityMatrix<T>, |a, _b, _f| a.clone() }
+additive_impl! { @op Add, add, +, ZeroMatrix<T>, DenseMatrix<T>, DenseMatrix<T>, |_a, b, _f| b.clone() }
+additive_impl! { @op Add, add, +, ZeroMatrix<T>, SparseMatrix<T>, SparseMatrix<T>, |_a, b, _f| b.deep_clone() }
+additive_impl! { @op Add, add, +, ZeroMatrix<T>, IdentityMatrix<T>, IdentityMatrix<T>, |_a, b, _f| b.clone() }
+additive_impl! { @op Sub, sub, -, ZeroMatrix<T>, DenseMatrix<T>, DenseMatrix<T>, |_a, b, _f| -b }
+additive_impl! { @op Sub, sub, -, ZeroMatrix<T>, SparseMatrix<T>, SparseMatrix<T>, |_a, b, _f| -b }
+additive_impl! { @op Sub, sub, -, ZeroMatrix<T>, IdentityMatrix<T>, DiagonalMatrix<T>, |_a, b, _f| -b }
+
+fn diagonal_zip<T, F>(a: Vec<T>, b: Vec<T>, f: F) -> DiagonalMatrix<T>
+    where T: Clone + Copy + Num, F: Fn(T, T) -> T
+{
+    DiagonalMatrix::new(a.into_iter().zip(b).map(|(x, y)| f(x, y)).collect())
+}
 
+/// Combine the diagonal of a triangular matrix with another diagonal.
+fn triangular_diag_zip<T, F>(t: &TriangularMatrix<T>, d: Vec<T>, f: F) -> TriangularMatrix<T>
+    where T: Clone + Copy + Num, F: Fn(T, T) -> T
+{
+    let c = t.clone();
+    for (i, y) in d.into_iter().enumerate() {
+        c.set(i, i, f(t.get(i, i).unwrap(), y));
+    }
+    c
+}
 
-impl<T: Clone + Num + FromPrimitive + ToPrimitive> Add for Vector<T>
+fn diag_triangular_zip<T, F>(d: Vec<T>, t: &TriangularMatrix<T>, f: F) -> TriangularMatrix<T>
+    where T: Clone + Copy + Num, F: Fn(T, T) -> T
+{
+    // Off the diagonal the result is 0 op t, and on it d op t.
+    let c = t.map(|x| f(T::zero(), x));
+    for (i, x) in d.into_iter().enumerate() {
+        c.set(i, i, f(x, t.get(i, i).unwrap()));
+    }
+    c
+}
+
+additive_impl! { DiagonalMatrix<T>, DiagonalMatrix<T>, DiagonalMatrix<T>, |a, b, f| {
+    diagonal_zip(a.diag(), b.diag(), f)
+}}
+additive_impl! { DiagonalMatrix<T>, IdentityMatrix<T>, DiagonalMatrix<T>, |a, b, f| {
+    diagonal_zip(a.diag(), b.diags(), f)
+}}
+additive_impl! { IdentityMatrix<T>, DiagonalMatrix<T>, DiagonalMatrix<T>, |a, b, f| {
+    diagonal_zip(a.diags(), b.diag(), f)
+}}
+additive_impl! { IdentityMatrix<T>, IdentityMatrix<T>, DiagonalMatrix<T>, |a, b, f| {
+    diagonal_zip(a.diags(), b.diags(), f)
+}}
+additive_impl! { DiagonalMatrix<T>, SparseMatrix<T>, SparseMatrix<T>, |a, b, f| {
+    sparse_zip(a.triplets(), b.triplets(), a.rows(), a.cols(), f)
+}}
+additive_impl! { SparseMatrix<T>, DiagonalMatrix<T>, SparseMatrix<T>, |a, b, f| {
+    sparse_zip(a.triplets(), b.triplets(), a.rows(), a.cols(), f)
+}}
+
+additive_impl! { TriangularMatrix<T>, DiagonalMatrix<T>, TriangularMatrix<T>, |a, b, f| {
+    triangular_diag_zip(a, b.diag(), f)
+}}
+additive_impl! { DiagonalMatrix<T>, TriangularMatrix<T>, TriangularMatrix<T>, |a, b, f| {
+    diag_triangular_zip(a.diag(), b, f)
+}}
+additive_impl! { TriangularMatrix<T>, IdentityMatrix<T>, TriangularMatrix<T>, |a, b, f| {
+    triangular_diag_zip(a, b.diags(), f)
+}}
+additive_impl! { IdentityMatrix<T>, TriangularMatrix<T>, TriangularMatrix<T>, |a, b, f| {
+    diag_triangular_zip(a.diags(), b, f)
+}}
+
+// Structured matrices combined with a dense matrix are dense.
+additive_impl! { DenseMatrix<T>, DiagonalMatrix<T>, DenseMatrix<T>, |a, b, f| dense_zip(a, b, f) }
+additive_impl! { DiagonalMatrix<T>, DenseMatrix<T>, DenseMatrix<T>, |a, b, f| dense_zip(a, b, f) }
+additive_impl! { DenseMatrix<T>, TriangularMatrix<T>, DenseMatrix<T>, |a, b, f| dense_zip(a, b, f) }
+additive_impl! { TriangularMatrix<T>, DenseMatrix<T>, DenseMatrix<T>, |a, b, f| dense_zip(a, b, f) }
+// An upper and a lower triangular matrix add up to a full one, so the sum
+// is dense. Use `TriangularMatrix::zip_map` to keep the packed storage.
+additive_impl! { TriangularMatrix<T>, TriangularMatrix<T>, DenseMatrix<T>, |a, b, f| dense_zip(a, b, f) }
+additive_impl! { DenseMatrix<T>, PermutationMatrix<T>, DenseMatrix<T>, |a, b, f| dense_zip(a, b, f) }
+additive_impl! { PermutationMatrix<T>, DenseMatrix<T>, DenseMatrix<T>, |a, b, f| dense_zip(a, b, f) }
+
+additive_impl! { DiagonalMatrix<T>, ZeroMatrix<T>, DiagonalMatrix<T>, |a, _b, _f| a.clone() }
+additive_impl! { TriangularMatrix<T>, ZeroMatrix<T>, TriangularMatrix<T>, |a, _b, _f| a.clone() }
+additive_impl! { @op Add, add, +, ZeroMatrix<T>, DiagonalMatrix<T>, DiagonalMatrix<T>, |_a, b, _f| b.clone() }
+additive_impl! { @op Add, add, +, ZeroMatrix<T>, TriangularMatrix<T>, TriangularMatrix<T>, |_a, b, _f| b.clone() }
+additive_impl! { @op Sub, sub, -, ZeroMatrix<T>, DiagonalMatrix<T>, DiagonalMatrix<T>, |_a, b, _f| -b }
+additive_impl! { @op Sub, sub, -, ZeroMatrix<T>, TriangularMatrix<T>, TriangularMatrix<T>, |_a, b, _f| -b }
+
+/// Implement `AddAssign` and `SubAssign` for a pair of matrix types. The
+/// body updates the left operand in place through `Matrix::set`.
+macro_rules! additive_assign_impl {
+    ($lhs:ty, $rhs:ty, |$a:ident, $b:ident, $f:ident| $body:expr) => (
+        additive_assign_impl!(@op AddAssign, add_assign, +, $lhs, $rhs, |$a, $b, $f| $body);
+        additive_assign_impl!(@op SubAssign, sub_assign, -, $lhs, $rhs, |$a, $b, $f| $body);
+    );
+    (@op $imp:ident, $method:ident, $op:tt, $lhs:ty, $rhs:ty,
+     |$a:ident, $b:ident, $f:ident| $body:expr) => (
+        impl<'b, T> $imp<&'b $rhs> for $lhs
+            where T: Clone + Copy + Num + Debug,
+        {
+            fn $method(&mut self, other: &'b $rhs) {
+                check_add_dims!(self, other);
+                let ($a, $b) = (&*self, other);
+                let $f = |x: T, y: T| x $op y;
+                $body
+            }
+        }
+
+        forward_assign_op!($imp, $method, $lhs, $rhs);
+    );
+}
+
+additive_assign_impl! { DenseMatrix<T>, DenseMatrix<T>, |a, b, f| {
+    for i in 0..a.rows() {
+        for j in 0..a.cols() {
+            a.set(i, j, f(a.get(i, j).unwrap(), b.get(i, j).unwrap()));
+        }
+    }
+}}
+additive_assign_impl! { DenseMatrix<T>, SparseMatrix<T>, |a, b, f| {
+    for (i, j, y) in b.triplets() {
+        a.set(i, j, f(a.get(i, j).unwrap(), y));
+    }
+}}
+additive_assign_impl! { SparseMatrix<T>, DenseMatrix<T>, |a, b, f| {
+    for i in 0..a.rows() {
+        for j in 0..a.cols() {
+            let y = b.get(i, j).unwrap();
+            if y != T::zero() { a.set(i, j, f(a.get(i, j).unwrap(), y)); }
+        }
+    }
+}}
+additive_assign_impl! { SparseMatrix<T>, SparseMatrix<T>, |a, b, f| {
+    for (i, j, y) in b.triplets() {
+        a.set(i, j, f(a.get(i, j).unwrap(), y));
+    }
+}}
+additive_assign_impl! { DenseMatrix<T>, IdentityMatrix<T>, |a, b, f| {
+    for i in 0..b.rows() {
+        a.set(i, i, f(a.get(i, i).unwrap(), T::one()));
+    }
+}}
+additive_assign_impl! { SparseMatrix<T>, IdentityMatrix<T>, |a, b, f| {
+    for i in 0..b.rows() {
+        a.set(i, i, f(a.get(i, i).unwrap(), T::one()));
+    }
+}}
+additive_assign_impl! { DenseMatrix<T>, DiagonalMatrix<T>, |a, b, f| {
+    for (i, d) in b.diag().into_iter().enumerate() {
+        a.set(i, i, f(a.get(i, i).unwrap(), d));
+    }
+}}
+additive_assign_impl! { SparseMatrix<T>, DiagonalMatrix<T>, |a, b, f| {
+    for (i, d) in b.diag().into_iter().enumerate() {
+        a.set(i, i, f(a.get(i, i).unwrap(), d));
+    }
+}}
+additive_assign_impl! { DenseMatrix<T>, TriangularMatrix<T>, |a, b, f| {
+    for i in 0..b.rows() {
+        for j in 0..b.cols() {
+            if b.in_triangle(i, j) { a.set(i, j, f(a.get(i, j).unwrap(), b.get(i, j).unwrap())); }
+        }
+    }
+}}
+additive_assign_impl! { DiagonalMatrix<T>, DiagonalMatrix<T>, |a, b, f| {
+    for (x, y) in a.diag.iter().zip(b.diag.iter()) {
+        x.set(f(x.get(), y.get()));
+    }
+}}
+additive_assign_impl! { DiagonalMatrix<T>, IdentityMatrix<T>, |a, _b, f| {
+    for x in a.diag.iter() {
+        x.set(f(x.get(), T::one()));
+    }
+}}
+// The packed storage of the left operand cannot hold the other triangle, so
+// updating it in place with a matrix of the opposite triangle panics.
+additive_assign_impl! { TriangularMatrix<T>, TriangularMatrix<T>, |a, b, f| {
+    if a.triangle() != b.triangle() {
+        panic!("{}: lhs={:?} rhs={:?}", ADD_TRIANGLE_ERROR, a.triangle(), b.triangle())
+    }
+    for (x, y) in a.mat.iter().zip(b.mat.iter()) {
+        x.set(f(x.get(), y.get()));
+    }
+}}
+additive_assign_impl! { TriangularMatrix<T>, DiagonalMatrix<T>, |a, b, f| {
+    for (i, d) in b.diag().into_iter().enumerate() {
+        a.set(i, i, f(a.get(i, i).unwrap(), d));
+    }
+}}
+additive_assign_impl! { TriangularMatrix<T>, IdentityMatrix<T>, |a, b, f| {
+    for i in 0..b.rows() {
+        a.set(i, i, f(a.get(i, i).unwrap(), T::one()));
+    }
+}}
+additive_assign_impl! { DiagonalMatrix<T>, ZeroMatrix<T>, |_a, _b, _f| {} }
+additive_assign_impl! { TriangularMatrix<T>, ZeroMatrix<T>, |_a, _b, _f| {} }
+additive_assign_impl! { DenseMatrix<T>, ZeroMatrix<T>, |_a, _b, _f| {} }
+additive_assign_impl! { SparseMatrix<T>, ZeroMatrix<T>, |_a, _b, _f| {} }
+additive_assign_impl! { IdentityMatrix<T>, ZeroMatrix<T>, |_a, _b, _f| {} }
+additive_assign_impl! { ZeroMatrix<T>, ZeroMatrix<T>, |_a, _b, _f| {} }
+
+impl<T: Clone + Num> Add for Vector<T>
     where T: Copy,
 {
     type Output = Vector<T>;
@@ -63,7 +352,7 @@ impl<T: Clone + Num + FromPrimitive + ToPrimitive> Add for Vector<T>
     }
 }
 
-impl<T: Clone + Num + FromPrimitive + ToPrimitive> Sub for Vector<T>
+impl<T: Clone + Num> Sub for Vector<T>
     where T: Copy,
 {
     type Output = Vector<T>;
@@ -82,99 +371,23 @@ impl<T: Clone + Num + FromPrimitive + ToPrimitive> Sub for Vector<T>
 }
 
 
-impl<T: Clone + Num + FromPrimitive + ToPrimitive> Add for DenseMatrix<T>
-    where T: Copy + Debug,
-{
-    type Output = DenseMatrix<T>;
-
-    fn add(self, other: DenseMatrix<T>) -> DenseMatrix<T> {
-        check_add_dims!(self, other);
-        let mut mat = Vec::new();
-        for i in 0..self.rows() {
-            for j in 0..self.cols() {
-                mat.push(self.get(i, j).unwrap() + other.get(i, j).unwrap());
-            }
-        }
-        DenseMatrix::from_vec(mat, self.rows(), self.cols(), None).unwrap()
-    }
-}
-
-impl<T: Clone + Num + FromPrimitive + ToPrimitive>
-    Add<IdentityMatrix<T>> for DenseMatrix<T>
-    where T: Copy + Debug + One,
-{
-    type Output = DenseMatrix<T>;
-
-    fn add(self, other: IdentityMatrix<T>) -> DenseMatrix<T> {
-        check_add_dims!(self, other);
-        let mut mat = Vec::with_capacity(self.rows()*self.cols());
-        for i in 0..self.rows() {
-            for j in 0..self.cols() {
-                if i == j {
-                    mat.push(self.get(i, j).unwrap() + T::one());
-                } else {
-                    mat.push(self.get(i, j).unwrap());
-                }
-            }
-        }
-        DenseMatrix::from_vec(mat, self.rows(), self.cols(),
-                              Some(self.read_order)).unwrap()
-    }
-}
-
-impl<T: Clone + Num + FromPrimitive + ToPrimitive>
-    Add<SparseMatrix<T>> for DenseMatrix<T>
-    where T: Copy + Debug,
-{
-    type Output = DenseMatrix<T>;
+impl Add for Gf2Matrix {
+    type Output = Gf2Matrix;
 
-    fn add(self, other: SparseMatrix<T>) -> DenseMatrix<T> {
+    /// Addition over GF(2) is a word-wise XOR.
+    fn add(self, other: Gf2Matrix) -> Gf2Matrix {
         check_add_dims!(self, other);
-        let mut mat = Vec::with_capacity(self.rows()*self.cols());
-        for i in 0..self.rows() {
-            for j in 0..self.cols() {
-                mat.push(self.get(i, j).unwrap() + other.get(i, j).unwrap());
-            }
+        for (a, b) in self.mat.iter().zip(other.mat.iter()) {
+            a.set(a.get() ^ b.get());
         }
-        DenseMatrix::from_vec(mat, self.rows(), self.cols(),
-                              Some(self.read_order)).unwrap()
+        self
     }
 }
 
-
-impl<T: Clone + Num + FromPrimitive + ToPrimitive> Add for IdentityMatrix<T>
-    where T: Copy + Debug,
-{
-    type Output = SparseMatrix<T>;
-
-    fn add(self, other: IdentityMatrix<T>) -> SparseMatrix<T> {
-        check_add_dims!(self, other);
-        let mut mat = Vec::with_capacity(self.rows());
-        for i in 0..self.rows() {
-            let one = self.get(i, i).unwrap();
-            mat.push((i, i, one + one));
-        }
-        SparseMatrix::from_tuple(mat, self.rows(), self.cols())
-    }
-}
-
-/*
-impl<T: Clone + Num + FromPrimitive + ToPrimitive>
-    Add<SparseMatrix<T>> for IdentityMatrix<T>
-{
-    type Output = SparseMatrix<T>;
-
-    fn add(self, other: SparseMatrix<T>) -> SparseMatrix<T> {
-        check_add_dims!(self, other);
-        for i in 0..self.rows() {
-        }
-    }
-}*/
-
 #[cfg(test)]
 mod tests {
     #![allow(non_snake_case)]
-    use ::{DenseMatrix, IdentityMatrix, ZeroMatrix};
+    use ::{DenseMatrix, DiagonalMatrix, IdentityMatrix, ReadOrder, ZeroMatrix};
     use ::{Vector};
 
     #[test]
@@ -214,6 +427,120 @@ mod tests {
         let A2 = DenseMatrix::new(&mat2).unwrap();
         let I = IdentityMatrix::new(4);
         assert_eq!(A1+I, A2);
+
+        // The sum keeps the read order of the dense operand.
+        let C = DenseMatrix::from_vec(vec![1, 4, 0, 2, 5, 0, 3, 6, 0], 3, 3, Some(ReadOrder::ColMajor)).unwrap();
+        let I = IdentityMatrix::new(3);
+        let D = &C + &I;
+        assert!(matches!(D.read_order, ReadOrder::ColMajor));
+        assert_eq!(D, dense![2, 2, 3; 4, 6, 6; 0, 0, 1].unwrap());
+        assert!(matches!((&C - &I).read_order, ReadOrder::ColMajor));
+        assert_eq!(&C - &I, dense![0, 2, 3; 4, 4, 6; 0, 0, -1].unwrap());
+        let S = sparse![vec![(2, 0, 7)]; 3, 3];
+        assert!(matches!((&C + &S).read_order, ReadOrder::ColMajor));
+        assert_eq!(&C + &S, dense![1, 2, 3; 4, 5, 6; 7, 0, 0].unwrap());
+    }
+
+    #[test]
+    fn test_ref_add_sub() {
+        let A = dense![1, 2; 3, 4].unwrap();
+        let B = dense![5, 6; 7, 8].unwrap();
+        let S = sparse![vec![(0, 1, 10)]; 2, 2];
+        let I: IdentityMatrix<i32> = eye!(2);
+        assert_eq!(&A + &B, dense![6, 8; 10, 12].unwrap());
+        assert_eq!(&B - &A, dense![4, 4; 4, 4].unwrap());
+        assert_eq!(A.clone() + &S, dense![1, 12; 3, 4].unwrap());
+        assert_eq!(&S - A.clone(), dense![-1, 8; -3, -4].unwrap());
+        assert_eq!(&A - &I, dense![0, 2; 3, 3].unwrap());
+        assert_eq!(&I + &I, DiagonalMatrix::new(vec![2, 2]));
+        assert_eq!(&S + &I, sparse![vec![(0, 0, 1), (0, 1, 10), (1, 1, 1)]; 2, 2]);
+        // The operands are untouched.
+        assert_eq!(A, dense![1, 2; 3, 4].unwrap());
+    }
+
+    #[test]
+    fn test_zero_sub() {
+        let Z: ZeroMatrix<i32> = zeros!(2, 2);
+        let A = dense![1, 2; 3, 4].unwrap();
+        let I = eye!(2);
+        assert_eq!(&A - &Z, A);
+        assert_eq!(&Z - &A, dense![-1, -2; -3, -4].unwrap());
+        assert_eq!(&Z - &I, DiagonalMatrix::new(vec![-1, -1]));
+        assert_eq!(&Z - &Z, Z);
+    }
+
+    #[test]
+    fn test_add_assign() {
+        let mut A = dense![1, 2; 3, 4].unwrap();
+        let ptr = A.mat.as_ptr();
+        A += dense![1, 1; 1, 1].unwrap();
+        A -= &sparse![vec![(1, 0, 3)]; 2, 2];
+        A += &IdentityMatrix::new(2);
+        A -= ZeroMatrix::new(2, 2);
+        assert_eq!(A, dense![3, 3; 1, 6].unwrap());
+        assert_eq!(A.mat.as_ptr(), ptr);
+
+        let mut S = sparse![vec![(0, 1, 2)]; 2, 2];
+        S += &sparse![vec![(0, 1, 1), (1, 0, 4)]; 2, 2];
+        S -= dense![0, 0; 0, 5].unwrap();
+        S += &IdentityMatrix::new(2);
+        assert_eq!(S, sparse![vec![(0, 0, 1), (0, 1, 3), (1, 0, 4), (1, 1, -4)]; 2, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bad_add_assign() {
+        let mut A = dense![1, 2; 3, 4].unwrap();
+        A += &dense![1, 2, 3; 4, 5, 6].unwrap();
+    }
+
+    #[test]
+    fn test_structured_add() {
+        use ::{PermutationMatrix, Triangle, TriangularMatrix};
+
+        let D = DiagonalMatrix::new(vec![1, 2]);
+        let I = IdentityMatrix::new(2);
+        let A = dense![1, 2; 3, 4].unwrap();
+        assert_eq!(&D + &D, DiagonalMatrix::new(vec![2, 4]));
+        assert_eq!(&D - &I, DiagonalMatrix::new(vec![0, 1]));
+        assert_eq!(&A + &D, dense![2, 2; 3, 6].unwrap());
+        assert_eq!(&sparse![vec![(0, 1, 7)]; 2, 2] + &D,
+                   sparse![vec![(0, 0, 1), (0, 1, 7), (1, 1, 2)]; 2, 2]);
+
+        let U = TriangularMatrix::from_dense(&A, Triangle::Upper).unwrap();
+        assert_eq!(&U + &U, dense![2, 4; 0, 8].unwrap());
+        assert_eq!((&D - &U).triangle(), Triangle::Upper);
+        assert_eq!(&D - &U, dense![0, -2; 0, -2].unwrap());
+        assert_eq!(&U + &A, dense![2, 4; 3, 8].unwrap());
+        let L = TriangularMatrix::from_dense(&A, Triangle::Lower).unwrap();
+        assert_eq!(&U + &L, dense![2, 2; 3, 8].unwrap());
+        assert_eq!(&U - &L, dense![0, 2; -3, 0].unwrap());
+        let V = U.zip_map(&U, |x, y| x + y);
+        assert_eq!(V.triangle(), Triangle::Upper);
+        assert_eq!(V, dense![2, 4; 0, 8].unwrap());
+        let mut W = U.clone();
+        W -= &U;
+        assert_eq!(W, dense![0, 0; 0, 0].unwrap());
+
+        let P = PermutationMatrix::new(vec![1, 0]).unwrap();
+        assert_eq!(&A - &P, dense![1, 1; 2, 4].unwrap());
+
+        let mut B = A.clone();
+        B += &D;
+        B -= &U;
+        assert_eq!(B, dense![1, 0; 3, 2].unwrap());
+        let mut E = D.clone();
+        E += &I;
+        assert_eq!(E, DiagonalMatrix::new(vec![2, 3]));
+    }
+
+    #[test]
+    #[should_panic(expected = "opposite triangle")]
+    fn test_triangular_add_assign_mismatch() {
+        use ::{Triangle, TriangularMatrix};
+
+        let mut U: TriangularMatrix<i32> = TriangularMatrix::zeros(2, Triangle::Upper);
+        U += &TriangularMatrix::zeros(2, Triangle::Lower);
     }
 
     #[test]
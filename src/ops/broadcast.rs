@@ -0,0 +1,101 @@
+use std::fmt::Debug;
+
+use ::{Num};
+use ::{DenseColumn, DenseMatrix, DenseRow, Matrix, Vector};
+
+static BROADCAST_DIM_ERROR: &str = "Cannot broadcast vector of given length";
+
+impl<T: Clone + Copy + Num + Debug> DenseMatrix<T> {
+    /// Add `row` to every row of the matrix.
+    ///
+    /// Panics if the length of `row` is not the number of columns.
+    pub fn broadcast_add_row(&self, row: &DenseRow<T>) -> DenseMatrix<T> {
+        self.broadcast_row(row, |a, b| a + b)
+    }
+
+    /// Subtract `row` from every row of the matrix. With the column means
+    /// this centers each column.
+    ///
+    /// Panics if the length of `row` is not the number of columns.
+    pub fn broadcast_sub_row(&self, row: &DenseRow<T>) -> DenseMatrix<T> {
+        self.broadcast_row(row, |a, b| a - b)
+    }
+
+    /// Add `col` to every column of the matrix.
+    ///
+    /// Panics if the length of `col` is not the number of rows.
+    pub fn broadcast_add_col(&self, col: &DenseColumn<T>) -> DenseMatrix<T> {
+        self.broadcast_col(col, |a, b| a + b)
+    }
+
+    /// Subtract `col` from every column of the matrix. With the row means
+    /// this centers each row.
+    ///
+    /// Panics if the length of `col` is not the number of rows.
+    pub fn broadcast_sub_col(&self, col: &DenseColumn<T>) -> DenseMatrix<T> {
+        self.broadcast_col(col, |a, b| a - b)
+    }
+
+    fn broadcast_row<F: Fn(T, T) -> T>(&self, row: &DenseRow<T>, f: F) -> DenseMatrix<T> {
+        if row.len() != self.cols() {
+            panic!("{}: lhs={:?} rhs={}", BROADCAST_DIM_ERROR, self.dims(), row)
+        }
+        let mut mat = Vec::with_capacity(self.rows()*self.cols());
+        for i in 0..self.rows() {
+            for j in 0..self.cols() {
+                mat.push(f(self.get(i, j).unwrap(), row.get(j).unwrap()));
+            }
+        }
+        DenseMatrix::from_vec(mat, self.rows(), self.cols(), None).unwrap()
+    }
+
+    fn broadcast_col<F: Fn(T, T) -> T>(&self, col: &DenseColumn<T>, f: F) -> DenseMatrix<T> {
+        if col.len() != self.rows() {
+            panic!("{}: lhs={:?} rhs={}", BROADCAST_DIM_ERROR, self.dims(), col)
+        }
+        let mut mat = Vec::with_capacity(self.rows()*self.cols());
+        for i in 0..self.rows() {
+            for j in 0..self.cols() {
+                mat.push(f(self.get(i, j).unwrap(), col.get(i).unwrap()));
+            }
+        }
+        DenseMatrix::from_vec(mat, self.rows(), self.cols(), None).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+    use ::{DenseColumn, DenseRow, Matrix};
+
+    #[test]
+    fn test_broadcast() {
+        let A = dense![1, 2, 3;
+                       4, 5, 6].unwrap();
+        let r = DenseRow::from_vec(vec![10, 20, 30]);
+        let c = DenseColumn::from_vec(vec![1, 2]);
+        assert_eq!(A.broadcast_add_row(&r), dense![11, 22, 33; 14, 25, 36].unwrap());
+        assert_eq!(A.broadcast_sub_col(&c), dense![0, 1, 2; 2, 3, 4].unwrap());
+        assert_eq!(A.transpose().broadcast_add_col(&DenseColumn::from_vec(vec![10, 20, 30])),
+                   dense![11, 14; 22, 25; 33, 36].unwrap());
+    }
+
+    #[test]
+    fn test_center_columns() {
+        let X = dense![1.0, 10.0;
+                       3.0, 20.0;
+                       5.0, 60.0].unwrap();
+        let mean = DenseRow::from_vec(vec![3.0, 30.0]);
+        let C = X.broadcast_sub_row(&mean);
+        for j in 0..2 {
+            assert_eq!((0..3).map(|i| C.get(i, j).unwrap()).sum::<f64>(), 0.0);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bad_broadcast() {
+        let A = dense![1, 2; 3, 4].unwrap();
+        A.broadcast_add_row(&DenseRow::from_vec(vec![1, 2, 3]));
+    }
+}
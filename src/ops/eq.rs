@@ -1,8 +1,10 @@
-use ::{FromPrimitive, Num, One, ToPrimitive, Zero};
-use ::{DenseMatrix, IdentityMatrix, Matrix, SparseMatrix, ZeroMatrix};
+use ::{Num, One, Zero};
+use ::{BandedMatrix, DenseMatrix, DiagonalMatrix, Gf2Matrix, IdentityMatrix, Matrix};
+use ::{CirculantMatrix, HankelMatrix, PermutationMatrix, SparseMatrix, ToeplitzMatrix};
+use ::{TriangularMatrix, TridiagonalMatrix, VandermondeMatrix, ZeroMatrix};
 use ::{Vector};
 
-impl<T: Clone + Num + ToPrimitive + FromPrimitive>
+impl<T: Clone + Num>
     PartialEq for Vec<T>
 {
     fn eq(&self, other: &Vector<T>) -> bool {
@@ -13,7 +15,7 @@ impl<T: Clone + Num + ToPrimitive + FromPrimitive>
     }
 }
 
-impl<T: Clone + Num + ToPrimitive + FromPrimitive>
+impl<T: Clone + Num>
     PartialEq for ZeroMatrix<T>
 {
     fn eq(&self, other: &ZeroMatrix<T>) -> bool {
@@ -21,7 +23,7 @@ impl<T: Clone + Num + ToPrimitive + FromPrimitive>
     }
 }
 
-impl<T: Clone + Num + ToPrimitive + FromPrimitive>
+impl<T: Clone + Num>
     PartialEq for IdentityMatrix<T>
 {
     fn eq(&self, other: &IdentityMatrix<T>) -> bool {
@@ -29,7 +31,7 @@ impl<T: Clone + Num + ToPrimitive + FromPrimitive>
     }
 }
 
-impl<T: Clone + Num + ToPrimitive + FromPrimitive>
+impl<T: Clone + Num>
     PartialEq for DenseMatrix<T>
     where T: Copy,
 {
@@ -46,7 +48,7 @@ impl<T: Clone + Num + ToPrimitive + FromPrimitive>
     }
 }
 
-impl<T: Clone + Num + One + ToPrimitive + FromPrimitive>
+impl<T: Clone + Num + One>
     PartialEq<IdentityMatrix<T>> for DenseMatrix<T>
     where T: Copy,
 {
@@ -60,7 +62,7 @@ impl<T: Clone + Num + One + ToPrimitive + FromPrimitive>
     }
 }
 
-impl<T: Clone + Num + One + ToPrimitive + FromPrimitive>
+impl<T: Clone + Num + One>
     PartialEq<DenseMatrix<T>> for IdentityMatrix<T>
     where T: Copy,
 {
@@ -74,7 +76,7 @@ impl<T: Clone + Num + One + ToPrimitive + FromPrimitive>
     }
 }
 
-impl<T: Clone + Num + Zero + ToPrimitive + FromPrimitive>
+impl<T: Clone + Num + Zero>
     PartialEq<ZeroMatrix<T>> for DenseMatrix<T>
     where T: Copy,
 {
@@ -84,7 +86,7 @@ impl<T: Clone + Num + Zero + ToPrimitive + FromPrimitive>
     }
 }
 
-impl<T: Clone + Num + Zero + ToPrimitive + FromPrimitive>
+impl<T: Clone + Num + Zero>
     PartialEq<DenseMatrix<T>> for ZeroMatrix<T>
     where T: Copy,
 {
@@ -94,13 +96,13 @@ impl<T: Clone + Num + Zero + ToPrimitive + FromPrimitive>
     }
 }
 
-impl<T: Clone + Num + Zero + ToPrimitive + FromPrimitive>
+impl<T: Clone + Num + Zero>
     PartialEq<IdentityMatrix<T>> for ZeroMatrix<T>
 {
     fn eq(&self, _other: &IdentityMatrix<T>) -> bool { false }
 }
 
-impl<T: Clone + Copy + Num + Zero + ToPrimitive + FromPrimitive>
+impl<T: Clone + Copy + Num + Zero>
     PartialEq<SparseMatrix<T>> for ZeroMatrix<T>
 {
     fn eq(&self, other: &SparseMatrix<T>) -> bool {
@@ -109,7 +111,7 @@ impl<T: Clone + Copy + Num + Zero + ToPrimitive + FromPrimitive>
     }
 }
 
-impl<T: Clone + Copy + Num + ToPrimitive + FromPrimitive>
+impl<T: Clone + Copy + Num>
     PartialEq<SparseMatrix<T>> for IdentityMatrix<T>
     where T: One,
 {
@@ -123,30 +125,26 @@ impl<T: Clone + Copy + Num + ToPrimitive + FromPrimitive>
     }
 }
 
-impl<T: Clone + Num + Zero + ToPrimitive + FromPrimitive>
+impl<T: Clone + Num + Zero>
     PartialEq<ZeroMatrix<T>> for IdentityMatrix<T>
 {
     fn eq(&self, _other: &ZeroMatrix<T>) -> bool { false }
 }
 
-impl<T: Clone + Num + Zero + ToPrimitive + FromPrimitive>
+impl<T: Clone + Num + Zero>
     PartialEq for SparseMatrix<T>
     where T: Copy,
 {
     fn eq(&self, other: &SparseMatrix<T>) -> bool {
         if self.dims() != other.dims() { return false }
-        if self.mat.borrow().keys().len() != other.mat.borrow().keys().len() {
-            return false
-        }
-        for (&(i1, j1), &(i2, j2)) in self.mat.borrow().keys()
-            .zip(other.mat.borrow().keys()) {
-            if self.get(i1, j1) != other.get(i2, j2) { return false }
-        }
-        true
+        // Compare at every position stored in either matrix, so that an
+        // explicitly stored zero equals a missing element.
+        self.triplets().into_iter().chain(other.triplets())
+            .all(|(i, j, _)| self.get(i, j) == other.get(i, j))
     }
 }
 
-impl<T: Clone + Num + Zero + ToPrimitive + FromPrimitive>
+impl<T: Clone + Num + Zero>
     PartialEq<DenseMatrix<T>> for SparseMatrix<T>
     where T: Copy,
 {
@@ -163,7 +161,7 @@ impl<T: Clone + Num + Zero + ToPrimitive + FromPrimitive>
     }
 }
 
-impl<T: Clone + Num + Zero + ToPrimitive + FromPrimitive>
+impl<T: Clone + Num + Zero>
     PartialEq<SparseMatrix<T>> for DenseMatrix<T>
     where T: Copy,
 {
@@ -180,7 +178,7 @@ impl<T: Clone + Num + Zero + ToPrimitive + FromPrimitive>
     }
 }
 
-impl<T: Clone + Num + One + Zero + ToPrimitive + FromPrimitive>
+impl<T: Clone + Num + One + Zero>
     PartialEq<IdentityMatrix<T>> for SparseMatrix<T>
     where T: Copy,
 {
@@ -194,7 +192,7 @@ impl<T: Clone + Num + One + Zero + ToPrimitive + FromPrimitive>
     }
 }
 
-impl<T: Clone + Copy + Num + Zero + ToPrimitive + FromPrimitive>
+impl<T: Clone + Copy + Num + Zero>
     PartialEq<ZeroMatrix<T>> for SparseMatrix<T>
 {
     fn eq(&self, other: &ZeroMatrix<T>) -> bool {
@@ -206,6 +204,125 @@ impl<T: Clone + Copy + Num + Zero + ToPrimitive + FromPrimitive>
     }
 }
 
+impl<T: Clone + Copy + Num>
+    PartialEq for DiagonalMatrix<T>
+{
+    fn eq(&self, other: &DiagonalMatrix<T>) -> bool {
+        self.dims() == other.dims() && self.diag() == other.diag()
+    }
+}
+
+impl<T: Clone + Copy + Num>
+    PartialEq<IdentityMatrix<T>> for DiagonalMatrix<T>
+{
+    fn eq(&self, other: &IdentityMatrix<T>) -> bool {
+        self.dims() == other.dims() && self.diag().iter().all(|d| d.is_one())
+    }
+}
+
+impl<T: Clone + Copy + Num>
+    PartialEq<DiagonalMatrix<T>> for IdentityMatrix<T>
+{
+    fn eq(&self, other: &DiagonalMatrix<T>) -> bool { other == self }
+}
+
+impl<T: Clone + Copy + Num>
+    PartialEq for PermutationMatrix<T>
+{
+    fn eq(&self, other: &PermutationMatrix<T>) -> bool {
+        self.perm() == other.perm()
+    }
+}
+
+impl<T: Clone + Copy + Num>
+    PartialEq<IdentityMatrix<T>> for PermutationMatrix<T>
+{
+    fn eq(&self, other: &IdentityMatrix<T>) -> bool {
+        self.dims() == other.dims() && self.is_diagonal()
+    }
+}
+
+impl<T: Clone + Copy + Num>
+    PartialEq<PermutationMatrix<T>> for IdentityMatrix<T>
+{
+    fn eq(&self, other: &PermutationMatrix<T>) -> bool { other == self }
+}
+
+impl<T: Clone + Copy + Num>
+    PartialEq for TriangularMatrix<T>
+{
+    fn eq(&self, other: &TriangularMatrix<T>) -> bool {
+        if self.dims() != other.dims() { return false }
+        if self.triangle() == other.triangle() {
+            return self.mat.iter().zip(other.mat.iter()).all(|(a, b)| a.get() == b.get())
+        }
+        // Opposite triangles are equal only when both are the same diagonal.
+        self.is_diagonal() && other.is_diagonal() && self.diags() == other.diags()
+    }
+}
+
+/// Compare two matrices of different kinds element by element.
+macro_rules! elementwise_eq_impl {
+    ($($lhs:ty, $rhs:ty);*) => ($(
+        impl<T: Clone + Copy + Num>
+            PartialEq<$rhs> for $lhs
+        {
+            fn eq(&self, other: &$rhs) -> bool {
+                if self.dims() != other.dims() { return false }
+                for i in 0..self.rows() {
+                    for j in 0..self.cols() {
+                        if self.get(i, j) != other.get(i, j) { return false }
+                    }
+                }
+                true
+            }
+        }
+    )*)
+}
+
+elementwise_eq_impl! {
+    DiagonalMatrix<T>, DenseMatrix<T>;
+    DenseMatrix<T>, DiagonalMatrix<T>;
+    DiagonalMatrix<T>, SparseMatrix<T>;
+    SparseMatrix<T>, DiagonalMatrix<T>;
+    PermutationMatrix<T>, DenseMatrix<T>;
+    DenseMatrix<T>, PermutationMatrix<T>;
+    TriangularMatrix<T>, DenseMatrix<T>;
+    DenseMatrix<T>, TriangularMatrix<T>;
+    TriangularMatrix<T>, DiagonalMatrix<T>;
+    DiagonalMatrix<T>, TriangularMatrix<T>;
+    BandedMatrix<T>, BandedMatrix<T>;
+    BandedMatrix<T>, DenseMatrix<T>;
+    DenseMatrix<T>, BandedMatrix<T>;
+    TridiagonalMatrix<T>, TridiagonalMatrix<T>;
+    TridiagonalMatrix<T>, BandedMatrix<T>;
+    BandedMatrix<T>, TridiagonalMatrix<T>;
+    TridiagonalMatrix<T>, DenseMatrix<T>;
+    DenseMatrix<T>, TridiagonalMatrix<T>;
+    ToeplitzMatrix<T>, ToeplitzMatrix<T>;
+    ToeplitzMatrix<T>, DenseMatrix<T>;
+    DenseMatrix<T>, ToeplitzMatrix<T>;
+    CirculantMatrix<T>, CirculantMatrix<T>;
+    CirculantMatrix<T>, ToeplitzMatrix<T>;
+    ToeplitzMatrix<T>, CirculantMatrix<T>;
+    CirculantMatrix<T>, DenseMatrix<T>;
+    DenseMatrix<T>, CirculantMatrix<T>;
+    HankelMatrix<T>, HankelMatrix<T>;
+    HankelMatrix<T>, DenseMatrix<T>;
+    DenseMatrix<T>, HankelMatrix<T>;
+    VandermondeMatrix<T>, VandermondeMatrix<T>;
+    VandermondeMatrix<T>, DenseMatrix<T>;
+    DenseMatrix<T>, VandermondeMatrix<T>
+}
+
+impl PartialEq for Gf2Matrix {
+    fn eq(&self, other: &Gf2Matrix) -> bool {
+        // Padding bits are always zero, so whole words can be compared.
+        self.dims() == other.dims() &&
+            self.mat.iter().zip(other.mat.iter()).all(|(a, b)| a.get() == b.get())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(non_snake_case)]
@@ -224,6 +341,20 @@ mod tests {
         assert_ne!(I, A2);
     }
 
+    #[test]
+    fn test_eq_sparse_sparse() {
+        // Equal values at different positions are not equal matrices.
+        let S1 = sparse![vec![(0, 0, 1)]; 2, 2];
+        let S2 = sparse![vec![(1, 1, 1)]; 2, 2];
+        assert_ne!(S1, S2);
+        assert_ne!(S2, S1);
+        // Insertion order does not matter, and a stored zero is missing.
+        let S3 = sparse![vec![(0, 1, 2), (1, 0, 3), (1, 1, 4)]; 2, 2];
+        let S4 = sparse![vec![(1, 1, 4), (0, 0, 0), (1, 0, 3), (0, 1, 2)]; 2, 2];
+        assert_eq!(S3, S4);
+        assert_eq!(S4, S3);
+    }
+
     #[test]
     fn test_eq_dense_zero() {
         let A1 = dense![0, 0, 0; 0, 0, 0; 0, 0, 0].unwrap();
@@ -319,4 +450,29 @@ mod tests {
 
 
     }
+
+    #[test]
+    fn test_eq_structured() {
+        use ::{DiagonalMatrix, PermutationMatrix, Triangle, TriangularMatrix};
+
+        let D = DiagonalMatrix::new(vec![1, 1, 1]);
+        let I = eye!(3);
+        assert_eq!(D, I);
+        assert_eq!(I, D);
+        assert_eq!(D, dense![1, 0, 0; 0, 1, 0; 0, 0, 1].unwrap());
+        assert_ne!(DiagonalMatrix::new(vec![1, 2, 1]), I);
+
+        let P: PermutationMatrix<i32> = PermutationMatrix::identity(3);
+        assert_eq!(P, I);
+        assert_ne!(PermutationMatrix::swap(3, 0, 1), I);
+        assert_eq!(PermutationMatrix::swap(3, 0, 1), dense![0, 1, 0; 1, 0, 0; 0, 0, 1].unwrap());
+
+        let U = TriangularMatrix::from_dense(&D.to_dense(), Triangle::Upper).unwrap();
+        let L = TriangularMatrix::from_dense(&D.to_dense(), Triangle::Lower).unwrap();
+        assert_eq!(U, L);
+        assert_eq!(U, D);
+        U.set(0, 2, 5);
+        assert_ne!(U, L);
+        assert_eq!(U, dense![1, 0, 5; 0, 1, 0; 0, 0, 1].unwrap());
+    }
 }
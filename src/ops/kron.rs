@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use ::{Num};
+use ::{DenseMatrix, Matrix, SparseMatrix};
+
+static KRON_SUM_DIM_ERROR: &str = "Cannot form the Kronecker sum of non-square matrices";
+
+/// The Kronecker product `A ⊗ B` and Kronecker sum `A ⊕ B`.
+///
+/// For `A` m by n and `B` p by q, `A ⊗ B` is the mp by nq block matrix whose
+/// block `i, j` is `a_ij B`. With the row major `vec` of `DenseMatrix`, it
+/// satisfies `vec(A X B) = (A ⊗ B^T) vec(X)`.
+pub trait Kronecker<Rhs = Self> {
+    type Output;
+
+    /// Get the Kronecker product `self ⊗ rhs`.
+    fn kron(&self, rhs: &Rhs) -> Self::Output;
+
+    /// Get the Kronecker sum `self ⊗ I + I ⊗ rhs` of two square matrices.
+    ///
+    /// Panics unless both operands are square.
+    fn kron_sum(&self, rhs: &Rhs) -> Self::Output;
+}
+
+/// Get the Kronecker product `a ⊗ b`.
+#[inline]
+pub fn kron<A: Kronecker<B>, B>(a: &A, b: &B) -> A::Output {
+    a.kron(b)
+}
+
+/// Get the Kronecker sum `a ⊕ b = a ⊗ I + I ⊗ b`.
+#[inline]
+pub fn kron_sum<A: Kronecker<B>, B>(a: &A, b: &B) -> A::Output {
+    a.kron_sum(b)
+}
+
+macro_rules! check_kron_sum_dims {
+    ($a:expr, $b:expr) => (
+        if !$a.is_square() || !$b.is_square() {
+            panic!("{}: lhs={:?} rhs={:?}", KRON_SUM_DIM_ERROR, $a, $b)
+        }
+    )
+}
+
+/// Form the Kronecker product of two operands given as triplets.
+fn kron_triplets<T>(a: &[(usize, usize, T)],
+                    b: &[(usize, usize, T)],
+                    (p, q): (usize, usize)) -> Vec<(usize, usize, T)>
+    where T: Clone + Copy + Num
+{
+    let mut v = Vec::with_capacity(a.len()*b.len());
+    for &(i, j, x) in a {
+        for &(k, l, y) in b {
+            v.push((i*p + k, j*q + l, x*y));
+        }
+    }
+    v
+}
+
+/// Form the Kronecker sum of two square operands of orders m and n given as
+/// triplets, summing coincident diagonal elements.
+fn kron_sum_triplets<T>(a: &[(usize, usize, T)],
+                        b: &[(usize, usize, T)],
+                        m: usize,
+                        n: usize) -> HashMap<(usize, usize), T>
+    where T: Clone + Copy + Num
+{
+    let mut map: HashMap<(usize, usize), T> = HashMap::new();
+    // A ⊗ I_n puts a_ij on the diagonal of block i, j.
+    for &(i, j, x) in a {
+        for k in 0..n {
+            *map.entry((i*n + k, j*n + k)).or_insert_with(T::zero) = x;
+        }
+    }
+    // I_m ⊗ B puts B on each diagonal block.
+    for r in 0..m {
+        for &(k, l, y) in b {
+            let e = map.entry((r*n + k, r*n + l)).or_insert_with(T::zero);
+            *e = *e + y;
+        }
+    }
+    map
+}
+
+/// Get the nonzero elements of a matrix as triplets, in row major order.
+fn nonzero_triplets<T, A>(a: &A) -> Vec<(usize, usize, T)>
+    where T: Clone + Copy + Num, A: Matrix<T>
+{
+    let mut v = Vec::new();
+    for i in 0..a.rows() {
+        for j in 0..a.cols() {
+            let x = a.get(i, j).unwrap();
+            if !x.is_zero() { v.push((i, j, x)) }
+        }
+    }
+    v
+}
+
+impl<T> Kronecker for DenseMatrix<T>
+    where T: Clone + Copy + Num + Debug,
+{
+    type Output = DenseMatrix<T>;
+
+    fn kron(&self, rhs: &DenseMatrix<T>) -> DenseMatrix<T> {
+        let (m, n) = self.dims();
+        let (p, q) = rhs.dims();
+        let (a, b) = (self.elements(), rhs.elements());
+        let mut mat = Vec::with_capacity(m*n*p*q);
+        for i in 0..m {
+            for k in 0..p {
+                for j in 0..n {
+                    let x = a[i*n + j];
+                    mat.extend(b[k*q..(k + 1)*q].iter().map(|&y| x*y));
+                }
+            }
+        }
+        DenseMatrix::from_vec(mat, m*p, n*q, None).unwrap()
+    }
+
+    fn kron_sum(&self, rhs: &DenseMatrix<T>) -> DenseMatrix<T> {
+        check_kron_sum_dims!(self, rhs);
+        let (m, n) = (self.rows(), rhs.rows());
+        let c = DenseMatrix::zeros(m*n, m*n);
+        let map = kron_sum_triplets(&nonzero_triplets(self), &nonzero_triplets(rhs), m, n);
+        for ((i, j), x) in map {
+            c.set(i, j, x);
+        }
+        c
+    }
+}
+
+/// Implement the Kronecker product and sum with a sparse result, given how
+/// to get the nonzero triplets of each operand.
+macro_rules! sparse_kron_impl {
+    ($lhs:ty, $rhs:ty, |$a:ident| $ta:expr, |$b:ident| $tb:expr) => (
+        impl<T> Kronecker<$rhs> for $lhs
+            where T: Clone + Copy + Num + Debug,
+        {
+            type Output = SparseMatrix<T>;
+
+            fn kron(&self, rhs: &$rhs) -> SparseMatrix<T> {
+                let ($a, $b) = (self, rhs);
+                let v = kron_triplets(&$ta, &$tb, rhs.dims());
+                SparseMatrix::from_tuple(v, self.rows()*rhs.rows(), self.cols()*rhs.cols())
+            }
+
+            fn kron_sum(&self, rhs: &$rhs) -> SparseMatrix<T> {
+                check_kron_sum_dims!(self, rhs);
+                let ($a, $b) = (self, rhs);
+                let (m, n) = (self.rows(), rhs.rows());
+                let map = kron_sum_triplets(&$ta, &$tb, m, n);
+                SparseMatrix::from_tuple(map.into_iter().map(|((i, j), x)| (i, j, x)).collect(),
+                                         m*n, m*n)
+            }
+        }
+    )
+}
+
+sparse_kron_impl! { SparseMatrix<T>, SparseMatrix<T>, |a| a.triplets(), |b| b.triplets() }
+sparse_kron_impl! { SparseMatrix<T>, DenseMatrix<T>, |a| a.triplets(), |b| nonzero_triplets(b) }
+sparse_kron_impl! { DenseMatrix<T>, SparseMatrix<T>, |a| nonzero_triplets(a), |b| b.triplets() }
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+    use ::{DenseMatrix, Matrix, SparseMatrix};
+    use super::{kron, kron_sum};
+
+    #[test]
+    fn test_dense_kron() {
+        let A = dense![1, 2; 3, 4].unwrap();
+        let B = dense![0, 5; 6, 7].unwrap();
+        let K = kron(&A, &B);
+        assert_eq!(K, dense![ 0,  5,  0, 10;
+                              6,  7, 12, 14;
+                              0, 15,  0, 20;
+                             18, 21, 24, 28].unwrap());
+
+        // (A ⊗ B)^T = A^T ⊗ B^T, and read order is respected.
+        let Kt = kron(&A.clone().transpose(), &B.clone().transpose());
+        assert_eq!(Kt, K.transpose());
+
+        let C = dense![1, 2, 3].unwrap();
+        assert_eq!(kron(&C, &A).dims(), (2, 6));
+        assert_eq!(kron(&A, &C).get(1, 4).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_sparse_kron() {
+        let S = sparse![vec![(0, 1, 2), (1, 0, 3)]; 2, 2];
+        let D = dense![1, 2; 3, 4].unwrap();
+        let K = kron(&S, &S);
+        assert_eq!(K.nnz(), 4);
+        assert_eq!(K, kron(&dense![0, 2; 3, 0].unwrap(), &dense![0, 2; 3, 0].unwrap()));
+
+        let KD = kron(&S, &D);
+        assert_eq!(KD.nnz(), 8);
+        assert_eq!(KD, kron(&dense![0, 2; 3, 0].unwrap(), &D));
+        assert_eq!(kron(&D, &S), kron(&D, &dense![0, 2; 3, 0].unwrap()));
+    }
+
+    #[test]
+    fn test_kron_sum() {
+        // The 2-D Laplacian on a 3 by 3 grid is the Kronecker sum of the 1-D
+        // Laplacian with itself.
+        let L = sparse![vec![(0, 0, 2), (0, 1, -1), (1, 0, -1), (1, 1, 2),
+                             (1, 2, -1), (2, 1, -1), (2, 2, 2)]; 3, 3];
+        let L2 = kron_sum(&L, &L);
+        assert_eq!(L2.dims(), (9, 9));
+        assert_eq!(L2.nnz(), 9 + 2*12);
+        assert_eq!(L2.get(4, 4).unwrap(), 4);
+        assert_eq!(L2.get(4, 1).unwrap(), -1);
+        assert_eq!(L2.get(4, 3).unwrap(), -1);
+        assert_eq!(L2.get(4, 0).unwrap(), 0);
+
+        let A = dense![1, 2; 3, 4].unwrap();
+        let B = dense![5].unwrap();
+        assert_eq!(kron_sum(&A, &B), dense![6, 2; 3, 9].unwrap());
+        let I: DenseMatrix<i32> = DenseMatrix::identity(2);
+        assert_eq!(kron_sum(&A, &A), kron(&A, &I) + kron(&I, &A));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_kron_sum_dims() {
+        let A: SparseMatrix<i32> = sparse!(2, 3);
+        let _panic = kron_sum(&A, &A);
+    }
+}
@@ -1,3 +1,65 @@
+pub use self::kron::{kron, kron_sum, Kronecker};
+pub use self::semiring::{Arithmetic, Boolean, MaxPlus, MaxTimes, MinPlus, Semiring, SparseSemiring};
+
+/// Implement the owned and mixed owned/borrowed forms of a binary operator
+/// in terms of the implementation for two references.
+macro_rules! forward_binop {
+    ($imp:ident, $method:ident, $lhs:ty, $rhs:ty, $out:ty) => (
+        impl<T> $imp<$rhs> for $lhs
+            where T: Clone + Copy + Num + Debug,
+        {
+            type Output = $out;
+
+            #[inline]
+            fn $method(self, other: $rhs) -> $out {
+                (&self).$method(&other)
+            }
+        }
+
+        impl<'b, T> $imp<&'b $rhs> for $lhs
+            where T: Clone + Copy + Num + Debug,
+        {
+            type Output = $out;
+
+            #[inline]
+            fn $method(self, other: &'b $rhs) -> $out {
+                (&self).$method(other)
+            }
+        }
+
+        impl<'a, T> $imp<$rhs> for &'a $lhs
+            where T: Clone + Copy + Num + Debug,
+        {
+            type Output = $out;
+
+            #[inline]
+            fn $method(self, other: $rhs) -> $out {
+                self.$method(&other)
+            }
+        }
+    )
+}
+
+/// Implement an owned right hand side of a compound assignment operator in
+/// terms of the implementation for a reference.
+macro_rules! forward_assign_op {
+    ($imp:ident, $method:ident, $lhs:ty, $rhs:ty) => (
+        impl<T> $imp<$rhs> for $lhs
+            where T: Clone + Copy + Num + Debug,
+        {
+            #[inline]
+            fn $method(&mut self, other: $rhs) {
+                self.$method(&other)
+            }
+        }
+    )
+}
+
 mod add;
+mod broadcast;
 mod eq;
+mod kron;
 mod mul;
+mod neg;
+mod scalar;
+mod semiring;
@@ -1,10 +1,13 @@
+use std::collections::HashMap;
 use std::fmt::Debug;
-use std::ops::Mul;
+use std::ops::{Mul, MulAssign};
 
-use ::{FromPrimitive, Num, ToPrimitive, Zero};
-use ::{DenseMatrix, IdentityMatrix, Matrix, SparseMatrix, ZeroMatrix};
+use ::{Num, Zero};
+use ::{DenseMatrix, DiagonalMatrix, Gf2Matrix, IdentityMatrix, Matrix, PermutationMatrix};
+use ::{SparseMatrix, TriangularMatrix, ZeroMatrix};
 use ::{DenseColumn, DenseRow, Vector};
 use ::{Vector};
+use super::{Arithmetic, Semiring, SparseSemiring};
 
 static MUL_DIM_ERROR: &str = "Cannot multiply matrices of given dimensions";
 
@@ -24,92 +27,410 @@ macro_rules! check_mul_dims {
     )
 }
 
-macro_rules! zero_mul_impl {
-    ($($t:ty)*) => ($(
-        impl<T: Clone + Num + FromPrimitive + ToPrimitive>
-            Mul<$t> for ZeroMatrix<T>
-            where T: Copy + Debug,
-        {
-            type Output = ZeroMatrix<T>;
+impl<T: Clone + Copy + Num> DenseMatrix<T> {
+    /// Multiply over the semiring `S`, so that
+    /// `C[i][j] = S::add` over `k` of `S::mul(A[i][k], B[k][j])`.
+    ///
+    /// Panics if the dimensions do not agree.
+    pub fn semiring_mul<S: Semiring<T>>(&self, rhs: &DenseMatrix<T>) -> DenseMatrix<T>
+        where T: Debug,
+    {
+        check_mul_dims!(self, rhs);
+        let (m, l, n) = (self.rows(), self.cols(), rhs.cols());
+        let (a, b) = (self.elements(), rhs.elements());
+        let mut c = vec![S::zero(); m*n];
+        for i in 0..m {
+            for k in 0..l {
+                let a_ik = a[i*l + k];
+                if a_ik == S::zero() { continue }
+                for j in 0..n {
+                    c[i*n + j] = S::add(c[i*n + j], S::mul(a_ik, b[k*n + j]));
+                }
+            }
+        }
+        DenseMatrix::from_vec(c, m, n, None).unwrap()
+    }
+}
 
-            #[inline]
-            fn mul(self, rhs: $t) -> ZeroMatrix<T> {
-                check_mul_dims!(self, rhs);
-                ZeroMatrix::new(self.rows(), rhs.cols())
+impl<T: Clone + Copy + Num> SparseMatrix<T> {
+    /// Multiply over a semiring `S` whose zero is `T::zero()`, visiting only
+    /// stored elements. Only the elements reached by at least one product
+    /// are stored in the result.
+    ///
+    /// Use `semiring_mul_dense` for a semiring like `MinPlus`, whose zero a
+    /// sparse matrix cannot leave unstored.
+    ///
+    /// Panics if the dimensions do not agree.
+    pub fn semiring_mul<S: SparseSemiring<T>>(&self, rhs: &SparseMatrix<T>) -> SparseMatrix<T>
+        where T: Debug,
+    {
+        check_mul_dims!(self, rhs);
+        let tuples = self.semiring_products::<S>(rhs).into_iter()
+            .map(|((i, j), c_ij)| (i, j, c_ij))
+            .collect();
+        SparseMatrix::from_tuple(tuples, self.rows(), rhs.cols())
+    }
+
+    /// Multiply over any semiring `S`, visiting only stored elements, into a
+    /// dense matrix.
+    ///
+    /// Elements that are not stored in either operand stand for `S::zero()`,
+    /// not `T::zero()`: for `MinPlus` a missing edge has infinite length.
+    /// Likewise the elements of the result that no product reaches are
+    /// `S::zero()`, so an unreachable pair reads back as infinitely far.
+    ///
+    /// Panics if the dimensions do not agree.
+    pub fn semiring_mul_dense<S: Semiring<T>>(&self, rhs: &SparseMatrix<T>) -> DenseMatrix<T>
+        where T: Debug,
+    {
+        check_mul_dims!(self, rhs);
+        let (m, n) = (self.rows(), rhs.cols());
+        let mut c = vec![S::zero(); m*n];
+        for ((i, j), c_ij) in self.semiring_products::<S>(rhs) {
+            c[i*n + j] = c_ij;
+        }
+        DenseMatrix::from_vec(c, m, n, None).unwrap()
+    }
+
+    /// Get the semiring sums of the products of stored elements, for the
+    /// elements of the product reached by at least one of them.
+    fn semiring_products<S: Semiring<T>>(&self, rhs: &SparseMatrix<T>) -> HashMap<(usize, usize), T> {
+        let mut rhs_rows: HashMap<usize, Vec<(usize, T)>> = HashMap::new();
+        for (k, j, b_kj) in rhs.triplets() {
+            rhs_rows.entry(k).or_default().push((j, b_kj));
+        }
+        let mut c: HashMap<(usize, usize), T> = HashMap::new();
+        for (i, k, a_ik) in self.triplets() {
+            if let Some(row) = rhs_rows.get(&k) {
+                for &(j, b_kj) in row {
+                    let p = S::mul(a_ik, b_kj);
+                    let c_ij = c.entry((i, j)).or_insert_with(S::zero);
+                    *c_ij = S::add(*c_ij, p);
+                }
             }
         }
+        c
+    }
+}
 
-        impl<T: Clone + Num + FromPrimitive + ToPrimitive>
-            Mul<ZeroMatrix<T>> for $t
-            where T: Copy + Debug,
+/// Implement `Mul` for a pair of matrix types given the body for two
+/// references.
+macro_rules! mul_impl {
+    ($lhs:ty, $rhs:ty, $out:ty, |$a:ident, $b:ident| $body:expr) => (
+        impl<'a, 'b, T> Mul<&'b $rhs> for &'a $lhs
+            where T: Clone + Copy + Num + Debug,
         {
-            type Output = ZeroMatrix<T>;
+            type Output = $out;
 
-            #[inline]
-            fn mul(self, rhs: ZeroMatrix<T>) -> ZeroMatrix<T> {
-                check_mul_dims!(self, rhs);
-                ZeroMatrix::new(self.rows(), rhs.cols())
+            fn mul(self, other: &'b $rhs) -> $out {
+                check_mul_dims!(self, other);
+                let ($a, $b) = (self, other);
+                $body
             }
         }
-    )*)
+
+        forward_binop!(Mul, mul, $lhs, $rhs, $out);
+    )
 }
 
-impl<T: Clone + Num + FromPrimitive + ToPrimitive> Mul for ZeroMatrix<T>
-    where T: Copy + Debug,
+/// Multiply a list of stored elements on the left of a matrix into a new
+/// dense matrix.
+fn triplets_mul<T, B>(a: Vec<(usize, usize, T)>, b: &B, m: usize) -> DenseMatrix<T>
+    where T: Clone + Copy + Num, B: Matrix<T>
 {
-    type Output = ZeroMatrix<T>;
+    let n = b.cols();
+    let mut c = vec![T::zero(); m*n];
+    for (i, k, a_ik) in a {
+        for j in 0..n {
+            c[i*n + j] = c[i*n + j] + a_ik * b.get(k, j).unwrap();
+        }
+    }
+    DenseMatrix::from_vec(c, m, n, None).unwrap()
+}
 
-    #[inline]
-    fn mul(self, rhs: ZeroMatrix<T>) -> ZeroMatrix<T> {
-        check_mul_dims!(self, rhs);
-        ZeroMatrix::new(self.rows(), rhs.cols())
+mul_impl! { DenseMatrix<T>, DenseMatrix<T>, DenseMatrix<T>, |a, b| a.semiring_mul::<Arithmetic>(b) }
+mul_impl! { SparseMatrix<T>, SparseMatrix<T>, SparseMatrix<T>, |a, b| a.semiring_mul::<Arithmetic>(b) }
+mul_impl! { SparseMatrix<T>, DenseMatrix<T>, DenseMatrix<T>, |a, b| triplets_mul(a.triplets(), b, a.rows()) }
+mul_impl! { DenseMatrix<T>, SparseMatrix<T>, DenseMatrix<T>, |a, b| {
+    // (AB)^T = B^T A^T, so walk the stored elements of B once.
+    let (m, n) = (a.rows(), b.cols());
+    let mut c = vec![T::zero(); m*n];
+    for (k, j, b_kj) in b.triplets() {
+        for i in 0..m {
+            c[i*n + j] = c[i*n + j] + a.get(i, k).unwrap() * b_kj;
+        }
     }
+    DenseMatrix::from_vec(c, m, n, None).unwrap()
+}}
+
+// Multiplying by the identity copies the other operand.
+mul_impl! { IdentityMatrix<T>, IdentityMatrix<T>, IdentityMatrix<T>, |a, _b| a.clone() }
+mul_impl! { IdentityMatrix<T>, DenseMatrix<T>, DenseMatrix<T>, |_a, b| b.clone() }
+mul_impl! { IdentityMatrix<T>, SparseMatrix<T>, SparseMatrix<T>, |_a, b| b.deep_clone() }
+mul_impl! { DenseMatrix<T>, IdentityMatrix<T>, DenseMatrix<T>, |a, _b| a.clone() }
+mul_impl! { SparseMatrix<T>, IdentityMatrix<T>, SparseMatrix<T>, |a, _b| a.deep_clone() }
+
+// Any product with a zero matrix is a zero matrix.
+mul_impl! { ZeroMatrix<T>, ZeroMatrix<T>, ZeroMatrix<T>, |a, b| ZeroMatrix::new(a.rows(), b.cols()) }
+mul_impl! { ZeroMatrix<T>, DenseMatrix<T>, ZeroMatrix<T>, |a, b| ZeroMatrix::new(a.rows(), b.cols()) }
+mul_impl! { ZeroMatrix<T>, SparseMatrix<T>, ZeroMatrix<T>, |a, b| ZeroMatrix::new(a.rows(), b.cols()) }
+mul_impl! { ZeroMatrix<T>, IdentityMatrix<T>, ZeroMatrix<T>, |a, b| ZeroMatrix::new(a.rows(), b.cols()) }
+mul_impl! { DenseMatrix<T>, ZeroMatrix<T>, ZeroMatrix<T>, |a, b| ZeroMatrix::new(a.rows(), b.cols()) }
+mul_impl! { SparseMatrix<T>, ZeroMatrix<T>, ZeroMatrix<T>, |a, b| ZeroMatrix::new(a.rows(), b.cols()) }
+mul_impl! { IdentityMatrix<T>, ZeroMatrix<T>, ZeroMatrix<T>, |a, b| ZeroMatrix::new(a.rows(), b.cols()) }
+
+/// Multiply two matrices of any kind into a new dense matrix.
+fn dense_mul<T, A, B>(a: &A, b: &B) -> DenseMatrix<T>
+    where T: Clone + Copy + Num, A: Matrix<T>, B: Matrix<T>
+{
+    let (m, l, n) = (a.rows(), a.cols(), b.cols());
+    let mut c = vec![T::zero(); m*n];
+    for i in 0..m {
+        for k in 0..l {
+            let a_ik = a.get(i, k).unwrap();
+            if a_ik.is_zero() { continue }
+            for j in 0..n {
+                c[i*n + j] = c[i*n + j] + a_ik * b.get(k, j).unwrap();
+            }
+        }
+    }
+    DenseMatrix::from_vec(c, m, n, None).unwrap()
 }
 
-zero_mul_impl! { DenseMatrix<T> SparseMatrix<T> }
+// A diagonal matrix scales rows from the left and columns from the right.
+mul_impl! { DiagonalMatrix<T>, DiagonalMatrix<T>, DiagonalMatrix<T>, |a, b| {
+    DiagonalMatrix::new(a.diag().into_iter().zip(b.diag()).map(|(x, y)| x * y).collect())
+}}
+mul_impl! { DiagonalMatrix<T>, DenseMatrix<T>, DenseMatrix<T>, |a, b| {
+    let c = b.clone();
+    for (i, d) in a.diag().into_iter().enumerate() {
+        for j in 0..c.cols() { c.set(i, j, d * c.get(i, j).unwrap()); }
+    }
+    c
+}}
+mul_impl! { DenseMatrix<T>, DiagonalMatrix<T>, DenseMatrix<T>, |a, b| {
+    let c = a.clone();
+    for (j, d) in b.diag().into_iter().enumerate() {
+        for i in 0..c.rows() { c.set(i, j, c.get(i, j).unwrap() * d); }
+    }
+    c
+}}
+mul_impl! { DiagonalMatrix<T>, SparseMatrix<T>, SparseMatrix<T>, |a, b| {
+    let d = a.diag();
+    let tuples = b.triplets().into_iter().map(|(i, j, x)| (i, j, d[i] * x)).collect();
+    SparseMatrix::from_tuple(tuples, b.rows(), b.cols())
+}}
+mul_impl! { SparseMatrix<T>, DiagonalMatrix<T>, SparseMatrix<T>, |a, b| {
+    let d = b.diag();
+    let tuples = a.triplets().into_iter().map(|(i, j, x)| (i, j, x * d[j])).collect();
+    SparseMatrix::from_tuple(tuples, a.rows(), a.cols())
+}}
+mul_impl! { DiagonalMatrix<T>, IdentityMatrix<T>, DiagonalMatrix<T>, |a, _b| a.clone() }
+mul_impl! { IdentityMatrix<T>, DiagonalMatrix<T>, DiagonalMatrix<T>, |_a, b| b.clone() }
+
+// A permutation matrix reorders rows from the left and columns from the
+// right, without any arithmetic.
+mul_impl! { PermutationMatrix<T>, PermutationMatrix<T>, PermutationMatrix<T>, |a, b| a.compose(b) }
+mul_impl! { PermutationMatrix<T>, DenseMatrix<T>, DenseMatrix<T>, |a, b| {
+    let n = b.cols();
+    let mut c = Vec::with_capacity(b.rows()*n);
+    for &p in a.perm() {
+        for j in 0..n { c.push(b.get(p, j).unwrap()); }
+    }
+    DenseMatrix::from_vec(c, b.rows(), n, None).unwrap()
+}}
+mul_impl! { DenseMatrix<T>, PermutationMatrix<T>, DenseMatrix<T>, |a, b| {
+    let inv = b.inverse();
+    let n = a.cols();
+    let mut c = Vec::with_capacity(a.rows()*n);
+    for i in 0..a.rows() {
+        for &k in inv.perm() { c.push(a.get(i, k).unwrap()); }
+    }
+    DenseMatrix::from_vec(c, a.rows(), n, None).unwrap()
+}}
+mul_impl! { PermutationMatrix<T>, SparseMatrix<T>, SparseMatrix<T>, |a, b| {
+    let inv = a.inverse();
+    let tuples = b.triplets().into_iter().map(|(k, j, x)| (inv.perm()[k], j, x)).collect();
+    SparseMatrix::from_tuple(tuples, b.rows(), b.cols())
+}}
+mul_impl! { SparseMatrix<T>, PermutationMatrix<T>, SparseMatrix<T>, |a, b| {
+    let tuples = a.triplets().into_iter().map(|(i, k, x)| (i, b.perm()[k], x)).collect();
+    SparseMatrix::from_tuple(tuples, a.rows(), a.cols())
+}}
+mul_impl! { PermutationMatrix<T>, IdentityMatrix<T>, PermutationMatrix<T>, |a, _b| a.clone() }
+mul_impl! { IdentityMatrix<T>, PermutationMatrix<T>, PermutationMatrix<T>, |_a, b| b.clone() }
+
+// Products of triangular matrices of the same kind stay triangular.
+// A product of an upper and a lower triangular matrix is full, so the
+// product is dense. Use `TriangularMatrix::mul_triangular` to keep the packed
+// storage.
+mul_impl! { TriangularMatrix<T>, TriangularMatrix<T>, DenseMatrix<T>, |a, b| {
+    if a.triangle() == b.triangle() { a.mul_triangular(b).to_dense() } else { dense_mul(a, b) }
+}}
+mul_impl! { TriangularMatrix<T>, DiagonalMatrix<T>, TriangularMatrix<T>, |a, b| {
+    let c = a.clone();
+    let d = b.diag();
+    for i in 0..c.rows() {
+        for (j, &d_j) in d.iter().enumerate() {
+            if c.in_triangle(i, j) { c.set(i, j, c.get(i, j).unwrap() * d_j); }
+        }
+    }
+    c
+}}
+mul_impl! { DiagonalMatrix<T>, TriangularMatrix<T>, TriangularMatrix<T>, |a, b| {
+    let c = b.clone();
+    for (i, d_i) in a.diag().into_iter().enumerate() {
+        for j in 0..c.cols() {
+            if c.in_triangle(i, j) { c.set(i, j, d_i * c.get(i, j).unwrap()); }
+        }
+    }
+    c
+}}
+mul_impl! { TriangularMatrix<T>, DenseMatrix<T>, DenseMatrix<T>, |a, b| dense_mul(a, b) }
+mul_impl! { DenseMatrix<T>, TriangularMatrix<T>, DenseMatrix<T>, |a, b| dense_mul(a, b) }
+mul_impl! { TriangularMatrix<T>, IdentityMatrix<T>, TriangularMatrix<T>, |a, _b| a.clone() }
+mul_impl! { IdentityMatrix<T>, TriangularMatrix<T>, TriangularMatrix<T>, |_a, b| b.clone() }
+
+mul_impl! { ZeroMatrix<T>, DiagonalMatrix<T>, ZeroMatrix<T>, |a, b| ZeroMatrix::new(a.rows(), b.cols()) }
+mul_impl! { ZeroMatrix<T>, PermutationMatrix<T>, ZeroMatrix<T>, |a, b| ZeroMatrix::new(a.rows(), b.cols()) }
+mul_impl! { ZeroMatrix<T>, TriangularMatrix<T>, ZeroMatrix<T>, |a, b| ZeroMatrix::new(a.rows(), b.cols()) }
+mul_impl! { DiagonalMatrix<T>, ZeroMatrix<T>, ZeroMatrix<T>, |a, b| ZeroMatrix::new(a.rows(), b.cols()) }
+mul_impl! { PermutationMatrix<T>, ZeroMatrix<T>, ZeroMatrix<T>, |a, b| ZeroMatrix::new(a.rows(), b.cols()) }
+mul_impl! { TriangularMatrix<T>, ZeroMatrix<T>, ZeroMatrix<T>, |a, b| ZeroMatrix::new(a.rows(), b.cols()) }
+
+impl<T> MulAssign<T> for DenseMatrix<T>
+    where T: Clone + Copy + Num,
+{
+    fn mul_assign(&mut self, scalar: T) {
+        for a in self.mat.iter() {
+            a.set(a.get() * scalar);
+        }
+    }
+}
 
-macro_rules! ident_mul_impl {
-    ($($t:ty)*) => ($(
-        impl<T: Clone + Num + FromPrimitive + ToPrimitive>
-            Mul<$t> for IdentityMatrix<T>
-            where T: Copy + Debug,
-        {
-            type Output = $t;
+impl<T> MulAssign<T> for SparseMatrix<T>
+    where T: Clone + Copy + Num,
+{
+    fn mul_assign(&mut self, scalar: T) {
+        // Drop the products that are zero, as `*` does.
+        self.mat.borrow_mut().retain(|_, a| {
+            a.set(a.get() * scalar);
+            !a.get().is_zero()
+        });
+    }
+}
 
-            #[inline]
-            fn mul(self, rhs: $t) -> $t {
-                check_mul_dims!(self, rhs);
-                rhs
-            }
+impl<T> MulAssign<T> for DiagonalMatrix<T>
+    where T: Clone + Copy + Num,
+{
+    fn mul_assign(&mut self, scalar: T) {
+        for a in self.diag.iter() {
+            a.set(a.get() * scalar);
         }
+    }
+}
 
-        impl<T: Clone + Num + FromPrimitive + ToPrimitive>
-            Mul<IdentityMatrix<T>> for $t
-            where T: Copy + Debug,
-        {
-            type Output = $t;
+impl<T> MulAssign<T> for TriangularMatrix<T>
+    where T: Clone + Copy + Num,
+{
+    fn mul_assign(&mut self, scalar: T) {
+        for a in self.mat.iter() {
+            a.set(a.get() * scalar);
+        }
+    }
+}
+
+impl<T> MulAssign<T> for ZeroMatrix<T>
+    where T: Clone + Copy + Num,
+{
+    #[inline]
+    fn mul_assign(&mut self, _scalar: T) {}
+}
 
-            #[inline]
-            fn mul(self, rhs: IdentityMatrix<T>) -> $t {
-                check_mul_dims!(self, rhs);
-                self
+/// Implement `MulAssign` for a matrix right hand side. The product must have
+/// the dimensions of the left operand, so the right operand must be square.
+macro_rules! mul_assign_impl {
+    ($lhs:ty, $rhs:ty, |$a:ident, $b:ident| $body:expr) => (
+        impl<'b, T> MulAssign<&'b $rhs> for $lhs
+            where T: Clone + Copy + Num + Debug,
+        {
+            fn mul_assign(&mut self, other: &'b $rhs) {
+                check_mul_dims!(self, other);
+                if !other.is_square() {
+                    panic!("{}: lhs={:?} rhs={:?}", MUL_DIM_ERROR, self, other)
+                }
+                let ($a, $b) = (&*self, other);
+                $body
             }
         }
-    )*)
+
+        forward_assign_op!(MulAssign, mul_assign, $lhs, $rhs);
+    )
 }
 
-impl<T: Clone + Num + FromPrimitive + ToPrimitive> Mul for IdentityMatrix<T>
-    where T: Copy,
+/// Overwrite a dense matrix with its product with `b`, one row at a time, so
+/// that only a single row of scratch space is needed.
+fn dense_mul_rows<T, B>(a: &DenseMatrix<T>, b: &B)
+    where T: Clone + Copy + Num, B: Matrix<T>
 {
-    type Output = IdentityMatrix<T>;
+    let n = a.cols();
+    let mut row = vec![T::zero(); n];
+    for i in 0..a.rows() {
+        for (k, r) in row.iter_mut().enumerate() {
+            *r = a.get(i, k).unwrap();
+        }
+        for j in 0..n {
+            let mut acc = T::zero();
+            for (k, &r) in row.iter().enumerate() {
+                acc = acc + r * b.get(k, j).unwrap();
+            }
+            a.set(i, j, acc);
+        }
+    }
+}
 
-    #[inline]
-    fn mul(self, _rhs: IdentityMatrix<T>) -> IdentityMatrix<T> {
-        self
+/// Replace the stored elements of a sparse matrix with the nonzero elements
+/// of `c`.
+fn sparse_replace<T, C>(a: &SparseMatrix<T>, c: &C)
+    where T: Clone + Copy + Num, C: Matrix<T>
+{
+    a.mat.borrow_mut().clear();
+    for i in 0..c.rows() {
+        for j in 0..c.cols() {
+            let c_ij = c.get(i, j).unwrap();
+            if !c_ij.is_zero() { a.set(i, j, c_ij); }
+        }
     }
 }
 
-ident_mul_impl! { DenseMatrix<T> SparseMatrix<T> ZeroMatrix<T> }
+mul_assign_impl! { DenseMatrix<T>, DenseMatrix<T>, |a, b| dense_mul_rows(a, b) }
+mul_assign_impl! { DenseMatrix<T>, SparseMatrix<T>, |a, b| {
+    let n = a.cols();
+    let b_triplets = b.triplets();
+    let mut row = vec![T::zero(); n];
+    for i in 0..a.rows() {
+        for r in row.iter_mut() { *r = T::zero(); }
+        for &(k, j, b_kj) in &b_triplets {
+            row[j] = row[j] + a.get(i, k).unwrap() * b_kj;
+        }
+        for (j, &r) in row.iter().enumerate() {
+            a.set(i, j, r);
+        }
+    }
+}}
+mul_assign_impl! { DenseMatrix<T>, IdentityMatrix<T>, |_a, _b| {} }
+mul_assign_impl! { DenseMatrix<T>, ZeroMatrix<T>, |a, _b| {
+    for x in a.mat.iter() { x.set(T::zero()); }
+}}
+mul_assign_impl! { SparseMatrix<T>, DenseMatrix<T>, |a, b| sparse_replace(a, &(a * b)) }
+mul_assign_impl! { SparseMatrix<T>, SparseMatrix<T>, |a, b| sparse_replace(a, &(a * b)) }
+mul_assign_impl! { SparseMatrix<T>, IdentityMatrix<T>, |_a, _b| {} }
+mul_assign_impl! { SparseMatrix<T>, ZeroMatrix<T>, |a, _b| a.mat.borrow_mut().clear() }
+mul_assign_impl! { IdentityMatrix<T>, IdentityMatrix<T>, |_a, _b| {} }
+mul_assign_impl! { ZeroMatrix<T>, DenseMatrix<T>, |_a, _b| {} }
+mul_assign_impl! { ZeroMatrix<T>, SparseMatrix<T>, |_a, _b| {} }
+mul_assign_impl! { ZeroMatrix<T>, IdentityMatrix<T>, |_a, _b| {} }
+mul_assign_impl! { ZeroMatrix<T>, ZeroMatrix<T>, |_a, _b| {} }
 
 /// The inner product of two dense vectors.
 impl<T: Clone + Num> Mul<DenseColumn<T>> for DenseRow<T>
@@ -152,6 +473,15 @@ impl<'a, T: 'a + Clone + Num> Mul<DenseRow<T>> for DenseColumn<T>
     }
 }
 
+impl Mul for Gf2Matrix {
+    type Output = Gf2Matrix;
+
+    #[inline]
+    fn mul(self, rhs: Gf2Matrix) -> Gf2Matrix {
+        self.mul_m4ri(&rhs)
+    }
+}
+
 impl<T: Clone + Num> Mul for Vector<T>
     where T: Copy,
 {
@@ -176,6 +506,7 @@ mod tests {
     use ::{IdentityMatrix, Matrix, Vector, ZeroMatrix};
     use ::{Matrix, ZeroMatrix};
     use ::{Vector};
+    use ::{Zero};
 
     #[test]
     fn test_good_zero_zero_mul() {
@@ -235,4 +566,182 @@ mod tests {
                        4, 8, 12].unwrap();
         assert_eq!(u*v.transpose(), A);
     }
+
+    #[test]
+    fn test_dense_mul() {
+        let A = dense![1, 2, 3;
+                       4, 5, 6].unwrap();
+        let B = dense![1, 0;
+                       0, 1;
+                       1, 1].unwrap();
+        let C = dense![4, 5;
+                       10, 11].unwrap();
+        assert_eq!(A.clone()*B.clone(), C);
+        assert_eq!(A.transpose()*C.clone(), dense![44, 49;
+                                                   58, 65;
+                                                   72, 81].unwrap());
+    }
+
+    #[test]
+    fn test_sparse_mul() {
+        let A = sparse![vec![(0, 1, 2), (1, 0, 3), (1, 2, 1)]; 2, 3];
+        let B = sparse![vec![(0, 0, 1), (1, 1, 4), (2, 1, 5)]; 3, 2];
+        let C = A*B;
+        assert_eq!(C.get(0, 1).unwrap(), 8);
+        assert_eq!(C.get(1, 0).unwrap(), 3);
+        assert_eq!(C.get(1, 1).unwrap(), 5);
+        assert_eq!(C.triplets().len(), 3);
+    }
+
+    #[test]
+    fn test_ref_mul() {
+        let A = dense![1, 2; 3, 4].unwrap();
+        let S = sparse![vec![(0, 1, 2), (1, 0, 1)]; 2, 2];
+        let I: IdentityMatrix<i32> = eye!(2);
+        let Z = zeros!(2, 3);
+        assert_eq!(&A * &A, dense![7, 10; 15, 22].unwrap());
+        assert_eq!(&A * &S, dense![2, 2; 4, 6].unwrap());
+        assert_eq!(S.clone() * &A, dense![6, 8; 1, 2].unwrap());
+        assert_eq!(&S * S.clone(), sparse![vec![(0, 0, 2), (1, 1, 2)]; 2, 2]);
+        assert_eq!(&I * &A, A);
+        assert_eq!(&A * &Z, zeros!(2, 3));
+        assert_eq!(&I * &I, I);
+    }
+
+    #[test]
+    fn test_mul_assign() {
+        let mut A = dense![1, 2; 3, 4].unwrap();
+        let ptr = A.mat.as_ptr();
+        A *= 2;
+        A *= &dense![0, 1; 1, 0].unwrap();
+        A *= sparse![vec![(0, 0, 1), (1, 1, 3)]; 2, 2];
+        A *= &IdentityMatrix::new(2);
+        assert_eq!(A, dense![4, 6; 8, 18].unwrap());
+        assert_eq!(A.mat.as_ptr(), ptr);
+
+        let mut S = sparse![vec![(0, 1, 2), (1, 0, 1)]; 2, 2];
+        S *= 3;
+        S *= &S.deep_clone();
+        assert_eq!(S, sparse![vec![(0, 0, 18), (1, 1, 18)]; 2, 2]);
+        S *= ZeroMatrix::new(2, 2);
+        assert_eq!(S, zeros!(2, 2));
+
+        let mut S = sparse![vec![(0, 1, 2), (1, 0, 1)]; 2, 2];
+        S *= i32::zero();
+        assert_eq!(S.nnz(), 0);
+        assert_eq!(S.nnz(), (&sparse![vec![(0, 1, 2), (1, 0, 1)]; 2, 2] * i32::zero()).nnz());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bad_mul_assign() {
+        let mut A = dense![1, 2, 3; 4, 5, 6].unwrap();
+        A *= &dense![1, 2; 3, 4; 5, 6].unwrap();
+    }
+
+    #[test]
+    fn test_structured_mul() {
+        use ::{DiagonalMatrix, PermutationMatrix, Triangle, TriangularMatrix};
+
+        let A = dense![1, 2; 3, 4].unwrap();
+        let D = DiagonalMatrix::new(vec![2, 10]);
+        assert_eq!(&D * &A, dense![2, 4; 30, 40].unwrap());
+        assert_eq!(&A * &D, dense![2, 20; 6, 40].unwrap());
+        assert_eq!(&D * &D, DiagonalMatrix::new(vec![4, 100]));
+        assert_eq!(&D * &sparse![vec![(1, 0, 1)]; 2, 2], sparse![vec![(1, 0, 10)]; 2, 2]);
+
+        let P = PermutationMatrix::new(vec![1, 0]).unwrap();
+        assert_eq!(&P * &A, dense![3, 4; 1, 2].unwrap());
+        assert_eq!(&A * &P, dense![2, 1; 4, 3].unwrap());
+        assert_eq!(&P * &P, PermutationMatrix::identity(2));
+        let S = sparse![vec![(0, 1, 5)]; 2, 2];
+        assert_eq!(&P * &S, &P.to_dense() * &S.clone());
+        assert_eq!(&S * &P, &S * &P.to_dense());
+
+        let U = TriangularMatrix::from_dense(&A, Triangle::Upper).unwrap();
+        let UU = U.mul_triangular(&U);
+        assert_eq!(UU.triangle(), Triangle::Upper);
+        assert_eq!(UU, dense![1, 10; 0, 16].unwrap());
+        assert_eq!(&U * &U, dense![1, 10; 0, 16].unwrap());
+        assert_eq!(&U * &D, dense![2, 20; 0, 40].unwrap());
+        assert_eq!(&A * &U, dense![1, 10; 3, 22].unwrap());
+        assert_eq!(&U * 2, dense![2, 4; 0, 8].unwrap());
+    }
+
+    #[test]
+    fn test_triangular_mul_opposite() {
+        use ::{Triangle, TriangularMatrix};
+
+        let A = dense![1, 2, 3; 4, 5, 6; 7, 8, 10].unwrap();
+        let U = TriangularMatrix::from_dense(&A, Triangle::Upper).unwrap();
+        let L = TriangularMatrix::from_dense(&A, Triangle::Lower).unwrap();
+        assert_eq!(&U * &L, &U.to_dense() * &L.to_dense());
+        assert_eq!(&L * &U, &L.to_dense() * &U.to_dense());
+        assert_eq!(&L * &L, &L.to_dense() * &L.to_dense());
+    }
+
+    #[test]
+    fn test_min_plus_shortest_paths() {
+        use ::{DenseMatrix, MinPlus};
+
+        let inf = f64::INFINITY;
+        // Edge weights of a directed graph, with zero on the diagonal.
+        let W = DenseMatrix::new(&[vec![0.0, 3.0, inf, 7.0],
+                                       vec![8.0, 0.0, 2.0, inf],
+                                       vec![5.0, inf, 0.0, 1.0],
+                                       vec![2.0, inf, inf, 0.0]]).unwrap();
+        // Paths of up to 4 edges after two squarings.
+        let W2 = W.semiring_mul::<MinPlus>(&W);
+        let D = W2.semiring_mul::<MinPlus>(&W2);
+        let expected = DenseMatrix::new(&[vec![0.0, 3.0, 5.0, 6.0],
+                                              vec![5.0, 0.0, 2.0, 3.0],
+                                              vec![3.0, 6.0, 0.0, 1.0],
+                                              vec![2.0, 5.0, 7.0, 0.0]]).unwrap();
+        assert_eq!(D, expected);
+    }
+
+    #[test]
+    fn test_max_semirings() {
+        use ::{DenseMatrix, MaxPlus, MaxTimes};
+
+        let A = DenseMatrix::new(&[vec![0.5, 0.2], vec![0.1, 0.9]]).unwrap();
+        let P = A.semiring_mul::<MaxTimes>(&A);
+        assert_eq!(P.get(0, 1).unwrap(), (0.5f64 * 0.2).max(0.2 * 0.9));
+        assert_eq!(P.get(1, 1).unwrap(), 0.9 * 0.9);
+
+        let ninf = f64::NEG_INFINITY;
+        let L = DenseMatrix::new(&[vec![ninf, 1.0, 4.0],
+                                       vec![ninf, ninf, 2.0],
+                                       vec![ninf, ninf, ninf]]).unwrap();
+        assert_eq!(L.semiring_mul::<MaxPlus>(&L).get(0, 2).unwrap(), 3.0);
+    }
+
+    #[test]
+    fn test_boolean_reachability() {
+        use ::Boolean;
+
+        // A directed path 0 -> 1 -> 2 -> 3 with self loops.
+        let A = sparse![vec![(0, 0, 1u8), (1, 1, 1), (2, 2, 1), (3, 3, 1),
+                             (0, 1, 1), (1, 2, 1), (2, 3, 1)]; 4, 4];
+        let A2 = A.semiring_mul::<Boolean>(&A);
+        let R = A2.semiring_mul::<Boolean>(&A2);
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(R.get(i, j).unwrap(), if i <= j { 1 } else { 0 });
+            }
+        }
+    }
+
+    #[test]
+    fn test_sparse_min_plus_unreachable() {
+        use ::MinPlus;
+
+        // A directed path 0 -> 1 -> 2 with unit weights.
+        let W = sparse![vec![(0, 1, 1.0), (1, 2, 1.0)]; 3, 3];
+        let W2 = W.semiring_mul_dense::<MinPlus>(&W);
+        assert_eq!(W2.get(0, 2).unwrap(), 2.0);
+        assert_eq!(W2.get(0, 0).unwrap(), f64::INFINITY);
+        assert_eq!(W2.get(2, 0).unwrap(), f64::INFINITY);
+        assert_eq!(W2.get(0, 1).unwrap(), f64::INFINITY);
+    }
 }
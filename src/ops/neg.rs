@@ -0,0 +1,116 @@
+use std::fmt::Debug;
+use std::ops::Neg;
+
+use ::{Num};
+use ::{DenseMatrix, DiagonalMatrix, IdentityMatrix, Matrix, SparseMatrix};
+use ::{TriangularMatrix, ZeroMatrix};
+
+impl<T> Neg for &DenseMatrix<T>
+    where T: Clone + Copy + Num + Debug,
+{
+    type Output = DenseMatrix<T>;
+
+    fn neg(self) -> DenseMatrix<T> {
+        let mat = self.elements().into_iter().map(|x| T::zero() - x).collect();
+        DenseMatrix::from_vec(mat, self.rows(), self.cols(), None).unwrap()
+    }
+}
+
+impl<T> Neg for &SparseMatrix<T>
+    where T: Clone + Copy + Num + Debug,
+{
+    type Output = SparseMatrix<T>;
+
+    fn neg(self) -> SparseMatrix<T> {
+        let tuples = self.triplets().into_iter()
+            .map(|(i, j, x)| (i, j, T::zero() - x))
+            .collect();
+        SparseMatrix::from_tuple(tuples, self.rows(), self.cols())
+    }
+}
+
+/// The negated identity is no longer an identity, so it is stored as a
+/// diagonal.
+impl<T> Neg for &IdentityMatrix<T>
+    where T: Clone + Copy + Num + Debug,
+{
+    type Output = DiagonalMatrix<T>;
+
+    fn neg(self) -> DiagonalMatrix<T> {
+        DiagonalMatrix::from_scalar(T::zero() - T::one(), self.rows())
+    }
+}
+
+impl<T> Neg for &DiagonalMatrix<T>
+    where T: Clone + Copy + Num + Debug,
+{
+    type Output = DiagonalMatrix<T>;
+
+    fn neg(self) -> DiagonalMatrix<T> {
+        DiagonalMatrix::new(self.diag().into_iter().map(|d| T::zero() - d).collect())
+    }
+}
+
+impl<T> Neg for &TriangularMatrix<T>
+    where T: Clone + Copy + Num + Debug,
+{
+    type Output = TriangularMatrix<T>;
+
+    fn neg(self) -> TriangularMatrix<T> {
+        self.map(|a| T::zero() - a)
+    }
+}
+
+impl<T> Neg for &ZeroMatrix<T>
+    where T: Clone + Copy + Num + Debug,
+{
+    type Output = ZeroMatrix<T>;
+
+    #[inline]
+    fn neg(self) -> ZeroMatrix<T> {
+        ZeroMatrix::new(self.rows(), self.cols())
+    }
+}
+
+macro_rules! forward_neg {
+    ($($t:ty => $out:ty),*) => ($(
+        impl<T> Neg for $t
+            where T: Clone + Copy + Num + Debug,
+        {
+            type Output = $out;
+
+            #[inline]
+            fn neg(self) -> $out {
+                -&self
+            }
+        }
+    )*)
+}
+
+forward_neg! {
+    DenseMatrix<T> => DenseMatrix<T>,
+    SparseMatrix<T> => SparseMatrix<T>,
+    IdentityMatrix<T> => DiagonalMatrix<T>,
+    DiagonalMatrix<T> => DiagonalMatrix<T>,
+    TriangularMatrix<T> => TriangularMatrix<T>,
+    ZeroMatrix<T> => ZeroMatrix<T>
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+    use ::{DiagonalMatrix, IdentityMatrix, ZeroMatrix};
+
+    #[test]
+    fn test_neg() {
+        let A = dense![1, -2; 0, 4].unwrap();
+        assert_eq!(-&A, dense![-1, 2; 0, -4].unwrap());
+        assert_eq!(-A, dense![-1, 2; 0, -4].unwrap());
+        let S = sparse![vec![(1, 0, 3)]; 2, 2];
+        assert_eq!(-&S, sparse![vec![(1, 0, -3)]; 2, 2]);
+        let I: IdentityMatrix<i32> = eye!(2);
+        assert_eq!(-I, DiagonalMatrix::new(vec![-1, -1]));
+        let Z: ZeroMatrix<i32> = zeros!(2, 2);
+        assert_eq!(-&Z, Z);
+    }
+}
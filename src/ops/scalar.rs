@@ -0,0 +1,259 @@
+use std::fmt::Debug;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
+use ::{Num};
+use ::{DenseMatrix, DiagonalMatrix, IdentityMatrix, Matrix, PermutationMatrix};
+use ::{SparseMatrix, TriangularMatrix, ZeroMatrix};
+use ::{DenseColumn, DenseRow, Vector};
+
+/// Apply a scalar operation to every element of a matrix into a new dense
+/// matrix.
+fn dense_map<T, A, F>(a: &A, f: F) -> DenseMatrix<T>
+    where T: Clone + Copy + Num, A: Matrix<T>, F: Fn(T) -> T
+{
+    let mut mat = Vec::with_capacity(a.rows()*a.cols());
+    for i in 0..a.rows() {
+        for j in 0..a.cols() {
+            mat.push(f(a.get(i, j).unwrap()));
+        }
+    }
+    DenseMatrix::from_vec(mat, a.rows(), a.cols(), None).unwrap()
+}
+
+/// Apply a scalar operation that maps zero to zero to the stored elements of
+/// a sparse matrix, dropping the elements it sends to zero.
+fn sparse_map<T, F>(a: Vec<(usize, usize, T)>, m: usize, n: usize, f: F) -> SparseMatrix<T>
+    where T: Clone + Copy + Num, F: Fn(T) -> T
+{
+    let tuples = a.into_iter()
+        .map(|(i, j, x)| (i, j, f(x)))
+        .filter(|&(_, _, x)| !x.is_zero())
+        .collect();
+    SparseMatrix::from_tuple(tuples, m, n)
+}
+
+/// Implement a binary operator between a matrix type and a scalar, given the
+/// body for a reference and the scalar operation as a closure.
+macro_rules! scalar_impl {
+    ($imp:ident, $method:ident, $op:tt, $lhs:ty, $out:ty, |$a:ident, $f:ident| $body:expr) => (
+        impl<'a, T> $imp<T> for &'a $lhs
+            where T: Clone + Copy + Num + Debug,
+        {
+            type Output = $out;
+
+            fn $method(self, scalar: T) -> $out {
+                let $a = self;
+                let $f = |x: T| x $op scalar;
+                $body
+            }
+        }
+
+        impl<T> $imp<T> for $lhs
+            where T: Clone + Copy + Num + Debug,
+        {
+            type Output = $out;
+
+            #[inline]
+            fn $method(self, scalar: T) -> $out {
+                (&self).$method(scalar)
+            }
+        }
+    )
+}
+
+scalar_impl! { Mul, mul, *, DenseMatrix<T>, DenseMatrix<T>, |a, f| dense_map(a, f) }
+scalar_impl! { Div, div, /, DenseMatrix<T>, DenseMatrix<T>, |a, f| dense_map(a, f) }
+scalar_impl! { Add, add, +, DenseMatrix<T>, DenseMatrix<T>, |a, f| dense_map(a, f) }
+scalar_impl! { Sub, sub, -, DenseMatrix<T>, DenseMatrix<T>, |a, f| dense_map(a, f) }
+
+// Scaling keeps a sparse matrix sparse. Adding a scalar fills it in.
+scalar_impl! { Mul, mul, *, SparseMatrix<T>, SparseMatrix<T>, |a, f| {
+    sparse_map(a.triplets(), a.rows(), a.cols(), f)
+}}
+scalar_impl! { Div, div, /, SparseMatrix<T>, SparseMatrix<T>, |a, f| {
+    sparse_map(a.triplets(), a.rows(), a.cols(), f)
+}}
+scalar_impl! { Add, add, +, SparseMatrix<T>, DenseMatrix<T>, |a, f| dense_map(a, f) }
+scalar_impl! { Sub, sub, -, SparseMatrix<T>, DenseMatrix<T>, |a, f| dense_map(a, f) }
+
+// A scaled identity is a diagonal matrix.
+scalar_impl! { Mul, mul, *, IdentityMatrix<T>, DiagonalMatrix<T>, |a, f| {
+    DiagonalMatrix::from_scalar(f(T::one()), a.rows())
+}}
+scalar_impl! { Div, div, /, IdentityMatrix<T>, DiagonalMatrix<T>, |a, f| {
+    DiagonalMatrix::from_scalar(f(T::one()), a.rows())
+}}
+scalar_impl! { Add, add, +, IdentityMatrix<T>, DenseMatrix<T>, |a, f| dense_map(a, f) }
+scalar_impl! { Sub, sub, -, IdentityMatrix<T>, DenseMatrix<T>, |a, f| dense_map(a, f) }
+
+scalar_impl! { Mul, mul, *, DiagonalMatrix<T>, DiagonalMatrix<T>, |a, f| {
+    DiagonalMatrix::new(a.diag().into_iter().map(f).collect())
+}}
+scalar_impl! { Div, div, /, DiagonalMatrix<T>, DiagonalMatrix<T>, |a, f| {
+    DiagonalMatrix::new(a.diag().into_iter().map(f).collect())
+}}
+scalar_impl! { Add, add, +, DiagonalMatrix<T>, DenseMatrix<T>, |a, f| dense_map(a, f) }
+scalar_impl! { Sub, sub, -, DiagonalMatrix<T>, DenseMatrix<T>, |a, f| dense_map(a, f) }
+
+scalar_impl! { Mul, mul, *, TriangularMatrix<T>, TriangularMatrix<T>, |a, f| a.map(f) }
+scalar_impl! { Div, div, /, TriangularMatrix<T>, TriangularMatrix<T>, |a, f| a.map(f) }
+scalar_impl! { Add, add, +, TriangularMatrix<T>, DenseMatrix<T>, |a, f| dense_map(a, f) }
+scalar_impl! { Sub, sub, -, TriangularMatrix<T>, DenseMatrix<T>, |a, f| dense_map(a, f) }
+
+scalar_impl! { Mul, mul, *, PermutationMatrix<T>, SparseMatrix<T>, |a, f| {
+    let tuples = a.perm().iter().enumerate().map(|(i, &p)| (i, p, T::one())).collect();
+    sparse_map(tuples, a.rows(), a.cols(), f)
+}}
+scalar_impl! { Div, div, /, PermutationMatrix<T>, SparseMatrix<T>, |a, f| {
+    let tuples = a.perm().iter().enumerate().map(|(i, &p)| (i, p, T::one())).collect();
+    sparse_map(tuples, a.rows(), a.cols(), f)
+}}
+scalar_impl! { Add, add, +, PermutationMatrix<T>, DenseMatrix<T>, |a, f| dense_map(a, f) }
+scalar_impl! { Sub, sub, -, PermutationMatrix<T>, DenseMatrix<T>, |a, f| dense_map(a, f) }
+
+scalar_impl! { Mul, mul, *, DenseRow<T>, DenseRow<T>, |a, f| a.map(f) }
+scalar_impl! { Div, div, /, DenseRow<T>, DenseRow<T>, |a, f| a.map(f) }
+scalar_impl! { Add, add, +, DenseRow<T>, DenseRow<T>, |a, f| a.map(f) }
+scalar_impl! { Sub, sub, -, DenseRow<T>, DenseRow<T>, |a, f| a.map(f) }
+scalar_impl! { Mul, mul, *, DenseColumn<T>, DenseColumn<T>, |a, f| a.map(f) }
+scalar_impl! { Div, div, /, DenseColumn<T>, DenseColumn<T>, |a, f| a.map(f) }
+scalar_impl! { Add, add, +, DenseColumn<T>, DenseColumn<T>, |a, f| a.map(f) }
+scalar_impl! { Sub, sub, -, DenseColumn<T>, DenseColumn<T>, |a, f| a.map(f) }
+
+scalar_impl! { Mul, mul, *, ZeroMatrix<T>, ZeroMatrix<T>, |a, _f| ZeroMatrix::new(a.rows(), a.cols()) }
+scalar_impl! { Div, div, /, ZeroMatrix<T>, ZeroMatrix<T>, |a, _f| ZeroMatrix::new(a.rows(), a.cols()) }
+scalar_impl! { Add, add, +, ZeroMatrix<T>, DenseMatrix<T>, |a, f| dense_map(a, f) }
+scalar_impl! { Sub, sub, -, ZeroMatrix<T>, DenseMatrix<T>, |a, f| dense_map(a, f) }
+
+/// Implement a compound assignment with a scalar that updates every stored
+/// element in place, or every element of a vector through `Vector::set`.
+macro_rules! scalar_assign_impl {
+    (@vector $imp:ident, $method:ident, $op:tt, $($t:ty),*) => ($(
+        impl<T> $imp<T> for $t
+            where T: Clone + Copy + Num,
+        {
+            fn $method(&mut self, scalar: T) {
+                for i in 0..self.len() {
+                    self.set(i, self.get(i).unwrap() $op scalar);
+                }
+            }
+        }
+    )*);
+    ($imp:ident, $method:ident, $op:tt, $($t:ty),*) => ($(
+        impl<T> $imp<T> for $t
+            where T: Clone + Copy + Num,
+        {
+            fn $method(&mut self, scalar: T) {
+                for a in self.mat.iter() {
+                    a.set(a.get() $op scalar);
+                }
+            }
+        }
+    )*)
+}
+
+scalar_assign_impl! { DivAssign, div_assign, /, DenseMatrix<T>, TriangularMatrix<T> }
+scalar_assign_impl! { AddAssign, add_assign, +, DenseMatrix<T> }
+scalar_assign_impl! { SubAssign, sub_assign, -, DenseMatrix<T> }
+scalar_assign_impl! { @vector MulAssign, mul_assign, *, DenseRow<T>, DenseColumn<T> }
+scalar_assign_impl! { @vector DivAssign, div_assign, /, DenseRow<T>, DenseColumn<T> }
+scalar_assign_impl! { @vector AddAssign, add_assign, +, DenseRow<T>, DenseColumn<T> }
+scalar_assign_impl! { @vector SubAssign, sub_assign, -, DenseRow<T>, DenseColumn<T> }
+
+impl<T> DivAssign<T> for SparseMatrix<T>
+    where T: Clone + Copy + Num,
+{
+    fn div_assign(&mut self, scalar: T) {
+        for a in self.mat.borrow().values() {
+            a.set(a.get() / scalar);
+        }
+    }
+}
+
+impl<T> DivAssign<T> for DiagonalMatrix<T>
+    where T: Clone + Copy + Num,
+{
+    fn div_assign(&mut self, scalar: T) {
+        for a in self.diag.iter() {
+            a.set(a.get() / scalar);
+        }
+    }
+}
+
+impl<T> DivAssign<T> for ZeroMatrix<T>
+    where T: Clone + Copy + Num,
+{
+    #[inline]
+    fn div_assign(&mut self, _scalar: T) {}
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+    use ::{DenseMatrix, DiagonalMatrix, IdentityMatrix, Zero, ZeroMatrix};
+
+    #[test]
+    fn test_dense_scalar() {
+        let A = dense![1.0, 2.0; 3.0, 4.0].unwrap();
+        assert_eq!(&A * 2.0, dense![2.0, 4.0; 6.0, 8.0].unwrap());
+        assert_eq!(&A / 2.0, dense![0.5, 1.0; 1.5, 2.0].unwrap());
+        assert_eq!(&A + 1.0, dense![2.0, 3.0; 4.0, 5.0].unwrap());
+        assert_eq!(A.clone() - 1.0, dense![0.0, 1.0; 2.0, 3.0].unwrap());
+
+        let mut B = A.clone();
+        B *= 3.0;
+        B /= 2.0;
+        B -= 0.5;
+        B += 1.0;
+        assert_eq!(B, dense![2.0, 3.5; 5.0, 6.5].unwrap());
+    }
+
+    #[test]
+    fn test_sparse_scalar() {
+        let S = sparse![vec![(0, 1, 4), (2, 0, 6)]; 3, 2];
+        let T = &S * 3;
+        assert_eq!(T, sparse![vec![(0, 1, 12), (2, 0, 18)]; 3, 2]);
+        assert_eq!(T.triplets().len(), 2);
+        assert_eq!(&S / 2, sparse![vec![(0, 1, 2), (2, 0, 3)]; 3, 2]);
+
+        // Scaling by zero stores nothing.
+        assert_eq!((&S * i32::zero()).nnz(), 0);
+        assert_eq!((&S / 5).triplets(), vec![(2, 0, 1)]);
+        assert_eq!(S + 1, dense![1, 5; 1, 1; 7, 1].unwrap());
+    }
+
+    #[test]
+    fn test_vector_scalar() {
+        use ::{DenseColumn, DenseRow, Vector};
+
+        fn to_vec<V: Vector<f64>>(v: &V) -> Vec<f64> {
+            (0..v.len()).map(|i| v.get(i).unwrap()).collect()
+        }
+
+        let r = DenseRow::from_vec(vec![1.0, 2.0, 4.0]);
+        assert_eq!(to_vec(&(&r * 2.0)), vec![2.0, 4.0, 8.0]);
+        assert_eq!(to_vec(&(&r / 2.0)), vec![0.5, 1.0, 2.0]);
+        assert_eq!(to_vec(&(&r + 1.0)), vec![2.0, 3.0, 5.0]);
+        assert_eq!(to_vec(&(r.clone() - 1.0)), vec![0.0, 1.0, 3.0]);
+
+        let mut c = DenseColumn::from_vec(vec![1.0, -2.0]);
+        assert_eq!(to_vec(&(c.clone() * -1.0)), vec![-1.0, 2.0]);
+        c *= 3.0;
+        c /= 2.0;
+        c += 1.0;
+        c -= 0.5;
+        assert_eq!(to_vec(&c), vec![2.0, -2.5]);
+    }
+
+    #[test]
+    fn test_identity_zero_scalar() {
+        let I: IdentityMatrix<i32> = eye!(3);
+        let D = &I * 5;
+        assert_eq!(D, DiagonalMatrix::new(vec![5, 5, 5]));
+        assert_eq!(I - 1, dense![0, -1, -1; -1, 0, -1; -1, -1, 0].unwrap());
+
+        let Z: ZeroMatrix<i32> = zeros!(2, 3);
+        assert_eq!(&Z * 7, Z);
+        assert_eq!(Z + 2, DenseMatrix::new(&vec![vec![2; 3]; 2]).unwrap());
+    }
+}
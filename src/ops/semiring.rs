@@ -0,0 +1,131 @@
+use ::{Float, Num};
+
+/// A Semiring supplies the "addition" and "multiplication" used by a matrix
+/// product. `add` must be associative and commutative with identity `zero`,
+/// `mul` must be associative with identity `one`, and `zero` must annihilate
+/// under `mul`.
+///
+/// The implementors are unit structs that select the semiring at the call
+/// site, e.g. `A.semiring_mul::<MinPlus>(&B)`.
+pub trait Semiring<T> {
+    /// The identity of `add`.
+    fn zero() -> T;
+
+    /// The identity of `mul`.
+    fn one() -> T;
+
+    /// The semiring addition.
+    fn add(a: T, b: T) -> T;
+
+    /// The semiring multiplication.
+    fn mul(a: T, b: T) -> T;
+}
+
+/// A Semiring whose `zero` is `T::zero()`, so that the elements a sparse
+/// matrix does not store are the semiring zero and a product of sparse
+/// matrices can itself be stored sparsely.
+pub trait SparseSemiring<T>: Semiring<T> {}
+
+/// Ordinary `(+, *)` arithmetic.
+#[derive(Clone, Copy, Debug)]
+pub struct Arithmetic;
+
+/// The tropical `(min, +)` semiring. Products compute shortest path lengths,
+/// with `+inf` standing for "no path".
+#[derive(Clone, Copy, Debug)]
+pub struct MinPlus;
+
+/// The `(max, +)` semiring. Products compute longest path lengths, with
+/// `-inf` standing for "no path".
+#[derive(Clone, Copy, Debug)]
+pub struct MaxPlus;
+
+/// The `(max, *)` semiring over nonnegative numbers. Products compute the
+/// best score over all paths, as in the Viterbi algorithm.
+#[derive(Clone, Copy, Debug)]
+pub struct MaxTimes;
+
+/// The boolean `(or, and)` semiring on `{0, 1}`, where any nonzero value is
+/// read as true. Products compute reachability.
+#[derive(Clone, Copy, Debug)]
+pub struct Boolean;
+
+impl<T: Num> Semiring<T> for Arithmetic {
+    #[inline]
+    fn zero() -> T { T::zero() }
+
+    #[inline]
+    fn one() -> T { T::one() }
+
+    #[inline]
+    fn add(a: T, b: T) -> T { a + b }
+
+    #[inline]
+    fn mul(a: T, b: T) -> T { a * b }
+}
+
+impl<T: Num> SparseSemiring<T> for Arithmetic {}
+
+impl<T: Float> Semiring<T> for MinPlus {
+    #[inline]
+    fn zero() -> T { T::infinity() }
+
+    #[inline]
+    fn one() -> T { T::zero() }
+
+    #[inline]
+    fn add(a: T, b: T) -> T { a.min(b) }
+
+    #[inline]
+    fn mul(a: T, b: T) -> T { a + b }
+}
+
+impl<T: Float> Semiring<T> for MaxPlus {
+    #[inline]
+    fn zero() -> T { T::neg_infinity() }
+
+    #[inline]
+    fn one() -> T { T::zero() }
+
+    #[inline]
+    fn add(a: T, b: T) -> T { a.max(b) }
+
+    #[inline]
+    fn mul(a: T, b: T) -> T { a + b }
+}
+
+impl<T: Num + PartialOrd> Semiring<T> for MaxTimes {
+    #[inline]
+    fn zero() -> T { T::zero() }
+
+    #[inline]
+    fn one() -> T { T::one() }
+
+    #[inline]
+    fn add(a: T, b: T) -> T { if a < b { b } else { a } }
+
+    #[inline]
+    fn mul(a: T, b: T) -> T { a * b }
+}
+
+impl<T: Num + PartialOrd> SparseSemiring<T> for MaxTimes {}
+
+impl<T: Num> Semiring<T> for Boolean {
+    #[inline]
+    fn zero() -> T { T::zero() }
+
+    #[inline]
+    fn one() -> T { T::one() }
+
+    #[inline]
+    fn add(a: T, b: T) -> T {
+        if a.is_zero() && b.is_zero() { T::zero() } else { T::one() }
+    }
+
+    #[inline]
+    fn mul(a: T, b: T) -> T {
+        if a.is_zero() || b.is_zero() { T::zero() } else { T::one() }
+    }
+}
+
+impl<T: Num> SparseSemiring<T> for Boolean {}
@@ -0,0 +1,139 @@
+use std::cmp;
+use std::collections::HashSet;
+
+use ::{BandedMatrix, ComplexField, DenseMatrix, Float, Matrix, Num, NumCast, SparseMatrix};
+use super::Rng;
+
+/// A distribution to draw the elements of a random matrix from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Distribution {
+    /// Uniform on `[lo, hi)`.
+    Uniform(f64, f64),
+    /// Normal with the given mean and standard deviation.
+    Normal(f64, f64),
+    /// One with probability `p`, zero otherwise.
+    Bernoulli(f64),
+    /// Uniform on the integers `lo..hi`.
+    Integers(i64, i64),
+}
+
+impl Distribution {
+    /// Draw one element. Panics if the value does not fit in `T`, or for an
+    /// empty integer range.
+    fn sample<T: NumCast>(&self, rng: &mut Rng) -> T {
+        let x = match *self {
+            Distribution::Uniform(lo, hi) => T::from(lo + (hi - lo) * rng.uniform()),
+            Distribution::Normal(mean, sd) => T::from(mean + sd * rng.normal()),
+            Distribution::Bernoulli(p) => T::from(if rng.uniform() < p { 1 } else { 0 }),
+            Distribution::Integers(lo, hi) => {
+                assert!(lo < hi, "empty integer range");
+                T::from(lo.wrapping_add(rng.below(hi.wrapping_sub(lo) as usize) as i64))
+            },
+        };
+        x.expect("random element does not fit in the element type")
+    }
+
+    /// Draw one element that is nonzero in `T`, redrawing zeros. Panics if
+    /// many draws in a row are all zero, as when an integer `T` truncates
+    /// every sample of `Uniform(0.0, 1.0)`.
+    fn sample_nonzero<T: NumCast + Num>(&self, rng: &mut Rng) -> T {
+        for _ in 0..1000 {
+            let x: T = self.sample(rng);
+            if !x.is_zero() { return x }
+        }
+        panic!("distribution draws only zeros in the element type")
+    }
+}
+
+impl<T: Clone + Copy + Num + NumCast> DenseMatrix<T> {
+    /// Create an `m` by `n` matrix with independent elements drawn from
+    /// `dist`, reproducibly from `seed`. Real samples are cast to `T`, so
+    /// an integer `T` truncates them.
+    pub fn random(m: usize, n: usize, dist: Distribution, seed: u64) -> DenseMatrix<T> {
+        let mut rng = Rng::new(seed);
+        let mat = (0..(m*n)).map(|_| dist.sample(&mut rng)).collect();
+        DenseMatrix::from_vec(mat, m, n, None).unwrap()
+    }
+}
+
+impl<T: Float + ComplexField> DenseMatrix<T> {
+    /// Create an `n` by `n` orthogonal matrix distributed uniformly (by the
+    /// Haar measure), from the QR factorization of a Gaussian matrix with
+    /// the signs of the columns of `Q` fixed by the diagonal of `R`.
+    pub fn random_orthogonal(n: usize, seed: u64) -> DenseMatrix<T> {
+        let qr = DenseMatrix::<T>::random(n, n, Distribution::Normal(0.0, 1.0), seed).qr();
+        let (q, r) = (qr.q(), qr.r());
+        for j in 0..n {
+            if r.get(j, j).unwrap() < T::zero() {
+                for i in 0..n {
+                    q.set(i, j, -q.get(i, j).unwrap());
+                }
+            }
+        }
+        q
+    }
+
+    /// Create an `n` by `n` symmetric positive definite matrix `Q D Q^T`
+    /// with a random orthogonal `Q` and eigenvalues spaced geometrically
+    /// from 1 down to `1/cond`, so that its 2-norm condition number is
+    /// `cond`. Panics unless `cond >= 1`.
+    pub fn random_spd(n: usize, cond: T, seed: u64) -> DenseMatrix<T> {
+        assert!(cond >= T::one(), "condition number below one");
+        let q = DenseMatrix::<T>::random_orthogonal(n, seed).elements();
+        let d: Vec<T> = (0..n).map(|i| {
+            if n == 1 { return T::one() }
+            cond.powf(-T::from(i).unwrap() / T::from(n - 1).unwrap())
+        }).collect();
+        let mut a = vec![T::zero(); n*n];
+        for i in 0..n {
+            for j in 0..(i + 1) {
+                let x = (0..n).fold(T::zero(), |acc, k| acc + q[i*n + k] * d[k] * q[j*n + k]);
+                a[i*n + j] = x;
+                a[j*n + i] = x;
+            }
+        }
+        DenseMatrix::from_vec(a, n, n, None).unwrap()
+    }
+}
+
+impl<T: Clone + Copy + Num + NumCast> SparseMatrix<T> {
+    /// Create an `m` by `n` sparse matrix with `round(density m n)` stored
+    /// elements at distinct random positions, drawn from `dist` with zeros
+    /// redrawn, so that every stored element is nonzero. Positions are drawn
+    /// by Floyd's algorithm, in time proportional to the number of elements
+    /// rather than to `m n`. Panics if `dist` only draws zeros in `T`.
+    pub fn random(m: usize, n: usize, density: f64, dist: Distribution, seed: u64) -> SparseMatrix<T> {
+        let total = m*n;
+        let density = density.clamp(0.0, 1.0);
+        let k = (density * total as f64).round() as usize;
+        let mut rng = Rng::new(seed);
+        let mut positions = HashSet::with_capacity(k);
+        for j in (total - k)..total {
+            let t = rng.below(j + 1);
+            if !positions.insert(t) { positions.insert(j); }
+        }
+        let mut positions: Vec<usize> = positions.into_iter().collect();
+        positions.sort();
+        let tuples = positions.into_iter()
+            .map(|p| (p / n, p % n, dist.sample_nonzero(&mut rng)))
+            .collect();
+        SparseMatrix::from_tuple(tuples, m, n)
+    }
+}
+
+impl<T: Clone + Copy + Num + NumCast> BandedMatrix<T> {
+    /// Create an `m` by `n` matrix with `kl` subdiagonals and `ku`
+    /// superdiagonals whose elements are drawn from `dist`.
+    pub fn random(m: usize, n: usize, kl: usize, ku: usize, dist: Distribution, seed: u64)
+        -> BandedMatrix<T>
+    {
+        let mut rng = Rng::new(seed);
+        let b = BandedMatrix::zeros(m, n, kl, ku);
+        for j in 0..n {
+            for i in j.saturating_sub(ku)..cmp::min(j + kl + 1, m) {
+                b.set(i, j, dist.sample(&mut rng));
+            }
+        }
+        b
+    }
+}
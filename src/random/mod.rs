@@ -0,0 +1,198 @@
+pub use self::matrix::Distribution;
+pub use self::sketch::Sketch;
+
+mod matrix;
+mod sketch;
+
+/// A small seedable pseudo-random number generator (xoshiro256**), so that
+/// randomized algorithms and random matrices can be reproduced from a seed.
+/// It is fast and statistically sound, but not suitable for cryptography.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: [u64; 4],
+    /// The second normal deviate from the last Box–Muller transform.
+    spare: Option<f64>,
+}
+
+impl Rng {
+    /// Create a generator whose state is expanded from `seed` with
+    /// SplitMix64. Equal seeds give equal streams.
+    pub fn new(seed: u64) -> Rng {
+        let mut x = seed;
+        let mut state = [0; 4];
+        for s in state.iter_mut() {
+            x = x.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            *s = z ^ (z >> 31);
+        }
+        Rng { state, spare: None }
+    }
+
+    /// Get the next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// Draw uniformly from `[0, 1)`.
+    pub fn uniform(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Draw from the standard normal distribution with the Box–Muller
+    /// transform.
+    pub fn normal(&mut self) -> f64 {
+        if let Some(z) = self.spare.take() { return z }
+        let u = 1.0 - self.uniform();
+        let theta = 2.0 * ::std::f64::consts::PI * self.uniform();
+        let r = (-2.0 * u.ln()).sqrt();
+        self.spare = Some(r * theta.sin());
+        r * theta.cos()
+    }
+
+    /// Draw uniformly from `0..n`, without modulo bias. `n` must be positive.
+    pub fn below(&mut self, n: usize) -> usize {
+        let n = n as u64;
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let x = self.next_u64();
+            if x < zone { return (x % n) as usize }
+        }
+    }
+
+    /// Draw `+1` or `-1` with equal probability.
+    pub fn sign(&mut self) -> f64 {
+        if self.next_u64() >> 63 == 0 { 1.0 } else { -1.0 }
+    }
+
+    /// Draw `k` distinct indices from `0..n` in random order, by a partial
+    /// Fisher–Yates shuffle. Requires `k <= n`.
+    pub fn sample(&mut self, n: usize, k: usize) -> Vec<usize> {
+        let mut pool: Vec<usize> = (0..n).collect();
+        for i in 0..k {
+            let j = i + self.below(n - i);
+            pool.swap(i, j);
+        }
+        pool.truncate(k);
+        pool
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+    use ::{BandedMatrix, DenseMatrix, LinearOperator, Matrix, SparseMatrix};
+    use super::{Distribution, Rng, Sketch};
+
+    #[test]
+    fn test_rng() {
+        let (mut a, mut b) = (Rng::new(1), Rng::new(1));
+        let x: Vec<u64> = (0..10).map(|_| a.next_u64()).collect();
+        assert_eq!(x, (0..10).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert!(x != (0..10).map(|_| Rng::new(2).next_u64()).collect::<Vec<_>>());
+
+        let mut rng = Rng::new(3);
+        let n = 20000;
+        let u: Vec<f64> = (0..n).map(|_| rng.uniform()).collect();
+        assert!(u.iter().all(|x| (0.0..1.0).contains(x)));
+        assert!((u.iter().sum::<f64>() / n as f64 - 0.5).abs() < 0.01);
+        let z: Vec<f64> = (0..n).map(|_| rng.normal()).collect();
+        let mean = z.iter().sum::<f64>() / n as f64;
+        let var = z.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / n as f64;
+        assert!(mean.abs() < 0.03 && (var - 1.0).abs() < 0.05);
+        assert!((0..100).all(|_| rng.below(7) < 7));
+        let mut s = rng.sample(10, 10);
+        s.sort();
+        assert_eq!(s, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_sketches() {
+        let tuples = vec![(0, 0, 1.0), (2, 1, -2.0), (5, 0, 3.0), (9, 2, 0.5), (4, 1, 1.5)];
+        let A: SparseMatrix<f64> = sparse![tuples.clone(); 10, 3];
+        let D = DenseMatrix::zeros(10, 3);
+        for &(i, j, x) in tuples.iter() {
+            D.set(i, j, x);
+        }
+        let x: Vec<f64> = (0..10).map(|i| i as f64 - 4.5).collect();
+        let y = vec![1.0, -1.0, 2.0, 0.5];
+        for S in [Sketch::gaussian(4, 10, 1), Sketch::srht(4, 10, 2).unwrap(), Sketch::count_sketch(4, 10, 3)].iter() {
+            assert_eq!(S.shape(), (4, 10));
+            let (SD, SA) = (S.sketch(&D).unwrap(), S.sketch_sparse(&A).unwrap());
+            assert_eq!(SD.dims(), (4, 3));
+            for i in 0..4 {
+                for j in 0..3 {
+                    assert!((SD.get(i, j).unwrap() - SA.get(i, j).unwrap()).abs() < 1e-12);
+                }
+            }
+            // y . S x = S^T y . x
+            let lhs: f64 = y.iter().zip(S.apply(&x)).map(|(a, b)| a * b).sum();
+            let rhs: f64 = S.apply_transpose(&y).iter().zip(&x).map(|(a, b)| a * b).sum();
+            assert!((lhs - rhs).abs() < 1e-10);
+            assert!(S.sketch(&DenseMatrix::zeros(9, 3)).is_err());
+        }
+
+        // Keeping every row, the SRHT is orthogonal.
+        let S: Sketch<f64> = Sketch::srht(16, 10, 5).unwrap();
+        let norm = |v: &[f64]| v.iter().map(|a| a * a).sum::<f64>();
+        assert!((norm(&S.apply(&x)) - norm(&x)).abs() < 1e-10);
+        assert!(Sketch::<f64>::srht(17, 10, 5).is_err());
+
+        // A large Gaussian sketch nearly preserves norms.
+        let S: Sketch<f64> = Sketch::gaussian(2000, 10, 6);
+        assert!((norm(&S.apply(&x)) / norm(&x) - 1.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_random_matrices() {
+        let A: DenseMatrix<f64> = DenseMatrix::random(6, 4, Distribution::Uniform(-1.0, 2.0), 9);
+        assert_eq!(A.dims(), (6, 4));
+        assert!(A.elements().iter().all(|x| (-1.0..2.0).contains(x)));
+        assert_eq!(A.elements(), DenseMatrix::random(6, 4, Distribution::Uniform(-1.0, 2.0), 9).elements());
+        let B: DenseMatrix<i64> = DenseMatrix::random(5, 5, Distribution::Integers(-3, 4), 1);
+        assert!(B.elements().iter().all(|x| (-3..4).contains(x)));
+        let C: DenseMatrix<u8> = DenseMatrix::random(5, 5, Distribution::Bernoulli(0.5), 2);
+        assert!(C.elements().iter().all(|&x| x <= 1));
+        let N: DenseMatrix<f64> = DenseMatrix::random(100, 100, Distribution::Normal(3.0, 0.5), 3);
+        let mean = N.elements().iter().sum::<f64>() / 1e4;
+        assert!((mean - 3.0).abs() < 0.02);
+
+        let U = Distribution::Uniform(0.0, 1.0);
+        let S: SparseMatrix<f64> = SparseMatrix::random(100, 50, 0.1, U, 4);
+        assert_eq!(S.nnz(), 500);
+        assert!(S.triplets().iter().all(|&(i, j, x)| i < 100 && j < 50 && x > 0.0 && x < 1.0));
+        assert_eq!(SparseMatrix::<f64>::random(3, 3, 1.0, U, 5).nnz(), 9);
+        assert_eq!(S.triplets(), SparseMatrix::random(100, 50, 0.1, U, 4).triplets());
+        let I: SparseMatrix<i64> = SparseMatrix::random(10, 10, 0.5, Distribution::Integers(-2, 3), 6);
+        assert_eq!(I.nnz(), 50);
+        assert!(I.triplets().iter().all(|&(_, _, x)| x != 0 && (-2..3).contains(&x)));
+
+        let Q: DenseMatrix<f64> = DenseMatrix::random_orthogonal(6, 6);
+        assert!(Q.is_unitary());
+        let P: DenseMatrix<f64> = DenseMatrix::random_spd(6, 1e3, 7);
+        assert!(P.is_symmetric());
+        let (values, _) = ::linalg::jacobi_eigen(&P.elements(), 6);
+        let max = values.iter().cloned().fold(0.0, f64::max);
+        let min = values.iter().cloned().fold(1.0, f64::min);
+        assert!((max - 1.0).abs() < 1e-12 && (max / min - 1e3).abs() < 1e-8);
+
+        let W: BandedMatrix<f64> = BandedMatrix::random(6, 5, 1, 2, Distribution::Normal(0.0, 1.0), 8);
+        assert_eq!((W.kl(), W.ku()), (1, 2));
+        for i in 0..6 {
+            for j in 0..5 {
+                let x = W.get(i, j).unwrap();
+                assert_eq!(x == 0.0, i > j + 1 || j > i + 2);
+            }
+        }
+    }
+}
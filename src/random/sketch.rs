@@ -0,0 +1,180 @@
+use ::{DenseMatrix, Float, LinearOperator, Matrix, SparseMatrix};
+use super::Rng;
+
+#[derive(Clone, Debug)]
+enum Kind<T> {
+    /// Independent `N(0, 1/s)` entries, row major `s*m`.
+    Gaussian(Vec<T>),
+    /// `P H D / sqrt(s)` on vectors zero padded to length `len`, a power of
+    /// two, with `D` the random `signs` and `P` keeping the sampled `rows`
+    /// of the Walsh–Hadamard matrix `H`.
+    Srht { signs: Vec<T>, rows: Vec<usize>, len: usize },
+    /// Entry `i` of the input is added with `signs[i]` to `buckets[i]`.
+    CountSketch { buckets: Vec<usize>, signs: Vec<T> },
+}
+
+/// A random sketching operator `S` that maps vectors of length `m` to a
+/// length `s`, usually much smaller, with `E[S^T S] = I`. Sketching `S A`
+/// approximately preserves the geometry of the columns of `A`.
+///
+/// A Gaussian sketch costs O(sm) per vector, a subsampled randomized
+/// Hadamard transform (SRHT) O(m log m), and a CountSketch O(m), or O(nnz)
+/// for a sparse matrix.
+#[derive(Clone, Debug)]
+pub struct Sketch<T> {
+    s: usize,
+    m: usize,
+    kind: Kind<T>,
+}
+
+/// Apply the unnormalized Walsh–Hadamard transform to `x` in place. The
+/// length of `x` must be a power of two.
+fn fwht<T: Float>(x: &mut [T]) {
+    let mut h = 1;
+    while h < x.len() {
+        for i in (0..x.len()).step_by(2*h) {
+            for j in i..(i + h) {
+                let (a, b) = (x[j], x[j + h]);
+                x[j] = a + b;
+                x[j + h] = a - b;
+            }
+        }
+        h *= 2;
+    }
+}
+
+impl<T: Float> Sketch<T> {
+    /// Create an `s` by `m` sketch with independent normal entries.
+    pub fn gaussian(s: usize, m: usize, seed: u64) -> Sketch<T> {
+        let mut rng = Rng::new(seed);
+        let scale = T::from(s).unwrap().sqrt();
+        let g = (0..(s*m)).map(|_| T::from(rng.normal()).unwrap() / scale).collect();
+        Sketch { s, m, kind: Kind::Gaussian(g) }
+    }
+
+    /// Create an `s` by `m` subsampled randomized Hadamard transform, which
+    /// keeps `s` distinct rows of the Hadamard transform of the randomly
+    /// signed input. Fails if `s` exceeds `m` rounded up to a power of two.
+    pub fn srht(s: usize, m: usize, seed: u64) -> ::Result<Sketch<T>> {
+        let len = m.next_power_of_two();
+        if s > len { return Err(::Error::InvalidDimensions) }
+        let mut rng = Rng::new(seed);
+        let signs = (0..m).map(|_| T::from(rng.sign()).unwrap()).collect();
+        let rows = rng.sample(len, s);
+        Ok(Sketch { s, m, kind: Kind::Srht { signs, rows, len } })
+    }
+
+    /// Create an `s` by `m` CountSketch, which has a single `±1` in each
+    /// column at a random row. Requires `s > 0` if `m > 0`.
+    pub fn count_sketch(s: usize, m: usize, seed: u64) -> Sketch<T> {
+        let mut rng = Rng::new(seed);
+        let mut buckets = Vec::with_capacity(m);
+        let mut signs = Vec::with_capacity(m);
+        for _ in 0..m {
+            buckets.push(rng.below(s));
+            signs.push(T::from(rng.sign()).unwrap());
+        }
+        Sketch { s, m, kind: Kind::CountSketch { buckets, signs } }
+    }
+
+    /// Get the nonzero entries `(r, S_ri)` of column `i`.
+    fn column(&self, i: usize) -> Vec<(usize, T)> {
+        match self.kind {
+            Kind::Gaussian(ref g) => (0..self.s).map(|r| (r, g[r*self.m + i])).collect(),
+            Kind::Srht { ref signs, ref rows, .. } => {
+                let scale = T::from(self.s).unwrap().sqrt();
+                rows.iter().enumerate().map(|(r, &p)| {
+                    let h = if (p & i).count_ones() & 1 == 0 { signs[i] } else { -signs[i] };
+                    (r, h / scale)
+                }).collect()
+            },
+            Kind::CountSketch { ref buckets, ref signs } => vec![(buckets[i], signs[i])],
+        }
+    }
+
+    /// Compute the `s` by `n` sketch `S A` of a dense `m` by `n` matrix.
+    pub fn sketch(&self, a: &DenseMatrix<T>) -> ::Result<DenseMatrix<T>> {
+        let (m, n) = a.dims();
+        if m != self.m { return Err(::Error::InvalidDimensions) }
+        let sa = DenseMatrix::zeros(self.s, n);
+        for j in 0..n {
+            let col: Vec<T> = (0..m).map(|i| a.get(i, j).unwrap()).collect();
+            for (r, x) in self.apply(&col).into_iter().enumerate() {
+                sa.set(r, j, x);
+            }
+        }
+        Ok(sa)
+    }
+
+    /// Compute the `s` by `n` sketch `S A` of a sparse `m` by `n` matrix,
+    /// visiting only its stored elements.
+    pub fn sketch_sparse(&self, a: &SparseMatrix<T>) -> ::Result<DenseMatrix<T>> {
+        let (m, n) = a.dims();
+        if m != self.m { return Err(::Error::InvalidDimensions) }
+        let mut sa = vec![T::zero(); self.s*n];
+        for (i, j, a_ij) in a.triplets() {
+            for (r, s_ri) in self.column(i) {
+                sa[r*n + j] = sa[r*n + j] + s_ri * a_ij;
+            }
+        }
+        DenseMatrix::from_vec(sa, self.s, n, None)
+    }
+}
+
+impl<T: Float> LinearOperator<T> for Sketch<T> {
+    fn shape(&self) -> (usize, usize) {
+        (self.s, self.m)
+    }
+
+    fn apply(&self, x: &[T]) -> Vec<T> {
+        let (s, m) = (self.s, self.m);
+        match self.kind {
+            Kind::Gaussian(ref g) => (0..s).map(|r| {
+                g[r*m..(r + 1)*m].iter().zip(x).fold(T::zero(), |acc, (&a, &b)| acc + a * b)
+            }).collect(),
+            Kind::Srht { ref signs, ref rows, len } => {
+                let mut y = vec![T::zero(); len];
+                for (i, (&d, &x_i)) in signs.iter().zip(x).enumerate() {
+                    y[i] = d * x_i;
+                }
+                fwht(&mut y);
+                let scale = T::from(s).unwrap().sqrt();
+                rows.iter().map(|&p| y[p] / scale).collect()
+            },
+            Kind::CountSketch { ref buckets, ref signs } => {
+                let mut y = vec![T::zero(); s];
+                for ((&r, &d), &x_i) in buckets.iter().zip(signs).zip(x) {
+                    y[r] = y[r] + d * x_i;
+                }
+                y
+            },
+        }
+    }
+
+    fn apply_transpose(&self, x: &[T]) -> Vec<T> {
+        let m = self.m;
+        match self.kind {
+            Kind::Gaussian(ref g) => {
+                let mut y = vec![T::zero(); m];
+                for (r, &x_r) in x.iter().enumerate() {
+                    for (y_i, &g_ri) in y.iter_mut().zip(&g[r*m..(r + 1)*m]) {
+                        *y_i = *y_i + g_ri * x_r;
+                    }
+                }
+                y
+            },
+            Kind::Srht { ref signs, ref rows, len } => {
+                let mut y = vec![T::zero(); len];
+                for (&p, &x_r) in rows.iter().zip(x) {
+                    y[p] = x_r;
+                }
+                fwht(&mut y);
+                let scale = T::from(self.s).unwrap().sqrt();
+                signs.iter().zip(y).map(|(&d, y_i)| d * y_i / scale).collect()
+            },
+            Kind::CountSketch { ref buckets, ref signs } => {
+                buckets.iter().zip(signs).map(|(&r, &d)| d * x[r]).collect()
+            },
+        }
+    }
+}
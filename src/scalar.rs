@@ -0,0 +1,143 @@
+use std::ops::Neg;
+
+use ::{Complex, Float, Num};
+use num::Integer;
+use num::rational::Ratio;
+
+/// Types with a complex conjugate. For real numbers it is the identity.
+pub trait Conjugate {
+    /// Get the complex conjugate.
+    fn conj(&self) -> Self;
+}
+
+macro_rules! real_conjugate_impl {
+    ($($t:ty)*) => ($(
+        impl Conjugate for $t {
+            #[inline]
+            fn conj(&self) -> $t { *self }
+        }
+    )*)
+}
+
+real_conjugate_impl! { f32 f64 i8 i16 i32 i64 isize u8 u16 u32 u64 usize }
+
+impl<T: Clone + Num + Neg<Output = T>> Conjugate for Complex<T> {
+    #[inline]
+    fn conj(&self) -> Complex<T> {
+        Complex::conj(self)
+    }
+}
+
+impl<T: Clone + Integer> Conjugate for Ratio<T> {
+    #[inline]
+    fn conj(&self) -> Ratio<T> { self.clone() }
+}
+
+/// A ComplexField is a real or complex floating point scalar. Factorizations
+/// that need division, square roots and moduli are written against this
+/// trait, so the same code serves `f64` and `Complex<f64>` matrices.
+pub trait ComplexField: Num + Copy + Neg<Output = Self> + Conjugate {
+    /// The underlying real type.
+    type Real: Float;
+
+    /// Embed a real number.
+    fn from_real(re: Self::Real) -> Self;
+
+    /// Get the real part.
+    fn re(&self) -> Self::Real;
+
+    /// Get the imaginary part.
+    fn im(&self) -> Self::Real;
+
+    /// Get the modulus `|z|`.
+    fn modulus(&self) -> Self::Real;
+
+    /// Get the squared modulus `|z|^2`.
+    fn modulus_sqr(&self) -> Self::Real;
+
+    /// Get the principal square root.
+    fn sqrt(&self) -> Self;
+
+    /// Convert from a complex number. Real types take the real part.
+    fn from_complex(z: Complex<Self::Real>) -> Self;
+
+    /// Convert to a complex number.
+    #[inline]
+    fn to_complex(&self) -> Complex<Self::Real> {
+        Complex::new(self.re(), self.im())
+    }
+
+    /// Multiply by a real number.
+    #[inline]
+    fn scale(&self, r: Self::Real) -> Self {
+        *self * Self::from_real(r)
+    }
+
+    /// Get the machine epsilon of the underlying real type.
+    #[inline]
+    fn epsilon() -> Self::Real {
+        Self::Real::epsilon()
+    }
+}
+
+macro_rules! real_field_impl {
+    ($($t:ty)*) => ($(
+        impl ComplexField for $t {
+            type Real = $t;
+
+            #[inline]
+            fn from_real(re: $t) -> $t { re }
+
+            #[inline]
+            fn re(&self) -> $t { *self }
+
+            #[inline]
+            fn im(&self) -> $t { 0.0 }
+
+            #[inline]
+            fn modulus(&self) -> $t { self.abs() }
+
+            #[inline]
+            fn modulus_sqr(&self) -> $t { self * self }
+
+            #[inline]
+            fn sqrt(&self) -> $t { Float::sqrt(*self) }
+
+            #[inline]
+            fn from_complex(z: Complex<$t>) -> $t { z.re }
+        }
+    )*)
+}
+
+real_field_impl! { f32 f64 }
+
+impl<T: Float> ComplexField for Complex<T> {
+    type Real = T;
+
+    #[inline]
+    fn from_real(re: T) -> Complex<T> { Complex::new(re, T::zero()) }
+
+    #[inline]
+    fn re(&self) -> T { self.re }
+
+    #[inline]
+    fn im(&self) -> T { self.im }
+
+    #[inline]
+    fn modulus(&self) -> T { self.norm() }
+
+    #[inline]
+    fn modulus_sqr(&self) -> T { self.norm_sqr() }
+
+    #[inline]
+    fn sqrt(&self) -> Complex<T> { Complex::sqrt(self) }
+
+    #[inline]
+    fn from_complex(z: Complex<T>) -> Complex<T> { z }
+
+    #[inline]
+    fn to_complex(&self) -> Complex<T> { *self }
+
+    #[inline]
+    fn scale(&self, r: T) -> Complex<T> { Complex::scale(self, r) }
+}
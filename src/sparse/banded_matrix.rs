@@ -0,0 +1,276 @@
+use std::cell::Cell;
+use std::cmp;
+use std::fmt;
+use std::ops::Range;
+
+use ::{Num};
+use ::{ComplexField, Conjugate, DenseMatrix, LinearOperator, Matrix};
+
+/// A BandedMatrix stores only the `kl` subdiagonals, the diagonal and the
+/// `ku` superdiagonals of an `m` by `n` matrix.
+///
+/// The band is kept in LAPACK band storage: column major with leading
+/// dimension `kl + ku + 1`, where `a_ij` is at row `ku + i - j` of column
+/// `j`. Slots in the corners that fall outside the matrix are zero.
+#[derive(Clone, Debug)]
+pub struct BandedMatrix<T> where T: Copy {
+    m: usize,
+    n: usize,
+    kl: usize,
+    ku: usize,
+    pub mat: Vec<Cell<T>>,
+}
+
+impl<T: Clone + Copy + Num> BandedMatrix<T> {
+    /// Create a new banded matrix of zeros.
+    pub fn zeros(m: usize, n: usize, kl: usize, ku: usize) -> BandedMatrix<T> {
+        BandedMatrix { m, n, kl, ku, mat: vec![Cell::new(T::zero()); (kl + ku + 1)*n] }
+    }
+
+    /// Create a new banded matrix from a band in LAPACK band storage. Fails
+    /// unless `ab` has length `(kl + ku + 1) n`.
+    pub fn from_band(ab: Vec<T>, m: usize, n: usize, kl: usize, ku: usize)
+        -> ::Result<BandedMatrix<T>>
+    {
+        if ab.len() != (kl + ku + 1)*n { return Err(::Error::InvalidDimensions) }
+        let b = BandedMatrix { m, n, kl, ku, mat: ab.into_iter().map(Cell::new).collect() };
+        // Keep the unused corner slots zero so the band compares exactly.
+        for j in 0..n {
+            for r in 0..b.ldab() {
+                if r + j < ku || r + j - ku >= m { b.mat[j*b.ldab() + r].set(T::zero()) }
+            }
+        }
+        Ok(b)
+    }
+
+    /// Take the band of a dense matrix, ignoring the elements outside it.
+    pub fn from_dense(a: &DenseMatrix<T>, kl: usize, ku: usize) -> BandedMatrix<T> {
+        let b = BandedMatrix::zeros(a.rows(), a.cols(), kl, ku);
+        for j in 0..b.n {
+            for i in b.col_range(j) {
+                b.mat[b.index(i, j)].set(a.get(i, j).unwrap());
+            }
+        }
+        b
+    }
+
+    /// Get the number of subdiagonals.
+    #[inline]
+    pub fn kl(&self) -> usize {
+        self.kl
+    }
+
+    /// Get the number of superdiagonals.
+    #[inline]
+    pub fn ku(&self) -> usize {
+        self.ku
+    }
+
+    /// Get the band in LAPACK band storage.
+    pub fn band(&self) -> Vec<T> {
+        self.mat.iter().map(Cell::get).collect()
+    }
+
+    /// Check if position i, j lies in the band.
+    #[inline]
+    pub fn in_band(&self, i: usize, j: usize) -> bool {
+        i <= j + self.kl && j <= i + self.ku
+    }
+
+    /// Get the leading dimension of the band storage.
+    #[inline]
+    fn ldab(&self) -> usize {
+        self.kl + self.ku + 1
+    }
+
+    /// Get the packed index of a position in the band.
+    #[inline]
+    fn index(&self, i: usize, j: usize) -> usize {
+        j*self.ldab() + self.ku + i - j
+    }
+
+    /// Get the rows of column j that lie in the band.
+    #[inline]
+    fn col_range(&self, j: usize) -> Range<usize> {
+        j.saturating_sub(self.ku)..cmp::min(self.m, j + self.kl + 1)
+    }
+
+    /// Get the band as `(i, j, a_ij)` triplets, column by column.
+    pub fn triplets(&self) -> Vec<(usize, usize, T)> {
+        let mut v = Vec::with_capacity(self.mat.len());
+        for j in 0..self.n {
+            for i in self.col_range(j) {
+                v.push((i, j, self.mat[self.index(i, j)].get()));
+            }
+        }
+        v
+    }
+
+    /// Check that every element of the band at a position where `outside`
+    /// holds is zero.
+    fn is_zero_where<F: Fn(usize, usize) -> bool>(&self, outside: F) -> bool {
+        (0..self.n).all(|j| self.col_range(j).all(|i| {
+            !outside(i, j) || self.mat[self.index(i, j)].get().is_zero()
+        }))
+    }
+
+    /// Apply `f` to every element of the band. `f` should map zero to zero,
+    /// since it is not applied outside the band.
+    pub fn map<F: Fn(T) -> T>(&self, f: F) -> BandedMatrix<T> {
+        let b = BandedMatrix::zeros(self.m, self.n, self.kl, self.ku);
+        for j in 0..self.n {
+            for i in self.col_range(j) {
+                let k = self.index(i, j);
+                b.mat[k].set(f(self.mat[k].get()));
+            }
+        }
+        b
+    }
+
+    /// Copy into a dense matrix.
+    pub fn to_dense(&self) -> DenseMatrix<T> {
+        DenseMatrix::from_vec(self.elements(), self.m, self.n, None).unwrap()
+    }
+
+    /// Get the conjugate transpose.
+    #[inline]
+    pub fn conj_transpose(&self) -> BandedMatrix<T> where T: Conjugate {
+        self.conj().transpose()
+    }
+}
+
+impl<T: Copy + Conjugate> Conjugate for BandedMatrix<T> {
+    fn conj(&self) -> BandedMatrix<T> {
+        BandedMatrix {
+            m: self.m,
+            n: self.n,
+            kl: self.kl,
+            ku: self.ku,
+            mat: self.mat.iter().map(|a| Cell::new(a.get().conj())).collect(),
+        }
+    }
+}
+
+impl<T: Clone + Copy + Num> Matrix<T> for BandedMatrix<T> {
+    fn is_symmetric(&self) -> bool {
+        self.is_square() && (0..self.n).all(|j| self.col_range(j).all(|i| {
+            self.mat[self.index(i, j)].get() == self.get(j, i).unwrap()
+        }))
+    }
+
+    fn is_orthogonal(&self) -> bool {
+        // Columns i and j share rows only when j - i < kl + ku + 1, so the
+        // rest of `A^T A` is zero. Check the entries on and above the
+        // diagonal that can be nonzero.
+        if !self.is_square() { return false }
+        (0..self.n).all(|i| (i..cmp::min(self.n, i + self.ldab())).all(|j| {
+            let (ri, rj) = (self.col_range(i), self.col_range(j));
+            let dot = (cmp::max(ri.start, rj.start)..cmp::min(ri.end, rj.end))
+                .fold(T::zero(), |acc, k| {
+                    acc + self.mat[self.index(k, i)].get() * self.mat[self.index(k, j)].get()
+                });
+            dot == if i == j { T::one() } else { T::zero() }
+        }))
+    }
+
+    fn is_unitary(&self) -> bool where T: ComplexField { self.to_dense().is_unitary() }
+
+    fn is_diagonal(&self) -> bool { self.is_zero_where(|i, j| i != j) }
+
+    fn is_lower_triangular(&self) -> bool { self.is_zero_where(|i, j| j > i) }
+
+    fn is_unilower_triangular(&self) -> bool {
+        self.is_lower_triangular() && self.diags().iter().all(|d| d.is_one())
+    }
+
+    fn is_strictly_lower_triangular(&self) -> bool { self.is_zero_where(|i, j| j >= i) }
+
+    fn is_lower_hessenberg(&self) -> bool { self.is_zero_where(|i, j| j > i + 1) }
+
+    fn is_upper_triangular(&self) -> bool { self.is_zero_where(|i, j| i > j) }
+
+    fn is_uniupper_triangular(&self) -> bool {
+        self.is_upper_triangular() && self.diags().iter().all(|d| d.is_one())
+    }
+
+    fn is_strictly_upper_triangular(&self) -> bool { self.is_zero_where(|i, j| i >= j) }
+
+    fn is_upper_hessenberg(&self) -> bool { self.is_zero_where(|i, j| i > j + 1) }
+
+    fn trace(&self) -> T {
+        (0..cmp::min(self.m, self.n)).fold(T::zero(), |acc, i| acc + self.mat[self.index(i, i)].get())
+    }
+
+    fn transpose(self) -> Self {
+        let t = BandedMatrix::zeros(self.n, self.m, self.ku, self.kl);
+        for j in 0..self.n {
+            for i in self.col_range(j) {
+                t.mat[t.index(j, i)].set(self.mat[self.index(i, j)].get());
+            }
+        }
+        t
+    }
+
+    fn rows(&self) -> usize { self.m }
+
+    fn cols(&self) -> usize { self.n }
+
+    fn get(&self, i: usize, j: usize) -> Option<T> {
+        if i >= self.m || j >= self.n { return None }
+        if self.in_band(i, j) {
+            Some(self.mat[self.index(i, j)].get())
+        } else {
+            Some(T::zero())
+        }
+    }
+
+    fn set(&self, i: usize, j: usize, val: T) -> Option<T> {
+        if i >= self.m || j >= self.n { return None }
+        if self.in_band(i, j) {
+            self.mat[self.index(i, j)].set(val);
+        } else if !val.is_zero() {
+            panic!("Cannot set a value outside the band of a BandedMatrix")
+        }
+        Some(val)
+    }
+
+    fn elements(&self) -> Vec<T> {
+        let mut v = vec![T::zero(); self.m*self.n];
+        for j in 0..self.n {
+            for i in self.col_range(j) {
+                v[i*self.n + j] = self.mat[self.index(i, j)].get();
+            }
+        }
+        v
+    }
+}
+
+impl<T: Clone + Copy + Num> LinearOperator<T> for BandedMatrix<T> {
+    fn shape(&self) -> (usize, usize) { (self.m, self.n) }
+
+    fn apply(&self, x: &[T]) -> Vec<T> {
+        let mut y = vec![T::zero(); self.m];
+        for (j, &x_j) in x.iter().enumerate().take(self.n) {
+            for i in self.col_range(j) {
+                y[i] = y[i] + self.mat[self.index(i, j)].get() * x_j;
+            }
+        }
+        y
+    }
+
+    fn apply_transpose(&self, x: &[T]) -> Vec<T> {
+        let mut y = vec![T::zero(); self.n];
+        for (j, y_j) in y.iter_mut().enumerate() {
+            for i in self.col_range(j) {
+                *y_j = *y_j + self.mat[self.index(i, j)].get() * x[i];
+            }
+        }
+        y
+    }
+}
+
+impl<T: Copy + fmt::Debug> fmt::Display for BandedMatrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BandedMatrix(m: {}, n: {}, kl: {}, ku: {})", self.m, self.n, self.kl, self.ku)
+    }
+}
@@ -0,0 +1,166 @@
+use std::cell::Cell;
+use std::fmt;
+
+use ::{Float, Num, One};
+use ::{ComplexField, Conjugate, DenseMatrix, LinearOperator, Matrix};
+
+/// A DiagonalMatrix is a square matrix that stores only its diagonal.
+#[derive(Clone, Debug)]
+pub struct DiagonalMatrix<T> where T: Copy {
+    n: usize,
+    pub diag: Vec<Cell<T>>,
+}
+
+impl<T: Clone + Copy + Num> DiagonalMatrix<T> {
+    /// Create a new diagonal matrix from its diagonal.
+    pub fn new(diag: Vec<T>) -> DiagonalMatrix<T> {
+        DiagonalMatrix { n: diag.len(), diag: diag.into_iter().map(Cell::new).collect() }
+    }
+
+    /// Create a new diagonal matrix with every diagonal element equal to `a`.
+    pub fn from_scalar(a: T, n: usize) -> DiagonalMatrix<T> {
+        DiagonalMatrix { n, diag: vec![Cell::new(a); n] }
+    }
+
+    /// Take the diagonal of a dense matrix. Fails unless the matrix is square
+    /// and diagonal.
+    pub fn from_dense(a: &DenseMatrix<T>) -> ::Result<DiagonalMatrix<T>> {
+        if !a.is_square() || !a.is_diagonal() { return Err(::Error::InvalidDimensions) }
+        Ok(DiagonalMatrix::new(a.diags()))
+    }
+
+    /// Get the diagonal as a `Vec`.
+    #[inline]
+    pub fn diag(&self) -> Vec<T> {
+        self.diag.iter().map(Cell::get).collect()
+    }
+
+    /// Get the diagonal as `(i, i, a_ii)` triplets.
+    pub fn triplets(&self) -> Vec<(usize, usize, T)> {
+        self.diag.iter().enumerate().map(|(i, d)| (i, i, d.get())).collect()
+    }
+
+    /// Get the determinant, the product of the diagonal.
+    pub fn det(&self) -> T {
+        self.diag.iter().fold(T::one(), |acc, d| acc * d.get())
+    }
+
+    /// Get the inverse. Fails if a diagonal element is zero.
+    pub fn inverse(&self) -> ::Result<DiagonalMatrix<T>> {
+        if self.diag.iter().any(|d| d.get().is_zero()) { return Err(::Error::Singular) }
+        Ok(DiagonalMatrix::new(self.diag.iter().map(|d| T::one() / d.get()).collect()))
+    }
+
+    /// Copy into a dense matrix.
+    pub fn to_dense(&self) -> DenseMatrix<T> {
+        DenseMatrix::from_vec(self.elements(), self.n, self.n, None).unwrap()
+    }
+
+    /// Get the conjugate transpose, which is the conjugate.
+    #[inline]
+    pub fn conj_transpose(&self) -> DiagonalMatrix<T> where T: Conjugate {
+        self.conj()
+    }
+}
+
+impl<T: Copy + Conjugate> Conjugate for DiagonalMatrix<T> {
+    fn conj(&self) -> DiagonalMatrix<T> {
+        DiagonalMatrix { n: self.n, diag: self.diag.iter().map(|d| Cell::new(d.get().conj())).collect() }
+    }
+}
+
+impl<T: Clone + Copy + Num> Matrix<T> for DiagonalMatrix<T> {
+    fn is_hermitian(&self) -> bool where T: Conjugate {
+        self.diag.iter().all(|d| d.get() == d.get().conj())
+    }
+
+    fn is_symmetric(&self) -> bool { true }
+
+    fn is_skew_hermitian(&self) -> bool where T: Conjugate {
+        self.diag.iter().all(|d| d.get() == T::zero() - d.get().conj())
+    }
+
+    fn is_unitary(&self) -> bool where T: ComplexField {
+        let tol = T::epsilon().sqrt();
+        self.diag.iter().all(|d| (d.get().modulus() - T::Real::one()).abs() <= tol)
+    }
+
+    fn is_orthogonal(&self) -> bool {
+        self.diag.iter().all(|d| d.get() == T::one() || d.get() == T::zero() - T::one())
+    }
+
+    fn is_diagonal(&self) -> bool { true }
+
+    fn is_lower_triangular(&self) -> bool { true }
+
+    fn is_unilower_triangular(&self) -> bool {
+        self.diag.iter().all(|d| d.get().is_one())
+    }
+
+    fn is_strictly_lower_triangular(&self) -> bool {
+        self.diag.iter().all(|d| d.get().is_zero())
+    }
+
+    fn is_lower_hessenberg(&self) -> bool { true }
+
+    fn is_upper_triangular(&self) -> bool { true }
+
+    fn is_uniupper_triangular(&self) -> bool { self.is_unilower_triangular() }
+
+    fn is_strictly_upper_triangular(&self) -> bool { self.is_strictly_lower_triangular() }
+
+    fn is_upper_hessenberg(&self) -> bool { true }
+
+    fn det(&self) -> ::Result<T> where T: ComplexField { Ok(DiagonalMatrix::det(self)) }
+
+    fn trace(&self) -> T {
+        self.diag.iter().fold(T::zero(), |acc, d| acc + d.get())
+    }
+
+    fn transpose(self) -> Self { self }
+
+    fn rows(&self) -> usize { self.n }
+
+    fn cols(&self) -> usize { self.n }
+
+    fn get(&self, i: usize, j: usize) -> Option<T> {
+        if i >= self.n || j >= self.n { return None }
+        if i == j { Some(self.diag[i].get()) } else { Some(T::zero()) }
+    }
+
+    fn set(&self, i: usize, j: usize, val: T) -> Option<T> {
+        if i >= self.n || j >= self.n { return None }
+        if i == j {
+            self.diag[i].set(val);
+        } else if !val.is_zero() {
+            panic!("Cannot set an off-diagonal value in a DiagonalMatrix")
+        }
+        Some(val)
+    }
+
+    fn elements(&self) -> Vec<T> {
+        let mut v = vec![T::zero(); self.n*self.n];
+        for (i, d) in self.diag.iter().enumerate() {
+            v[i*self.n + i] = d.get();
+        }
+        v
+    }
+
+    fn diags(&self) -> Vec<T> { self.diag() }
+}
+
+impl<T: Clone + Copy + Num> LinearOperator<T> for DiagonalMatrix<T> {
+    fn shape(&self) -> (usize, usize) { (self.n, self.n) }
+
+    fn apply(&self, x: &[T]) -> Vec<T> {
+        self.diag.iter().zip(x).map(|(d, &x)| d.get() * x).collect()
+    }
+
+    fn apply_transpose(&self, x: &[T]) -> Vec<T> { self.apply(x) }
+}
+
+impl<T: Copy + fmt::Debug> fmt::Display for DiagonalMatrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DiagonalMatrix(diag: {:?})", self.diag)
+    }
+}
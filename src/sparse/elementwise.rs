@@ -0,0 +1,130 @@
+use ::{Num, NumCast};
+use ::{DenseColumn, DenseRow, Matrix, SparseMatrix};
+
+static ELEMENTWISE_DIM_ERROR: &str = "Cannot combine matrices of given dimensions";
+
+/// The element-wise operations on a sparse matrix visit only the stored
+/// elements. Elements that are not stored are zero, and are accounted for
+/// without being visited.
+impl<T: Clone + Copy + Num> SparseMatrix<T> {
+    /// Apply `f` to every stored element. `f` should map zero to zero, since
+    /// it is not applied to the elements that are not stored.
+    pub fn map<U, F>(&self, f: F) -> SparseMatrix<U>
+        where U: Clone + Copy + Num, F: Fn(T) -> U
+    {
+        let tuples = self.triplets().into_iter().map(|(i, j, a)| (i, j, f(a))).collect();
+        SparseMatrix::from_tuple(tuples, self.rows(), self.cols())
+    }
+
+    /// The element-wise (Hadamard) product. Only positions stored in both
+    /// operands are visited.
+    ///
+    /// Panics if the dimensions do not agree.
+    pub fn hadamard(&self, other: &SparseMatrix<T>) -> SparseMatrix<T> {
+        if self.dims() != other.dims() {
+            panic!("{}: lhs={:?} rhs={:?}", ELEMENTWISE_DIM_ERROR, self.dims(), other.dims())
+        }
+        let (small, large) = if self.nnz() <= other.nnz() { (self, other) } else { (other, self) };
+        let tuples = small.triplets().into_iter()
+            .map(|(i, j, a)| (i, j, a * large.get(i, j).unwrap()))
+            .filter(|&(_, _, c)| !c.is_zero())
+            .collect();
+        SparseMatrix::from_tuple(tuples, self.rows(), self.cols())
+    }
+
+    /// Get the number of stored elements.
+    #[inline]
+    pub fn nnz(&self) -> usize {
+        self.mat.borrow().len()
+    }
+
+    /// Get the sum of all elements.
+    pub fn sum(&self) -> T {
+        self.mat.borrow().values().fold(T::zero(), |acc, a| acc + a.get())
+    }
+
+    /// Get the product of all elements, which is zero unless every element
+    /// is stored.
+    pub fn product(&self) -> T {
+        if self.nnz() < self.rows()*self.cols() { return T::zero() }
+        self.mat.borrow().values().fold(T::one(), |acc, a| acc * a.get())
+    }
+
+    /// Get the least element, ignoring NaN.
+    pub fn min(&self) -> Option<T> where T: PartialOrd {
+        self.argmin().map(|(i, j)| self.get(i, j).unwrap())
+    }
+
+    /// Get the greatest element, ignoring NaN.
+    pub fn max(&self) -> Option<T> where T: PartialOrd {
+        self.argmax().map(|(i, j)| self.get(i, j).unwrap())
+    }
+
+    /// Get the position of the first least element in row major order.
+    pub fn argmin(&self) -> Option<(usize, usize)> where T: PartialOrd {
+        self.arg_extreme(|a, b| a < b)
+    }
+
+    /// Get the position of the first greatest element in row major order.
+    pub fn argmax(&self) -> Option<(usize, usize)> where T: PartialOrd {
+        self.arg_extreme(|a, b| a > b)
+    }
+
+    /// Find the first element that no other element is `better` than,
+    /// treating the first element that is not stored as a candidate zero.
+    fn arg_extreme<F>(&self, better: F) -> Option<(usize, usize)>
+        where T: PartialOrd, F: Fn(T, T) -> bool
+    {
+        let n = self.cols();
+        let mut best: Option<(usize, T)> = None;
+        let mut missing = None;
+        for (k, (i, j, a)) in self.triplets().into_iter().enumerate() {
+            if missing.is_none() && i*n + j != k { missing = Some(k) }
+            best = match best {
+                None if a.partial_cmp(&a).is_some() => Some((i*n + j, a)),
+                Some((_, b)) if better(a, b) => Some((i*n + j, a)),
+                _ => best,
+            };
+        }
+        if missing.is_none() && self.nnz() < self.rows()*n { missing = Some(self.nnz()) }
+        let best = match (best, missing) {
+            (None, Some(k)) => Some(k),
+            (Some((p, b)), Some(k)) => {
+                let zero = T::zero();
+                if better(zero, b) || (zero == b && k < p) { Some(k) } else { Some(p) }
+            },
+            (best, None) => best.map(|(p, _)| p),
+        };
+        best.map(|k| (k / n, k % n))
+    }
+
+    /// Sum the rows together, giving the sum of each column.
+    pub fn sum_rows(&self) -> DenseRow<T> {
+        let mut sums = vec![T::zero(); self.cols()];
+        for (_, j, a) in self.triplets() {
+            sums[j] = sums[j] + a;
+        }
+        DenseRow::from_vec(sums)
+    }
+
+    /// Sum the columns together, giving the sum of each row.
+    pub fn sum_cols(&self) -> DenseColumn<T> {
+        let mut sums = vec![T::zero(); self.rows()];
+        for (i, _, a) in self.triplets() {
+            sums[i] = sums[i] + a;
+        }
+        DenseColumn::from_vec(sums)
+    }
+
+    /// Average the rows, giving the mean of each column.
+    pub fn mean_rows(&self) -> DenseRow<T> where T: NumCast {
+        let m = T::from(self.rows()).unwrap();
+        self.sum_rows().map(|s| s / m)
+    }
+
+    /// Average the columns, giving the mean of each row.
+    pub fn mean_cols(&self) -> DenseColumn<T> where T: NumCast {
+        let n = T::from(self.cols()).unwrap();
+        self.sum_cols().map(|s| s / n)
+    }
+}
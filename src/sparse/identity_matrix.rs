@@ -1,7 +1,7 @@
 use std::fmt;
 use std::marker::PhantomData;
-use ::{FromPrimitive, Num, One, Zero};
-use ::Matrix;
+use ::{Float, Num, NumCast, One, Zero};
+use ::{ComplexField, Conjugate, LinearOperator, Matrix};
 
 #[derive(Clone, Debug)]
 pub struct IdentityMatrix<T> {
@@ -13,11 +13,35 @@ impl<T: Num + One + Zero> IdentityMatrix<T> {
     pub fn new(n: usize) -> IdentityMatrix<T> {
         IdentityMatrix { m: n, mat: PhantomData }
     }
+
+    /// Get the conjugate transpose, which is the matrix itself.
+    #[inline]
+    pub fn conj_transpose(&self) -> IdentityMatrix<T> {
+        IdentityMatrix { n: self.n, mat: PhantomData }
+    }
+
+    /// Raise to the power `k`, which is the matrix itself for every `k`.
+    #[inline]
+    pub fn pow(&self, _k: i32) -> IdentityMatrix<T> {
+        IdentityMatrix { n: self.n, mat: PhantomData }
+    }
+}
+
+impl<T> Conjugate for IdentityMatrix<T> {
+    fn conj(&self) -> IdentityMatrix<T> {
+        IdentityMatrix { n: self.n, mat: PhantomData }
+    }
 }
 
-impl<T: Clone + Num + One + Zero + FromPrimitive> Matrix<T> for IdentityMatrix<T> {
+impl<T: Clone + Num> Matrix<T> for IdentityMatrix<T> {
+    fn is_hermitian(&self) -> bool where T: Conjugate { true }
+
     fn is_symmetric(&self) -> bool { true }
 
+    fn is_skew_hermitian(&self) -> bool where T: Conjugate { self.n == 0 }
+
+    fn is_unitary(&self) -> bool where T: ComplexField { true }
+
     fn is_orthogonal(&self) -> bool { true }
 
     fn is_diagonal(&self) -> bool { true }
@@ -39,7 +63,7 @@ impl<T: Clone + Num + One + Zero + FromPrimitive> Matrix<T> for IdentityMatrix<T
     fn is_upper_hessenberg(&self) -> bool { true }
 
     fn trace(&self) -> T {
-        T::from_usize(self.n).expect("IdentityMatrix::trace")
+        (0..self.n).fold(T::zero(), |acc, _| acc + T::one())
     }
 
     fn transpose(self) -> Self { self }
@@ -63,20 +87,46 @@ impl<T: Clone + Num + One + Zero + FromPrimitive> Matrix<T> for IdentityMatrix<T
         panic!("Cannot set a value in an IdentityMatrix")
     }
 
+    fn norm_1(&self) -> T::Real where T: ComplexField { self.norm_max() }
+
+    fn norm_inf(&self) -> T::Real where T: ComplexField { self.norm_max() }
+
+    fn norm_fro(&self) -> T::Real where T: ComplexField {
+        <T::Real as NumCast>::from(self.n).unwrap().sqrt()
+    }
+
+    fn norm_max(&self) -> T::Real where T: ComplexField {
+        if self.n == 0 { T::Real::zero() } else { T::Real::one() }
+    }
+
+    fn norm_2(&self) -> ::Result<T::Real> where T: ComplexField { Ok(self.norm_max()) }
+
+    fn cond_1_est(&self) -> ::Result<T::Real> where T: ComplexField { Ok(self.norm_max()) }
+
+    fn det(&self) -> ::Result<T> where T: ComplexField { Ok(T::one()) }
+
+    fn log_det(&self) -> ::Result<(T, T::Real)> where T: ComplexField {
+        Ok((T::one(), T::Real::zero()))
+    }
+
     fn elements(&self) -> Vec<T> {
         let mut v = vec![T::zero(); self.n*self.n];
         for i in 0..self.n {
-            for j in 0..self.n {
-                if i == j {
-                    v[i] = T::one();
-                }
-            }
+            v[i*self.n + i] = T::one();
         }
         v
     }
 }
 
-impl<T: Clone + Num + One + Zero + FromPrimitive> IntoIterator for IdentityMatrix<T> {
+impl<T: Clone + Num> LinearOperator<T> for IdentityMatrix<T> {
+    fn shape(&self) -> (usize, usize) { (self.n, self.n) }
+
+    fn apply(&self, x: &[T]) -> Vec<T> { x.to_vec() }
+
+    fn apply_transpose(&self, x: &[T]) -> Vec<T> { x.to_vec() }
+}
+
+impl<T: Clone + Num> IntoIterator for IdentityMatrix<T> {
     type Item = T;
     type IntoIter = IdentityMatrixIntoIterator<T>;
 
@@ -96,7 +146,7 @@ pub struct IdentityMatrixIntoIterator<T> {
     mat: IdentityMatrix<T>,
 }
 
-impl<T: Clone + Num + One + Zero + FromPrimitive> Iterator for IdentityMatrixIntoIterator<T> {
+impl<T: Clone + Num> Iterator for IdentityMatrixIntoIterator<T> {
     type Item = T;
 
     fn next(&mut self) -> Option<T> {
@@ -1,9 +1,21 @@
+pub use self::banded_matrix::BandedMatrix;
+pub use self::diagonal_matrix::DiagonalMatrix;
 pub use self::identity_matrix::IdentityMatrix;
+pub use self::permutation_matrix::PermutationMatrix;
 pub use self::sparse_matrix::SparseMatrix;
+pub use self::triangular_matrix::{Triangle, TriangularMatrix};
+pub use self::tridiagonal_matrix::TridiagonalMatrix;
 pub use self::zero_matrix::ZeroMatrix;
 
+mod banded_matrix;
+mod diagonal_matrix;
+mod elementwise;
 mod identity_matrix;
+mod permutation_matrix;
+mod reshape;
 mod sparse_matrix;
+mod triangular_matrix;
+mod tridiagonal_matrix;
 mod zero_matrix;
 
 #[cfg(test)]
@@ -22,6 +34,24 @@ mod tests {
         assert_eq!(iter.next(), None);
     }
 
+    #[test]
+    fn test_identity_elements() {
+        use matrix::Matrix;
+
+        let I3: IdentityMatrix<i32> = IdentityMatrix::new(3);
+        assert_eq!(I3.elements(), vec![1, 0, 0, 0, 1, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_zero_get() {
+        use matrix::Matrix;
+
+        let Z: ZeroMatrix<i32> = ZeroMatrix::new(2, 3);
+        assert_eq!(Z.get(1, 2), Some(0));
+        assert_eq!(Z.get(2, 0), None);
+        assert_eq!(Z.get(0, 3), None);
+    }
+
     #[test]
     fn test_zero_iter() {
         let Z2 = ZeroMatrix::new(2, 2);
@@ -32,4 +62,223 @@ mod tests {
         assert_eq!(iter.next(), Some(0));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn test_sparse_elementwise() {
+        use vector::Vector;
+
+        let S = sparse![vec![(0, 1, 3), (1, 0, -2), (1, 2, 5)]; 2, 3];
+        let T = sparse![vec![(0, 1, 2), (1, 1, 7)]; 2, 3];
+        assert_eq!(S.nnz(), 3);
+        assert_eq!(S.map(|x| x * 10), sparse![vec![(0, 1, 30), (1, 0, -20), (1, 2, 50)]; 2, 3]);
+        let H = S.hadamard(&T);
+        assert_eq!(H.triplets(), vec![(0, 1, 6)]);
+        assert_eq!(S.sum(), 6);
+        assert_eq!(S.product(), 0);
+        assert_eq!(S.max(), Some(5));
+        assert_eq!(S.argmin(), Some((1, 0)));
+        assert_eq!(S.sum_rows().get(2), Some(5));
+        assert_eq!(S.sum_cols().get(1), Some(3));
+
+        // The first element that is not stored stands in for a zero.
+        let N = sparse![vec![(0, 0, -1.0), (1, 1, -3.0)]; 2, 2];
+        assert_eq!(N.argmax(), Some((0, 1)));
+        assert_eq!(N.max(), Some(0.0));
+        assert_eq!(N.mean_rows().get(1), Some(-1.5));
+        let E: ::SparseMatrix<f64> = sparse!(2, 2);
+        assert_eq!(E.argmin(), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_diagonal() {
+        use sparse::DiagonalMatrix;
+        use {LinearOperator, Matrix};
+
+        let D = DiagonalMatrix::new(vec![2.0, -1.0, 4.0]);
+        assert!(D.is_diagonal() && D.is_upper_triangular() && D.is_symmetric());
+        assert_eq!(D.det(), -8.0);
+        assert_eq!(D.trace(), 5.0);
+        assert_eq!(D.get(0, 1), Some(0.0));
+        assert_eq!(D.apply(&[1.0, 1.0, 1.0]), vec![2.0, -1.0, 4.0]);
+        assert_eq!(D.inverse().unwrap().diag(), vec![0.5, -1.0, 0.25]);
+        assert!(DiagonalMatrix::new(vec![1.0, 0.0]).inverse().is_err());
+
+        let A = dense![2.0, 0.0, 0.0; 0.0, -1.0, 0.0; 0.0, 0.0, 4.0].unwrap();
+        assert_eq!(DiagonalMatrix::from_dense(&A).unwrap(), D);
+        assert_eq!(D.to_dense(), A);
+        assert!(DiagonalMatrix::from_dense(&dense![1.0, 2.0; 0.0, 1.0].unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_permutation() {
+        use sparse::PermutationMatrix;
+        use {LinearOperator, Matrix};
+
+        let P: PermutationMatrix<i32> = PermutationMatrix::new(vec![2, 0, 1]).unwrap();
+        assert!(PermutationMatrix::<i32>::new(vec![0, 0, 1]).is_err());
+        assert_eq!(P.apply(&[10, 20, 30]), vec![30, 10, 20]);
+        assert_eq!(P.apply_transpose(&P.apply(&[10, 20, 30])), vec![10, 20, 30]);
+        assert_eq!(P.compose(&P.inverse()), PermutationMatrix::identity(3));
+        assert_eq!(P.det(), 1);
+        assert_eq!(PermutationMatrix::<i32>::swap(3, 0, 2).det(), -1);
+        assert!(P.is_orthogonal() && !P.is_symmetric() && P.is_upper_hessenberg());
+        assert_eq!(P.to_dense(), dense![0, 0, 1; 1, 0, 0; 0, 1, 0].unwrap());
+        assert_eq!(P.clone().transpose(), P.inverse());
+    }
+
+    #[test]
+    fn test_triangular() {
+        use sparse::{Triangle, TriangularMatrix};
+        use Matrix;
+
+        let A = dense![2.0, 1.0, 3.0;
+                       9.0, 4.0, 1.0;
+                       9.0, 9.0, 5.0].unwrap();
+        let U = TriangularMatrix::from_dense(&A, Triangle::Upper).unwrap();
+        assert_eq!(U.mat.len(), 6);
+        assert_eq!(U.to_dense(), dense![2.0, 1.0, 3.0; 0.0, 4.0, 1.0; 0.0, 0.0, 5.0].unwrap());
+        assert!(U.is_upper_triangular() && !U.is_lower_triangular());
+        assert_eq!(U.det(), 40.0);
+        // U x = b with x = (1, 1, 1).
+        assert_eq!(U.solve_vector(&[6.0, 5.0, 5.0]).unwrap(), vec![1.0, 1.0, 1.0]);
+
+        let L = U.clone().transpose();
+        assert_eq!(L.triangle(), Triangle::Lower);
+        assert_eq!(L.get(2, 0), Some(3.0));
+        assert_eq!(L.solve_vector(&[2.0, 5.0, 9.0]).unwrap(), vec![1.0, 1.0, 1.0]);
+        assert!(TriangularMatrix::zeros(2, Triangle::Lower).solve_vector(&[1.0, 1.0]).is_err());
+    }
+
+    #[test]
+    fn test_banded() {
+        use sparse::BandedMatrix;
+        use {LinearOperator, Matrix};
+
+        let A = dense![1.0, 2.0, 3.0, 0.0;
+                       4.0, 5.0, 6.0, 7.0;
+                       0.0, 8.0, 9.0, 1.0;
+                       0.0, 0.0, 2.0, 3.0].unwrap();
+        let B = BandedMatrix::from_dense(&A, 1, 2);
+        let ab = vec![0.0, 0.0, 1.0, 4.0, 0.0, 2.0, 5.0, 8.0, 3.0, 6.0, 9.0, 2.0, 7.0, 1.0, 3.0, 0.0];
+        assert_eq!(B.band(), ab);
+        assert_eq!(BandedMatrix::from_band(ab, 4, 4, 1, 2).unwrap(), A);
+        assert!(BandedMatrix::<f64>::from_band(vec![0.0; 5], 4, 4, 1, 2).is_err());
+        assert_eq!(B.get(3, 0), Some(0.0));
+        assert_eq!(B.trace(), 18.0);
+        assert_eq!(B.apply(&[1.0, 1.0, 1.0, 1.0]), vec![6.0, 22.0, 18.0, 5.0]);
+        assert_eq!(B.apply_transpose(&[1.0, 1.0, 1.0, 1.0]), vec![5.0, 15.0, 20.0, 11.0]);
+        assert!(B.is_upper_hessenberg() && !B.is_lower_hessenberg());
+
+        let T = B.clone().transpose();
+        assert_eq!((T.kl(), T.ku()), (2, 1));
+        assert_eq!(T.to_dense(), A.clone().transpose());
+
+        // The predicates look at the stored values, not just the bandwidth.
+        let W = BandedMatrix::from_dense(&A, 2, 2);
+        assert!(W.is_upper_hessenberg());
+        W.set(2, 0, 1.0);
+        assert!(!W.is_upper_hessenberg());
+        let D = BandedMatrix::from_dense(&dense![1.0, 0.0; 0.0, 2.0].unwrap(), 1, 1);
+        assert!(D.is_diagonal() && D.is_upper_triangular() && D.is_symmetric());
+
+        assert!(!B.is_orthogonal() && !D.is_orthogonal());
+        let P = dense![0, 1, 0; 0, 0, -1; 1, 0, 0].unwrap();
+        assert!(BandedMatrix::from_dense(&P, 2, 1).is_orthogonal());
+        assert!(BandedMatrix::from_dense(&P, 2, 2).is_orthogonal());
+        assert!(!BandedMatrix::from_dense(&P, 1, 1).is_orthogonal());
+        assert!(!BandedMatrix::from_dense(&dense![1, 0; 0, 1; 0, 0].unwrap(), 1, 1).is_orthogonal());
+    }
+
+    #[test]
+    fn test_tridiagonal() {
+        use sparse::{BandedMatrix, TridiagonalMatrix};
+        use {Error, LinearOperator, Matrix};
+
+        let T = TridiagonalMatrix::new(vec![1.0; 3], vec![4.0; 4], vec![1.0; 3]).unwrap();
+        assert!(TridiagonalMatrix::new(vec![1.0; 2], vec![4.0; 4], vec![1.0; 3]).is_err());
+        assert_eq!(T.det(), 209.0);
+        assert!(T.is_symmetric() && T.is_upper_hessenberg() && T.is_lower_hessenberg());
+        assert!(!T.is_upper_triangular());
+        assert_eq!(T.apply(&[1.0, 1.0, 1.0, 1.0]), vec![5.0, 6.0, 6.0, 5.0]);
+        let x = T.solve_vector(&[5.0, 6.0, 6.0, 5.0]).unwrap();
+        assert!(x.iter().all(|&a: &f64| (a - 1.0).abs() < 1e-12));
+
+        let A = dense![2.0, -1.0, 0.0; 3.0, 2.0, -1.0; 0.0, 3.0, 2.0].unwrap();
+        let S = TridiagonalMatrix::from_dense(&A).unwrap();
+        assert_eq!(S, A);
+        assert_eq!(S.to_banded(), BandedMatrix::from_dense(&A, 1, 1));
+        assert_eq!(S.clone().transpose().to_dense(), A.transpose());
+        assert_eq!(S.det(), 2.0*(4.0 + 3.0) + 3.0*2.0);
+
+        // Thomas does not pivot, but the banded LU does.
+        let P = TridiagonalMatrix::new(vec![1.0], vec![0.0, 0.0], vec![1.0]).unwrap();
+        match P.solve_vector(&[1.0, 2.0]) {
+            Err(Error::Singular) => (),
+            _ => panic!("expected a zero pivot"),
+        }
+        assert_eq!(P.to_banded().solve_vector(&[1.0, 2.0]).unwrap(), vec![2.0, 1.0]);
+
+        assert!(P.is_orthogonal() && !T.is_orthogonal() && !S.is_orthogonal());
+        assert!(TridiagonalMatrix::new(vec![0; 3], vec![1; 4], vec![0; 3]).unwrap().is_orthogonal());
+        assert!(TridiagonalMatrix::new(vec![1, 0], vec![0, 0, -1], vec![-1, 0]).unwrap().is_orthogonal());
+        // Columns 0 and 2 share row 1.
+        assert!(!TridiagonalMatrix::new(vec![1, 0], vec![0, 0, 0], vec![1, 1]).unwrap().is_orthogonal());
+    }
+
+    #[test]
+    fn test_sparse_vec() {
+        use ::{Matrix, ReadOrder};
+
+        let S = sparse![vec![(0, 1, 4), (2, 0, 6)]; 3, 2];
+        let v = S.vec();
+        assert_eq!(v.dims(), (6, 1));
+        assert_eq!(v, sparse![vec![(1, 0, 4), (4, 0, 6)]; 6, 1]);
+        assert_eq!(v.unvec(3, 2, None).unwrap(), S);
+
+        let T = S.deep_clone().transpose();
+        let w = T.vec();
+        assert_eq!(w, sparse![vec![(1, 0, 4), (4, 0, 6)]; 6, 1]);
+        let U = w.unvec(2, 3, Some(ReadOrder::ColMajor)).unwrap();
+        assert_eq!(U, T);
+        assert!(matches!(U.read_order, ReadOrder::ColMajor));
+        assert!(v.unvec(4, 2, None).is_err());
+    }
+
+    #[test]
+    fn test_sparse_stack_reshape() {
+        use ::{DenseMatrix, Matrix, SparseMatrix};
+
+        let S = sparse![vec![(0, 1, 4), (2, 0, 6)]; 3, 2];
+        let T = sparse![vec![(1, 0, 1)]; 3, 1];
+        let dense = |A: &SparseMatrix<i32>| DenseMatrix::from_vec(A.elements(), A.rows(), A.cols(), None).unwrap();
+        let (Sd, Td) = (dense(&S), dense(&T));
+
+        let H = SparseMatrix::hstack(&[&S, &T]).unwrap();
+        assert_eq!(H.nnz(), 3);
+        assert_eq!(H, DenseMatrix::hstack(&[&Sd, &Td]).unwrap());
+        let V = SparseMatrix::vstack(&[&S, &S]).unwrap();
+        assert_eq!(V.nnz(), 4);
+        assert_eq!(V, DenseMatrix::vstack(&[&Sd, &Sd]).unwrap());
+        assert!(SparseMatrix::vstack(&[&S, &T]).is_err());
+        assert_eq!(SparseMatrix::block_diag(&[&S, &T]), DenseMatrix::block_diag(&[&Sd, &Td]));
+
+        assert_eq!(S.reshape(2, 3).unwrap(), Sd.reshape(2, 3).unwrap());
+        assert_eq!(S.tile(2, 3), Sd.tile(2, 3));
+        assert_eq!(S.repeat(2, 3).nnz(), 12);
+        assert_eq!(S.repeat(2, 3), Sd.repeat(2, 3));
+        assert_eq!(S.flip_ud(), Sd.flip_ud());
+        assert_eq!(S.flip_lr(), Sd.flip_lr());
+        for k in 0..4 {
+            assert_eq!(H.rot90(k), dense(&H).rot90(k));
+        }
+
+        let R = S.insert_row(0, &[0, 5]).unwrap();
+        assert_eq!(R.nnz(), 3);
+        assert_eq!(R, Sd.insert_row(0, &[0, 5]).unwrap());
+        assert_eq!(R.remove_row(0).unwrap(), S);
+        let C = S.insert_col(1, &[1, 0, 0]).unwrap();
+        assert_eq!(C, Sd.insert_col(1, &[1, 0, 0]).unwrap());
+        assert_eq!(C.remove_col(1).unwrap(), S);
+        assert_eq!(S.remove_col(0).unwrap().nnz(), 1);
+    }
 }
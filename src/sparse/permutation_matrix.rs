@@ -0,0 +1,191 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use ::{Num};
+use ::{ComplexField, Conjugate, DenseMatrix, LinearOperator, Matrix};
+
+/// A PermutationMatrix stores only an index vector `perm`, and has a one in
+/// row `i` at column `perm[i]`. Applied to a vector it gathers
+/// `y[i] = x[perm[i]]`, and on the left of a matrix it reorders the rows the
+/// same way.
+#[derive(Clone, Debug)]
+pub struct PermutationMatrix<T> {
+    perm: Vec<usize>,
+    mat: PhantomData<T>,
+}
+
+impl<T: Clone + Copy + Num> PermutationMatrix<T> {
+    /// Create a new permutation matrix from its index vector. Fails unless
+    /// `perm` contains each of `0..perm.len()` exactly once.
+    pub fn new(perm: Vec<usize>) -> ::Result<PermutationMatrix<T>> {
+        let mut seen = vec![false; perm.len()];
+        for &p in &perm {
+            if p >= perm.len() || seen[p] { return Err(::Error::InvalidDimensions) }
+            seen[p] = true;
+        }
+        Ok(PermutationMatrix { perm, mat: PhantomData })
+    }
+
+    /// Create the identity permutation of size n.
+    pub fn identity(n: usize) -> PermutationMatrix<T> {
+        PermutationMatrix { perm: (0..n).collect(), mat: PhantomData }
+    }
+
+    /// Create the permutation that swaps `i` and `j`.
+    pub fn swap(n: usize, i: usize, j: usize) -> PermutationMatrix<T> {
+        let mut perm: Vec<usize> = (0..n).collect();
+        perm.swap(i, j);
+        PermutationMatrix { perm, mat: PhantomData }
+    }
+
+    /// Get the index vector.
+    #[inline]
+    pub fn perm(&self) -> &[usize] {
+        &self.perm
+    }
+
+    /// Get the inverse, which is also the transpose, in O(n).
+    pub fn inverse(&self) -> PermutationMatrix<T> {
+        let mut inv = vec![0; self.perm.len()];
+        for (i, &p) in self.perm.iter().enumerate() {
+            inv[p] = i;
+        }
+        PermutationMatrix { perm: inv, mat: PhantomData }
+    }
+
+    /// Compose with `other` in O(n), giving the permutation matrix
+    /// `self * other`.
+    ///
+    /// Panics if the sizes do not agree.
+    pub fn compose(&self, other: &PermutationMatrix<T>) -> PermutationMatrix<T> {
+        if self.perm.len() != other.perm.len() {
+            panic!("Cannot compose permutations of given sizes: lhs={} rhs={}",
+                   self.perm.len(), other.perm.len())
+        }
+        let perm = self.perm.iter().map(|&p| other.perm[p]).collect();
+        PermutationMatrix { perm, mat: PhantomData }
+    }
+
+    /// Get the sign, `1` for an even permutation and `-1` for an odd one,
+    /// which is the determinant.
+    pub fn det(&self) -> T {
+        let mut visited = vec![false; self.perm.len()];
+        let mut odd = false;
+        for start in 0..self.perm.len() {
+            if visited[start] { continue }
+            let mut len = 0;
+            let mut i = start;
+            while !visited[i] {
+                visited[i] = true;
+                i = self.perm[i];
+                len += 1;
+            }
+            // A cycle of length k is k - 1 transpositions.
+            if len % 2 == 0 { odd = !odd }
+        }
+        if odd { T::zero() - T::one() } else { T::one() }
+    }
+
+    /// Copy into a dense matrix.
+    pub fn to_dense(&self) -> DenseMatrix<T> {
+        DenseMatrix::from_vec(self.elements(), self.rows(), self.cols(), None).unwrap()
+    }
+}
+
+impl<T> Conjugate for PermutationMatrix<T> {
+    fn conj(&self) -> PermutationMatrix<T> {
+        PermutationMatrix { perm: self.perm.clone(), mat: PhantomData }
+    }
+}
+
+impl<T: Clone + Copy + Num> Matrix<T> for PermutationMatrix<T> {
+    fn is_hermitian(&self) -> bool where T: Conjugate { self.is_symmetric() }
+
+    /// A permutation matrix is symmetric when it is an involution.
+    fn is_symmetric(&self) -> bool {
+        self.perm.iter().enumerate().all(|(i, &p)| self.perm[p] == i)
+    }
+
+    fn is_skew_hermitian(&self) -> bool where T: Conjugate { self.perm.is_empty() }
+
+    fn is_unitary(&self) -> bool where T: ComplexField { true }
+
+    fn is_orthogonal(&self) -> bool { true }
+
+    fn is_diagonal(&self) -> bool {
+        self.perm.iter().enumerate().all(|(i, &p)| p == i)
+    }
+
+    fn is_lower_triangular(&self) -> bool { self.is_diagonal() }
+
+    fn is_unilower_triangular(&self) -> bool { self.is_diagonal() }
+
+    fn is_strictly_lower_triangular(&self) -> bool { self.perm.is_empty() }
+
+    fn is_lower_hessenberg(&self) -> bool {
+        self.perm.iter().enumerate().all(|(i, &p)| p <= i + 1)
+    }
+
+    fn is_upper_triangular(&self) -> bool { self.is_diagonal() }
+
+    fn is_uniupper_triangular(&self) -> bool { self.is_diagonal() }
+
+    fn is_strictly_upper_triangular(&self) -> bool { self.perm.is_empty() }
+
+    fn is_upper_hessenberg(&self) -> bool {
+        self.perm.iter().enumerate().all(|(i, &p)| p + 1 >= i)
+    }
+
+    fn trace(&self) -> T {
+        self.perm.iter().enumerate()
+            .filter(|&(i, &p)| p == i)
+            .fold(T::zero(), |acc, _| acc + T::one())
+    }
+
+    fn transpose(self) -> Self { self.inverse() }
+
+    fn rows(&self) -> usize { self.perm.len() }
+
+    fn cols(&self) -> usize { self.perm.len() }
+
+    fn get(&self, i: usize, j: usize) -> Option<T> {
+        if i >= self.rows() || j >= self.cols() { return None }
+        if self.perm[i] == j { Some(T::one()) } else { Some(T::zero()) }
+    }
+
+    fn set(&self, i: usize, j: usize, _val: T) -> Option<T> {
+        if i >= self.rows() || j >= self.cols() { return None }
+        panic!("Cannot set a value in a PermutationMatrix")
+    }
+
+    fn elements(&self) -> Vec<T> {
+        let n = self.perm.len();
+        let mut v = vec![T::zero(); n*n];
+        for (i, &p) in self.perm.iter().enumerate() {
+            v[i*n + p] = T::one();
+        }
+        v
+    }
+}
+
+impl<T: Clone + Copy + Num> LinearOperator<T> for PermutationMatrix<T> {
+    fn shape(&self) -> (usize, usize) { (self.perm.len(), self.perm.len()) }
+
+    fn apply(&self, x: &[T]) -> Vec<T> {
+        self.perm.iter().map(|&p| x[p]).collect()
+    }
+
+    fn apply_transpose(&self, x: &[T]) -> Vec<T> {
+        let mut y = x.to_vec();
+        for (i, &p) in self.perm.iter().enumerate() {
+            y[p] = x[i];
+        }
+        y
+    }
+}
+
+impl<T> fmt::Display for PermutationMatrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PermutationMatrix(perm: {:?})", self.perm)
+    }
+}
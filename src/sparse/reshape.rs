@@ -0,0 +1,167 @@
+use ::{Num};
+use ::{Matrix, SparseMatrix};
+
+/// Move the stored elements of each operand by an offset into a new m by n
+/// sparse matrix.
+fn place<T>(mats: &[(&SparseMatrix<T>, usize, usize)], m: usize, n: usize) -> SparseMatrix<T>
+    where T: Clone + Copy + Num
+{
+    let mut tuples = Vec::with_capacity(mats.iter().map(|&(a, _, _)| a.nnz()).sum());
+    for &(a, r0, c0) in mats {
+        tuples.extend(a.triplets().into_iter().map(|(i, j, x)| (r0 + i, c0 + j, x)));
+    }
+    SparseMatrix::from_tuple(tuples, m, n)
+}
+
+/// These mirror the operations on `DenseMatrix`, but only move the stored
+/// elements, so they cost O(nnz) rather than O(mn). The results are always
+/// new row major matrices that do not share storage with the operands.
+impl<T: Clone + Copy + Num> SparseMatrix<T> {
+    /// Move each stored element to the position given by `f`, dropping it if
+    /// `f` returns `None`.
+    fn remap<F>(&self, m: usize, n: usize, f: F) -> SparseMatrix<T>
+        where F: Fn(usize, usize) -> Option<(usize, usize)>
+    {
+        let tuples = self.triplets().into_iter()
+            .filter_map(|(i, j, x)| f(i, j).map(|(r, c)| (r, c, x)))
+            .collect();
+        SparseMatrix::from_tuple(tuples, m, n)
+    }
+
+    /// Concatenate matrices with equal row counts side by side. Fails if
+    /// `mats` is empty or the row counts differ.
+    pub fn hstack(mats: &[&SparseMatrix<T>]) -> ::Result<SparseMatrix<T>> {
+        let m = match mats.first() {
+            Some(a) => a.rows(),
+            None => return Err(::Error::InvalidDimensions),
+        };
+        if mats.iter().any(|a| a.rows() != m) { return Err(::Error::InvalidDimensions) }
+        let mut c0 = 0;
+        let placed: Vec<_> = mats.iter().map(|&a| { c0 += a.cols(); (a, 0, c0 - a.cols()) }).collect();
+        Ok(place(&placed, m, c0))
+    }
+
+    /// Concatenate matrices with equal column counts one above another. Fails
+    /// if `mats` is empty or the column counts differ.
+    pub fn vstack(mats: &[&SparseMatrix<T>]) -> ::Result<SparseMatrix<T>> {
+        let n = match mats.first() {
+            Some(a) => a.cols(),
+            None => return Err(::Error::InvalidDimensions),
+        };
+        if mats.iter().any(|a| a.cols() != n) { return Err(::Error::InvalidDimensions) }
+        let mut r0 = 0;
+        let placed: Vec<_> = mats.iter().map(|&a| { r0 += a.rows(); (a, r0 - a.rows(), 0) }).collect();
+        Ok(place(&placed, r0, n))
+    }
+
+    /// Place matrices along the diagonal of a block matrix, with nothing
+    /// stored elsewhere.
+    pub fn block_diag(mats: &[&SparseMatrix<T>]) -> SparseMatrix<T> {
+        let (mut r0, mut c0) = (0, 0);
+        let placed: Vec<_> = mats.iter().map(|&a| {
+            r0 += a.rows();
+            c0 += a.cols();
+            (a, r0 - a.rows(), c0 - a.cols())
+        }).collect();
+        place(&placed, r0, c0)
+    }
+
+    /// Reshape into an m by n matrix, reading and filling in row major order.
+    /// Fails unless the number of elements is unchanged.
+    pub fn reshape(&self, m: usize, n: usize) -> ::Result<SparseMatrix<T>> {
+        let c = self.cols();
+        if m*n != self.rows()*c { return Err(::Error::InvalidDimensions) }
+        Ok(self.remap(m, n, |i, j| { let k = i*c + j; Some((k / n, k % n)) }))
+    }
+
+    /// Repeat the whole matrix `r` times vertically and `c` times
+    /// horizontally.
+    pub fn tile(&self, r: usize, c: usize) -> SparseMatrix<T> {
+        let (m, n) = self.dims();
+        let mut tuples = Vec::with_capacity(self.nnz()*r*c);
+        for (i, j, x) in self.triplets() {
+            for p in 0..r {
+                tuples.extend((0..c).map(|q| (p*m + i, q*n + j, x)));
+            }
+        }
+        SparseMatrix::from_tuple(tuples, m*r, n*c)
+    }
+
+    /// Repeat each element into an `r` by `c` block, so that the result is
+    /// `A ⊗ ones(r, c)`.
+    pub fn repeat(&self, r: usize, c: usize) -> SparseMatrix<T> {
+        let (m, n) = self.dims();
+        let mut tuples = Vec::with_capacity(self.nnz()*r*c);
+        for (i, j, x) in self.triplets() {
+            for p in 0..r {
+                tuples.extend((0..c).map(|q| (i*r + p, j*c + q, x)));
+            }
+        }
+        SparseMatrix::from_tuple(tuples, m*r, n*c)
+    }
+
+    /// Reverse the order of the rows.
+    pub fn flip_ud(&self) -> SparseMatrix<T> {
+        let (m, n) = self.dims();
+        self.remap(m, n, |i, j| Some((m - 1 - i, j)))
+    }
+
+    /// Reverse the order of the columns.
+    pub fn flip_lr(&self) -> SparseMatrix<T> {
+        let (m, n) = self.dims();
+        self.remap(m, n, |i, j| Some((i, n - 1 - j)))
+    }
+
+    /// Rotate by 90 degrees counterclockwise `k` times.
+    pub fn rot90(&self, k: usize) -> SparseMatrix<T> {
+        let (m, n) = self.dims();
+        match k % 4 {
+            0 => self.remap(m, n, |i, j| Some((i, j))),
+            1 => self.remap(n, m, |i, j| Some((n - 1 - j, i))),
+            2 => self.remap(m, n, |i, j| Some((m - 1 - i, n - 1 - j))),
+            _ => self.remap(n, m, |i, j| Some((j, m - 1 - i))),
+        }
+    }
+
+    /// Insert `row` so that it becomes row i, storing only its nonzero
+    /// elements. Fails unless `i <= rows()` and `row` has length `cols()`.
+    pub fn insert_row(&self, i: usize, row: &[T]) -> ::Result<SparseMatrix<T>> {
+        let (m, n) = self.dims();
+        if i > m || row.len() != n { return Err(::Error::InvalidDimensions) }
+        let a = self.remap(m + 1, n, |r, j| Some((if r < i { r } else { r + 1 }, j)));
+        for (j, &x) in row.iter().enumerate().filter(|&(_, x)| !x.is_zero()) {
+            a.set(i, j, x);
+        }
+        Ok(a)
+    }
+
+    /// Insert `col` so that it becomes column j, storing only its nonzero
+    /// elements. Fails unless `j <= cols()` and `col` has length `rows()`.
+    pub fn insert_col(&self, j: usize, col: &[T]) -> ::Result<SparseMatrix<T>> {
+        let (m, n) = self.dims();
+        if j > n || col.len() != m { return Err(::Error::InvalidDimensions) }
+        let a = self.remap(m, n + 1, |i, c| Some((i, if c < j { c } else { c + 1 })));
+        for (i, &x) in col.iter().enumerate().filter(|&(_, x)| !x.is_zero()) {
+            a.set(i, j, x);
+        }
+        Ok(a)
+    }
+
+    /// Remove row i. Fails unless `i < rows()`.
+    pub fn remove_row(&self, i: usize) -> ::Result<SparseMatrix<T>> {
+        let (m, n) = self.dims();
+        if i >= m { return Err(::Error::InvalidDimensions) }
+        Ok(self.remap(m - 1, n, |r, j| {
+            if r < i { Some((r, j)) } else if r > i { Some((r - 1, j)) } else { None }
+        }))
+    }
+
+    /// Remove column j. Fails unless `j < cols()`.
+    pub fn remove_col(&self, j: usize) -> ::Result<SparseMatrix<T>> {
+        let (m, n) = self.dims();
+        if j >= n { return Err(::Error::InvalidDimensions) }
+        Ok(self.remap(m, n - 1, |i, c| {
+            if c < j { Some((i, c)) } else if c > j { Some((i, c - 1)) } else { None }
+        }))
+    }
+}
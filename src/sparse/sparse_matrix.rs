@@ -4,7 +4,7 @@ use std::fmt;
 use std::rc::Rc;
 
 use ::{Num, Zero};
-use ::{Matrix, ReadOrder};
+use ::{Conjugate, LinearOperator, Matrix, ReadOrder};
 
 #[derive(Clone, Debug)]
 pub struct SparseMatrix<T> where T: Copy {
@@ -42,6 +42,19 @@ impl<T: Clone + Copy + Num + Zero> SparseMatrix<T> {
         }
     }
 
+    /// Copy the matrix into new storage. `clone` shares the storage with the
+    /// original, so writes through either are visible in both.
+    pub fn deep_clone(&self) -> SparseMatrix<T> {
+        SparseMatrix::from_tuple(self.triplets(), self.rows(), self.cols())
+    }
+
+    /// Get the conjugate transpose `A^H`. For real matrices this is just the
+    /// transpose.
+    #[inline]
+    pub fn conj_transpose(&self) -> SparseMatrix<T> where T: Conjugate {
+        self.conj().transpose()
+    }
+
     /// Flip the read order. Toggles between row major and column major.
     #[inline]
     pub fn flip_read_order(mut self) -> Self {
@@ -51,6 +64,50 @@ impl<T: Clone + Copy + Num + Zero> SparseMatrix<T> {
         };
         self
     }
+
+    /// Get the stored elements as `(i, j, a_ij)` tuples in the matrix's read
+    /// order, sorted by row and then column.
+    pub fn triplets(&self) -> Vec<(usize, usize, T)> {
+        let mut triplets: Vec<(usize, usize, T)> = self.mat.borrow().iter()
+            .map(|(&(i, j), a)| match self.read_order {
+                ReadOrder::RowMajor => (i, j, a.get()),
+                ReadOrder::ColMajor => (j, i, a.get()),
+            })
+            .collect();
+        triplets.sort_by_key(|&(i, j, _)| (i, j));
+        triplets
+    }
+
+    /// Stack the elements into an mn by 1 column in the matrix's read order:
+    /// the rows one after another for `RowMajor`, or the columns for
+    /// `ColMajor`. Only the stored elements are moved.
+    pub fn vec(&self) -> SparseMatrix<T> {
+        let (m, n) = (self.rows(), self.cols());
+        let tuples = self.triplets().into_iter()
+            .map(|(i, j, a)| match self.read_order {
+                ReadOrder::RowMajor => (i*n + j, 0, a),
+                ReadOrder::ColMajor => (j*m + i, 0, a),
+            })
+            .collect();
+        SparseMatrix::from_tuple(tuples, m*n, 1)
+    }
+
+    /// Reshape an mn by 1 column into an m by n matrix filled in the given
+    /// read order, which inverts `vec` for a matrix of that read order. If no
+    /// ReadOrder is provided, then assumes `ReadOrder::RowMajor`.
+    pub fn unvec(&self, m: usize, n: usize, read_order: Option<ReadOrder>)
+        -> ::Result<SparseMatrix<T>>
+    {
+        if self.dims() != (m*n, 1) { return Err(::Error::InvalidDimensions) }
+        let entries = self.triplets().into_iter().map(|(k, _, a)| (k, a));
+        Ok(match read_order {
+            Some(ReadOrder::ColMajor) => {
+                let tuples = entries.map(|(k, a)| (k / m, k % m, a)).collect();
+                SparseMatrix::from_tuple(tuples, n, m).flip_read_order()
+            },
+            _ => SparseMatrix::from_tuple(entries.map(|(k, a)| (k / n, k % n, a)).collect(), m, n),
+        })
+    }
 }
 
 impl<T: Clone + Copy + Num> Matrix<T> for SparseMatrix<T> {
@@ -185,6 +242,49 @@ impl<T: Clone + Copy + Num> Matrix<T> for SparseMatrix<T> {
     }
 }
 
+/// The conjugate never shares storage with the original matrix.
+impl<T: Copy + Conjugate> Conjugate for SparseMatrix<T> {
+    fn conj(&self) -> SparseMatrix<T> {
+        let map = self.mat.borrow().iter()
+            .map(|(&k, v)| (k, Cell::new(v.get().conj())))
+            .collect();
+        SparseMatrix {
+            read_order: self.read_order.clone(),
+            m: self.m, n: self.n,
+            mat: Rc::new(RefCell::new(map)),
+        }
+    }
+}
+
+impl<T: Clone + Copy + Num> LinearOperator<T> for SparseMatrix<T> {
+    fn shape(&self) -> (usize, usize) {
+        self.dims()
+    }
+
+    /// Only the stored elements are visited, so this costs O(nnz).
+    fn apply(&self, x: &[T]) -> Vec<T> {
+        let mut y = vec![T::zero(); self.rows()];
+        for (&(i, j), a) in self.mat.borrow().iter() {
+            match self.read_order {
+                ReadOrder::RowMajor => y[i] = y[i] + a.get() * x[j],
+                ReadOrder::ColMajor => y[j] = y[j] + a.get() * x[i],
+            }
+        }
+        y
+    }
+
+    fn apply_transpose(&self, x: &[T]) -> Vec<T> {
+        let mut y = vec![T::zero(); self.cols()];
+        for (&(i, j), a) in self.mat.borrow().iter() {
+            match self.read_order {
+                ReadOrder::RowMajor => y[j] = y[j] + a.get() * x[i],
+                ReadOrder::ColMajor => y[i] = y[i] + a.get() * x[j],
+            }
+        }
+        y
+    }
+}
+
 impl<T: Clone + Copy + Num + Zero> IntoIterator for SparseMatrix<T> {
     type Item = T;
     type IntoIter = SparseMatrixIntoIterator<T>;
@@ -0,0 +1,313 @@
+use std::cell::Cell;
+use std::fmt;
+use std::ops::Range;
+
+use ::{Num};
+use ::{ComplexField, Conjugate, DenseMatrix, LinearOperator, Matrix};
+
+/// Which triangle of a TriangularMatrix is stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Triangle {
+    Upper,
+    Lower,
+}
+
+/// A TriangularMatrix is a square upper or lower triangular matrix in packed
+/// storage, holding only the `n(n + 1)/2` elements of its triangle.
+///
+/// The lower triangle is packed by rows and the upper triangle by columns,
+/// so both use the same layout and `transpose` only flips the triangle.
+#[derive(Clone, Debug)]
+pub struct TriangularMatrix<T> where T: Copy {
+    n: usize,
+    triangle: Triangle,
+    pub mat: Vec<Cell<T>>,
+}
+
+impl<T: Clone + Copy + Num> TriangularMatrix<T> {
+    /// Create a new triangular matrix of zeros.
+    pub fn zeros(n: usize, triangle: Triangle) -> TriangularMatrix<T> {
+        TriangularMatrix { n, triangle, mat: vec![Cell::new(T::zero()); n*(n + 1)/2] }
+    }
+
+    /// Take the given triangle of a square dense matrix, ignoring the other
+    /// elements.
+    pub fn from_dense(a: &DenseMatrix<T>, triangle: Triangle)
+        -> ::Result<TriangularMatrix<T>>
+    {
+        if !a.is_square() { return Err(::Error::InvalidDimensions) }
+        let t = TriangularMatrix::zeros(a.rows(), triangle);
+        for i in 0..a.rows() {
+            for j in t.row_range(i) {
+                t.mat[t.index(i, j)].set(a.get(i, j).unwrap());
+            }
+        }
+        Ok(t)
+    }
+
+    /// Get which triangle is stored.
+    #[inline]
+    pub fn triangle(&self) -> Triangle {
+        self.triangle
+    }
+
+    /// Check if position i, j lies in the stored triangle.
+    #[inline]
+    pub fn in_triangle(&self, i: usize, j: usize) -> bool {
+        match self.triangle {
+            Triangle::Upper => i <= j,
+            Triangle::Lower => j <= i,
+        }
+    }
+
+    /// Get the columns of row i that lie in the stored triangle.
+    #[inline]
+    fn row_range(&self, i: usize) -> Range<usize> {
+        match self.triangle {
+            Triangle::Upper => i..self.n,
+            Triangle::Lower => 0..(i + 1),
+        }
+    }
+
+    /// Get the packed index of a position in the stored triangle.
+    #[inline]
+    fn index(&self, i: usize, j: usize) -> usize {
+        let (r, c) = match self.triangle {
+            Triangle::Upper => (j, i),
+            Triangle::Lower => (i, j),
+        };
+        r*(r + 1)/2 + c
+    }
+
+    /// Apply `f` to every stored element. `f` should map zero to zero, since
+    /// it is not applied outside the triangle.
+    pub fn map<F: Fn(T) -> T>(&self, f: F) -> TriangularMatrix<T> {
+        TriangularMatrix {
+            n: self.n,
+            triangle: self.triangle,
+            mat: self.mat.iter().map(|a| Cell::new(f(a.get()))).collect(),
+        }
+    }
+
+    /// Combine the stored elements of two matrices of the same size and
+    /// triangle, keeping the packed storage. `f` should map two zeros to
+    /// zero, since it is not applied outside the triangle.
+    ///
+    /// Panics if the sizes or the triangles differ. The operators `+` and
+    /// `-` accept opposite triangles and give a dense matrix.
+    pub fn zip_map<F: Fn(T, T) -> T>(&self, other: &TriangularMatrix<T>, f: F) -> TriangularMatrix<T> {
+        if self.n != other.n || self.triangle != other.triangle {
+            panic!("Cannot combine triangular matrices of different sizes or triangles: \
+                    lhs={:?} {} rhs={:?} {}", self.triangle, self.n, other.triangle, other.n)
+        }
+        TriangularMatrix {
+            n: self.n,
+            triangle: self.triangle,
+            mat: self.mat.iter().zip(&other.mat).map(|(a, b)| Cell::new(f(a.get(), b.get()))).collect(),
+        }
+    }
+
+    /// Multiply two matrices of the same size and triangle, keeping the
+    /// packed storage. Only the terms between `i` and `j` contribute to the
+    /// element at i, j.
+    ///
+    /// Panics if the sizes or the triangles differ. The operator `*` accepts
+    /// opposite triangles and gives a dense matrix.
+    pub fn mul_triangular(&self, other: &TriangularMatrix<T>) -> TriangularMatrix<T> {
+        if self.n != other.n || self.triangle != other.triangle {
+            panic!("Cannot multiply triangular matrices of different sizes or triangles: \
+                    lhs={:?} {} rhs={:?} {}", self.triangle, self.n, other.triangle, other.n)
+        }
+        let c = TriangularMatrix::zeros(self.n, self.triangle);
+        for i in 0..self.n {
+            for j in c.row_range(i) {
+                let (lo, hi) = if i <= j { (i, j) } else { (j, i) };
+                let c_ij = (lo..(hi + 1)).fold(T::zero(), |acc, k| {
+                    acc + self.get(i, k).unwrap() * other.get(k, j).unwrap()
+                });
+                c.mat[c.index(i, j)].set(c_ij);
+            }
+        }
+        c
+    }
+
+    /// Get the determinant, the product of the diagonal.
+    pub fn det(&self) -> T {
+        (0..self.n).fold(T::one(), |acc, i| acc * self.mat[self.index(i, i)].get())
+    }
+
+    /// Solve `A x = b` by forward or back substitution. Fails if a diagonal
+    /// element is zero.
+    pub fn solve_vector(&self, b: &[T]) -> ::Result<Vec<T>> {
+        if b.len() != self.n { return Err(::Error::InvalidDimensions) }
+        let mut x = b.to_vec();
+        let order: Vec<usize> = match self.triangle {
+            Triangle::Lower => (0..self.n).collect(),
+            Triangle::Upper => (0..self.n).rev().collect(),
+        };
+        for (k, &i) in order.iter().enumerate() {
+            let d = self.mat[self.index(i, i)].get();
+            if d.is_zero() { return Err(::Error::Singular) }
+            let mut acc = x[i];
+            for &j in &order[..k] {
+                acc = acc - self.mat[self.index(i, j)].get() * x[j];
+            }
+            x[i] = acc / d;
+        }
+        Ok(x)
+    }
+
+    /// Copy into a dense matrix.
+    pub fn to_dense(&self) -> DenseMatrix<T> {
+        DenseMatrix::from_vec(self.elements(), self.n, self.n, None).unwrap()
+    }
+
+    /// Get the conjugate transpose.
+    #[inline]
+    pub fn conj_transpose(&self) -> TriangularMatrix<T> where T: Conjugate {
+        self.conj().transpose()
+    }
+}
+
+impl<T: Copy + Conjugate> Conjugate for TriangularMatrix<T> {
+    fn conj(&self) -> TriangularMatrix<T> {
+        TriangularMatrix {
+            n: self.n,
+            triangle: self.triangle,
+            mat: self.mat.iter().map(|a| Cell::new(a.get().conj())).collect(),
+        }
+    }
+}
+
+impl<T: Clone + Copy + Num> Matrix<T> for TriangularMatrix<T> {
+    fn is_symmetric(&self) -> bool { self.is_diagonal() }
+
+    fn is_orthogonal(&self) -> bool {
+        // A triangular orthogonal matrix is diagonal with entries of +-1.
+        self.is_diagonal() && (0..self.n).all(|i| {
+            let d = self.mat[self.index(i, i)].get();
+            d == T::one() || d == T::zero() - T::one()
+        })
+    }
+
+    fn is_unitary(&self) -> bool where T: ComplexField {
+        self.is_diagonal() && self.to_dense().is_unitary()
+    }
+
+    fn is_diagonal(&self) -> bool {
+        (0..self.n).all(|i| (0..self.n).all(|j| {
+            i == j || !self.in_triangle(i, j) || self.mat[self.index(i, j)].get().is_zero()
+        }))
+    }
+
+    fn is_lower_triangular(&self) -> bool {
+        self.triangle == Triangle::Lower || self.is_diagonal()
+    }
+
+    fn is_unilower_triangular(&self) -> bool {
+        self.is_lower_triangular() && self.diags().iter().all(|d| d.is_one())
+    }
+
+    fn is_strictly_lower_triangular(&self) -> bool {
+        self.is_lower_triangular() && self.diags().iter().all(|d| d.is_zero())
+    }
+
+    fn is_lower_hessenberg(&self) -> bool {
+        self.triangle == Triangle::Lower ||
+            (0..self.n).all(|i| ((i + 2)..self.n).all(|j| self.get(i, j).unwrap().is_zero()))
+    }
+
+    fn is_upper_triangular(&self) -> bool {
+        self.triangle == Triangle::Upper || self.is_diagonal()
+    }
+
+    fn is_uniupper_triangular(&self) -> bool {
+        self.is_upper_triangular() && self.diags().iter().all(|d| d.is_one())
+    }
+
+    fn is_strictly_upper_triangular(&self) -> bool {
+        self.is_upper_triangular() && self.diags().iter().all(|d| d.is_zero())
+    }
+
+    fn is_upper_hessenberg(&self) -> bool {
+        self.triangle == Triangle::Upper ||
+            (2..self.n).all(|i| (0..(i - 1)).all(|j| self.get(i, j).unwrap().is_zero()))
+    }
+
+    fn det(&self) -> ::Result<T> where T: ComplexField { Ok(TriangularMatrix::det(self)) }
+
+    fn trace(&self) -> T {
+        (0..self.n).fold(T::zero(), |acc, i| acc + self.mat[self.index(i, i)].get())
+    }
+
+    fn transpose(self) -> Self {
+        let triangle = match self.triangle {
+            Triangle::Upper => Triangle::Lower,
+            Triangle::Lower => Triangle::Upper,
+        };
+        TriangularMatrix { n: self.n, triangle, mat: self.mat }
+    }
+
+    fn rows(&self) -> usize { self.n }
+
+    fn cols(&self) -> usize { self.n }
+
+    fn get(&self, i: usize, j: usize) -> Option<T> {
+        if i >= self.n || j >= self.n { return None }
+        if self.in_triangle(i, j) {
+            Some(self.mat[self.index(i, j)].get())
+        } else {
+            Some(T::zero())
+        }
+    }
+
+    fn set(&self, i: usize, j: usize, val: T) -> Option<T> {
+        if i >= self.n || j >= self.n { return None }
+        if self.in_triangle(i, j) {
+            self.mat[self.index(i, j)].set(val);
+        } else if !val.is_zero() {
+            panic!("Cannot set a value outside the triangle of a TriangularMatrix")
+        }
+        Some(val)
+    }
+
+    fn elements(&self) -> Vec<T> {
+        let mut v = Vec::with_capacity(self.n*self.n);
+        for i in 0..self.n {
+            for j in 0..self.n {
+                v.push(self.get(i, j).unwrap());
+            }
+        }
+        v
+    }
+}
+
+impl<T: Clone + Copy + Num> LinearOperator<T> for TriangularMatrix<T> {
+    fn shape(&self) -> (usize, usize) { (self.n, self.n) }
+
+    fn apply(&self, x: &[T]) -> Vec<T> {
+        let mut y = vec![T::zero(); self.n];
+        for (i, y_i) in y.iter_mut().enumerate() {
+            for j in self.row_range(i) {
+                *y_i = *y_i + self.mat[self.index(i, j)].get() * x[j];
+            }
+        }
+        y
+    }
+
+    fn apply_transpose(&self, x: &[T]) -> Vec<T> {
+        let mut y = vec![T::zero(); self.n];
+        for (i, &x_i) in x.iter().enumerate().take(self.n) {
+            for j in self.row_range(i) {
+                y[j] = y[j] + self.mat[self.index(i, j)].get() * x_i;
+            }
+        }
+        y
+    }
+}
+
+impl<T: Copy + fmt::Debug> fmt::Display for TriangularMatrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TriangularMatrix({:?}, n: {})", self.triangle, self.n)
+    }
+}
@@ -0,0 +1,281 @@
+use std::cell::Cell;
+use std::fmt;
+
+use ::{Num};
+use ::{BandedMatrix, ComplexField, Conjugate, DenseMatrix, LinearOperator, Matrix};
+
+/// A TridiagonalMatrix is a square matrix that stores only its subdiagonal,
+/// diagonal and superdiagonal.
+#[derive(Clone, Debug)]
+pub struct TridiagonalMatrix<T> where T: Copy {
+    n: usize,
+    /// The subdiagonal, `a_(i+1),i`.
+    pub lower: Vec<Cell<T>>,
+    pub diag: Vec<Cell<T>>,
+    /// The superdiagonal, `a_i,(i+1)`.
+    pub upper: Vec<Cell<T>>,
+}
+
+impl<T: Clone + Copy + Num> TridiagonalMatrix<T> {
+    /// Create a new tridiagonal matrix from its three diagonals. Fails unless
+    /// `lower` and `upper` are one shorter than `diag`.
+    pub fn new(lower: Vec<T>, diag: Vec<T>, upper: Vec<T>) -> ::Result<TridiagonalMatrix<T>> {
+        let n = diag.len();
+        let off = n.saturating_sub(1);
+        if lower.len() != off || upper.len() != off { return Err(::Error::InvalidDimensions) }
+        Ok(TridiagonalMatrix {
+            n,
+            lower: lower.into_iter().map(Cell::new).collect(),
+            diag: diag.into_iter().map(Cell::new).collect(),
+            upper: upper.into_iter().map(Cell::new).collect(),
+        })
+    }
+
+    /// Take the three diagonals of a square dense matrix, ignoring the other
+    /// elements.
+    pub fn from_dense(a: &DenseMatrix<T>) -> ::Result<TridiagonalMatrix<T>> {
+        if !a.is_square() { return Err(::Error::InvalidDimensions) }
+        let n = a.rows();
+        let off = n.saturating_sub(1);
+        TridiagonalMatrix::new((0..off).map(|i| a.get(i + 1, i).unwrap()).collect(),
+                               a.diags(),
+                               (0..off).map(|i| a.get(i, i + 1).unwrap()).collect())
+    }
+
+    /// Get the subdiagonal as a `Vec`.
+    #[inline]
+    pub fn lower(&self) -> Vec<T> {
+        self.lower.iter().map(Cell::get).collect()
+    }
+
+    /// Get the diagonal as a `Vec`.
+    #[inline]
+    pub fn diag(&self) -> Vec<T> {
+        self.diag.iter().map(Cell::get).collect()
+    }
+
+    /// Get the superdiagonal as a `Vec`.
+    #[inline]
+    pub fn upper(&self) -> Vec<T> {
+        self.upper.iter().map(Cell::get).collect()
+    }
+
+    /// Apply `f` to every element of the three diagonals. `f` should map zero
+    /// to zero, since it is not applied elsewhere.
+    pub fn map<F: Fn(T) -> T>(&self, f: F) -> TridiagonalMatrix<T> {
+        let g = |v: &Vec<Cell<T>>| v.iter().map(|a| Cell::new(f(a.get()))).collect();
+        TridiagonalMatrix { n: self.n, lower: g(&self.lower), diag: g(&self.diag), upper: g(&self.upper) }
+    }
+
+    /// Get the determinant in O(n) by the three term recurrence
+    /// `f_i = a_ii f_(i-1) - a_i,(i-1) a_(i-1),i f_(i-2)`.
+    pub fn det(&self) -> T {
+        let (mut f0, mut f1) = (T::one(), T::one());
+        for i in 0..self.n {
+            let mut f = self.diag[i].get() * f1;
+            if i > 0 {
+                f = f - self.lower[i - 1].get() * self.upper[i - 1].get() * f0;
+            }
+            f0 = f1;
+            f1 = f;
+        }
+        f1
+    }
+
+    /// Solve `A x = b` in O(n) with the Thomas algorithm.
+    ///
+    /// The Thomas algorithm is Gaussian elimination without pivoting, so it
+    /// is only stable when `A` is diagonally dominant or positive definite.
+    /// It fails if a pivot is zero; use `to_banded().lu()` for a pivoted
+    /// solve of a general tridiagonal system.
+    pub fn solve_vector(&self, b: &[T]) -> ::Result<Vec<T>> {
+        if b.len() != self.n { return Err(::Error::InvalidDimensions) }
+        if self.n == 0 { return Ok(Vec::new()) }
+        let mut c = vec![T::zero(); self.n];
+        let mut x = b.to_vec();
+        for i in 0..self.n {
+            let mut w = self.diag[i].get();
+            if i > 0 {
+                let l = self.lower[i - 1].get();
+                w = w - l * c[i - 1];
+                x[i] = x[i] - l * x[i - 1];
+            }
+            if w.is_zero() { return Err(::Error::Singular) }
+            if i + 1 < self.n { c[i] = self.upper[i].get() / w }
+            x[i] = x[i] / w;
+        }
+        for i in (0..(self.n - 1)).rev() {
+            x[i] = x[i] - c[i] * x[i + 1];
+        }
+        Ok(x)
+    }
+
+    /// Copy into a banded matrix with one subdiagonal and one superdiagonal.
+    pub fn to_banded(&self) -> BandedMatrix<T> {
+        let b = BandedMatrix::zeros(self.n, self.n, 1, 1);
+        for i in 0..self.n {
+            b.set(i, i, self.diag[i].get());
+            if i + 1 < self.n {
+                b.set(i + 1, i, self.lower[i].get());
+                b.set(i, i + 1, self.upper[i].get());
+            }
+        }
+        b
+    }
+
+    /// Copy into a dense matrix.
+    pub fn to_dense(&self) -> DenseMatrix<T> {
+        DenseMatrix::from_vec(self.elements(), self.n, self.n, None).unwrap()
+    }
+
+    /// Get the conjugate transpose.
+    #[inline]
+    pub fn conj_transpose(&self) -> TridiagonalMatrix<T> where T: Conjugate {
+        self.conj().transpose()
+    }
+}
+
+impl<T: Copy + Conjugate> Conjugate for TridiagonalMatrix<T> {
+    fn conj(&self) -> TridiagonalMatrix<T> {
+        let g = |v: &Vec<Cell<T>>| v.iter().map(|a| Cell::new(a.get().conj())).collect();
+        TridiagonalMatrix { n: self.n, lower: g(&self.lower), diag: g(&self.diag), upper: g(&self.upper) }
+    }
+}
+
+impl<T: Clone + Copy + Num> Matrix<T> for TridiagonalMatrix<T> {
+    fn is_symmetric(&self) -> bool { self.lower() == self.upper() }
+
+    fn is_orthogonal(&self) -> bool {
+        // Column j shares rows with columns j + 1 and j + 2 only, so
+        // `A^T A = I` is a unit norm and two zero products per column.
+        let (l, d, u) = (self.lower(), self.diag(), self.upper());
+        let n = self.n;
+        (0..n).all(|j| {
+            let mut norm = d[j]*d[j];
+            if j > 0 { norm = norm + u[j - 1]*u[j - 1] }
+            if j + 1 < n { norm = norm + l[j]*l[j] }
+            norm.is_one()
+                && (j + 1 >= n || (d[j]*u[j] + l[j]*d[j + 1]).is_zero())
+                && (j + 2 >= n || (l[j]*u[j + 1]).is_zero())
+        })
+    }
+
+    fn is_unitary(&self) -> bool where T: ComplexField { self.to_dense().is_unitary() }
+
+    fn is_diagonal(&self) -> bool {
+        self.lower.iter().chain(self.upper.iter()).all(|a| a.get().is_zero())
+    }
+
+    fn is_lower_triangular(&self) -> bool {
+        self.upper.iter().all(|a| a.get().is_zero())
+    }
+
+    fn is_unilower_triangular(&self) -> bool {
+        self.is_lower_triangular() && self.diag.iter().all(|d| d.get().is_one())
+    }
+
+    fn is_strictly_lower_triangular(&self) -> bool {
+        self.is_lower_triangular() && self.diag.iter().all(|d| d.get().is_zero())
+    }
+
+    fn is_lower_hessenberg(&self) -> bool { true }
+
+    fn is_upper_triangular(&self) -> bool {
+        self.lower.iter().all(|a| a.get().is_zero())
+    }
+
+    fn is_uniupper_triangular(&self) -> bool {
+        self.is_upper_triangular() && self.diag.iter().all(|d| d.get().is_one())
+    }
+
+    fn is_strictly_upper_triangular(&self) -> bool {
+        self.is_upper_triangular() && self.diag.iter().all(|d| d.get().is_zero())
+    }
+
+    fn is_upper_hessenberg(&self) -> bool { true }
+
+    fn det(&self) -> ::Result<T> where T: ComplexField { Ok(TridiagonalMatrix::det(self)) }
+
+    fn trace(&self) -> T {
+        self.diag.iter().fold(T::zero(), |acc, d| acc + d.get())
+    }
+
+    fn transpose(self) -> Self {
+        TridiagonalMatrix { n: self.n, lower: self.upper, diag: self.diag, upper: self.lower }
+    }
+
+    fn rows(&self) -> usize { self.n }
+
+    fn cols(&self) -> usize { self.n }
+
+    fn get(&self, i: usize, j: usize) -> Option<T> {
+        if i >= self.n || j >= self.n { return None }
+        if i == j {
+            Some(self.diag[i].get())
+        } else if i == j + 1 {
+            Some(self.lower[j].get())
+        } else if j == i + 1 {
+            Some(self.upper[i].get())
+        } else {
+            Some(T::zero())
+        }
+    }
+
+    fn set(&self, i: usize, j: usize, val: T) -> Option<T> {
+        if i >= self.n || j >= self.n { return None }
+        if i == j {
+            self.diag[i].set(val);
+        } else if i == j + 1 {
+            self.lower[j].set(val);
+        } else if j == i + 1 {
+            self.upper[i].set(val);
+        } else if !val.is_zero() {
+            panic!("Cannot set a value outside the band of a TridiagonalMatrix")
+        }
+        Some(val)
+    }
+
+    fn elements(&self) -> Vec<T> {
+        let n = self.n;
+        let mut v = vec![T::zero(); n*n];
+        for i in 0..n {
+            v[i*n + i] = self.diag[i].get();
+            if i + 1 < n {
+                v[(i + 1)*n + i] = self.lower[i].get();
+                v[i*n + i + 1] = self.upper[i].get();
+            }
+        }
+        v
+    }
+
+    fn diags(&self) -> Vec<T> { self.diag() }
+}
+
+impl<T: Clone + Copy + Num> LinearOperator<T> for TridiagonalMatrix<T> {
+    fn shape(&self) -> (usize, usize) { (self.n, self.n) }
+
+    fn apply(&self, x: &[T]) -> Vec<T> {
+        (0..self.n).map(|i| {
+            let mut y = self.diag[i].get() * x[i];
+            if i > 0 { y = y + self.lower[i - 1].get() * x[i - 1] }
+            if i + 1 < self.n { y = y + self.upper[i].get() * x[i + 1] }
+            y
+        }).collect()
+    }
+
+    fn apply_transpose(&self, x: &[T]) -> Vec<T> {
+        (0..self.n).map(|i| {
+            let mut y = self.diag[i].get() * x[i];
+            if i > 0 { y = y + self.upper[i - 1].get() * x[i - 1] }
+            if i + 1 < self.n { y = y + self.lower[i].get() * x[i + 1] }
+            y
+        }).collect()
+    }
+}
+
+impl<T: Copy + fmt::Debug> fmt::Display for TridiagonalMatrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TridiagonalMatrix(lower: {:?}, diag: {:?}, upper: {:?})",
+               self.lower, self.diag, self.upper)
+    }
+}
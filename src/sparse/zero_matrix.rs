@@ -1,8 +1,8 @@
 use std::fmt;
 use std::marker::PhantomData;
 
-use ::{Num, Zero};
-use ::Matrix;
+use ::{Float, Num, Zero};
+use ::{ComplexField, Conjugate, LinearOperator, Matrix, SparseMatrix};
 
 #[derive(Clone, Debug)]
 pub struct ZeroMatrix<T> {
@@ -15,11 +15,39 @@ impl<T: Clone + Num + Zero> ZeroMatrix<T> {
     pub fn new(m: usize, n: usize) -> ZeroMatrix<T> {
         ZeroMatrix { m: m, n: n, mat: PhantomData }
     }
+
+    /// Get the conjugate transpose, an `n` by `m` ZeroMatrix.
+    #[inline]
+    pub fn conj_transpose(&self) -> ZeroMatrix<T> {
+        Self::new(self.n, self.m)
+    }
+
+    /// Raise a square zero matrix to the power `k`. The result is a
+    /// `SparseMatrix` because `0^0 = I`, but for `k > 0` it stores nothing.
+    /// Fails with `Error::Singular` if `k < 0`, unless the matrix is empty.
+    pub fn pow(&self, k: i32) -> ::Result<SparseMatrix<T>> where T: Copy {
+        if !self.is_square() { return Err(::Error::InvalidDimensions) }
+        if k < 0 && self.n > 0 { return Err(::Error::Singular) }
+        if k > 0 { return Ok(SparseMatrix::new(self.n, self.n)) }
+        Ok(SparseMatrix::from_tuple((0..self.n).map(|i| (i, i, T::one())).collect(), self.n, self.n))
+    }
+}
+
+impl<T> Conjugate for ZeroMatrix<T> {
+    fn conj(&self) -> ZeroMatrix<T> {
+        ZeroMatrix { m: self.m, n: self.n, mat: PhantomData }
+    }
 }
 
 impl<T: Clone + Num + Zero> Matrix<T> for ZeroMatrix<T> {
+    fn is_hermitian(&self) -> bool where T: Conjugate { self.is_square() }
+
     fn is_symmetric(&self) -> bool { self.is_square() }
 
+    fn is_skew_hermitian(&self) -> bool where T: Conjugate { self.is_square() }
+
+    fn is_unitary(&self) -> bool where T: ComplexField { false }
+
     fn is_orthogonal(&self) -> bool { false }
 
     fn is_diagonal(&self) -> bool { true }
@@ -51,10 +79,35 @@ impl<T: Clone + Num + Zero> Matrix<T> for ZeroMatrix<T> {
     fn cols(&self) -> usize { self.n }
 
     fn get(&self, i: usize, j: usize) -> Option<T> {
-        if i >= self.rows() || j >= self.cols() {
-            return Some(Zero::zero())
-        }
-        None
+        if i >= self.rows() || j >= self.cols() { return None }
+        Some(Zero::zero())
+    }
+
+    fn norm_1(&self) -> T::Real where T: ComplexField { T::Real::zero() }
+
+    fn norm_inf(&self) -> T::Real where T: ComplexField { T::Real::zero() }
+
+    fn norm_fro(&self) -> T::Real where T: ComplexField { T::Real::zero() }
+
+    fn norm_max(&self) -> T::Real where T: ComplexField { T::Real::zero() }
+
+    fn norm_2(&self) -> ::Result<T::Real> where T: ComplexField { Ok(T::Real::zero()) }
+
+    fn cond_1_est(&self) -> ::Result<T::Real> where T: ComplexField {
+        if !self.is_square() { return Err(::Error::InvalidDimensions) }
+        if self.n == 0 { Ok(T::Real::zero()) } else { Err(::Error::Singular) }
+    }
+
+    /// The empty matrix has determinant one.
+    fn det(&self) -> ::Result<T> where T: ComplexField {
+        if !self.is_square() { return Err(::Error::InvalidDimensions) }
+        Ok(if self.n == 0 { T::one() } else { T::zero() })
+    }
+
+    fn log_det(&self) -> ::Result<(T, T::Real)> where T: ComplexField {
+        if !self.is_square() { return Err(::Error::InvalidDimensions) }
+        if self.n == 0 { return Ok((T::one(), T::Real::zero())) }
+        Ok((T::zero(), T::Real::neg_infinity()))
     }
 
     fn set(&self, i: usize, j: usize, _val: T) -> Option<T> {
@@ -68,6 +121,14 @@ impl<T: Clone + Num + Zero> Matrix<T> for ZeroMatrix<T> {
     }
 }
 
+impl<T: Clone + Num + Zero> LinearOperator<T> for ZeroMatrix<T> {
+    fn shape(&self) -> (usize, usize) { (self.m, self.n) }
+
+    fn apply(&self, _x: &[T]) -> Vec<T> { vec![T::zero(); self.m] }
+
+    fn apply_transpose(&self, _x: &[T]) -> Vec<T> { vec![T::zero(); self.n] }
+}
+
 impl<T: Clone + Num + Zero> IntoIterator for ZeroMatrix<T> {
     type Item = T;
     type IntoIter = ZeroMatrixIntoIterator<T>;
@@ -0,0 +1,143 @@
+use std::fmt;
+
+use ::{Num};
+use ::{ComplexField, DenseMatrix, LinearOperator, Matrix};
+use super::fft::convolve;
+
+/// A CirculantMatrix is a square Toeplitz matrix whose rows are cyclic
+/// shifts of each other, `a_ij = c_((i-j) mod n)`. It stores only its first
+/// column `c`.
+#[derive(Clone, Debug)]
+pub struct CirculantMatrix<T> {
+    c: Vec<T>,
+}
+
+impl<T: Clone + Copy + Num> CirculantMatrix<T> {
+    /// Create a new circulant matrix from its first column.
+    pub fn new(c: Vec<T>) -> CirculantMatrix<T> {
+        CirculantMatrix { c }
+    }
+
+    /// Get the first column.
+    #[inline]
+    pub fn first_col(&self) -> &[T] {
+        &self.c
+    }
+
+    /// Get `c_((i-j) mod n)`.
+    #[inline]
+    fn t(&self, i: usize, j: usize) -> T {
+        let n = self.c.len();
+        self.c[(i + n - j) % n]
+    }
+
+    /// Copy into a dense matrix.
+    pub fn to_dense(&self) -> DenseMatrix<T> {
+        DenseMatrix::from_vec(self.elements(), self.rows(), self.cols(), None).unwrap()
+    }
+}
+
+/// Compute the cyclic convolution of `c` and `x`, both of length `n`, by
+/// folding their linear convolution.
+fn cyclic_convolve<T: ComplexField>(c: &[T], x: &[T]) -> Vec<T> {
+    let n = c.len();
+    let z = convolve(c, &x[..n]);
+    (0..n).map(|i| if i + n < z.len() { z[i] + z[i + n] } else { z[i] }).collect()
+}
+
+impl<T: Clone + Copy + Num> Matrix<T> for CirculantMatrix<T> {
+    fn is_symmetric(&self) -> bool {
+        let n = self.c.len();
+        (1..n).all(|k| self.c[k] == self.c[n - k])
+    }
+
+    fn is_orthogonal(&self) -> bool { self.to_dense().is_orthogonal() }
+
+    fn is_unitary(&self) -> bool where T: ComplexField { self.to_dense().is_unitary() }
+
+    fn is_diagonal(&self) -> bool {
+        self.c.iter().skip(1).all(|a| a.is_zero())
+    }
+
+    // Every element off the diagonal appears in both triangles, so a
+    // circulant matrix is triangular only if it is diagonal.
+    fn is_lower_triangular(&self) -> bool { self.is_diagonal() }
+
+    fn is_unilower_triangular(&self) -> bool {
+        self.is_diagonal() && self.c.iter().take(1).all(|a| a.is_one())
+    }
+
+    fn is_strictly_lower_triangular(&self) -> bool {
+        self.c.iter().all(|a| a.is_zero())
+    }
+
+    fn is_lower_hessenberg(&self) -> bool {
+        // Above the superdiagonal lie c_1, ..., c_(n-2).
+        let n = self.c.len();
+        n < 3 || self.c[1..(n - 1)].iter().all(|a| a.is_zero())
+    }
+
+    fn is_upper_triangular(&self) -> bool { self.is_diagonal() }
+
+    fn is_uniupper_triangular(&self) -> bool { self.is_unilower_triangular() }
+
+    fn is_strictly_upper_triangular(&self) -> bool { self.is_strictly_lower_triangular() }
+
+    fn is_upper_hessenberg(&self) -> bool {
+        self.c.iter().skip(2).all(|a| a.is_zero())
+    }
+
+    fn trace(&self) -> T {
+        self.c.iter().fold(T::zero(), |acc, _| acc + self.c[0])
+    }
+
+    fn transpose(self) -> Self {
+        let n = self.c.len();
+        CirculantMatrix { c: (0..n).map(|k| self.c[(n - k) % n]).collect() }
+    }
+
+    fn rows(&self) -> usize { self.c.len() }
+
+    fn cols(&self) -> usize { self.c.len() }
+
+    fn get(&self, i: usize, j: usize) -> Option<T> {
+        if i >= self.rows() || j >= self.cols() { return None }
+        Some(self.t(i, j))
+    }
+
+    fn set(&self, i: usize, j: usize, _val: T) -> Option<T> {
+        if i >= self.rows() || j >= self.cols() { return None }
+        panic!("Cannot set a single value in a CirculantMatrix")
+    }
+
+    fn elements(&self) -> Vec<T> {
+        let n = self.c.len();
+        let mut v = Vec::with_capacity(n*n);
+        for i in 0..n {
+            for j in 0..n {
+                v.push(self.t(i, j));
+            }
+        }
+        v
+    }
+}
+
+/// Circulant products are cyclic convolutions, and are computed with FFTs in
+/// O(n log n).
+impl<T: ComplexField> LinearOperator<T> for CirculantMatrix<T> {
+    fn shape(&self) -> (usize, usize) { (self.rows(), self.cols()) }
+
+    fn apply(&self, x: &[T]) -> Vec<T> {
+        cyclic_convolve(&self.c, x)
+    }
+
+    fn apply_transpose(&self, x: &[T]) -> Vec<T> {
+        cyclic_convolve(&self.clone().transpose().c, x)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Display for CirculantMatrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CirculantMatrix(c: {:?})", self.c)
+    }
+}
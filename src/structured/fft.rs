@@ -0,0 +1,88 @@
+use std::f64::consts::PI;
+
+use ::{Complex, Float, NumCast, Zero};
+use ::ComplexField;
+
+/// Below this length a direct convolution is cheaper than three FFTs.
+const DIRECT_LEN: usize = 32;
+
+/// Compute the discrete Fourier transform of `a` in place with the
+/// iterative radix-2 Cooley-Tukey algorithm. The length of `a` must be a
+/// power of two. The inverse transform is scaled by `1/n`.
+pub fn fft<R: Float>(a: &mut [Complex<R>], inverse: bool) {
+    let n = a.len();
+    if n <= 1 { return }
+    debug_assert!(n.is_power_of_two());
+
+    // Reorder into bit reversed order.
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j { a.swap(i, j) }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        // Compute each twiddle directly instead of by repeated
+        // multiplication, which accumulates rounding error.
+        let w: Vec<Complex<R>> = (0..half).map(|k| {
+            let theta = <R as NumCast>::from(sign * 2.0 * PI * k as f64 / len as f64).unwrap();
+            Complex::new(theta.cos(), theta.sin())
+        }).collect();
+        for start in (0..n).step_by(len) {
+            for k in 0..half {
+                let u = a[start + k];
+                let v = a[start + k + half] * w[k];
+                a[start + k] = u + v;
+                a[start + k + half] = u - v;
+            }
+        }
+        len <<= 1;
+    }
+
+    if inverse {
+        let scale = <R as NumCast>::from(n).unwrap();
+        for z in a.iter_mut() {
+            *z = z.unscale(scale);
+        }
+    }
+}
+
+/// Compute the linear convolution `c_k = sum_i a_i b_(k-i)` of length
+/// `a.len() + b.len() - 1`, with FFTs once the inputs are long enough.
+pub fn convolve<T: ComplexField>(a: &[T], b: &[T]) -> Vec<T> {
+    if a.is_empty() || b.is_empty() { return Vec::new() }
+    let len = a.len() + b.len() - 1;
+    if a.len().min(b.len()) <= DIRECT_LEN {
+        let mut c = vec![T::zero(); len];
+        for (i, &a_i) in a.iter().enumerate() {
+            for (j, &b_j) in b.iter().enumerate() {
+                c[i + j] = c[i + j] + a_i * b_j;
+            }
+        }
+        return c
+    }
+
+    let n = len.next_power_of_two();
+    let pad = |v: &[T]| {
+        let mut z: Vec<Complex<T::Real>> = v.iter().map(|x| x.to_complex()).collect();
+        z.resize(n, Complex::new(T::Real::zero(), T::Real::zero()));
+        z
+    };
+    let mut fa = pad(a);
+    let mut fb = pad(b);
+    fft(&mut fa, false);
+    fft(&mut fb, false);
+    for (x, y) in fa.iter_mut().zip(fb.iter()) {
+        *x = *x * *y;
+    }
+    fft(&mut fa, true);
+    fa.into_iter().take(len).map(T::from_complex).collect()
+}
@@ -0,0 +1,126 @@
+use std::fmt;
+
+use ::{Num};
+use ::{ComplexField, DenseMatrix, LinearOperator, Matrix};
+use super::fft::convolve;
+
+/// A HankelMatrix is constant along each antidiagonal, `a_ij = h_(i+j)`. It
+/// stores only the `m + n - 1` values `h`.
+#[derive(Clone, Debug)]
+pub struct HankelMatrix<T> {
+    m: usize,
+    n: usize,
+    h: Vec<T>,
+}
+
+impl<T: Clone + Copy + Num> HankelMatrix<T> {
+    /// Create a new Hankel matrix from its first column and last row. Fails
+    /// if either is empty or if they disagree on the corner
+    /// `c[m - 1] == r[0]`.
+    pub fn new(c: Vec<T>, r: Vec<T>) -> ::Result<HankelMatrix<T>> {
+        if c.is_empty() || r.is_empty() || c[c.len() - 1] != r[0] {
+            return Err(::Error::InvalidDimensions)
+        }
+        let (m, n) = (c.len(), r.len());
+        let h = c.into_iter().chain(r.into_iter().skip(1)).collect();
+        Ok(HankelMatrix { m, n, h })
+    }
+
+    /// Get the values `h_0, ..., h_(m+n-2)` along the antidiagonals.
+    #[inline]
+    pub fn antidiagonals(&self) -> &[T] {
+        &self.h
+    }
+
+    /// Copy into a dense matrix.
+    pub fn to_dense(&self) -> DenseMatrix<T> {
+        DenseMatrix::from_vec(self.elements(), self.m, self.n, None).unwrap()
+    }
+
+    /// Check that every element at a position where `outside` holds is zero.
+    fn is_zero_where<F: Fn(usize, usize) -> bool>(&self, outside: F) -> bool {
+        (0..self.m).all(|i| (0..self.n).all(|j| !outside(i, j) || self.h[i + j].is_zero()))
+    }
+}
+
+impl<T: Clone + Copy + Num> Matrix<T> for HankelMatrix<T> {
+    fn is_symmetric(&self) -> bool { self.is_square() }
+
+    fn is_orthogonal(&self) -> bool { self.to_dense().is_orthogonal() }
+
+    fn is_unitary(&self) -> bool where T: ComplexField { self.to_dense().is_unitary() }
+
+    fn is_diagonal(&self) -> bool { self.is_zero_where(|i, j| i != j) }
+
+    fn is_lower_triangular(&self) -> bool { self.is_zero_where(|i, j| j > i) }
+
+    fn is_unilower_triangular(&self) -> bool {
+        self.is_lower_triangular() && self.diags().iter().all(|d| d.is_one())
+    }
+
+    fn is_strictly_lower_triangular(&self) -> bool { self.is_zero_where(|i, j| j >= i) }
+
+    fn is_lower_hessenberg(&self) -> bool { self.is_zero_where(|i, j| j > i + 1) }
+
+    fn is_upper_triangular(&self) -> bool { self.is_zero_where(|i, j| i > j) }
+
+    fn is_uniupper_triangular(&self) -> bool {
+        self.is_upper_triangular() && self.diags().iter().all(|d| d.is_one())
+    }
+
+    fn is_strictly_upper_triangular(&self) -> bool { self.is_zero_where(|i, j| i >= j) }
+
+    fn is_upper_hessenberg(&self) -> bool { self.is_zero_where(|i, j| i > j + 1) }
+
+    fn trace(&self) -> T {
+        (0..self.m.min(self.n)).fold(T::zero(), |acc, i| acc + self.h[2*i])
+    }
+
+    fn transpose(self) -> Self {
+        HankelMatrix { m: self.n, n: self.m, h: self.h }
+    }
+
+    fn rows(&self) -> usize { self.m }
+
+    fn cols(&self) -> usize { self.n }
+
+    fn get(&self, i: usize, j: usize) -> Option<T> {
+        if i >= self.m || j >= self.n { return None }
+        Some(self.h[i + j])
+    }
+
+    fn set(&self, i: usize, j: usize, _val: T) -> Option<T> {
+        if i >= self.m || j >= self.n { return None }
+        panic!("Cannot set a single value in a HankelMatrix")
+    }
+
+    fn elements(&self) -> Vec<T> {
+        let mut v = Vec::with_capacity(self.m*self.n);
+        for i in 0..self.m {
+            v.extend_from_slice(&self.h[i..(i + self.n)]);
+        }
+        v
+    }
+}
+
+/// Hankel products are convolutions with the reversed vector, and are
+/// computed with FFTs in O((m + n) log(m + n)).
+impl<T: ComplexField> LinearOperator<T> for HankelMatrix<T> {
+    fn shape(&self) -> (usize, usize) { (self.m, self.n) }
+
+    fn apply(&self, x: &[T]) -> Vec<T> {
+        let rev: Vec<T> = x[..self.n].iter().rev().cloned().collect();
+        convolve(&self.h, &rev).into_iter().skip(self.n - 1).take(self.m).collect()
+    }
+
+    fn apply_transpose(&self, x: &[T]) -> Vec<T> {
+        let rev: Vec<T> = x[..self.m].iter().rev().cloned().collect();
+        convolve(&self.h, &rev).into_iter().skip(self.m - 1).take(self.n).collect()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Display for HankelMatrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HankelMatrix(m: {}, n: {}, h: {:?})", self.m, self.n, self.h)
+    }
+}
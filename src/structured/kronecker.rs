@@ -0,0 +1,88 @@
+use std::fmt;
+
+use ::{Num};
+use ::{DenseMatrix, Kronecker, LinearOperator, Matrix};
+
+/// A KroneckerMatrix is the Kronecker product `A ⊗ B` of two operators, kept
+/// as its factors.
+///
+/// For `A` m by n and `B` p by q, applying the product to a vector of length
+/// nq costs n applications of `B` and p applications of `A`, instead of the
+/// mnpq of the formed product.
+#[derive(Clone, Debug)]
+pub struct KroneckerMatrix<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> KroneckerMatrix<A, B> {
+    /// Create a new Kronecker product `a ⊗ b` of two operators.
+    pub fn new(a: A, b: B) -> KroneckerMatrix<A, B> {
+        KroneckerMatrix { a, b }
+    }
+
+    /// Get the factors `(A, B)`.
+    #[inline]
+    pub fn factors(&self) -> (&A, &B) {
+        (&self.a, &self.b)
+    }
+
+    /// Form the product as a dense matrix.
+    pub fn to_dense<T>(&self) -> DenseMatrix<T>
+        where T: Clone + Copy + Num + fmt::Debug, A: Matrix<T>, B: Matrix<T>
+    {
+        let a = DenseMatrix::from_vec(self.a.elements(), self.a.rows(), self.a.cols(), None);
+        let b = DenseMatrix::from_vec(self.b.elements(), self.b.rows(), self.b.cols(), None);
+        a.unwrap().kron(&b.unwrap())
+    }
+}
+
+/// Compute `(A ⊗ B) x` for `A` m by n and `B` p by q given their actions.
+///
+/// The chunks `x_j = x[jq..(j + 1)q]` are the columns of a q by n matrix
+/// `X`, and the result holds the columns of `B X A^T`.
+fn apply_kron<T, F, G>(x: &[T], (m, n): (usize, usize), (p, q): (usize, usize), fa: F, fb: G)
+    -> Vec<T>
+    where T: Clone + Copy + Num, F: Fn(&[T]) -> Vec<T>, G: Fn(&[T]) -> Vec<T>
+{
+    // Z = B X, stored as its n columns.
+    let z: Vec<Vec<T>> = (0..n).map(|j| fb(&x[j*q..(j + 1)*q])).collect();
+    let mut y = vec![T::zero(); m*p];
+    let mut w = vec![T::zero(); n];
+    for k in 0..p {
+        for (w_j, z_j) in w.iter_mut().zip(&z) {
+            *w_j = z_j[k];
+        }
+        for (i, y_ik) in fa(&w).into_iter().enumerate() {
+            y[i*p + k] = y_ik;
+        }
+    }
+    y
+}
+
+impl<T, A, B> LinearOperator<T> for KroneckerMatrix<A, B>
+    where T: Clone + Copy + Num, A: LinearOperator<T>, B: LinearOperator<T>
+{
+    fn shape(&self) -> (usize, usize) {
+        let ((m, n), (p, q)) = (self.a.shape(), self.b.shape());
+        (m*p, n*q)
+    }
+
+    fn apply(&self, x: &[T]) -> Vec<T> {
+        apply_kron(x, self.a.shape(), self.b.shape(),
+                   |v| self.a.apply(v), |v| self.b.apply(v))
+    }
+
+    /// `(A ⊗ B)^T = A^T ⊗ B^T`, so this applies the transposed factors.
+    fn apply_transpose(&self, x: &[T]) -> Vec<T> {
+        let ((m, n), (p, q)) = (self.a.shape(), self.b.shape());
+        apply_kron(x, (n, m), (q, p),
+                   |v| self.a.apply_transpose(v), |v| self.b.apply_transpose(v))
+    }
+}
+
+impl<A: fmt::Debug, B: fmt::Debug> fmt::Display for KroneckerMatrix<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "KroneckerMatrix(a: {:?}, b: {:?})", self.a, self.b)
+    }
+}
@@ -0,0 +1,206 @@
+pub use self::circulant::CirculantMatrix;
+pub use self::hankel::HankelMatrix;
+pub use self::kronecker::KroneckerMatrix;
+pub use self::toeplitz::ToeplitzMatrix;
+pub use self::vandermonde::VandermondeMatrix;
+
+mod circulant;
+mod fft;
+mod hankel;
+mod kronecker;
+mod toeplitz;
+mod vandermonde;
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+    use ::{DenseMatrix, Error, LinearOperator, Matrix, Rational};
+    use super::{CirculantMatrix, HankelMatrix, KroneckerMatrix, ToeplitzMatrix, VandermondeMatrix};
+
+    fn matvec(A: &DenseMatrix<f64>, x: &[f64]) -> Vec<f64> {
+        (0..A.rows()).map(|i| {
+            (0..A.cols()).fold(0.0, |acc, j| acc + A.get(i, j).unwrap() * x[j])
+        }).collect()
+    }
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert!((x - y).abs() < 1e-8, "{} != {}", x, y);
+        }
+    }
+
+    fn r(a: i64) -> Rational {
+        Rational::from_integer(a as isize)
+    }
+
+    #[test]
+    fn test_fft() {
+        use super::fft::convolve;
+
+        // Long enough to take the FFT path.
+        let a: Vec<f64> = (0..70).map(|i| ((i*7) % 11) as f64 - 5.0).collect();
+        let b: Vec<f64> = (0..40).map(|i| ((i*3) % 5) as f64 + 0.5).collect();
+        let mut direct = vec![0.0; a.len() + b.len() - 1];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                direct[i + j] += x*y;
+            }
+        }
+        assert_close(&convolve(&a, &b), &direct);
+    }
+
+    #[test]
+    fn test_toeplitz() {
+        let T = ToeplitzMatrix::new(vec![1.0, 2.0, 3.0], vec![1.0, 4.0, 5.0, 6.0]).unwrap();
+        let A = dense![1.0, 4.0, 5.0, 6.0; 2.0, 1.0, 4.0, 5.0; 3.0, 2.0, 1.0, 4.0].unwrap();
+        assert_eq!(T.to_dense(), A);
+        assert!(ToeplitzMatrix::new(vec![1.0, 2.0], vec![0.0, 1.0]).is_err());
+        assert_eq!(T.apply(&[1.0, 0.0, -1.0, 2.0]), matvec(&A, &[1.0, 0.0, -1.0, 2.0]));
+        assert_close(&T.apply_transpose(&[1.0, 2.0, 3.0]), &matvec(&A.transpose(), &[1.0, 2.0, 3.0]));
+        assert!(!T.is_upper_hessenberg());
+        assert!(ToeplitzMatrix::new(vec![1.0, 2.0, 0.0], vec![1.0, 3.0, 4.0]).unwrap().is_upper_hessenberg());
+
+        assert!(!T.is_orthogonal());
+        assert!(ToeplitzMatrix::new(vec![0, 1], vec![0, 1]).unwrap().is_orthogonal());
+
+        let n = 100;
+        let c: Vec<f64> = (0..n).map(|i| 1.0 / (i + 1) as f64).collect();
+        let r: Vec<f64> = (0..n).map(|i| if i == 0 { 1.0 } else { (i % 7) as f64 }).collect();
+        let B = ToeplitzMatrix::new(c, r).unwrap();
+        let x: Vec<f64> = (0..n).map(|i| (i % 5) as f64 - 2.0).collect();
+        assert_close(&B.apply(&x), &matvec(&B.to_dense(), &x));
+    }
+
+    #[test]
+    fn test_levinson() {
+        let T = ToeplitzMatrix::symmetric(vec![4.0, 1.0, 0.5, 0.25]).unwrap();
+        let x = [1.0, 2.0, 3.0, 4.0];
+        let b = matvec(&T.to_dense(), &x);
+        assert_close(&T.solve_levinson(&b).unwrap(), &x);
+
+        // Exact over the rationals.
+        let S = ToeplitzMatrix::symmetric(vec![r(2), r(1), r(0)]).unwrap();
+        let b = vec![r(3), r(4), r(3)];
+        assert_eq!(S.solve_levinson(&b).unwrap(), vec![r(1), r(1), r(1)]);
+
+        let N = ToeplitzMatrix::new(vec![1.0, 2.0], vec![1.0, 3.0]).unwrap();
+        match N.solve_levinson(&[1.0, 1.0]) {
+            Err(Error::NotSymmetric) => (),
+            _ => panic!("expected a nonsymmetric matrix"),
+        }
+        // The leading 1 by 1 block is singular.
+        let Z = ToeplitzMatrix::symmetric(vec![0.0, 1.0]).unwrap();
+        assert!(Z.solve_levinson(&[1.0, 1.0]).is_err());
+        assert!(ToeplitzMatrix::<f64>::symmetric(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_circulant() {
+        let C = CirculantMatrix::new(vec![1.0, 2.0, 3.0, 4.0]);
+        let A = dense![1.0, 4.0, 3.0, 2.0;
+                       2.0, 1.0, 4.0, 3.0;
+                       3.0, 2.0, 1.0, 4.0;
+                       4.0, 3.0, 2.0, 1.0].unwrap();
+        assert_eq!(C, A);
+        assert_eq!(C.trace(), 4.0);
+        assert_eq!(C.apply(&[1.0, -1.0, 2.0, 0.5]), matvec(&A, &[1.0, -1.0, 2.0, 0.5]));
+        assert_eq!(C.clone().transpose(), A.transpose());
+        assert!(!C.is_symmetric() && CirculantMatrix::new(vec![1, 2, 3, 2]).is_symmetric());
+        assert!(CirculantMatrix::new(vec![1, 2, 0, 0]).is_upper_hessenberg());
+        assert!(!CirculantMatrix::new(vec![1, 2, 0, 0]).is_lower_hessenberg());
+
+        assert!(!C.is_orthogonal());
+        // The cyclic shift is a permutation.
+        assert!(CirculantMatrix::new(vec![0, 1, 0, 0]).is_orthogonal());
+
+        let n = 65;
+        let c: Vec<f64> = (0..n).map(|i| ((i*i) % 13) as f64).collect();
+        let x: Vec<f64> = (0..n).map(|i| (i % 4) as f64 - 1.5).collect();
+        let B = CirculantMatrix::new(c);
+        assert_close(&B.apply(&x), &matvec(&B.to_dense(), &x));
+        assert_close(&B.apply_transpose(&x), &matvec(&B.to_dense().transpose(), &x));
+    }
+
+    #[test]
+    fn test_hankel() {
+        let H = HankelMatrix::new(vec![1.0, 2.0, 3.0], vec![3.0, 4.0, 5.0, 6.0]).unwrap();
+        let A = dense![1.0, 2.0, 3.0, 4.0; 2.0, 3.0, 4.0, 5.0; 3.0, 4.0, 5.0, 6.0].unwrap();
+        assert_eq!(H, A);
+        assert!(HankelMatrix::new(vec![1.0, 2.0], vec![3.0, 4.0]).is_err());
+        assert_eq!(H.trace(), 9.0);
+        assert!(!H.is_symmetric() && H.clone().transpose() == A.clone().transpose());
+        assert_eq!(H.apply(&[1.0, 0.0, 2.0, -1.0]), matvec(&A, &[1.0, 0.0, 2.0, -1.0]));
+        assert_eq!(H.apply_transpose(&[1.0, 1.0, 1.0]), vec![6.0, 9.0, 12.0, 15.0]);
+
+        assert!(!H.is_orthogonal());
+        assert!(HankelMatrix::new(vec![0, 0, 1], vec![1, 0, 0]).unwrap().is_orthogonal());
+
+        let n = 50;
+        let h: Vec<f64> = (0..n).map(|i| (i % 9) as f64 - 4.0).collect();
+        let B = HankelMatrix::new(h.clone(), h[(n - 1)..].iter().chain(&h).cloned().collect()).unwrap();
+        let x: Vec<f64> = (0..B.cols()).map(|i| (i % 3) as f64).collect();
+        assert_close(&B.apply(&x), &matvec(&B.to_dense(), &x));
+    }
+
+    #[test]
+    fn test_vandermonde() {
+        let V = VandermondeMatrix::square(vec![r(1), r(2), r(3), r(-1)]);
+        assert_eq!(V.get(2, 3), Some(r(27)));
+        // p(x) = 1 - 2x + 3x^3
+        let a = vec![r(1), r(-2), r(0), r(3)];
+        let f = V.apply(&a);
+        assert_eq!(f, vec![r(2), r(21), r(76), r(0)]);
+        assert_eq!(V.solve_vector(&f).unwrap(), a);
+
+        let W = V.clone().transpose();
+        assert_eq!(W.to_dense(), V.to_dense().transpose());
+        let b = W.apply(&a);
+        assert_eq!(b, V.apply_transpose(&a));
+        assert_eq!(W.solve_vector(&b).unwrap(), a);
+
+        assert!(!V.is_orthogonal());
+        assert!(VandermondeMatrix::square(vec![1]).is_orthogonal());
+
+        let R = VandermondeMatrix::new(vec![2.0, 3.0], 3);
+        assert_eq!(R.to_dense(), dense![1.0, 2.0, 4.0; 1.0, 3.0, 9.0].unwrap());
+        assert!(R.solve_vector(&[1.0, 1.0]).is_err());
+        match VandermondeMatrix::square(vec![1.0, 2.0, 1.0]).solve_vector(&[1.0, 1.0, 1.0]) {
+            Err(Error::Singular) => (),
+            _ => panic!("expected repeated nodes"),
+        }
+    }
+
+    #[test]
+    fn test_kronecker() {
+        use ::{SparseMatrix, TridiagonalMatrix};
+
+        let A = dense![1.0, 2.0, 0.0; -1.0, 3.0, 1.0].unwrap();
+        let B = dense![2.0, 1.0; 0.0, 4.0; 1.0, -1.0; 5.0, 0.0].unwrap();
+        let K = KroneckerMatrix::new(A.clone(), B.clone());
+        let D = K.to_dense();
+        assert_eq!(K.shape(), (8, 6));
+        assert_eq!(D.dims(), (8, 6));
+
+        let x: Vec<f64> = (0..6).map(|i| (i as f64) - 2.5).collect();
+        assert_close(&K.apply(&x), &matvec(&D, &x));
+        let y: Vec<f64> = (0..8).map(|i| ((i*3) % 5) as f64).collect();
+        assert_close(&K.apply_transpose(&y), &matvec(&D.clone().transpose(), &y));
+
+        // vec(A X B^T) = (A ⊗ B) vec(X) with the row major vec.
+        let X = dense![1.0, 0.0; 2.0, -1.0; 3.0, 1.0].unwrap();
+        let AXBt = &(&A * &X) * &B.clone().transpose();
+        assert_close(&K.apply(&X.elements()), &AXBt.elements());
+
+        // The factors can be any operators, such as the 2-D Laplacian terms.
+        let L = TridiagonalMatrix::new(vec![-1.0; 3], vec![2.0; 4], vec![-1.0; 3]).unwrap();
+        let I: SparseMatrix<f64> = SparseMatrix::from_tuple((0..4).map(|i| (i, i, 1.0)).collect(), 4, 4);
+        let LI = KroneckerMatrix::new(L.clone(), I.clone());
+        let IL = KroneckerMatrix::new(I, L);
+        let u = vec![1.0; 16];
+        let Lu: Vec<f64> = LI.apply(&u).iter().zip(IL.apply(&u)).map(|(a, b)| a + b).collect();
+        assert_eq!(Lu.iter().filter(|&&a| a == 0.0).count(), 4);
+        assert_eq!(Lu[0], 2.0);
+        assert_eq!(Lu[1], 1.0);
+    }
+}
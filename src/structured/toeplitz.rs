@@ -0,0 +1,201 @@
+use std::fmt;
+
+use ::{Num};
+use ::{ComplexField, DenseMatrix, LinearOperator, Matrix};
+use super::fft::convolve;
+
+/// A ToeplitzMatrix is constant along each diagonal, `a_ij = t_(i-j)`. It
+/// stores only its first column `c` and first row `r`.
+#[derive(Clone, Debug)]
+pub struct ToeplitzMatrix<T> {
+    /// The first column, `t_0, t_1, ..., t_(m-1)`.
+    c: Vec<T>,
+    /// The first row, `t_0, t_-1, ..., t_-(n-1)`.
+    r: Vec<T>,
+}
+
+impl<T: Clone + Copy + Num> ToeplitzMatrix<T> {
+    /// Create a new Toeplitz matrix from its first column and first row. Fails
+    /// if either is empty or if they disagree on the diagonal `c[0] == r[0]`.
+    pub fn new(c: Vec<T>, r: Vec<T>) -> ::Result<ToeplitzMatrix<T>> {
+        if c.is_empty() || r.is_empty() || c[0] != r[0] {
+            return Err(::Error::InvalidDimensions)
+        }
+        Ok(ToeplitzMatrix { c, r })
+    }
+
+    /// Create a new symmetric Toeplitz matrix from its first column. Fails if
+    /// `c` is empty.
+    pub fn symmetric(c: Vec<T>) -> ::Result<ToeplitzMatrix<T>> {
+        if c.is_empty() { return Err(::Error::InvalidDimensions) }
+        Ok(ToeplitzMatrix { r: c.clone(), c })
+    }
+
+    /// Get the first column.
+    #[inline]
+    pub fn first_col(&self) -> &[T] {
+        &self.c
+    }
+
+    /// Get the first row.
+    #[inline]
+    pub fn first_row(&self) -> &[T] {
+        &self.r
+    }
+
+    /// Get `t_k` for `k = i - j`, given as the pair `(i, j)`.
+    #[inline]
+    fn t(&self, i: usize, j: usize) -> T {
+        if i >= j { self.c[i - j] } else { self.r[j - i] }
+    }
+
+    /// Copy into a dense matrix.
+    pub fn to_dense(&self) -> DenseMatrix<T> {
+        DenseMatrix::from_vec(self.elements(), self.rows(), self.cols(), None).unwrap()
+    }
+
+    /// Solve `A x = b` for a symmetric Toeplitz matrix in O(n^2) with the
+    /// Levinson-Durbin recursion.
+    ///
+    /// Fails with `Error::NotSymmetric` unless the matrix is symmetric, and
+    /// with `Error::Singular` if a leading principal submatrix is singular,
+    /// even if the matrix itself is not.
+    pub fn solve_levinson(&self, b: &[T]) -> ::Result<Vec<T>> {
+        if !self.is_square() || b.len() != self.rows() { return Err(::Error::InvalidDimensions) }
+        if !self.is_symmetric() { return Err(::Error::NotSymmetric) }
+        let n = b.len();
+        let t0 = self.c[0];
+        if t0.is_zero() { return Err(::Error::Singular) }
+
+        // Work with the unit diagonal matrix A / t0.
+        let r: Vec<T> = self.c[1..].iter().map(|&a| a / t0).collect();
+        let b: Vec<T> = b.iter().map(|&a| a / t0).collect();
+        let mut x = vec![b[0]];
+        if n == 1 { return Ok(x) }
+
+        // y solves the Yule-Walker system T_k y = -(r_1, ..., r_k).
+        let mut y = vec![T::zero() - r[0]];
+        let mut alpha = T::zero() - r[0];
+        let mut beta = T::one();
+        for k in 1..n {
+            beta = (T::one() - alpha * alpha) * beta;
+            if beta.is_zero() { return Err(::Error::Singular) }
+            let dot = (0..k).fold(T::zero(), |acc, i| acc + r[i] * x[k - 1 - i]);
+            let mu = (b[k] - dot) / beta;
+            for i in 0..k {
+                x[i] = x[i] + mu * y[k - 1 - i];
+            }
+            x.push(mu);
+
+            if k < n - 1 {
+                let dot = (0..k).fold(T::zero(), |acc, i| acc + r[i] * y[k - 1 - i]);
+                alpha = (T::zero() - r[k] - dot) / beta;
+                let z: Vec<T> = (0..k).map(|i| y[i] + alpha * y[k - 1 - i]).collect();
+                y = z;
+                y.push(alpha);
+            }
+        }
+        Ok(x)
+    }
+}
+
+impl<T: Clone + Copy + Num> Matrix<T> for ToeplitzMatrix<T> {
+    fn is_symmetric(&self) -> bool { self.c == self.r }
+
+    fn is_orthogonal(&self) -> bool { self.to_dense().is_orthogonal() }
+
+    fn is_unitary(&self) -> bool where T: ComplexField { self.to_dense().is_unitary() }
+
+    fn is_diagonal(&self) -> bool {
+        self.is_lower_triangular() && self.is_upper_triangular()
+    }
+
+    fn is_lower_triangular(&self) -> bool {
+        self.r[1..].iter().all(|a| a.is_zero())
+    }
+
+    fn is_unilower_triangular(&self) -> bool {
+        self.is_lower_triangular() && self.c[0].is_one()
+    }
+
+    fn is_strictly_lower_triangular(&self) -> bool {
+        self.is_lower_triangular() && self.c[0].is_zero()
+    }
+
+    fn is_lower_hessenberg(&self) -> bool {
+        self.r.iter().skip(2).all(|a| a.is_zero())
+    }
+
+    fn is_upper_triangular(&self) -> bool {
+        self.c[1..].iter().all(|a| a.is_zero())
+    }
+
+    fn is_uniupper_triangular(&self) -> bool {
+        self.is_upper_triangular() && self.c[0].is_one()
+    }
+
+    fn is_strictly_upper_triangular(&self) -> bool {
+        self.is_upper_triangular() && self.c[0].is_zero()
+    }
+
+    fn is_upper_hessenberg(&self) -> bool {
+        self.c.iter().skip(2).all(|a| a.is_zero())
+    }
+
+    fn trace(&self) -> T {
+        (0..self.rows().min(self.cols())).fold(T::zero(), |acc, _| acc + self.c[0])
+    }
+
+    fn transpose(self) -> Self {
+        ToeplitzMatrix { c: self.r, r: self.c }
+    }
+
+    fn rows(&self) -> usize { self.c.len() }
+
+    fn cols(&self) -> usize { self.r.len() }
+
+    fn get(&self, i: usize, j: usize) -> Option<T> {
+        if i >= self.rows() || j >= self.cols() { return None }
+        Some(self.t(i, j))
+    }
+
+    fn set(&self, i: usize, j: usize, _val: T) -> Option<T> {
+        if i >= self.rows() || j >= self.cols() { return None }
+        panic!("Cannot set a single value in a ToeplitzMatrix")
+    }
+
+    fn elements(&self) -> Vec<T> {
+        let mut v = Vec::with_capacity(self.rows()*self.cols());
+        for i in 0..self.rows() {
+            for j in 0..self.cols() {
+                v.push(self.t(i, j));
+            }
+        }
+        v
+    }
+}
+
+/// Toeplitz products are convolutions, and are computed with FFTs in
+/// O((m + n) log(m + n)).
+impl<T: ComplexField> LinearOperator<T> for ToeplitzMatrix<T> {
+    fn shape(&self) -> (usize, usize) { (self.rows(), self.cols()) }
+
+    fn apply(&self, x: &[T]) -> Vec<T> {
+        // With u = (t_-(n-1), ..., t_(m-1)), y_i is entry i + n - 1 of u * x.
+        let n = self.cols();
+        let u: Vec<T> = self.r.iter().rev().chain(self.c[1..].iter()).cloned().collect();
+        convolve(&u, &x[..n]).into_iter().skip(n - 1).take(self.rows()).collect()
+    }
+
+    fn apply_transpose(&self, x: &[T]) -> Vec<T> {
+        let m = self.rows();
+        let u: Vec<T> = self.c.iter().rev().chain(self.r[1..].iter()).cloned().collect();
+        convolve(&u, &x[..m]).into_iter().skip(m - 1).take(self.cols()).collect()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Display for ToeplitzMatrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ToeplitzMatrix(c: {:?}, r: {:?})", self.c, self.r)
+    }
+}
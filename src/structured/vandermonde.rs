@@ -0,0 +1,206 @@
+use std::fmt;
+
+use ::{Num};
+use ::{ComplexField, DenseMatrix, LinearOperator, Matrix};
+
+/// A VandermondeMatrix has rows of successive powers of its nodes,
+/// `a_ij = x_i^j`, and stores only the nodes `x`. Its transpose, with
+/// `a_ij = x_j^i`, is stored the same way.
+#[derive(Clone, Debug)]
+pub struct VandermondeMatrix<T> {
+    x: Vec<T>,
+    /// The number of powers `x^0, ..., x^(n-1)`.
+    n: usize,
+    transposed: bool,
+}
+
+impl<T: Clone + Copy + Num> VandermondeMatrix<T> {
+    /// Create a new Vandermonde matrix with a row of powers `x_i^0, ...,
+    /// x_i^(n-1)` for each node.
+    pub fn new(x: Vec<T>, n: usize) -> VandermondeMatrix<T> {
+        VandermondeMatrix { x, n, transposed: false }
+    }
+
+    /// Create a new square Vandermonde matrix, with as many powers as nodes.
+    pub fn square(x: Vec<T>) -> VandermondeMatrix<T> {
+        let n = x.len();
+        VandermondeMatrix::new(x, n)
+    }
+
+    /// Get the nodes.
+    #[inline]
+    pub fn nodes(&self) -> &[T] {
+        &self.x
+    }
+
+    /// Compute `sum_j x_i^j v_j` for each node by Horner's rule.
+    fn powers_apply(&self, v: &[T]) -> Vec<T> {
+        self.x.iter().map(|&x| {
+            v[..self.n].iter().rev().fold(T::zero(), |acc, &a| acc * x + a)
+        }).collect()
+    }
+
+    /// Compute `sum_i x_i^j v_i` for each power.
+    fn powers_apply_transpose(&self, v: &[T]) -> Vec<T> {
+        let mut y = vec![T::zero(); self.n];
+        for (&x, &v_i) in self.x.iter().zip(v) {
+            let mut p = v_i;
+            for y_j in y.iter_mut() {
+                *y_j = *y_j + p;
+                p = p * x;
+            }
+        }
+        y
+    }
+
+    /// Copy into a dense matrix.
+    pub fn to_dense(&self) -> DenseMatrix<T> {
+        DenseMatrix::from_vec(self.elements(), self.rows(), self.cols(), None).unwrap()
+    }
+
+    /// Solve `A x = b` for a square Vandermonde matrix in O(n^2) with the
+    /// Björck-Pereyra algorithm. For `A` this is polynomial interpolation,
+    /// giving the coefficients of the polynomial through `(x_i, b_i)`.
+    ///
+    /// Fails with `Error::Singular` if two nodes are equal.
+    pub fn solve_vector(&self, b: &[T]) -> ::Result<Vec<T>> {
+        if !self.is_square() || b.len() != self.n { return Err(::Error::InvalidDimensions) }
+        let x = &self.x;
+        let mut a = b.to_vec();
+        if a.is_empty() { return Ok(a) }
+        let n = a.len() - 1;
+        if !self.transposed {
+            // Newton divided differences, then expand the Newton form into
+            // monomial coefficients.
+            for k in 0..n {
+                for i in ((k + 1)..(n + 1)).rev() {
+                    let d = x[i] - x[i - k - 1];
+                    if d.is_zero() { return Err(::Error::Singular) }
+                    a[i] = (a[i] - a[i - 1]) / d;
+                }
+            }
+            for k in (0..n).rev() {
+                for i in k..n {
+                    a[i] = a[i] - a[i + 1] * x[k];
+                }
+            }
+        } else {
+            // The same steps transposed and in reverse order.
+            for (k, &x_k) in x.iter().enumerate().take(n) {
+                for i in ((k + 1)..(n + 1)).rev() {
+                    a[i] = a[i] - x_k * a[i - 1];
+                }
+            }
+            for k in (0..n).rev() {
+                for i in (k + 1)..(n + 1) {
+                    let d = x[i] - x[i - k - 1];
+                    if d.is_zero() { return Err(::Error::Singular) }
+                    a[i] = a[i] / d;
+                }
+                for i in k..n {
+                    a[i] = a[i] - a[i + 1];
+                }
+            }
+        }
+        Ok(a)
+    }
+
+    /// Check that every element at a position where `outside` holds is zero.
+    fn is_zero_where<F: Fn(usize, usize) -> bool>(&self, outside: F) -> bool {
+        let n = self.cols();
+        self.elements().iter().enumerate().all(|(k, a)| !outside(k / n, k % n) || a.is_zero())
+    }
+}
+
+impl<T: Clone + Copy + Num> Matrix<T> for VandermondeMatrix<T> {
+    fn is_symmetric(&self) -> bool {
+        let a = self.elements();
+        let n = self.cols();
+        self.is_square() && (0..n).all(|i| (0..i).all(|j| a[i*n + j] == a[j*n + i]))
+    }
+
+    fn is_orthogonal(&self) -> bool { self.to_dense().is_orthogonal() }
+
+    fn is_unitary(&self) -> bool where T: ComplexField { self.to_dense().is_unitary() }
+
+    fn is_diagonal(&self) -> bool { self.is_zero_where(|i, j| i != j) }
+
+    fn is_lower_triangular(&self) -> bool { self.is_zero_where(|i, j| j > i) }
+
+    fn is_unilower_triangular(&self) -> bool {
+        self.is_lower_triangular() && self.diags().iter().all(|d| d.is_one())
+    }
+
+    fn is_strictly_lower_triangular(&self) -> bool { self.is_zero_where(|i, j| j >= i) }
+
+    fn is_lower_hessenberg(&self) -> bool { self.is_zero_where(|i, j| j > i + 1) }
+
+    fn is_upper_triangular(&self) -> bool { self.is_zero_where(|i, j| i > j) }
+
+    fn is_uniupper_triangular(&self) -> bool {
+        self.is_upper_triangular() && self.diags().iter().all(|d| d.is_one())
+    }
+
+    fn is_strictly_upper_triangular(&self) -> bool { self.is_zero_where(|i, j| i >= j) }
+
+    fn is_upper_hessenberg(&self) -> bool { self.is_zero_where(|i, j| i > j + 1) }
+
+    fn trace(&self) -> T {
+        self.diags().into_iter().fold(T::zero(), |acc, d| acc + d)
+    }
+
+    fn transpose(self) -> Self {
+        VandermondeMatrix { x: self.x, n: self.n, transposed: !self.transposed }
+    }
+
+    fn rows(&self) -> usize {
+        if self.transposed { self.n } else { self.x.len() }
+    }
+
+    fn cols(&self) -> usize {
+        if self.transposed { self.x.len() } else { self.n }
+    }
+
+    fn get(&self, i: usize, j: usize) -> Option<T> {
+        if i >= self.rows() || j >= self.cols() { return None }
+        let (node, power) = if self.transposed { (j, i) } else { (i, j) };
+        Some((0..power).fold(T::one(), |acc, _| acc * self.x[node]))
+    }
+
+    fn set(&self, i: usize, j: usize, _val: T) -> Option<T> {
+        if i >= self.rows() || j >= self.cols() { return None }
+        panic!("Cannot set a single value in a VandermondeMatrix")
+    }
+
+    fn elements(&self) -> Vec<T> {
+        let (m, n) = (self.x.len(), self.n);
+        let mut v = vec![T::zero(); m*n];
+        for (i, &x) in self.x.iter().enumerate() {
+            let mut p = T::one();
+            for j in 0..n {
+                let k = if self.transposed { j*m + i } else { i*n + j };
+                v[k] = p;
+                p = p * x;
+            }
+        }
+        v
+    }
+}
+
+impl<T: Clone + Copy + Num> LinearOperator<T> for VandermondeMatrix<T> {
+    fn shape(&self) -> (usize, usize) { (self.rows(), self.cols()) }
+
+    fn apply(&self, x: &[T]) -> Vec<T> {
+        if self.transposed { self.powers_apply_transpose(x) } else { self.powers_apply(x) }
+    }
+
+    fn apply_transpose(&self, x: &[T]) -> Vec<T> {
+        if self.transposed { self.powers_apply(x) } else { self.powers_apply_transpose(x) }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Display for VandermondeMatrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VandermondeMatrix(x: {:?}, n: {}, transposed: {})", self.x, self.n, self.transposed)
+    }
+}